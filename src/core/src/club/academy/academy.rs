@@ -4,8 +4,9 @@ use crate::club::academy::settings::AcademySettings;
 use crate::context::GlobalContext;
 use crate::utils::IntegerUtils;
 use crate::{PlayerCollection, StaffCollection};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClubAcademy {
     settings: AcademySettings,
     players: PlayerCollection,
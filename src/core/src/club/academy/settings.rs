@@ -1,6 +1,7 @@
 use std::ops::Range;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AcademySettings {
     pub players_count_range: Range<u8>,
 }
@@ -1,7 +1,8 @@
 use crate::club::{BoardMood, BoardResult, StaffClubContract};
 use crate::context::{GlobalContext, SimulationContext};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClubBoard {
     pub mood: BoardMood,
     pub director: Option<StaffClubContract>,
@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BoardMood {
     pub state: BoardMoodState,
 }
@@ -11,7 +13,7 @@ impl BoardMood {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum BoardMoodState {
     Poor,
     Normal,
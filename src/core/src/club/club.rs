@@ -5,8 +5,9 @@ use crate::club::{ClubFinances, ClubMood, ClubResult};
 use crate::context::GlobalContext;
 use crate::shared::Location;
 use crate::TeamCollection;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Club {
     pub id: u32,
     pub name: String,
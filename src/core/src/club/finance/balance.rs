@@ -4,8 +4,12 @@ use crate::club::{
 use crate::context::GlobalContext;
 use chrono::NaiveDate;
 use log::debug;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+// Part of the balance a club is ready to spend on a single transfer
+const TRANSFER_BUDGET_SHARE: f64 = 0.5;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClubFinances {
     pub balance: ClubFinancialBalance,
     pub history: ClubFinancialBalanceHistory,
@@ -58,6 +62,46 @@ impl ClubFinances {
         self.balance.push_outcome(amount);
     }
 
+    pub fn transfer_budget(&self) -> f64 {
+        self.balance.balance.max(0) as f64 * TRANSFER_BUDGET_SHARE
+    }
+
+    pub fn pay_transfer_fee(&mut self, club_name: &str, amount: i32) {
+        debug!(
+            "club: {}, finance: pay transfer fee, amount = {}",
+            club_name, amount
+        );
+
+        self.balance.push_outcome(amount);
+    }
+
+    pub fn receive_transfer_fee(&mut self, club_name: &str, amount: i32) {
+        debug!(
+            "club: {}, finance: receive transfer fee, amount = {}",
+            club_name, amount
+        );
+
+        self.balance.push_income(amount);
+    }
+
+    pub fn pay_loan_wage(&mut self, club_name: &str, amount: i32) {
+        debug!(
+            "club: {}, finance: pay loan wage, amount = {}",
+            club_name, amount
+        );
+
+        self.balance.push_outcome(amount);
+    }
+
+    pub fn receive_loan_wage(&mut self, club_name: &str, amount: i32) {
+        debug!(
+            "club: {}, finance: receive loan wage, amount = {}",
+            club_name, amount
+        );
+
+        self.balance.push_income(amount);
+    }
+
     fn start_new_month(&mut self, club_name: &str, date: NaiveDate) {
         debug!(
             "club: {}, finance: add history, date = {}, balance = {}, income={}, outcome={}",
@@ -69,7 +113,7 @@ impl ClubFinances {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct ClubFinancialBalance {
     pub balance: i32,
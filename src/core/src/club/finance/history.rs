@@ -1,8 +1,9 @@
 use crate::club::ClubFinancialBalance;
 use chrono::NaiveDate;
 use std::collections::LinkedList;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClubFinancialBalanceHistory {
     history: LinkedList<(NaiveDate, ClubFinancialBalance)>,
 }
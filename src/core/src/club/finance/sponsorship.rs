@@ -1,6 +1,7 @@
 use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClubSponsorship {
     pub sponsorship_contracts: Vec<ClubSponsorshipContract>,
 }
@@ -24,7 +25,7 @@ impl ClubSponsorship {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClubSponsorshipContract {
     pub sponsor_name: String,
     pub wage: i32,
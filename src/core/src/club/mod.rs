@@ -12,6 +12,9 @@ pub mod staff;
 pub mod status;
 pub mod team;
 
+#[cfg(test)]
+pub mod testing;
+
 pub use board::*;
 pub use club::*;
 pub use context::*;
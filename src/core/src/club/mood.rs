@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClubMood {
     pub state: ClubMoodState,
 }
@@ -11,7 +13,7 @@ impl ClubMood {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum ClubMoodState {
     Poor,
     Normal,
@@ -1,8 +1,9 @@
 use crate::context::SimulationContext;
 pub use chrono::prelude::{DateTime, Datelike, NaiveDate, Utc};
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum ContractType {
     PartTime,
     FullTime,
@@ -11,7 +12,7 @@ pub enum ContractType {
     NonContract,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum PlayerSquadStatus {
     Invalid,
     NotYetSet,
@@ -25,14 +26,14 @@ pub enum PlayerSquadStatus {
     SquadStatusCount,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PlayerTransferStatus {
     TransferListed,
     LoadListed,
     TransferAndLoadListed,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerClubContract {
     pub shirt_number: Option<u8>,
 
@@ -81,7 +82,7 @@ impl PlayerClubContract {
 }
 
 // Bonuses
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum ContractBonusType {
     AppearanceFee,
     GoalFee,
@@ -94,7 +95,7 @@ pub enum ContractBonusType {
     UnusedSubstitutionFee,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ContractBonus {
     pub value: i32,
     pub bonus_type: ContractBonusType,
@@ -107,7 +108,7 @@ impl ContractBonus {
 }
 
 // Clauses
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum ContractClauseType {
     MinimumFeeRelease,
     RelegationFeeRelease,
@@ -131,7 +132,7 @@ pub enum ContractClauseType {
     OptionalContractExtensionByClub,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ContractClause {
     pub value: i32,
     pub bonus_type: ContractClauseType,
@@ -118,6 +118,8 @@ impl PlayerGenerator {
             relations: Relations::new(),
             statistics: PlayerStatistics::default(),
             statistics_history: PlayerStatisticsHistory::new(),
+            suspended_matches: 0,
+            injury: None,
         }
     }
 }
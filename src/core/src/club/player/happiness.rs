@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerHappiness {
     positive: Vec<PositiveHappiness>,
     negative: Vec<NegativeHappiness>,
@@ -17,12 +19,12 @@ impl PlayerHappiness {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PositiveHappiness {
     pub description: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct NegativeHappiness {
     pub description: String,
 }
@@ -0,0 +1,302 @@
+use crate::club::{PlayerAttributes, StaffMedical};
+use crate::utils::RandomGenerator;
+use chrono::{Duration, NaiveDate};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// Chances of getting hurt at a single exposure of a fresh, fit player
+pub const FOUL_INJURY_CHANCE: f32 = 0.04;
+pub const TACKLE_INJURY_CHANCE: f32 = 0.008;
+pub const MATCH_MINUTE_INJURY_CHANCE: f32 = 0.00008;
+pub const TRAINING_INJURY_CHANCE: f32 = 0.002;
+
+pub const MAX_JADEDNESS: i16 = 10000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum InjurySeverity {
+    Minor,
+    Moderate,
+    Serious,
+    Severe,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InjuryType {
+    // knocks from a challenge
+    Bruise,
+    DeadLeg,
+    AnkleSprain,
+    Concussion,
+    KneeLigament,
+    BrokenLeg,
+    // the body giving way under load
+    CalfStrain,
+    HamstringStrain,
+    GroinStrain,
+    MetatarsalFracture,
+    CruciateLigament,
+}
+
+// How the injury happened, a challenge or overload in a match or at training
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InjuryCause {
+    Contact,
+    NonContact,
+}
+
+// Injury types with how often they happen among the ones of the same cause
+const CONTACT_INJURIES: [(InjuryType, u32); 6] = [
+    (InjuryType::Bruise, 35),
+    (InjuryType::DeadLeg, 25),
+    (InjuryType::AnkleSprain, 20),
+    (InjuryType::Concussion, 8),
+    (InjuryType::KneeLigament, 8),
+    (InjuryType::BrokenLeg, 4),
+];
+
+const NON_CONTACT_INJURIES: [(InjuryType, u32); 5] = [
+    (InjuryType::CalfStrain, 30),
+    (InjuryType::HamstringStrain, 35),
+    (InjuryType::GroinStrain, 20),
+    (InjuryType::MetatarsalFracture, 10),
+    (InjuryType::CruciateLigament, 5),
+];
+
+impl InjuryType {
+    pub fn severity(&self) -> InjurySeverity {
+        match self {
+            InjuryType::Bruise | InjuryType::DeadLeg | InjuryType::CalfStrain => {
+                InjurySeverity::Minor
+            }
+            InjuryType::AnkleSprain
+            | InjuryType::Concussion
+            | InjuryType::HamstringStrain
+            | InjuryType::GroinStrain => InjurySeverity::Moderate,
+            InjuryType::KneeLigament | InjuryType::MetatarsalFracture => InjurySeverity::Serious,
+            InjuryType::BrokenLeg | InjuryType::CruciateLigament => InjurySeverity::Severe,
+        }
+    }
+
+    // Usual days out, from the quickest to the slowest recovery
+    pub fn recovery_days(&self) -> (u16, u16) {
+        match self {
+            InjuryType::Bruise => (2, 7),
+            InjuryType::DeadLeg => (3, 10),
+            InjuryType::CalfStrain => (7, 21),
+            InjuryType::AnkleSprain => (10, 28),
+            InjuryType::Concussion => (7, 21),
+            InjuryType::HamstringStrain => (14, 42),
+            InjuryType::GroinStrain => (10, 35),
+            InjuryType::KneeLigament => (30, 90),
+            InjuryType::MetatarsalFracture => (42, 90),
+            InjuryType::BrokenLeg => (120, 240),
+            InjuryType::CruciateLigament => (180, 300),
+        }
+    }
+
+    pub fn random(cause: InjuryCause) -> Self {
+        Self::choose(cause, RandomGenerator::current().gen())
+    }
+
+    fn choose(cause: InjuryCause, roll: f32) -> Self {
+        let injuries: &[(InjuryType, u32)] = match cause {
+            InjuryCause::Contact => &CONTACT_INJURIES,
+            InjuryCause::NonContact => &NON_CONTACT_INJURIES,
+        };
+
+        let total: u32 = injuries.iter().map(|(_, weight)| weight).sum();
+        let mut target = roll.clamp(0.0, 1.0) * total as f32;
+
+        for &(injury_type, weight) in injuries {
+            if target < weight as f32 {
+                return injury_type;
+            }
+
+            target -= weight as f32;
+        }
+
+        injuries[injuries.len() - 1].0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Injury {
+    pub injury_type: InjuryType,
+    pub start_date: NaiveDate,
+    pub return_date: NaiveDate,
+}
+
+impl Injury {
+    pub fn new(injury_type: InjuryType, start_date: NaiveDate, days: u16) -> Self {
+        Injury {
+            injury_type,
+            start_date,
+            return_date: start_date + Duration::days(days as i64),
+        }
+    }
+
+    // Random injury of the cause, good physios and a naturally fit body shorten the time out
+    pub fn random(
+        cause: InjuryCause,
+        start_date: NaiveDate,
+        natural_fitness: f32,
+        medical: &StaffMedical,
+    ) -> Self {
+        let injury_type = InjuryType::random(cause);
+
+        let days = Self::recovery_days(
+            injury_type,
+            natural_fitness,
+            medical,
+            RandomGenerator::current().gen(),
+        );
+
+        Injury::new(injury_type, start_date, days)
+    }
+
+    fn recovery_days(
+        injury_type: InjuryType,
+        natural_fitness: f32,
+        medical: &StaffMedical,
+        roll: f32,
+    ) -> u16 {
+        let (min_days, max_days) = injury_type.recovery_days();
+
+        let days = min_days as f32 + (max_days - min_days) as f32 * roll.clamp(0.0, 1.0);
+
+        // 1..20 scales, the best physio takes off a fifth, the worst adds a tenth
+        let physio = 1.1 - medical.physiotherapy as f32 / 20.0 * 0.3;
+        let fitness = 1.1 - natural_fitness / 20.0 * 0.2;
+
+        (days * physio * fitness).round().max(1.0) as u16
+    }
+
+    pub fn severity(&self) -> InjurySeverity {
+        self.injury_type.severity()
+    }
+
+    pub fn is_healed(&self, date: NaiveDate) -> bool {
+        date >= self.return_date
+    }
+
+    pub fn days_left(&self, date: NaiveDate) -> i64 {
+        (self.return_date - date).num_days().max(0)
+    }
+}
+
+pub struct InjuryRisk;
+
+impl InjuryRisk {
+    // A tired, jaded or unfit body breaks down easier, good sports science keeps
+    // the load in check
+    pub fn chance(
+        base_chance: f32,
+        attributes: &PlayerAttributes,
+        natural_fitness: f32,
+        sports_science: u8,
+    ) -> f32 {
+        let condition = attributes.condition_percentage().min(100) as f32 / 100.0;
+        let jadedness = (attributes.jadedness.max(0) as f32 / MAX_JADEDNESS as f32).min(1.0);
+
+        let fatigue = 1.0 + (1.0 - condition) * 2.0;
+        let jaded = 1.0 + jadedness;
+        let fitness = 1.3 - natural_fitness / 20.0 * 0.6;
+        let science = 1.2 - sports_science as f32 / 20.0 * 0.4;
+
+        base_chance * fatigue * jaded * fitness * science
+    }
+
+    pub fn is_injured(
+        base_chance: f32,
+        attributes: &PlayerAttributes,
+        natural_fitness: f32,
+        sports_science: u8,
+    ) -> bool {
+        let chance = Self::chance(base_chance, attributes, natural_fitness, sports_science);
+
+        RandomGenerator::current().gen::<f32>() < chance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::club::CONDITION_MAX_VALUE;
+
+    fn medical(physiotherapy: u8, sports_science: u8) -> StaffMedical {
+        StaffMedical {
+            physiotherapy,
+            sports_science,
+            non_player_tendencies: 10,
+        }
+    }
+
+    fn attributes(condition: i16, jadedness: i16) -> PlayerAttributes {
+        PlayerAttributes {
+            condition,
+            jadedness,
+            ..PlayerAttributes::default()
+        }
+    }
+
+    #[test]
+    fn tired_and_jaded_players_get_injured_more_often() {
+        let fresh = InjuryRisk::chance(0.01, &attributes(CONDITION_MAX_VALUE, 0), 10.0, 10);
+        let tired = InjuryRisk::chance(0.01, &attributes(5000, 0), 10.0, 10);
+        let jaded = InjuryRisk::chance(0.01, &attributes(5000, 8000), 10.0, 10);
+
+        assert!(tired > fresh);
+        assert!(jaded > tired);
+
+        let unfit = InjuryRisk::chance(0.01, &attributes(CONDITION_MAX_VALUE, 0), 2.0, 10);
+        let poor_science = InjuryRisk::chance(0.01, &attributes(CONDITION_MAX_VALUE, 0), 10.0, 1);
+
+        assert!(unfit > fresh);
+        assert!(poor_science > fresh);
+    }
+
+    #[test]
+    fn good_physio_shortens_recovery() {
+        let poor = Injury::recovery_days(InjuryType::HamstringStrain, 10.0, &medical(1, 10), 0.5);
+        let good = Injury::recovery_days(InjuryType::HamstringStrain, 10.0, &medical(20, 10), 0.5);
+
+        assert!(good < poor);
+
+        let (min_days, max_days) = InjuryType::HamstringStrain.recovery_days();
+
+        assert!(good >= min_days * 3 / 4 && poor <= max_days);
+    }
+
+    #[test]
+    fn injury_heals_on_return_date() {
+        let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let injury = Injury::new(InjuryType::AnkleSprain, start, 14);
+
+        assert_eq!(14, injury.days_left(start));
+        assert!(!injury.is_healed(start + Duration::days(13)));
+        assert!(injury.is_healed(start + Duration::days(14)));
+        assert_eq!(InjurySeverity::Moderate, injury.severity());
+    }
+
+    #[test]
+    fn cause_decides_injury_types() {
+        assert_eq!(
+            InjuryType::Bruise,
+            InjuryType::choose(InjuryCause::Contact, 0.0)
+        );
+        assert_eq!(
+            InjuryType::BrokenLeg,
+            InjuryType::choose(InjuryCause::Contact, 1.0)
+        );
+        assert_eq!(
+            InjuryType::CalfStrain,
+            InjuryType::choose(InjuryCause::NonContact, 0.0)
+        );
+
+        for roll in [0.1, 0.3, 0.5, 0.7, 0.9] {
+            let injury_type = InjuryType::choose(InjuryCause::NonContact, roll);
+
+            assert!(NON_CONTACT_INJURIES.iter().any(|(t, _)| *t == injury_type));
+        }
+    }
+}
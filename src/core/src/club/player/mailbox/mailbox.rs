@@ -3,25 +3,26 @@ use crate::{Player, PlayerMailboxResult, PlayerResult};
 use chrono::NaiveDate;
 use std::collections::VecDeque;
 use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerMessage {
     pub message_type: PlayerMessageType,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum PlayerMessageType {
     Greeting,
     ContractProposal(PlayerContractProposal),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerContractProposal {
     pub salary: u32,
     pub years: u8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerMailbox {
     messages: Mutex<VecDeque<PlayerMessage>>,
 }
@@ -4,6 +4,7 @@ pub mod context;
 pub mod contract;
 pub mod generators;
 pub mod happiness;
+pub mod injury;
 pub mod mailbox;
 pub mod player;
 pub mod position;
@@ -20,6 +21,7 @@ pub use context::*;
 pub use contract::*;
 pub use generators::*;
 pub use happiness::*;
+pub use injury::*;
 pub use mailbox::*;
 pub use player::*;
 pub use position::*;
@@ -1,7 +1,7 @@
 use crate::club::player::utils::PlayerUtils;
 use crate::club::{
-    PersonBehaviour, PlayerAttributes, PlayerClubContract, PlayerCollectionResult, PlayerMailbox,
-    PlayerResult, PlayerSkills, PlayerTraining, Staff,
+    Injury, PersonBehaviour, PlayerAttributes, PlayerClubContract, PlayerCollectionResult,
+    PlayerMailbox, PlayerResult, PlayerSkills, PlayerTraining, Staff,
 };
 use crate::context::GlobalContext;
 use crate::shared::fullname::FullName;
@@ -9,14 +9,23 @@ use crate::training::result::PlayerTrainingResult;
 use crate::utils::{DateUtils, Logging};
 use crate::{
     Person, PersonAttributes, PlayerHappiness, PlayerPositionType, PlayerPositions,
-    PlayerStatistics, PlayerStatisticsHistory, PlayerStatus, PlayerTrainingHistory,
-    PlayerValueCalculator, Relations,
+    PlayerStatistics, PlayerStatisticsHistory, PlayerStatus, PlayerStatusType,
+    PlayerTrainingHistory, PlayerValueCalculator, Relations,
 };
+use crate::r#match::statistics::MatchPlayerResult;
 use chrono::{NaiveDate, NaiveDateTime};
 use std::fmt::{Display, Formatter, Result};
 use std::ops::Index;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+const RED_CARD_SUSPENSION: u8 = 3;
+const SECOND_YELLOW_SUSPENSION: u8 = 1;
+
+// Every this many yellow cards in a season cost a match
+const YELLOW_CARDS_SUSPENSION_LIMIT: u8 = 5;
+const YELLOW_CARDS_SUSPENSION: u8 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Player {
     //person data
     pub id: u32,
@@ -41,6 +50,10 @@ pub struct Player {
 
     pub statistics: PlayerStatistics,
     pub statistics_history: PlayerStatisticsHistory,
+
+    // matches left to sit out
+    pub suspended_matches: u8,
+    pub injury: Option<Injury>,
 }
 
 impl Player {
@@ -75,6 +88,8 @@ impl Player {
             relations: Relations::new(),
             statistics: PlayerStatistics::default(),
             statistics_history: PlayerStatisticsHistory::new(),
+            suspended_matches: 0,
+            injury: None,
         }
     }
 
@@ -87,6 +102,7 @@ impl Player {
             self.behaviour.try_increase();
         }
 
+        self.process_injury(now.date());
         self.process_contract(&mut result, now);
         self.process_mailbox(&mut result, now.date());
 
@@ -97,6 +113,12 @@ impl Player {
         result
     }
 
+    fn process_injury(&mut self, now: NaiveDate) {
+        if self.injury.as_ref().is_some_and(|injury| injury.is_healed(now)) {
+            self.recover();
+        }
+    }
+
     fn process_contract(&mut self, result: &mut PlayerResult, now: NaiveDateTime) {
         if let Some(ref mut contract) = self.contract {
             const HALF_YEAR_DAYS: i64 = 30 * 6;
@@ -159,6 +181,86 @@ impl Player {
         }
     }
 
+    pub fn is_on_loan(&self) -> bool {
+        self.statuses.get().contains(&PlayerStatusType::Loa)
+    }
+
+    // A straight red costs more games than a second yellow, yellows are also
+    // punished once they add up over the season
+    pub fn add_match_cards(&mut self, result: &MatchPlayerResult, date: NaiveDate) {
+        let suspension = if result.red_cards > 0 {
+            if result.yellow_cards > 1 {
+                SECOND_YELLOW_SUSPENSION
+            } else {
+                RED_CARD_SUSPENSION
+            }
+        } else if result.yellow_cards > 0
+            && self
+                .statistics
+                .yellow_cards
+                .is_multiple_of(YELLOW_CARDS_SUSPENSION_LIMIT)
+        {
+            YELLOW_CARDS_SUSPENSION
+        } else {
+            0
+        };
+
+        if self.statistics.yellow_cards % YELLOW_CARDS_SUSPENSION_LIMIT
+            == YELLOW_CARDS_SUSPENSION_LIMIT - 1
+        {
+            if !self.statuses.get().contains(&PlayerStatusType::Yel) {
+                self.statuses.add(date, PlayerStatusType::Yel);
+            }
+        } else {
+            self.statuses.remove(PlayerStatusType::Yel);
+        }
+
+        if suspension > 0 {
+            self.suspend(suspension, date);
+        }
+    }
+
+    pub fn suspend(&mut self, matches: u8, date: NaiveDate) {
+        if self.suspended_matches == 0 {
+            self.statuses.add(date, PlayerStatusType::Sus);
+        }
+
+        self.suspended_matches = self.suspended_matches.saturating_add(matches);
+        self.player_attributes.is_banned = true;
+    }
+
+    // Called for every match of the team the player misses
+    pub fn serve_suspension(&mut self) {
+        if self.suspended_matches == 0 {
+            return;
+        }
+
+        self.suspended_matches -= 1;
+
+        if self.suspended_matches == 0 {
+            self.player_attributes.is_banned = false;
+            self.statuses.remove(PlayerStatusType::Sus);
+        }
+    }
+
+    // A longer injury on top of an existing one pushes the return date back
+    pub fn injure(&mut self, injury: Injury) {
+        match &self.injury {
+            Some(current) if current.return_date >= injury.return_date => return,
+            Some(_) => {}
+            None => self.statuses.add(injury.start_date, PlayerStatusType::Inj),
+        }
+
+        self.injury = Some(injury);
+        self.player_attributes.is_injured = true;
+    }
+
+    pub fn recover(&mut self) {
+        self.injury = None;
+        self.player_attributes.is_injured = false;
+        self.statuses.remove(PlayerStatusType::Inj);
+    }
+
     pub fn growth_potential(&self, now: NaiveDate) -> u8 {
         PlayerUtils::growth_potential(self, now)
     }
@@ -190,7 +292,7 @@ impl Person for Player {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum PlayerPreferredFoot {
     Left,
     Right,
@@ -204,9 +306,7 @@ impl Display for Player {
     }
 }
 
-const DEFAULT_PLAYER_TRANSFER_BUFFER_SIZE: usize = 10;
-
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerCollection {
     pub players: Vec<Player>,
 }
@@ -229,15 +329,7 @@ impl PlayerCollection {
             })
             .collect();
 
-        let mut outgoing_players = Vec::with_capacity(DEFAULT_PLAYER_TRANSFER_BUFFER_SIZE);
-
-        for transfer_request_player_id in player_results.iter().flat_map(|p| &p.transfer_requests) {
-            if let Some(player) = self.take_player(transfer_request_player_id) {
-                outgoing_players.push(player)
-            }
-        }
-
-        PlayerCollectionResult::new(player_results, outgoing_players)
+        PlayerCollectionResult::new(player_results)
     }
 
     pub fn by_position(&self, position: &PlayerPositionType) -> Vec<&Player> {
@@ -294,8 +386,108 @@ impl PartialEq for Player {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::{InjuryType, PlayerGenerator};
+
+    fn player() -> Player {
+        PlayerGenerator::generate(
+            1,
+            NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+            PlayerPositionType::DefenderCenter,
+            15,
+        )
+    }
+
+    fn cards(yellow_cards: u8, red_cards: u8) -> MatchPlayerResult {
+        MatchPlayerResult {
+            player_id: 1,
+            team_id: 1,
+            minutes: 90,
+            fouls: 2,
+            yellow_cards,
+            red_cards,
+            rating: 6.0,
+            ..Default::default()
+        }
+    }
+
+    fn play(player: &mut Player, result: &MatchPlayerResult) {
+        let date = NaiveDate::from_ymd_opt(2024, 9, 1).unwrap();
+
+        player.statistics.add_match(result);
+        player.add_match_cards(result, date);
+    }
+
     #[test]
     fn player_is_correct() {
         assert_eq!(10, 10);
     }
+
+    #[test]
+    fn red_card_bans_player_until_served() {
+        let mut player = player();
+
+        play(&mut player, &cards(0, 1));
+
+        assert!(player.player_attributes.is_banned);
+        assert_eq!(RED_CARD_SUSPENSION, player.suspended_matches);
+        assert!(player.statuses.get().contains(&PlayerStatusType::Sus));
+
+        for _ in 0..RED_CARD_SUSPENSION {
+            player.serve_suspension();
+        }
+
+        assert!(!player.player_attributes.is_banned);
+        assert!(!player.statuses.get().contains(&PlayerStatusType::Sus));
+    }
+
+    #[test]
+    fn second_yellow_is_shorter_ban() {
+        let mut player = player();
+
+        play(&mut player, &cards(2, 1));
+
+        assert_eq!(SECOND_YELLOW_SUSPENSION, player.suspended_matches);
+    }
+
+    #[test]
+    fn accumulated_yellow_cards_ban_player() {
+        let mut player = player();
+
+        for _ in 0..YELLOW_CARDS_SUSPENSION_LIMIT - 1 {
+            play(&mut player, &cards(1, 0));
+        }
+
+        assert!(!player.player_attributes.is_banned);
+        assert!(player.statuses.get().contains(&PlayerStatusType::Yel));
+
+        play(&mut player, &cards(1, 0));
+
+        assert!(player.player_attributes.is_banned);
+        assert_eq!(YELLOW_CARDS_SUSPENSION, player.suspended_matches);
+        assert!(!player.statuses.get().contains(&PlayerStatusType::Yel));
+    }
+
+    #[test]
+    fn injured_player_recovers_on_return_date() {
+        let mut player = player();
+        let date = NaiveDate::from_ymd_opt(2024, 9, 1).unwrap();
+
+        player.injure(Injury::new(InjuryType::AnkleSprain, date, 14));
+        player.injure(Injury::new(InjuryType::Bruise, date, 3));
+
+        assert!(player.player_attributes.is_injured);
+        assert_eq!(1, player.statuses.get().len());
+        assert_eq!(14, player.injury.as_ref().unwrap().days_left(date));
+
+        player.process_injury(date + chrono::Duration::days(13));
+
+        assert!(player.player_attributes.is_injured);
+
+        player.process_injury(date + chrono::Duration::days(14));
+
+        assert!(!player.player_attributes.is_injured);
+        assert!(player.injury.is_none());
+        assert!(!player.statuses.get().contains(&PlayerStatusType::Inj));
+    }
 }
@@ -1,7 +1,7 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter, Result};
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Serialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Serialize, Deserialize)]
 pub enum PlayerPositionType {
     Goalkeeper,
     Sweeper,
@@ -111,7 +111,7 @@ impl PlayerPositionType {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerPositions {
     pub positions: Vec<PlayerPosition>,
 }
@@ -148,9 +148,29 @@ impl PlayerPositions {
             None => 0,
         }
     }
+
+    // How well the player fills the position from 0 to 1, half as well when it's
+    // only a position of the same group they know
+    pub fn suitability(&self, position: PlayerPositionType) -> f32 {
+        let level = self.get_level(position);
+
+        if level > 0 {
+            return level as f32 / 20.0;
+        }
+
+        let group_level = self
+            .positions
+            .iter()
+            .filter(|p| p.position.position_group() == position.position_group())
+            .map(|p| p.level)
+            .max()
+            .unwrap_or(0);
+
+        group_level as f32 / 40.0
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerPosition {
     pub position: PlayerPositionType,
     pub level: u8,
@@ -224,7 +244,7 @@ mod tests {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum PlayerFieldPositionGroup {
     Goalkeeper,
     Defender,
@@ -1,17 +1,12 @@
-use crate::club::Player;
 use crate::simulator::SimulatorData;
 
 pub struct PlayerCollectionResult {
     pub players: Vec<PlayerResult>,
-    pub outgoing_players: Vec<Player>,
 }
 
 impl PlayerCollectionResult {
-    pub fn new(players: Vec<PlayerResult>, outgoing_players: Vec<Player>) -> Self {
-        PlayerCollectionResult {
-            players,
-            outgoing_players,
-        }
+    pub fn new(players: Vec<PlayerResult>) -> Self {
+        PlayerCollectionResult { players }
     }
 
     pub fn process(&self, data: &mut SimulatorData) {
@@ -1,4 +1,6 @@
-#[derive(Debug, Copy, Clone, Default)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 pub struct PlayerSkills {
     pub technical: Technical,
     pub mental: Mental,
@@ -23,7 +25,7 @@ impl PlayerSkills {
     }
 }
 
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 pub struct Technical {
     pub corners: f32,
     pub crossing: f32,
@@ -63,7 +65,7 @@ impl Technical {
     pub fn rest(&mut self) {}
 }
 
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 pub struct Mental {
     pub aggression: f32,
     pub anticipation: f32,
@@ -103,7 +105,7 @@ impl Mental {
     pub fn rest(&mut self) {}
 }
 
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
 pub struct Physical {
     pub acceleration: f32,
     pub agility: f32,
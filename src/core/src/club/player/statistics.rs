@@ -1,9 +1,12 @@
 use crate::league::Season;
+use crate::r#match::statistics::MatchPlayerResult;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct PlayerStatistics {
     pub played: u16,
     pub played_subs: u16,
+    pub minutes: u16,
 
     pub goals: u16,
     pub assists: u16,
@@ -12,21 +15,55 @@ pub struct PlayerStatistics {
     pub yellow_cards: u8,
     pub red_cards: u8,
 
+    // per game
     pub shots_on_target: f32,
     pub tackling: f32,
-    pub passes: u8,
+    pub passes: u16,
 
     pub average_rating: f32,
 }
 
-#[derive(Debug)]
+impl PlayerStatistics {
+    pub fn add_match(&mut self, result: &MatchPlayerResult) {
+        let previous_games = (self.played + self.played_subs) as f32;
+
+        if result.substitute {
+            self.played_subs += 1;
+        } else {
+            self.played += 1;
+        }
+
+        let games = previous_games + 1.0;
+
+        let average = |current: f32, value: f32| (current * previous_games + value) / games;
+
+        self.minutes = self.minutes.saturating_add(result.minutes as u16);
+        self.goals += result.goals as u16;
+        self.assists += result.assists as u16;
+        self.penalties += result.penalty_goals as u16;
+        self.yellow_cards = self.yellow_cards.saturating_add(result.yellow_cards);
+        self.red_cards = self.red_cards.saturating_add(result.red_cards);
+        self.passes = self.passes.saturating_add(result.passes);
+
+        self.shots_on_target = average(self.shots_on_target, result.shots_on_target as f32);
+        self.tackling = average(self.tackling, result.tackles as f32);
+        self.average_rating = average(self.average_rating, result.rating);
+
+        if result.player_of_the_match {
+            self.player_of_the_match = self.player_of_the_match.saturating_add(1);
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerStatisticsHistory {
     pub items: Vec<PlayerStatisticsHistoryItem>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerStatisticsHistoryItem {
     pub season: Season,
+    pub club_id: u32,
     pub statistics: PlayerStatistics,
 }
 
@@ -34,4 +71,77 @@ impl PlayerStatisticsHistory {
     pub fn new() -> Self {
         PlayerStatisticsHistory { items: Vec::new() }
     }
+
+    pub fn add(&mut self, season: Season, club_id: u32, statistics: PlayerStatistics) {
+        self.items.push(PlayerStatisticsHistoryItem {
+            season,
+            club_id,
+            statistics,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn match_result(
+        goals: u8,
+        shots_on_target: u16,
+        rating: f32,
+        substitute: bool,
+    ) -> MatchPlayerResult {
+        MatchPlayerResult {
+            player_id: 1,
+            team_id: 1,
+            minutes: 90,
+            substitute,
+            goals,
+            penalty_goals: goals.min(1),
+            assists: 1,
+            // off target attempts are left out of the average
+            shots: shots_on_target * 2,
+            shots_on_target,
+            passes: 30,
+            tackles: 2,
+            fouls: 1,
+            yellow_cards: 1,
+            rating,
+            player_of_the_match: goals > 0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn add_match_accumulates_totals_and_averages() {
+        let mut statistics = PlayerStatistics::default();
+
+        statistics.add_match(&match_result(2, 4, 8.0, false));
+        statistics.add_match(&match_result(0, 2, 6.0, true));
+
+        assert_eq!(1, statistics.played);
+        assert_eq!(1, statistics.played_subs);
+        assert_eq!(180, statistics.minutes);
+        assert_eq!(2, statistics.goals);
+        assert_eq!(2, statistics.assists);
+        assert_eq!(1, statistics.penalties);
+        assert_eq!(2, statistics.yellow_cards);
+        assert_eq!(60, statistics.passes);
+        assert_eq!(1, statistics.player_of_the_match);
+        assert_eq!(3.0, statistics.shots_on_target);
+        assert_eq!(2.0, statistics.tackling);
+        assert_eq!(7.0, statistics.average_rating);
+    }
+
+    #[test]
+    fn player_of_the_match_count_does_not_overflow() {
+        let mut statistics = PlayerStatistics {
+            player_of_the_match: u8::MAX,
+            ..Default::default()
+        };
+
+        statistics.add_match(&match_result(1, 1, 8.0, false));
+
+        assert_eq!(u8::MAX, statistics.player_of_the_match);
+    }
 }
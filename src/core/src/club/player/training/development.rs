@@ -0,0 +1,261 @@
+use crate::training::result::PlayerTrainingResult;
+use crate::training::skills::{MentalSkill, PhysicalSkill, TechnicalSkill};
+use crate::{Person, Player};
+use chrono::NaiveDate;
+
+pub const MIN_SKILL: f32 = 1.0;
+pub const MAX_SKILL: f32 = 20.0;
+
+// ages from which a kind of skill starts to fade, physical ones go first
+const PHYSICAL_PEAK_AGE: u8 = 29;
+const TECHNICAL_PEAK_AGE: u8 = 31;
+const MENTAL_PEAK_AGE: u8 = 33;
+
+// skill points lost a week for every year past the peak
+const PHYSICAL_DECLINE_PER_YEAR: f32 = 0.006;
+const TECHNICAL_DECLINE_PER_YEAR: f32 = 0.004;
+const MENTAL_DECLINE_PER_YEAR: f32 = 0.002;
+
+const PHYSICAL_SKILLS: [PhysicalSkill; 8] = [
+    PhysicalSkill::Acceleration,
+    PhysicalSkill::Agility,
+    PhysicalSkill::Balance,
+    PhysicalSkill::Jumping,
+    PhysicalSkill::NaturalFitness,
+    PhysicalSkill::Pace,
+    PhysicalSkill::Stamina,
+    PhysicalSkill::Strength,
+];
+
+const TECHNICAL_SKILLS: [TechnicalSkill; 14] = [
+    TechnicalSkill::Corners,
+    TechnicalSkill::Crossing,
+    TechnicalSkill::Dribbling,
+    TechnicalSkill::Finishing,
+    TechnicalSkill::FirstTouch,
+    TechnicalSkill::FreeKicks,
+    TechnicalSkill::Heading,
+    TechnicalSkill::LongShots,
+    TechnicalSkill::LongThrows,
+    TechnicalSkill::Marking,
+    TechnicalSkill::Passing,
+    TechnicalSkill::PenaltyTaking,
+    TechnicalSkill::Tackling,
+    TechnicalSkill::Technique,
+];
+
+const MENTAL_SKILLS: [MentalSkill; 14] = [
+    MentalSkill::Aggression,
+    MentalSkill::Anticipation,
+    MentalSkill::Bravery,
+    MentalSkill::Composure,
+    MentalSkill::Concentration,
+    MentalSkill::Decisions,
+    MentalSkill::Determination,
+    MentalSkill::Flair,
+    MentalSkill::Leadership,
+    MentalSkill::OffTheBall,
+    MentalSkill::Positioning,
+    MentalSkill::Teamwork,
+    MentalSkill::Vision,
+    MentalSkill::WorkRate,
+];
+
+pub struct PlayerDevelopment;
+
+impl PlayerDevelopment {
+    // Skill points every skill may still gain before the player reaches their
+    // potential, a potential of 0 is unknown and sets no limit
+    pub fn growth_room(player: &Player) -> f32 {
+        let potential_ability = player.player_attributes.potential_ability;
+
+        if potential_ability == 0 {
+            return MAX_SKILL;
+        }
+
+        (Self::ability_level(potential_ability) - Self::current_level(player)).max(0.0)
+    }
+
+    // Each skill grows from where it is, strong skills stay above weak ones
+    pub fn skill_ceiling(skill: f32, growth_room: f32) -> f32 {
+        (skill + growth_room).min(MAX_SKILL)
+    }
+
+    // Where the player stands on the skill scale, the recorded current ability
+    // isn't kept up with training so the skills count once they're higher
+    fn current_level(player: &Player) -> f32 {
+        let skills = &player.skills;
+
+        let skills_level =
+            (skills.technical.average() + skills.mental.average() + skills.physical.average())
+                / 3.0;
+
+        Self::ability_level(player.player_attributes.current_ability).max(skills_level)
+    }
+
+    // Abilities from 0 to 200 on the skill scale from 1 to 20
+    fn ability_level(ability: u8) -> f32 {
+        (ability as f32 / 10.0).clamp(MIN_SKILL, MAX_SKILL)
+    }
+
+    // Share of a training gain that sticks, young players learn fastest
+    pub fn growth_factor(age: u8) -> f32 {
+        match age {
+            0..=18 => 1.0,
+            19..=21 => 0.8,
+            22..=24 => 0.55,
+            25..=27 => 0.3,
+            28..=30 => 0.15,
+            _ => 0.05,
+        }
+    }
+
+    // A gain stops at the ceiling and never lowers a skill already above it,
+    // a loss stops at the lowest level
+    pub fn apply(skill: &mut f32, change: f32, ceiling: f32) {
+        if change >= 0.0 {
+            if *skill < ceiling {
+                *skill = (*skill + change).min(ceiling);
+            }
+        } else {
+            *skill = (*skill + change).max(MIN_SKILL);
+        }
+    }
+
+    // A week of ageing past the peak years
+    pub fn decline(player: &Player, now: NaiveDate, result: &mut PlayerTrainingResult) {
+        let age = player.age(now);
+
+        let physical = Self::weekly_decline(age, PHYSICAL_PEAK_AGE, PHYSICAL_DECLINE_PER_YEAR);
+        let technical = Self::weekly_decline(age, TECHNICAL_PEAK_AGE, TECHNICAL_DECLINE_PER_YEAR);
+        let mental = Self::weekly_decline(age, MENTAL_PEAK_AGE, MENTAL_DECLINE_PER_YEAR);
+
+        if physical > 0.0 {
+            result
+                .physical
+                .skill_increase
+                .extend(PHYSICAL_SKILLS.iter().map(|skill| (*skill, -physical)));
+        }
+
+        if technical > 0.0 {
+            result
+                .technical
+                .skill_increase
+                .extend(TECHNICAL_SKILLS.iter().map(|skill| (*skill, -technical)));
+        }
+
+        if mental > 0.0 {
+            result
+                .mental
+                .skill_increase
+                .extend(MENTAL_SKILLS.iter().map(|skill| (*skill, -mental)));
+        }
+    }
+
+    fn weekly_decline(age: u8, peak_age: u8, per_year: f32) -> f32 {
+        age.saturating_sub(peak_age) as f32 * per_year
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::club::staff::staff_stub::StaffStub;
+    use crate::{PlayerGenerator, PlayerPositionType};
+    use chrono::{Datelike, Duration};
+
+    fn player(age: i32, potential_ability: u8) -> Player {
+        let today = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+
+        let mut player = PlayerGenerator::generate(1, today, PlayerPositionType::Striker, 15);
+        player.birth_date = NaiveDate::from_ymd_opt(today.year() - age, 1, 1).unwrap();
+        player.player_attributes.potential_ability = potential_ability;
+        player.attributes.ambition = 20.0;
+        player.attributes.professionalism = 20.0;
+
+        player
+    }
+
+    fn train_seasons(player: &mut Player, seasons: usize) {
+        let coach = StaffStub::default();
+
+        let mut date = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+
+        // four sessions a week
+        for _ in 0..(seasons * 52) {
+            for _ in 0..4 {
+                player
+                    .train(&coach, date.and_hms_opt(0, 0, 0).unwrap())
+                    .apply(player);
+            }
+            date += Duration::weeks(1);
+        }
+    }
+
+    #[test]
+    fn growth_stops_at_potential() {
+        let mut player = player(17, 105);
+
+        let free_kicks = player.skills.technical.free_kicks;
+
+        train_seasons(&mut player, 10);
+
+        assert!(player.skills.technical.free_kicks > free_kicks);
+        assert_eq!(0.0, PlayerDevelopment::growth_room(&player));
+
+        // skills out of the coach's focus are left as they were
+        assert_eq!(10.0, player.skills.technical.passing);
+    }
+
+    #[test]
+    fn ceiling_follows_each_skill_and_current_ability() {
+        let mut player = player(17, 150);
+
+        assert_eq!(5.0, PlayerDevelopment::growth_room(&player));
+        assert_eq!(12.0, PlayerDevelopment::skill_ceiling(7.0, 5.0));
+        assert_eq!(MAX_SKILL, PlayerDevelopment::skill_ceiling(18.0, 5.0));
+
+        // the recorded ability counts when the skills lag behind it
+        player.player_attributes.current_ability = 140;
+        assert_eq!(1.0, PlayerDevelopment::growth_room(&player));
+
+        player.player_attributes.current_ability = 160;
+        assert_eq!(0.0, PlayerDevelopment::growth_room(&player));
+    }
+
+    #[test]
+    fn unknown_potential_does_not_hold_growth_back() {
+        let mut player = player(17, 0);
+
+        assert_eq!(MAX_SKILL, PlayerDevelopment::growth_room(&player));
+
+        let free_kicks = player.skills.technical.free_kicks;
+
+        train_seasons(&mut player, 1);
+
+        assert!(player.skills.technical.free_kicks > free_kicks);
+    }
+
+    #[test]
+    fn veterans_decline_but_not_below_the_lowest_level() {
+        let date = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+
+        let mut veteran = player(36, 200);
+        let young = player(24, 200);
+
+        let mut result = PlayerTrainingResult::new(veteran.id);
+        PlayerDevelopment::decline(&young, date, &mut result);
+
+        assert!(result.physical.skill_increase.is_empty());
+
+        for _ in 0..(52 * 50) {
+            let mut result = PlayerTrainingResult::new(veteran.id);
+            PlayerDevelopment::decline(&veteran, date, &mut result);
+            result.apply(&mut veteran);
+        }
+
+        assert_eq!(MIN_SKILL, veteran.skills.physical.pace);
+        assert!(veteran.skills.mental.vision >= MIN_SKILL);
+        assert!(veteran.skills.mental.vision < 10.0);
+    }
+}
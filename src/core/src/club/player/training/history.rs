@@ -1,15 +1,21 @@
 use crate::PlayerSkills;
 use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+// skills are written down at most this often
+const SKILL_RECORD_INTERVAL_WEEKS: i64 = 4;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerTrainingHistory {
     records: Vec<TrainingRecord>,
+    last_session: Option<NaiveDateTime>,
 }
 
 impl PlayerTrainingHistory {
     pub fn new() -> Self {
         PlayerTrainingHistory {
             records: Vec::new(),
+            last_session: None,
         }
     }
 
@@ -21,11 +27,30 @@ impl PlayerTrainingHistory {
         self.records.last()
     }
 
+    // Skills over time, oldest first
+    pub fn records(&self) -> &[TrainingRecord] {
+        &self.records
+    }
+
+    pub fn record_skills(&mut self, date: NaiveDateTime, skills: PlayerSkills) {
+        let is_due = self.records.last().is_none_or(|record| {
+            date.signed_duration_since(record.date).num_weeks() >= SKILL_RECORD_INTERVAL_WEEKS
+        });
+
+        if is_due {
+            self.add_record(TrainingRecord::new(date, skills));
+        }
+    }
+
+    pub fn add_session(&mut self, date: NaiveDateTime) {
+        self.last_session = Some(date);
+    }
+
     pub fn weeks_since_last_training(&self, now: NaiveDate) -> u32 {
         let mut weeks_since_last_training: u32 = 0;
 
-        if let Some(last_training_record) = self.records.last() {
-            let duration = now.signed_duration_since(last_training_record.date.date());
+        if let Some(last_session) = self.last_session {
+            let duration = now.signed_duration_since(last_session.date());
             weeks_since_last_training = duration.num_weeks() as u32;
         }
 
@@ -33,8 +58,14 @@ impl PlayerTrainingHistory {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrainingRecord {
-    date: NaiveDateTime,
-    _skills: PlayerSkills,
-}
\ No newline at end of file
+    pub date: NaiveDateTime,
+    pub skills: PlayerSkills,
+}
+
+impl TrainingRecord {
+    pub fn new(date: NaiveDateTime, skills: PlayerSkills) -> Self {
+        TrainingRecord { date, skills }
+    }
+}
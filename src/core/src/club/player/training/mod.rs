@@ -1,7 +1,9 @@
+pub mod development;
 pub mod history;
 pub mod result;
 pub mod skills;
 pub mod training;
 
+pub use development::*;
 pub use history::*;
 pub use training::*;
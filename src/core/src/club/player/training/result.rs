@@ -1,7 +1,7 @@
 ﻿use crate::training::skills::result::{
     PlayerTrainingMentalResult, PlayerTrainingPhysicalResult, PlayerTrainingTechnicalResult,
 };
-use crate::SimulatorData;
+use crate::{Player, SimulatorData};
 
 pub struct PlayerTrainingResult {
     pub player_id: u32,
@@ -20,9 +20,25 @@ impl PlayerTrainingResult {
         }
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.mental.skill_increase.is_empty()
+            && self.physical.skill_increase.is_empty()
+            && self.technical.skill_increase.is_empty()
+    }
+
+    pub fn apply(&self, player: &mut Player) {
+        self.mental.apply(player);
+        self.physical.apply(player);
+        self.technical.apply(player);
+    }
+
     pub fn process(&self, data: &mut SimulatorData) {
-        self.mental.process(data, self.player_id);
-        self.physical.process(data, self.player_id);
-        self.technical.process(data, self.player_id);
+        let date = data.date;
+
+        if let Some(player) = data.player_mut(self.player_id) {
+            self.apply(player);
+
+            player.training_history.record_skills(date, player.skills);
+        }
     }
 }
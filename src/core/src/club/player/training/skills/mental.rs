@@ -130,11 +130,46 @@ pub fn determine_mental_skills_to_increase(
                         skills_to_increase.push((MentalSkill::Leadership, increase_amount));
                     }
                 }
-                MentalFocusType::OffTheBall => {}
-                MentalFocusType::Positioning => {}
-                MentalFocusType::Teamwork => {}
-                MentalFocusType::Vision => {}
-                MentalFocusType::WorkRate => {}
+                MentalFocusType::OffTheBall => {
+                    if player.skills.mental.off_the_ball < 20.0 {
+                        let increase_amount = base_increase_amount
+                            * MENTAL_SKILL_INCREASE_SPEED_MAP[&MentalSkill::OffTheBall];
+
+                        skills_to_increase.push((MentalSkill::OffTheBall, increase_amount));
+                    }
+                }
+                MentalFocusType::Positioning => {
+                    if player.skills.mental.positioning < 20.0 {
+                        let increase_amount = base_increase_amount
+                            * MENTAL_SKILL_INCREASE_SPEED_MAP[&MentalSkill::Positioning];
+
+                        skills_to_increase.push((MentalSkill::Positioning, increase_amount));
+                    }
+                }
+                MentalFocusType::Teamwork => {
+                    if player.skills.mental.teamwork < 20.0 {
+                        let increase_amount = base_increase_amount
+                            * MENTAL_SKILL_INCREASE_SPEED_MAP[&MentalSkill::Teamwork];
+
+                        skills_to_increase.push((MentalSkill::Teamwork, increase_amount));
+                    }
+                }
+                MentalFocusType::Vision => {
+                    if player.skills.mental.vision < 20.0 {
+                        let increase_amount = base_increase_amount
+                            * MENTAL_SKILL_INCREASE_SPEED_MAP[&MentalSkill::Vision];
+
+                        skills_to_increase.push((MentalSkill::Vision, increase_amount));
+                    }
+                }
+                MentalFocusType::WorkRate => {
+                    if player.skills.mental.work_rate < 20.0 {
+                        let increase_amount = base_increase_amount
+                            * MENTAL_SKILL_INCREASE_SPEED_MAP[&MentalSkill::WorkRate];
+
+                        skills_to_increase.push((MentalSkill::WorkRate, increase_amount));
+                    }
+                }
             }
         }
     }
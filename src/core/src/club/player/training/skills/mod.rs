@@ -31,7 +31,12 @@ fn determine_base_value_to_skill_increase(
     let potential_ability_factor = player.player_attributes.potential_ability as f32 / 200.0;
     let current_ability_factor = player.player_attributes.current_ability as f32 / 200.0;
 
-    let ability_factor = (1.0 - (current_ability_factor / potential_ability_factor)) * 0.3;
+    // players without a known potential have no room to grow
+    let ability_factor = if potential_ability_factor > 0.0 {
+        (1.0 - (current_ability_factor / potential_ability_factor)).max(0.0) * 0.3
+    } else {
+        0.0
+    };
 
     base_value = base_value
         * ability_factor
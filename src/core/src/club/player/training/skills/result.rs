@@ -1,5 +1,6 @@
-﻿use crate::training::skills::{MentalSkill, PhysicalSkill, TechnicalSkill};
-use crate::SimulatorData;
+﻿use crate::training::development::PlayerDevelopment;
+use crate::training::skills::{MentalSkill, PhysicalSkill, TechnicalSkill};
+use crate::Player;
 
 pub struct PlayerTrainingTechnicalResult {
     pub skill_increase: Vec<(TechnicalSkill, f32)>,
@@ -12,29 +13,30 @@ impl PlayerTrainingTechnicalResult {
         }
     }
 
-    pub fn process(&self, data: &mut SimulatorData, player_id: u32) {
-        let player = match data.player_mut(player_id) {
-            Some(player) => player,
-            None => return,
-        };
-
-        for (skill, increase) in &self.skill_increase {
-            match skill {
-                TechnicalSkill::Corners => player.skills.technical.corners += increase,
-                TechnicalSkill::Crossing => player.skills.technical.crossing += increase,
-                TechnicalSkill::Dribbling => player.skills.technical.dribbling += increase,
-                TechnicalSkill::Finishing => player.skills.technical.finishing += increase,
-                TechnicalSkill::FirstTouch => player.skills.technical.first_touch += increase,
-                TechnicalSkill::FreeKicks => player.skills.technical.free_kicks += increase,
-                TechnicalSkill::Heading => player.skills.technical.heading += increase,
-                TechnicalSkill::LongShots => player.skills.technical.long_shots += increase,
-                TechnicalSkill::LongThrows => player.skills.technical.long_throws += increase,
-                TechnicalSkill::Marking => player.skills.technical.marking += increase,
-                TechnicalSkill::Passing => player.skills.technical.passing += increase,
-                TechnicalSkill::PenaltyTaking => player.skills.technical.penalty_taking += increase,
-                TechnicalSkill::Tackling => player.skills.technical.tackling += increase,
-                TechnicalSkill::Technique => player.skills.technical.technique += increase,
-            }
+    pub fn apply(&self, player: &mut Player) {
+        let growth_room = PlayerDevelopment::growth_room(player);
+
+        for (skill, change) in &self.skill_increase {
+            let value = match skill {
+                TechnicalSkill::Corners => &mut player.skills.technical.corners,
+                TechnicalSkill::Crossing => &mut player.skills.technical.crossing,
+                TechnicalSkill::Dribbling => &mut player.skills.technical.dribbling,
+                TechnicalSkill::Finishing => &mut player.skills.technical.finishing,
+                TechnicalSkill::FirstTouch => &mut player.skills.technical.first_touch,
+                TechnicalSkill::FreeKicks => &mut player.skills.technical.free_kicks,
+                TechnicalSkill::Heading => &mut player.skills.technical.heading,
+                TechnicalSkill::LongShots => &mut player.skills.technical.long_shots,
+                TechnicalSkill::LongThrows => &mut player.skills.technical.long_throws,
+                TechnicalSkill::Marking => &mut player.skills.technical.marking,
+                TechnicalSkill::Passing => &mut player.skills.technical.passing,
+                TechnicalSkill::PenaltyTaking => &mut player.skills.technical.penalty_taking,
+                TechnicalSkill::Tackling => &mut player.skills.technical.tackling,
+                TechnicalSkill::Technique => &mut player.skills.technical.technique,
+            };
+
+            let ceiling = PlayerDevelopment::skill_ceiling(*value, growth_room);
+
+            PlayerDevelopment::apply(value, *change, ceiling);
         }
     }
 }
@@ -50,29 +52,30 @@ impl PlayerTrainingMentalResult {
         }
     }
 
-    pub fn process(&self, data: &mut SimulatorData, player_id: u32) {
-        let player = match data.player_mut(player_id) {
-            Some(player) => player,
-            None => return,
-        };
-
-        for (skill, increase) in &self.skill_increase {
-            match skill {
-                MentalSkill::Aggression => player.skills.mental.aggression += increase,
-                MentalSkill::Anticipation => player.skills.mental.anticipation += increase,
-                MentalSkill::Bravery => player.skills.mental.bravery += increase,
-                MentalSkill::Composure => player.skills.mental.composure += increase,
-                MentalSkill::Concentration => player.skills.mental.concentration += increase,
-                MentalSkill::Decisions => player.skills.mental.decisions += increase,
-                MentalSkill::Determination => player.skills.mental.determination += increase,
-                MentalSkill::Flair => player.skills.mental.flair += increase,
-                MentalSkill::Leadership => player.skills.mental.leadership += increase,
-                MentalSkill::OffTheBall => player.skills.mental.off_the_ball += increase,
-                MentalSkill::Positioning => player.skills.mental.positioning += increase,
-                MentalSkill::Teamwork => player.skills.mental.teamwork += increase,
-                MentalSkill::Vision => player.skills.mental.vision += increase,
-                MentalSkill::WorkRate => player.skills.mental.work_rate += increase,
-            }
+    pub fn apply(&self, player: &mut Player) {
+        let growth_room = PlayerDevelopment::growth_room(player);
+
+        for (skill, change) in &self.skill_increase {
+            let value = match skill {
+                MentalSkill::Aggression => &mut player.skills.mental.aggression,
+                MentalSkill::Anticipation => &mut player.skills.mental.anticipation,
+                MentalSkill::Bravery => &mut player.skills.mental.bravery,
+                MentalSkill::Composure => &mut player.skills.mental.composure,
+                MentalSkill::Concentration => &mut player.skills.mental.concentration,
+                MentalSkill::Decisions => &mut player.skills.mental.decisions,
+                MentalSkill::Determination => &mut player.skills.mental.determination,
+                MentalSkill::Flair => &mut player.skills.mental.flair,
+                MentalSkill::Leadership => &mut player.skills.mental.leadership,
+                MentalSkill::OffTheBall => &mut player.skills.mental.off_the_ball,
+                MentalSkill::Positioning => &mut player.skills.mental.positioning,
+                MentalSkill::Teamwork => &mut player.skills.mental.teamwork,
+                MentalSkill::Vision => &mut player.skills.mental.vision,
+                MentalSkill::WorkRate => &mut player.skills.mental.work_rate,
+            };
+
+            let ceiling = PlayerDevelopment::skill_ceiling(*value, growth_room);
+
+            PlayerDevelopment::apply(value, *change, ceiling);
         }
     }
 }
@@ -88,23 +91,24 @@ impl PlayerTrainingPhysicalResult {
         }
     }
 
-    pub fn process(&self, data: &mut SimulatorData, player_id: u32) {
-        let player = match data.player_mut(player_id) {
-            Some(player) => player,
-            None => return,
-        };
-
-        for (skill, increase) in &self.skill_increase {
-            match skill {
-                PhysicalSkill::Acceleration => player.skills.physical.acceleration += increase,
-                PhysicalSkill::Agility => player.skills.physical.agility += increase,
-                PhysicalSkill::Balance => player.skills.physical.balance += increase,
-                PhysicalSkill::Jumping => player.skills.physical.jumping += increase,
-                PhysicalSkill::NaturalFitness => player.skills.physical.natural_fitness += increase,
-                PhysicalSkill::Pace => player.skills.physical.pace += increase,
-                PhysicalSkill::Stamina => player.skills.physical.stamina += increase,
-                PhysicalSkill::Strength => player.skills.physical.strength += increase,
-            }
+    pub fn apply(&self, player: &mut Player) {
+        let growth_room = PlayerDevelopment::growth_room(player);
+
+        for (skill, change) in &self.skill_increase {
+            let value = match skill {
+                PhysicalSkill::Acceleration => &mut player.skills.physical.acceleration,
+                PhysicalSkill::Agility => &mut player.skills.physical.agility,
+                PhysicalSkill::Balance => &mut player.skills.physical.balance,
+                PhysicalSkill::Jumping => &mut player.skills.physical.jumping,
+                PhysicalSkill::NaturalFitness => &mut player.skills.physical.natural_fitness,
+                PhysicalSkill::Pace => &mut player.skills.physical.pace,
+                PhysicalSkill::Stamina => &mut player.skills.physical.stamina,
+                PhysicalSkill::Strength => &mut player.skills.physical.strength,
+            };
+
+            let ceiling = PlayerDevelopment::skill_ceiling(*value, growth_room);
+
+            PlayerDevelopment::apply(value, *change, ceiling);
         }
     }
 }
@@ -3,10 +3,12 @@ use crate::training::skills::{
     determine_mental_skills_to_increase, determine_physical_skills_to_increase,
     determine_technical_skills_to_increase,
 };
-use crate::{Player, Staff};
+use crate::training::development::PlayerDevelopment;
+use crate::{Person, Player, Staff};
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PlayerTraining {}
 
 impl PlayerTraining {
@@ -23,36 +25,50 @@ impl PlayerTraining {
 
         let weeks_since_last_training = training_history.weeks_since_last_training(now);
 
+        let growth = PlayerDevelopment::growth_factor(player.age(now));
+
         // technical
         result
             .technical
             .skill_increase
-            .extend(determine_technical_skills_to_increase(
-                now,
-                weeks_since_last_training,
-                coach,
-                player,
-            ));
+            .extend(
+                determine_technical_skills_to_increase(
+                    now,
+                    weeks_since_last_training,
+                    coach,
+                    player,
+                )
+                .into_iter()
+                .map(|(skill, amount)| (skill, amount * growth)),
+            );
 
         result
             .mental
             .skill_increase
-            .extend(determine_mental_skills_to_increase(
-                now,
-                weeks_since_last_training,
-                coach,
-                player,
-            ));
+            .extend(
+                determine_mental_skills_to_increase(
+                    now,
+                    weeks_since_last_training,
+                    coach,
+                    player,
+                )
+                .into_iter()
+                .map(|(skill, amount)| (skill, amount * growth)),
+            );
 
         result
             .physical
             .skill_increase
-            .extend(determine_physical_skills_to_increase(
-                now,
-                weeks_since_last_training,
-                coach,
-                player,
-            ));
+            .extend(
+                determine_physical_skills_to_increase(
+                    now,
+                    weeks_since_last_training,
+                    coach,
+                    player,
+                )
+                .into_iter()
+                .map(|(skill, amount)| (skill, amount * growth)),
+            );
 
         result
     }
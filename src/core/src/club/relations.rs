@@ -1,6 +1,7 @@
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Relations {
     players: RelationStore,
     _staffs: RelationStore,
@@ -38,7 +39,7 @@ impl Relations {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RelationStore {
     pub relation_data: HashMap<u32, f32>,
 }
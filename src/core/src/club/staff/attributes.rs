@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StaffAttributes {
     pub coaching: StaffCoaching,
     pub goalkeeping: StaffGoalkeeperCoaching,
@@ -8,7 +10,7 @@ pub struct StaffAttributes {
     pub medical: StaffMedical,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StaffCoaching {
     pub attacking: u8,
     pub defending: u8,
@@ -19,14 +21,14 @@ pub struct StaffCoaching {
     pub working_with_youngsters: u8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StaffGoalkeeperCoaching {
     pub distribution: u8,
     pub handling: u8,
     pub shot_stopping: u8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StaffMental {
     pub adaptability: u8,
     pub determination: u8,
@@ -35,21 +37,21 @@ pub struct StaffMental {
     pub motivating: u8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StaffKnowledge {
     pub judging_player_ability: u8,
     pub judging_player_potential: u8,
     pub tactical_knowledge: u8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StaffDataAnalysis {
     pub judging_player_data: u8,
     pub judging_team_data: u8,
     pub presenting_data: u8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StaffMedical {
     pub physiotherapy: u8,
     pub sports_science: u8,
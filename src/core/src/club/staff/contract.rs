@@ -1,7 +1,8 @@
 use crate::context::SimulationContext;
 pub use chrono::prelude::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum StaffPosition {
     Free,
     Coach,
@@ -27,13 +28,13 @@ pub enum StaffPosition {
     CaretakerManager,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum StaffStatus {
     Active,
     ExpiredContract,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StaffClubContract {
     expired: NaiveDate,
     pub salary: u32,
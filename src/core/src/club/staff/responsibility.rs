@@ -1,4 +1,6 @@
-#[derive(Debug, Default)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct StaffResponsibility {
     pub board: BoardResponsibility,
     pub recruitment: RecruitmentResponsibility,
@@ -9,19 +11,19 @@ pub struct StaffResponsibility {
     pub training: TrainingResponsibility,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct BoardResponsibility {
     pub hire_fire_director: Option<u32>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct RecruitmentResponsibility {
     pub hire_fire_head_of_youth_development: Option<u32>,
     pub hire_fire_chief_scout: Option<u32>,
     pub hire_fire_other_staff: Option<u32>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct IncomingTransfersResponsibility {
     pub find_and_make_offers_first_team: Option<u32>,
     pub finalize_first_team_signings: Option<u32>,
@@ -30,13 +32,13 @@ pub struct IncomingTransfersResponsibility {
     pub finalize_youth_team_signings: Option<u32>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct OutgoingTransfersResponsibility {
     pub find_clubs_for_transfers_and_loans_listed_first_team: Option<u32>,
     pub find_clubs_for_transfers_and_loans_listed_youth_team: Option<u32>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ContractRenewalResponsibility {
     pub handle_first_team_contracts: Option<u32>,
     pub handle_youth_team_contracts: Option<u32>,
@@ -44,13 +46,13 @@ pub struct ContractRenewalResponsibility {
     pub handle_other_staff_contracts: Option<u32>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ScoutingResponsibility {
     pub handle_scouting_tasks: Option<u32>,
     pub updates_you_on_players_found: Option<u32>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct TrainingResponsibility {
     pub training_first_team: Option<u32>,
     pub training_youth_team: Option<u32>,
@@ -11,8 +11,9 @@ use crate::{
 };
 use chrono::{NaiveDate, NaiveDateTime};
 use std::fmt::{Display, Formatter, Result};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Staff {
     pub id: u32,
     pub full_name: FullName,
@@ -84,7 +85,7 @@ impl Display for Staff {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StaffCollection {
     pub staffs: Vec<Staff>,
 
@@ -176,22 +177,22 @@ impl StaffCollection {
         self.get_by_id(staff_id.unwrap())
     }
 
-    fn get_by_position(&self, position: StaffPosition) -> &Staff {
-        let staffs: Vec<&Staff> = self
-            .staffs
-            .iter()
-            .filter(|staff| {
-                staff.contract.is_some() && staff.contract.as_ref().unwrap().position == position
-            })
-            .collect();
+    // Head of the medical department, or any physio when there is none
+    pub fn physio(&self) -> &Staff {
+        self.find_by_position(StaffPosition::HeadOfPhysio)
+            .unwrap_or_else(|| self.get_by_position(StaffPosition::Physio))
+    }
 
-        if staffs.is_empty() {
-            return &self.stub;
-        }
+    fn get_by_position(&self, position: StaffPosition) -> &Staff {
+        self.find_by_position(position).unwrap_or(&self.stub)
+    }
 
+    fn find_by_position(&self, position: StaffPosition) -> Option<&Staff> {
         //TODO most relevant
 
-        staffs.first().unwrap()
+        self.staffs.iter().find(|staff| {
+            staff.contract.is_some() && staff.contract.as_ref().unwrap().position == position
+        })
     }
 
     fn get_by_id(&self, id: u32) -> &Staff {
@@ -225,7 +226,7 @@ impl Person for Staff {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum StaffLicenseType {
     ContinentalPro,
     ContinentalA,
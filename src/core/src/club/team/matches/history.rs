@@ -1,9 +1,10 @@
 use crate::r#match::TeamScore;
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
 
 const DEFAULT_MATCH_LIST_SIZE: usize = 10;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MatchHistory {
     items: Vec<MatchHistoryItem>,
 }
@@ -18,9 +19,17 @@ impl MatchHistory {
     pub fn add(&mut self, item: MatchHistoryItem) {
         self.items.push(item);
     }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct MatchHistoryItem {
     date: NaiveDateTime,
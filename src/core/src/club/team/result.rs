@@ -1,6 +1,5 @@
 use crate::club::team::behaviour::TeamBehaviourResult;
 use crate::club::PlayerCollectionResult;
-use crate::shared::{Currency, CurrencyValue};
 use crate::simulator::SimulatorData;
 use crate::{StaffCollectionResult, TeamTrainingResult};
 
@@ -30,18 +29,21 @@ impl TeamResult {
     }
 
     pub fn process(&self, data: &mut SimulatorData) {
+        let date = data.date.date();
+
         let team = data.team_mut(self.team_id).unwrap();
 
-        for player_result in &self.players.players {
-            team.add_player_to_transfer_list(
-                player_result.player_id,
-                CurrencyValue {
-                    amount: 100000 as f64,
-                    currency: Currency::Usd,
-                },
-            )
+        for player_id in self
+            .players
+            .players
+            .iter()
+            .flat_map(|player_result| &player_result.transfer_requests)
+        {
+            team.list_player(*player_id, date);
         }
 
+        team.list_surplus_players(date);
+
         self.players.process(data);
         self.staffs.process(data);
         self.training.process(data);
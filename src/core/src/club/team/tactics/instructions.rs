@@ -0,0 +1,222 @@
+use crate::club::Staff;
+use crate::MatchTacticType;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mentality {
+    VeryDefensive,
+    Defensive,
+    #[default]
+    Balanced,
+    Attacking,
+    VeryAttacking,
+}
+
+impl Mentality {
+    pub fn more_attacking(&self) -> Self {
+        match self {
+            Mentality::VeryDefensive => Mentality::Defensive,
+            Mentality::Defensive => Mentality::Balanced,
+            Mentality::Balanced => Mentality::Attacking,
+            Mentality::Attacking | Mentality::VeryAttacking => Mentality::VeryAttacking,
+        }
+    }
+
+    pub fn more_defensive(&self) -> Self {
+        match self {
+            Mentality::VeryDefensive | Mentality::Defensive => Mentality::VeryDefensive,
+            Mentality::Balanced => Mentality::Defensive,
+            Mentality::Attacking => Mentality::Balanced,
+            Mentality::VeryAttacking => Mentality::Attacking,
+        }
+    }
+
+    // Share of the pitch length, from the own goal, past which midfielders join the attack
+    pub fn support_line(&self) -> f32 {
+        match self {
+            Mentality::VeryDefensive => 0.75,
+            Mentality::Defensive => 0.7,
+            Mentality::Balanced => 2.0 / 3.0,
+            Mentality::Attacking => 0.6,
+            Mentality::VeryAttacking => 0.55,
+        }
+    }
+
+    // Scales how many chances a side goes for
+    pub fn chances_factor(&self) -> f32 {
+        match self {
+            Mentality::VeryDefensive => 0.7,
+            Mentality::Defensive => 0.85,
+            Mentality::Balanced => 1.0,
+            Mentality::Attacking => 1.15,
+            Mentality::VeryAttacking => 1.3,
+        }
+    }
+
+    // Scales how good the chances a side leaves the opponent are
+    pub fn exposure_factor(&self) -> f32 {
+        match self {
+            Mentality::VeryDefensive => 0.85,
+            Mentality::Defensive => 0.92,
+            Mentality::Balanced => 1.0,
+            Mentality::Attacking => 1.08,
+            Mentality::VeryAttacking => 1.15,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PressingIntensity {
+    Low,
+    #[default]
+    Standard,
+    High,
+}
+
+impl PressingIntensity {
+    // Scales how far from the ball carrier players start closing them down
+    pub fn distance_factor(&self) -> f32 {
+        match self {
+            PressingIntensity::Low => 0.7,
+            PressingIntensity::Standard => 1.0,
+            PressingIntensity::High => 1.4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DefensiveLine {
+    Deep,
+    #[default]
+    Standard,
+    High,
+}
+
+impl DefensiveLine {
+    // How far up the pitch the back line stands at kick-off, in field units
+    pub fn offset(&self) -> f32 {
+        match self {
+            DefensiveLine::Deep => -25.0,
+            DefensiveLine::Standard => 0.0,
+            DefensiveLine::High => 25.0,
+        }
+    }
+
+    // Scales how far defenders follow an attack up the pitch
+    pub fn push_up_factor(&self) -> f32 {
+        match self {
+            DefensiveLine::Deep => 0.85,
+            DefensiveLine::Standard => 1.0,
+            DefensiveLine::High => 1.15,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Width {
+    Narrow,
+    #[default]
+    Standard,
+    Wide,
+}
+
+impl Width {
+    // Scales the distance of outfield players from the middle of the pitch
+    pub fn spread(&self) -> f32 {
+        match self {
+            Width::Narrow => 0.85,
+            Width::Standard => 1.0,
+            Width::Wide => 1.12,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Tempo {
+    Slow,
+    #[default]
+    Standard,
+    Fast,
+}
+
+impl Tempo {
+    // Scales how long a player keeps the ball before moving it on
+    pub fn possession_time_factor(&self) -> f32 {
+        match self {
+            Tempo::Slow => 1.5,
+            Tempo::Standard => 1.0,
+            Tempo::Fast => 0.6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PassingDirectness {
+    Short,
+    #[default]
+    Mixed,
+    Direct,
+}
+
+impl PassingDirectness {
+    // Scales how far away a passer looks for a teammate
+    pub fn pass_range_factor(&self) -> f32 {
+        match self {
+            PassingDirectness::Short => 0.7,
+            PassingDirectness::Mixed => 1.0,
+            PassingDirectness::Direct => 1.4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamInstructions {
+    pub mentality: Mentality,
+    pub pressing: PressingIntensity,
+    pub defensive_line: DefensiveLine,
+    pub width: Width,
+    pub tempo: Tempo,
+    pub passing: PassingDirectness,
+}
+
+impl TeamInstructions {
+    // What the coach asks of the team, from their coaching strengths and the formation
+    pub fn for_coach(coach: &Staff, tactic_type: MatchTacticType) -> Self {
+        let coaching = &coach.staff_attributes.coaching;
+
+        let mentality = match coaching.attacking as i16 - coaching.defending as i16 {
+            diff if diff >= 8 => Mentality::VeryAttacking,
+            diff if diff >= 4 => Mentality::Attacking,
+            diff if diff <= -8 => Mentality::VeryDefensive,
+            diff if diff <= -4 => Mentality::Defensive,
+            _ => Mentality::Balanced,
+        };
+
+        let pressing = match coaching.fitness {
+            fitness if fitness >= 15 => PressingIntensity::High,
+            fitness if fitness <= 7 => PressingIntensity::Low,
+            _ => PressingIntensity::Standard,
+        };
+
+        let (defensive_line, tempo) = match mentality {
+            Mentality::Attacking | Mentality::VeryAttacking => (DefensiveLine::High, Tempo::Fast),
+            Mentality::Defensive | Mentality::VeryDefensive => (DefensiveLine::Deep, Tempo::Slow),
+            Mentality::Balanced => (DefensiveLine::Standard, Tempo::Standard),
+        };
+
+        let passing = match coaching.technical {
+            technical if technical >= 15 => PassingDirectness::Short,
+            technical if technical <= 7 => PassingDirectness::Direct,
+            _ => PassingDirectness::Mixed,
+        };
+
+        TeamInstructions {
+            mentality,
+            pressing,
+            defensive_line,
+            width: tactic_type.natural_width(),
+            tempo,
+            passing,
+        }
+    }
+}
@@ -1,4 +1,8 @@
+pub mod instructions;
+pub mod roles;
 pub mod tactics;
 pub mod types;
 
+pub use instructions::*;
+pub use roles::*;
 pub use tactics::*;
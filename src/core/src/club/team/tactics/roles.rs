@@ -0,0 +1,341 @@
+use crate::{
+    DefensiveLine, Mentality, PassingDirectness, PlayerPositionType, PlayerSkills,
+    PressingIntensity, TeamInstructions, Width,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Duty {
+    Defend,
+    #[default]
+    Support,
+    Attack,
+}
+
+impl Duty {
+    pub fn for_mentality(mentality: Mentality) -> Self {
+        match mentality {
+            Mentality::VeryDefensive | Mentality::Defensive => Duty::Defend,
+            Mentality::Balanced => Duty::Support,
+            Mentality::Attacking | Mentality::VeryAttacking => Duty::Attack,
+        }
+    }
+
+    // Scales how far from their place a player goes to join an attack
+    pub fn advance_factor(&self) -> f32 {
+        match self {
+            Duty::Defend => 0.7,
+            Duty::Support => 1.0,
+            Duty::Attack => 1.3,
+        }
+    }
+
+    // Moves the line, as a share of the pitch length, past which a player joins the attack
+    pub fn support_line_shift(&self) -> f32 {
+        match self {
+            Duty::Defend => 0.1,
+            Duty::Support => 0.0,
+            Duty::Attack => -0.08,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlayerRole {
+    Goalkeeper,
+    SweeperKeeper,
+    CentralDefender,
+    BallPlayingDefender,
+    FullBack,
+    WingBack,
+    AnchorMan,
+    DeepLyingPlaymaker,
+    CentralMidfielder,
+    BoxToBoxMidfielder,
+    AdvancedPlaymaker,
+    WideMidfielder,
+    Winger,
+    InvertedWinger,
+    ShadowStriker,
+    AdvancedForward,
+    DeepLyingForward,
+    TargetMan,
+    Poacher,
+}
+
+impl PlayerRole {
+    pub fn fits(&self, position: PlayerPositionType) -> bool {
+        use PlayerPositionType::*;
+
+        match self {
+            PlayerRole::Goalkeeper | PlayerRole::SweeperKeeper => position == Goalkeeper,
+            PlayerRole::CentralDefender | PlayerRole::BallPlayingDefender => matches!(
+                position,
+                Sweeper | DefenderCenterLeft | DefenderCenter | DefenderCenterRight
+            ),
+            PlayerRole::FullBack => matches!(position, DefenderLeft | DefenderRight),
+            PlayerRole::WingBack => matches!(
+                position,
+                DefenderLeft | DefenderRight | WingbackLeft | WingbackRight
+            ),
+            PlayerRole::AnchorMan => position == DefensiveMidfielder,
+            PlayerRole::DeepLyingPlaymaker => matches!(
+                position,
+                DefensiveMidfielder
+                    | MidfielderCenterLeft
+                    | MidfielderCenter
+                    | MidfielderCenterRight
+            ),
+            PlayerRole::CentralMidfielder | PlayerRole::BoxToBoxMidfielder => matches!(
+                position,
+                MidfielderCenterLeft | MidfielderCenter | MidfielderCenterRight
+            ),
+            PlayerRole::AdvancedPlaymaker => matches!(
+                position,
+                MidfielderCenterLeft
+                    | MidfielderCenter
+                    | MidfielderCenterRight
+                    | AttackingMidfielderCenter
+            ),
+            PlayerRole::WideMidfielder => matches!(position, MidfielderLeft | MidfielderRight),
+            PlayerRole::Winger | PlayerRole::InvertedWinger => matches!(
+                position,
+                MidfielderLeft
+                    | MidfielderRight
+                    | AttackingMidfielderLeft
+                    | AttackingMidfielderRight
+            ),
+            PlayerRole::ShadowStriker => position == AttackingMidfielderCenter,
+            PlayerRole::AdvancedForward
+            | PlayerRole::DeepLyingForward
+            | PlayerRole::TargetMan
+            | PlayerRole::Poacher => {
+                matches!(
+                    position,
+                    Striker | ForwardLeft | ForwardCenter | ForwardRight
+                )
+            }
+        }
+    }
+
+    pub fn duties(&self) -> &'static [Duty] {
+        match self {
+            PlayerRole::Goalkeeper
+            | PlayerRole::CentralDefender
+            | PlayerRole::BallPlayingDefender
+            | PlayerRole::AnchorMan => &[Duty::Defend],
+            PlayerRole::DeepLyingPlaymaker => &[Duty::Defend, Duty::Support],
+            PlayerRole::TargetMan | PlayerRole::DeepLyingForward => &[Duty::Support, Duty::Attack],
+            PlayerRole::ShadowStriker | PlayerRole::Poacher => &[Duty::Attack],
+            _ => &[Duty::Defend, Duty::Support, Duty::Attack],
+        }
+    }
+
+    // How well the player's skills suit the role, from 0 to 1
+    pub fn rating(&self, skills: &PlayerSkills) -> f32 {
+        let (t, m, p) = (&skills.technical, &skills.mental, &skills.physical);
+
+        let key_skills = match self {
+            PlayerRole::Goalkeeper => vec![m.positioning, m.concentration, p.jumping, p.agility],
+            PlayerRole::SweeperKeeper => {
+                vec![m.anticipation, m.decisions, p.acceleration, t.passing]
+            }
+            PlayerRole::CentralDefender => vec![t.marking, t.tackling, t.heading, p.strength],
+            PlayerRole::BallPlayingDefender => vec![t.marking, t.tackling, t.passing, m.composure],
+            PlayerRole::FullBack => vec![t.marking, t.tackling, m.positioning, p.pace],
+            PlayerRole::WingBack => vec![t.crossing, t.tackling, p.stamina, p.pace],
+            PlayerRole::AnchorMan => vec![t.tackling, m.positioning, m.concentration, p.strength],
+            PlayerRole::DeepLyingPlaymaker => vec![t.passing, m.vision, m.composure, t.first_touch],
+            PlayerRole::CentralMidfielder => vec![t.passing, t.tackling, m.decisions, m.teamwork],
+            PlayerRole::BoxToBoxMidfielder => vec![t.passing, t.tackling, m.work_rate, p.stamina],
+            PlayerRole::AdvancedPlaymaker => vec![t.passing, t.technique, m.vision, m.flair],
+            PlayerRole::WideMidfielder => vec![t.crossing, t.passing, m.work_rate, p.stamina],
+            PlayerRole::Winger => vec![t.crossing, t.dribbling, p.pace, p.acceleration],
+            PlayerRole::InvertedWinger => vec![t.dribbling, t.long_shots, t.technique, p.pace],
+            PlayerRole::ShadowStriker => vec![t.finishing, m.off_the_ball, m.anticipation, p.pace],
+            PlayerRole::AdvancedForward => vec![t.finishing, t.dribbling, m.off_the_ball, p.pace],
+            PlayerRole::DeepLyingForward => vec![t.passing, t.first_touch, m.vision, t.finishing],
+            PlayerRole::TargetMan => vec![t.heading, t.first_touch, p.strength, p.jumping],
+            PlayerRole::Poacher => vec![t.finishing, m.anticipation, m.off_the_ball, m.composure],
+        };
+
+        key_skills.iter().sum::<f32>() / key_skills.len() as f32 / 20.0
+    }
+
+    // Scales how far from the ball carrier the player starts closing them down
+    pub fn pressing_factor(&self) -> f32 {
+        match self {
+            PlayerRole::AdvancedForward | PlayerRole::BoxToBoxMidfielder => 1.3,
+            PlayerRole::TargetMan | PlayerRole::AnchorMan | PlayerRole::DeepLyingPlaymaker => 0.8,
+            PlayerRole::Poacher => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    // Scales how far away the player looks for a teammate to pass to
+    pub fn pass_range_factor(&self) -> f32 {
+        match self {
+            PlayerRole::BallPlayingDefender | PlayerRole::DeepLyingPlaymaker => 1.3,
+            PlayerRole::AdvancedPlaymaker | PlayerRole::DeepLyingForward => 1.2,
+            PlayerRole::TargetMan => 0.7,
+            _ => 1.0,
+        }
+    }
+
+    // Scales how long the player runs with the ball before moving it on
+    pub fn carry_factor(&self) -> f32 {
+        match self {
+            PlayerRole::Winger | PlayerRole::InvertedWinger => 1.5,
+            PlayerRole::AdvancedPlaymaker | PlayerRole::BoxToBoxMidfielder => 1.2,
+            PlayerRole::DeepLyingPlaymaker | PlayerRole::TargetMan => 0.8,
+            _ => 1.0,
+        }
+    }
+
+    // Scales how far from goal the keeper comes off the line
+    pub fn coming_out_factor(&self) -> f32 {
+        match self {
+            PlayerRole::SweeperKeeper => 1.5,
+            _ => 1.0,
+        }
+    }
+
+    // Keeps the ball on the ground under pressure instead of clearing it
+    pub fn plays_out_from_back(&self) -> bool {
+        matches!(
+            self,
+            PlayerRole::BallPlayingDefender | PlayerRole::SweeperKeeper
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TacticalRole {
+    pub role: PlayerRole,
+    pub duty: Duty,
+}
+
+impl TacticalRole {
+    // A duty the role can't take is replaced with the nearest one it can
+    pub fn new(role: PlayerRole, duty: Duty) -> Self {
+        let duties = role.duties();
+
+        let duty = if duties.contains(&duty) {
+            duty
+        } else if duty == Duty::Defend {
+            duties[0]
+        } else {
+            duties[duties.len() - 1]
+        };
+
+        TacticalRole { role, duty }
+    }
+
+    // The role the coach's instructions ask of a position when none is set
+    pub fn for_position(position: PlayerPositionType, instructions: &TeamInstructions) -> Self {
+        use PlayerPositionType::*;
+
+        let duty = Duty::for_mentality(instructions.mentality);
+        let is_short_passing = instructions.passing == PassingDirectness::Short;
+        let is_direct_passing = instructions.passing == PassingDirectness::Direct;
+
+        let (role, duty) = match position {
+            Goalkeeper if instructions.defensive_line == DefensiveLine::High => {
+                (PlayerRole::SweeperKeeper, Duty::Support)
+            }
+            Goalkeeper => (PlayerRole::Goalkeeper, Duty::Defend),
+            Sweeper | DefenderCenterLeft | DefenderCenter | DefenderCenterRight => {
+                if is_short_passing {
+                    (PlayerRole::BallPlayingDefender, Duty::Defend)
+                } else {
+                    (PlayerRole::CentralDefender, Duty::Defend)
+                }
+            }
+            DefenderLeft | DefenderRight => match duty {
+                Duty::Attack => (PlayerRole::WingBack, Duty::Attack),
+                _ => (PlayerRole::FullBack, duty),
+            },
+            WingbackLeft | WingbackRight => (PlayerRole::WingBack, duty),
+            DefensiveMidfielder if is_short_passing => {
+                (PlayerRole::DeepLyingPlaymaker, Duty::Defend)
+            }
+            DefensiveMidfielder => (PlayerRole::AnchorMan, Duty::Defend),
+            MidfielderCenter if is_short_passing => (PlayerRole::DeepLyingPlaymaker, Duty::Support),
+            MidfielderCenterLeft | MidfielderCenter | MidfielderCenterRight => {
+                if instructions.pressing == PressingIntensity::High {
+                    (PlayerRole::BoxToBoxMidfielder, Duty::Support)
+                } else {
+                    (PlayerRole::CentralMidfielder, duty)
+                }
+            }
+            MidfielderLeft | MidfielderRight => (PlayerRole::WideMidfielder, duty),
+            AttackingMidfielderLeft | AttackingMidfielderRight => {
+                if instructions.width == Width::Narrow {
+                    (PlayerRole::InvertedWinger, Duty::Attack)
+                } else {
+                    (PlayerRole::Winger, Duty::Attack)
+                }
+            }
+            AttackingMidfielderCenter if instructions.mentality == Mentality::VeryAttacking => {
+                (PlayerRole::ShadowStriker, Duty::Attack)
+            }
+            AttackingMidfielderCenter => (PlayerRole::AdvancedPlaymaker, Duty::Attack),
+            // strike partners complement each other
+            ForwardLeft if is_direct_passing => (PlayerRole::TargetMan, Duty::Support),
+            ForwardLeft => (PlayerRole::DeepLyingForward, Duty::Support),
+            ForwardRight => (PlayerRole::Poacher, Duty::Attack),
+            Striker | ForwardCenter if is_direct_passing => (PlayerRole::TargetMan, Duty::Attack),
+            Striker | ForwardCenter => (PlayerRole::AdvancedForward, Duty::Attack),
+        };
+
+        TacticalRole::new(role, duty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_for_positions_fit_and_take_their_duties() {
+        let instructions = [
+            TeamInstructions::default(),
+            TeamInstructions {
+                mentality: Mentality::VeryAttacking,
+                pressing: PressingIntensity::High,
+                defensive_line: DefensiveLine::High,
+                width: Width::Narrow,
+                passing: PassingDirectness::Short,
+                ..TeamInstructions::default()
+            },
+            TeamInstructions {
+                mentality: Mentality::Defensive,
+                passing: PassingDirectness::Direct,
+                ..TeamInstructions::default()
+            },
+        ];
+
+        for instructions in &instructions {
+            for (_, positions) in crate::TACTICS_POSITIONS {
+                for position in positions {
+                    let role = TacticalRole::for_position(*position, instructions);
+
+                    assert!(role.role.fits(*position), "{:?} at {:?}", role, position);
+                    assert!(role.role.duties().contains(&role.duty));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unavailable_duty_is_replaced() {
+        assert_eq!(
+            Duty::Support,
+            TacticalRole::new(PlayerRole::TargetMan, Duty::Defend).duty
+        );
+        assert_eq!(
+            Duty::Support,
+            TacticalRole::new(PlayerRole::DeepLyingPlaymaker, Duty::Attack).duty
+        );
+    }
+}
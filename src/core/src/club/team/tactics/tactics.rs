@@ -1,14 +1,44 @@
-use crate::club::{PersonBehaviourState, PlayerPositionType, Staff};
-use crate::Team;
+use crate::club::{PlayerFieldPositionGroup, PlayerPositionType, Staff};
+use crate::{Player, TacticalRole, Team, TeamInstructions, Width};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tactics {
     pub tactic_type: MatchTacticType,
+    pub instructions: TeamInstructions,
+    // roles set for positions, the others follow from the instructions
+    pub roles: Vec<(PlayerPositionType, TacticalRole)>,
 }
 
 impl Tactics {
     pub fn new(tactic_type: MatchTacticType) -> Self {
-        Tactics { tactic_type }
+        Tactics {
+            tactic_type,
+            instructions: TeamInstructions::default(),
+            roles: Vec::new(),
+        }
+    }
+
+    pub fn with_instructions(mut self, instructions: TeamInstructions) -> Self {
+        self.instructions = instructions;
+        self
+    }
+
+    // A role that doesn't suit the position is ignored
+    pub fn with_role(mut self, position: PlayerPositionType, role: TacticalRole) -> Self {
+        if role.role.fits(position) {
+            self.roles.retain(|(p, _)| *p != position);
+            self.roles.push((position, role));
+        }
+        self
+    }
+
+    pub fn role(&self, position: PlayerPositionType) -> TacticalRole {
+        self.roles
+            .iter()
+            .find(|(p, _)| *p == position)
+            .map(|(_, role)| *role)
+            .unwrap_or_else(|| TacticalRole::for_position(position, &self.instructions))
     }
 
     pub fn positions(&self) -> &[PlayerPositionType; 11] {
@@ -21,50 +51,402 @@ impl Tactics {
     }
 }
 
-pub const TACTICS_POSITIONS: &[(MatchTacticType, [PlayerPositionType; 11])] = &[(
-    MatchTacticType::T442,
-    [
-        PlayerPositionType::Goalkeeper,
-        PlayerPositionType::DefenderLeft,
-        PlayerPositionType::DefenderCenterLeft,
-        PlayerPositionType::DefenderCenterRight,
-        PlayerPositionType::DefenderRight,
-        PlayerPositionType::MidfielderLeft,
-        PlayerPositionType::MidfielderCenterLeft,
-        PlayerPositionType::MidfielderCenterRight,
-        PlayerPositionType::MidfielderRight,
-        PlayerPositionType::ForwardLeft,
-        PlayerPositionType::ForwardRight,
-    ],
-)];
-
-#[derive(Debug, Eq, PartialEq, PartialOrd, Clone)]
+pub const TACTICS_POSITIONS: &[(MatchTacticType, [PlayerPositionType; 11])] = &[
+    (
+        MatchTacticType::T442,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::DefenderRight,
+            PlayerPositionType::MidfielderLeft,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::MidfielderRight,
+            PlayerPositionType::ForwardLeft,
+            PlayerPositionType::ForwardRight,
+        ],
+    ),
+    (
+        MatchTacticType::T442Diamond,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::DefenderRight,
+            PlayerPositionType::DefensiveMidfielder,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::AttackingMidfielderCenter,
+            PlayerPositionType::ForwardLeft,
+            PlayerPositionType::ForwardRight,
+        ],
+    ),
+    (
+        MatchTacticType::T442DiamondWide,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::DefenderRight,
+            PlayerPositionType::DefensiveMidfielder,
+            PlayerPositionType::MidfielderLeft,
+            PlayerPositionType::MidfielderRight,
+            PlayerPositionType::AttackingMidfielderCenter,
+            PlayerPositionType::ForwardLeft,
+            PlayerPositionType::ForwardRight,
+        ],
+    ),
+    (
+        MatchTacticType::T433,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::DefenderRight,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenter,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::AttackingMidfielderLeft,
+            PlayerPositionType::AttackingMidfielderRight,
+            PlayerPositionType::Striker,
+        ],
+    ),
+    (
+        MatchTacticType::T4231,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::DefenderRight,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::AttackingMidfielderLeft,
+            PlayerPositionType::AttackingMidfielderCenter,
+            PlayerPositionType::AttackingMidfielderRight,
+            PlayerPositionType::Striker,
+        ],
+    ),
+    (
+        MatchTacticType::T451,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::DefenderRight,
+            PlayerPositionType::MidfielderLeft,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenter,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::MidfielderRight,
+            PlayerPositionType::Striker,
+        ],
+    ),
+    (
+        MatchTacticType::T4141,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::DefenderRight,
+            PlayerPositionType::DefensiveMidfielder,
+            PlayerPositionType::MidfielderLeft,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::MidfielderRight,
+            PlayerPositionType::Striker,
+        ],
+    ),
+    (
+        MatchTacticType::T4411,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::DefenderRight,
+            PlayerPositionType::MidfielderLeft,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::MidfielderRight,
+            PlayerPositionType::AttackingMidfielderCenter,
+            PlayerPositionType::Striker,
+        ],
+    ),
+    (
+        MatchTacticType::T4312,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::DefenderRight,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenter,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::AttackingMidfielderCenter,
+            PlayerPositionType::ForwardLeft,
+            PlayerPositionType::ForwardRight,
+        ],
+    ),
+    (
+        MatchTacticType::T4222,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::DefenderRight,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::AttackingMidfielderLeft,
+            PlayerPositionType::AttackingMidfielderRight,
+            PlayerPositionType::ForwardLeft,
+            PlayerPositionType::ForwardRight,
+        ],
+    ),
+    (
+        MatchTacticType::T352,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenter,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::WingbackLeft,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenter,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::WingbackRight,
+            PlayerPositionType::ForwardLeft,
+            PlayerPositionType::ForwardRight,
+        ],
+    ),
+    (
+        MatchTacticType::T343,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenter,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::MidfielderLeft,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::MidfielderRight,
+            PlayerPositionType::AttackingMidfielderLeft,
+            PlayerPositionType::AttackingMidfielderRight,
+            PlayerPositionType::Striker,
+        ],
+    ),
+    (
+        MatchTacticType::T532,
+        [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::WingbackLeft,
+            PlayerPositionType::DefenderCenterLeft,
+            PlayerPositionType::DefenderCenter,
+            PlayerPositionType::DefenderCenterRight,
+            PlayerPositionType::WingbackRight,
+            PlayerPositionType::MidfielderCenterLeft,
+            PlayerPositionType::MidfielderCenter,
+            PlayerPositionType::MidfielderCenterRight,
+            PlayerPositionType::ForwardLeft,
+            PlayerPositionType::ForwardRight,
+        ],
+    ),
+];
+
+#[derive(Debug, Copy, Eq, PartialEq, PartialOrd, Clone, Serialize, Deserialize)]
 pub enum MatchTacticType {
     T442,
-    // T235,
-    // T451,
-    // T433,
-    // T442Diamond,
-    // T442DiamondWide,
-    // T442Narrow,
-    // T352,
-    // T4231,
-    // T4141,
-    // T4411,
-    // T343,
-    // T1333,
-    // T4312,
-    // T4222,
+    T442Diamond,
+    T442DiamondWide,
+    T433,
+    T4231,
+    T451,
+    T4141,
+    T4411,
+    T4312,
+    T4222,
+    T352,
+    T343,
+    T532,
 }
 
+impl MatchTacticType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MatchTacticType::T442 => "4-4-2",
+            MatchTacticType::T442Diamond => "4-4-2 Diamond",
+            MatchTacticType::T442DiamondWide => "4-4-2 Diamond Wide",
+            MatchTacticType::T433 => "4-3-3",
+            MatchTacticType::T4231 => "4-2-3-1",
+            MatchTacticType::T451 => "4-5-1",
+            MatchTacticType::T4141 => "4-1-4-1",
+            MatchTacticType::T4411 => "4-4-1-1",
+            MatchTacticType::T4312 => "4-3-1-2",
+            MatchTacticType::T4222 => "4-2-2-2",
+            MatchTacticType::T352 => "3-5-2",
+            MatchTacticType::T343 => "3-4-3",
+            MatchTacticType::T532 => "5-3-2",
+        }
+    }
+
+    // Formations without wide players play narrow, those built on them stretch the pitch
+    pub fn natural_width(&self) -> Width {
+        match self {
+            MatchTacticType::T442Diamond | MatchTacticType::T4312 | MatchTacticType::T4222 => {
+                Width::Narrow
+            }
+            MatchTacticType::T433
+            | MatchTacticType::T343
+            | MatchTacticType::T352
+            | MatchTacticType::T532 => Width::Wide,
+            _ => Width::Standard,
+        }
+    }
+}
+
+// how much the coach's attacking or defensive leaning weighs against the squad fit
+const COACH_PREFERENCE_WEIGHT: f32 = 0.25;
+
 pub struct TacticsSelector;
 
 impl TacticsSelector {
-    pub fn select(_: &Team, coach: &Staff) -> Tactics {
-        match coach.behaviour.state {
-            PersonBehaviourState::Poor => Tactics::new(MatchTacticType::T442),
-            PersonBehaviourState::Normal => Tactics::new(MatchTacticType::T442),
-            PersonBehaviourState::Good => Tactics::new(MatchTacticType::T442),
+    // The formation the available players fill best, leaning towards what the coach likes
+    pub fn select(team: &Team, coach: &Staff) -> Tactics {
+        let players: Vec<&Player> = team
+            .players
+            .players()
+            .into_iter()
+            .filter(|p| !p.player_attributes.is_injured && !p.player_attributes.is_banned)
+            .collect();
+
+        let mut best = (MatchTacticType::T442, f32::MIN);
+
+        for (tactic_type, positions) in TACTICS_POSITIONS {
+            let score =
+                Self::squad_fit(&players, positions) + Self::coach_preference(coach, positions);
+
+            if score > best.1 {
+                best = (*tactic_type, score);
+            }
+        }
+
+        let tactic_type = best.0;
+
+        Tactics::new(tactic_type).with_instructions(TeamInstructions::for_coach(coach, tactic_type))
+    }
+
+    // Sum of how well the best free player fills each position
+    fn squad_fit(players: &[&Player], positions: &[PlayerPositionType; 11]) -> f32 {
+        let mut taken: Vec<u32> = Vec::with_capacity(positions.len());
+
+        positions
+            .iter()
+            .map(|position| {
+                let best = players
+                    .iter()
+                    .filter(|p| !taken.contains(&p.id))
+                    .map(|p| (p.id, p.positions.suitability(*position)))
+                    .filter(|(_, suitability)| *suitability > 0.0)
+                    .max_by(|a, b| a.1.total_cmp(&b.1));
+
+                match best {
+                    Some((player_id, suitability)) => {
+                        taken.push(player_id);
+                        suitability
+                    }
+                    None => 0.0,
+                }
+            })
+            .sum()
+    }
+
+    // Attack-minded coaches favour more players up front, defensive ones fewer
+    fn coach_preference(coach: &Staff, positions: &[PlayerPositionType; 11]) -> f32 {
+        let coaching = &coach.staff_attributes.coaching;
+        let leaning = (coaching.attacking as f32 - coaching.defending as f32) / 20.0;
+
+        let attackers: f32 = positions
+            .iter()
+            .map(|position| match position {
+                PlayerPositionType::AttackingMidfielderLeft
+                | PlayerPositionType::AttackingMidfielderCenter
+                | PlayerPositionType::AttackingMidfielderRight => 0.5,
+                _ if position.position_group() == PlayerFieldPositionGroup::Forward => 1.0,
+                _ => 0.0,
+            })
+            .sum();
+
+        leaning * (attackers - 2.0) * COACH_PREFERENCE_WEIGHT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::club::staff::staff_stub::StaffStub;
+    use crate::r#match::POSITION_POSITIONING;
+    use crate::club::testing;
+    use crate::PlayerGenerator;
+    use chrono::NaiveDate;
+
+    fn team(positions: &[PlayerPositionType]) -> Team {
+        let date = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+
+        let players = positions
+            .iter()
+            .enumerate()
+            .map(|(idx, position)| {
+                let mut player = PlayerGenerator::generate(1, date, *position, 18);
+                player.id = idx as u32 + 1;
+                player
+            })
+            .collect();
+
+        testing::team(1, 1, 1, players)
+    }
+
+    fn positions(tactic_type: MatchTacticType) -> &'static [PlayerPositionType; 11] {
+        TACTICS_POSITIONS
+            .iter()
+            .find(|(t, _)| *t == tactic_type)
+            .map(|(_, positions)| positions)
+            .unwrap()
+    }
+
+    #[test]
+    fn formations_have_eleven_distinct_placed_positions() {
+        for (tactic_type, positions) in TACTICS_POSITIONS {
+            assert_eq!(positions[0], PlayerPositionType::Goalkeeper);
+
+            for (idx, position) in positions.iter().enumerate() {
+                assert!(
+                    !positions[..idx].contains(position),
+                    "{} repeats {}",
+                    tactic_type.name(),
+                    position
+                );
+                assert!(POSITION_POSITIONING.iter().any(|(p, _, _)| p == position));
+            }
+        }
+    }
+
+    #[test]
+    fn selector_picks_formation_fitting_squad() {
+        let coach = StaffStub::default();
+
+        for tactic_type in [MatchTacticType::T442, MatchTacticType::T343] {
+            let team = team(positions(tactic_type));
+
+            assert_eq!(TacticsSelector::select(&team, &coach).tactic_type, tactic_type);
         }
     }
 }
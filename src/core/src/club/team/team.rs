@@ -1,16 +1,19 @@
 use crate::club::team::behaviour::TeamBehaviour;
 use crate::context::GlobalContext;
 use crate::r#match::{SquadSelector, TeamSquad};
-use crate::shared::CurrencyValue;
+use crate::shared::{Currency, CurrencyValue};
+use crate::transfers::{LOAN_MAX_AGE, MAX_SQUAD_SIZE};
 use crate::{
-    MatchHistory, Player, PlayerCollection, StaffCollection, Tactics, MatchTacticType,
-    TacticsSelector, TeamReputation, TeamResult, TeamTraining, TrainingSchedule, TransferItem,
-    Transfers,
+    MatchHistory, Person, Player, PlayerCollection, PlayerStatusType, PlayerTransferStatus,
+    StaffCollection, Tactics, MatchTacticType, TacticsSelector, TeamReputation, TeamResult,
+    TeamTraining, TrainingSchedule, TransferItem, Transfers, WeeklyTrainingPlan,
 };
+use chrono::NaiveDate;
 use std::borrow::Cow;
 use std::str::FromStr;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum TeamType {
     Main = 0,
     B = 1,
@@ -20,7 +23,7 @@ pub enum TeamType {
     U23 = 5,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Team {
     pub id: u32,
     pub league_id: u32,
@@ -81,6 +84,102 @@ impl Team {
         })
     }
 
+    // Asking price is the player's market value on the day he is listed
+    pub fn list_player(&mut self, player_id: u32, date: NaiveDate) {
+        if self.transfer_list.contains(player_id) {
+            return;
+        }
+
+        let player = match self.players.players.iter_mut().find(|p| p.id == player_id) {
+            Some(player) => player,
+            None => return,
+        };
+
+        // loanees belong to their parent club
+        if player.is_on_loan() {
+            return;
+        }
+
+        let value = player.value(date);
+
+        if let Some(contract) = &mut player.contract {
+            contract.is_transfer_listed = true;
+            contract.transfer_status = match contract.transfer_status {
+                Some(PlayerTransferStatus::LoadListed) => {
+                    Some(PlayerTransferStatus::TransferAndLoadListed)
+                }
+                _ => Some(PlayerTransferStatus::TransferListed),
+            };
+        }
+
+        player.statuses.add(date, PlayerStatusType::Lst);
+
+        self.add_player_to_transfer_list(player_id, CurrencyValue::new(value, Currency::Usd));
+    }
+
+    pub fn list_player_for_loan(&mut self, player_id: u32) {
+        let player = match self.players.players.iter_mut().find(|p| p.id == player_id) {
+            Some(player) => player,
+            None => return,
+        };
+
+        if player.is_on_loan() {
+            return;
+        }
+
+        if let Some(contract) = &mut player.contract {
+            contract.transfer_status = match contract.transfer_status {
+                Some(PlayerTransferStatus::TransferListed) => {
+                    Some(PlayerTransferStatus::TransferAndLoadListed)
+                }
+                _ => Some(PlayerTransferStatus::LoadListed),
+            };
+        }
+    }
+
+    // The least valuable players over the squad limit: youngsters go out on loan,
+    // players a bit older may be either loaned or sold, the rest are sold
+    pub fn list_surplus_players(&mut self, date: NaiveDate) {
+        let is_listed = |player: &Player| {
+            player.is_on_loan()
+                || player
+                    .contract
+                    .as_ref()
+                    .is_some_and(|contract| contract.transfer_status.is_some())
+        };
+
+        let surplus = self
+            .players
+            .players
+            .len()
+            .saturating_sub(MAX_SQUAD_SIZE)
+            .saturating_sub(self.players.players.iter().filter(|p| is_listed(p)).count());
+
+        if surplus == 0 {
+            return;
+        }
+
+        let mut candidates: Vec<(u32, u8, f64)> = self
+            .players
+            .players
+            .iter()
+            .filter(|player| !is_listed(player))
+            .map(|player| (player.id, player.age(date), player.value(date)))
+            .collect();
+
+        candidates.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        for (player_id, age, _) in candidates.into_iter().take(surplus) {
+            if age <= LOAN_MAX_AGE + 2 {
+                self.list_player_for_loan(player_id);
+            }
+
+            if age > LOAN_MAX_AGE {
+                self.list_player(player_id, date);
+            }
+        }
+    }
+
     pub fn get_week_salary(&self) -> u32 {
         self.players
             .players
@@ -100,12 +199,15 @@ impl Team {
     pub fn get_match_squad(&self) -> TeamSquad {
         let head_coach = self.staffs.head_coach();
 
-        let squad = SquadSelector::select(self, head_coach);
+        // the coach picks the formation for the players available on the day
+        let tactics = TacticsSelector::select(self, head_coach);
+
+        let squad = SquadSelector::select(self, head_coach, &tactics);
 
         TeamSquad {
             team_id: self.id,
             team_name: self.name.clone(),
-            tactics: TacticsSelector::select(self, head_coach),
+            tactics,
             main_squad: squad.main_squad,
             substitutes: squad.substitutes,
         }
@@ -120,22 +222,28 @@ impl Team {
     }
 
     pub fn simulate(&mut self, ctx: GlobalContext<'_>) -> TeamResult {
+        if self.training_schedule.is_default && ctx.simulation.is_week_beginning() {
+            let coach = self.staffs.training_coach(&self.team_type);
+            self.training_schedule.plan = WeeklyTrainingPlan::for_coach(coach);
+        }
+
+        let match_days = ctx
+            .match_calendar
+            .map(|calendar| calendar.match_days(self.id))
+            .unwrap_or(&[]);
+
         let result = TeamResult::new(
             self.id,
             self.players.simulate(ctx.with_player(None)),
             self.staffs.simulate(ctx.with_staff(None)),
             TeamBehaviour::simulate(&mut self.players, &mut self.staffs),
-            TeamTraining::train(self, ctx.simulation.date),
+            TeamTraining::train(self, ctx.simulation.date, match_days),
         );
 
         if self.tactics.is_none() {
             self.tactics = Some(TacticsSelector::select(self, self.staffs.head_coach()));
         };
 
-        if self.training_schedule.is_default {
-            //let coach = self.staffs.head_coach();
-        }
-
         result
     }
 }
@@ -0,0 +1,48 @@
+use crate::league::ScheduleItem;
+use chrono::{Duration, NaiveDate};
+use std::collections::HashMap;
+
+const MATCH_CALENDAR_DAYS: i64 = 7;
+
+// Days teams play on around today, training is planned around them
+#[derive(Debug, Default, Clone)]
+pub struct MatchCalendar {
+    match_days: HashMap<u32, Vec<NaiveDate>>,
+}
+
+impl MatchCalendar {
+    pub fn new() -> Self {
+        MatchCalendar {
+            match_days: HashMap::new(),
+        }
+    }
+
+    // Fixtures of the week before and after the day
+    pub fn add_fixtures<'f>(
+        &mut self,
+        date: NaiveDate,
+        fixtures: impl Iterator<Item = &'f ScheduleItem>,
+    ) {
+        let from = date - Duration::days(MATCH_CALENDAR_DAYS);
+        let to = date + Duration::days(MATCH_CALENDAR_DAYS);
+
+        fixtures
+            .filter(|item| (from..=to).contains(&item.date.date()))
+            .for_each(|item| self.add(item));
+    }
+
+    pub fn add(&mut self, item: &ScheduleItem) {
+        let date = item.date.date();
+
+        for team_id in [item.home_team_id, item.away_team_id] {
+            self.match_days.entry(team_id).or_default().push(date);
+        }
+    }
+
+    pub fn match_days(&self, team_id: u32) -> &[NaiveDate] {
+        self.match_days
+            .get(&team_id)
+            .map(|days| days.as_slice())
+            .unwrap_or(&[])
+    }
+}
@@ -1,7 +1,11 @@
+mod calendar;
+mod plan;
 mod result;
 mod schedule;
 mod training;
 
+pub use calendar::*;
+pub use plan::*;
 pub use result::*;
 pub use schedule::*;
 pub use training::*;
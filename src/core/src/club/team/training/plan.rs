@@ -0,0 +1,380 @@
+use crate::training::result::PlayerTrainingResult;
+use crate::training::skills::TechnicalSkill;
+use crate::{PlayerAttributes, Staff, CONDITION_MAX_VALUE, MAX_JADEDNESS};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+// days around a session looked at for other matches, and how many make the fixtures congested
+const CONGESTION_DAYS: i64 = 3;
+const CONGESTED_MATCHES: usize = 2;
+
+// condition restored and jadedness shed by a day off or a recovery session
+const REST_RECOVERY: (i16, i16) = (2500, 400);
+const RECOVERY_SESSION_RECOVERY: (i16, i16) = (1500, 250);
+
+// a coach has to be this much better in one area to build the week around it
+const COACH_SPECIALITY_MARGIN: u8 = 3;
+
+const SET_PIECE_SKILLS: [TechnicalSkill; 4] = [
+    TechnicalSkill::Corners,
+    TechnicalSkill::FreeKicks,
+    TechnicalSkill::LongThrows,
+    TechnicalSkill::PenaltyTaking,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrainingSessionType {
+    Tactical,
+    Physical,
+    Technical,
+    SetPieces,
+    Recovery,
+    Rest,
+}
+
+impl TrainingSessionType {
+    // Share of the technical, mental and physical gains the session works on
+    fn focus(&self) -> (f32, f32, f32) {
+        match self {
+            TrainingSessionType::Tactical => (0.25, 1.0, 0.0),
+            TrainingSessionType::Physical => (0.0, 0.25, 1.0),
+            TrainingSessionType::Technical => (1.0, 0.25, 0.0),
+            TrainingSessionType::SetPieces => (1.0, 0.0, 0.0),
+            TrainingSessionType::Recovery | TrainingSessionType::Rest => (0.0, 0.0, 0.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrainingIntensity {
+    Low,
+    Medium,
+    High,
+}
+
+impl TrainingIntensity {
+    pub fn skill_gain_factor(&self) -> f32 {
+        match self {
+            TrainingIntensity::Low => 0.5,
+            TrainingIntensity::Medium => 1.0,
+            TrainingIntensity::High => 1.4,
+        }
+    }
+
+    pub fn injury_risk_factor(&self) -> f32 {
+        match self {
+            TrainingIntensity::Low => 0.4,
+            TrainingIntensity::Medium => 1.0,
+            TrainingIntensity::High => 2.0,
+        }
+    }
+
+    // condition lost and jadedness gained through a session
+    fn load(&self) -> (i16, i16) {
+        match self {
+            TrainingIntensity::Low => (300, 0),
+            TrainingIntensity::Medium => (800, 100),
+            TrainingIntensity::High => (1500, 300),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrainingSession {
+    pub session_type: TrainingSessionType,
+    pub intensity: TrainingIntensity,
+}
+
+impl TrainingSession {
+    pub fn new(session_type: TrainingSessionType, intensity: TrainingIntensity) -> Self {
+        TrainingSession {
+            session_type,
+            intensity,
+        }
+    }
+
+    pub fn recovery() -> Self {
+        Self::new(TrainingSessionType::Recovery, TrainingIntensity::Low)
+    }
+
+    pub fn rest() -> Self {
+        Self::new(TrainingSessionType::Rest, TrainingIntensity::Low)
+    }
+
+    // Recovery and days off bring no skill gains and no injury risk
+    pub fn is_workout(&self) -> bool {
+        !matches!(
+            self.session_type,
+            TrainingSessionType::Recovery | TrainingSessionType::Rest
+        )
+    }
+
+    pub fn injury_chance(&self, base_chance: f32) -> f32 {
+        if self.is_workout() {
+            base_chance * self.intensity.injury_risk_factor()
+        } else {
+            0.0
+        }
+    }
+
+    // Keeps the gains the session works on, scaled by how hard it is
+    pub fn shape(&self, result: &mut PlayerTrainingResult) {
+        let (technical, mental, physical) = self.session_type.focus();
+        let gain = self.intensity.skill_gain_factor();
+
+        if self.session_type == TrainingSessionType::SetPieces {
+            result
+                .technical
+                .skill_increase
+                .retain(|(skill, _)| SET_PIECE_SKILLS.contains(skill));
+        }
+
+        scale(&mut result.technical.skill_increase, technical * gain);
+        scale(&mut result.mental.skill_increase, mental * gain);
+        scale(&mut result.physical.skill_increase, physical * gain);
+    }
+
+    pub fn apply_load(&self, attributes: &mut PlayerAttributes) {
+        let (condition, jadedness) = match self.session_type {
+            TrainingSessionType::Rest => (REST_RECOVERY.0, -REST_RECOVERY.1),
+            TrainingSessionType::Recovery => {
+                (RECOVERY_SESSION_RECOVERY.0, -RECOVERY_SESSION_RECOVERY.1)
+            }
+            _ => {
+                let (cost, jadedness) = self.intensity.load();
+                (-cost, jadedness)
+            }
+        };
+
+        attributes.condition = attributes
+            .condition
+            .saturating_add(condition)
+            .clamp(0, CONDITION_MAX_VALUE);
+        attributes.jadedness = attributes
+            .jadedness
+            .saturating_add(jadedness)
+            .clamp(0, MAX_JADEDNESS);
+    }
+}
+
+fn scale<S>(skills: &mut Vec<(S, f32)>, factor: f32) {
+    if factor <= 0.0 {
+        skills.clear();
+        return;
+    }
+
+    for (_, amount) in skills.iter_mut() {
+        *amount *= factor;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyTrainingPlan {
+    // Monday first
+    sessions: [TrainingSession; 7],
+}
+
+impl WeeklyTrainingPlan {
+    pub fn new(sessions: [TrainingSession; 7]) -> Self {
+        WeeklyTrainingPlan { sessions }
+    }
+
+    // Recovery after the weekend, the hard work early in the week, set pieces before the weekend
+    pub fn balanced() -> Self {
+        Self::with_core_sessions(
+            TrainingSession::new(TrainingSessionType::Physical, TrainingIntensity::High),
+            TrainingSession::new(TrainingSessionType::Technical, TrainingIntensity::Medium),
+            TrainingSession::new(TrainingSessionType::Tactical, TrainingIntensity::Medium),
+        )
+    }
+
+    // The coach builds the week around what they teach best
+    pub fn for_coach(coach: &Staff) -> Self {
+        let coaching = &coach.staff_attributes.coaching;
+
+        let strongest = coaching
+            .fitness
+            .max(coaching.tactical)
+            .max(coaching.technical);
+        let weakest = coaching
+            .fitness
+            .min(coaching.tactical)
+            .min(coaching.technical);
+
+        if strongest - weakest < COACH_SPECIALITY_MARGIN {
+            return Self::balanced();
+        }
+
+        use TrainingIntensity::*;
+        use TrainingSessionType::*;
+
+        if coaching.fitness == strongest {
+            Self::with_core_sessions(
+                TrainingSession::new(Physical, High),
+                TrainingSession::new(Technical, Medium),
+                TrainingSession::new(Physical, Medium),
+            )
+        } else if coaching.tactical == strongest {
+            Self::with_core_sessions(
+                TrainingSession::new(Physical, Medium),
+                TrainingSession::new(Tactical, High),
+                TrainingSession::new(Tactical, Medium),
+            )
+        } else {
+            Self::with_core_sessions(
+                TrainingSession::new(Physical, Medium),
+                TrainingSession::new(Technical, High),
+                TrainingSession::new(Tactical, Medium),
+            )
+        }
+    }
+
+    fn with_core_sessions(
+        tuesday: TrainingSession,
+        wednesday: TrainingSession,
+        thursday: TrainingSession,
+    ) -> Self {
+        Self::new([
+            TrainingSession::recovery(),
+            tuesday,
+            wednesday,
+            thursday,
+            TrainingSession::new(TrainingSessionType::SetPieces, TrainingIntensity::Low),
+            TrainingSession::rest(),
+            TrainingSession::rest(),
+        ])
+    }
+
+    pub fn planned(&self, weekday: Weekday) -> TrainingSession {
+        self.sessions[weekday.num_days_from_monday() as usize]
+    }
+
+    // No training on a match day, recovery the day after and while the fixtures pile up,
+    // only light set pieces the day before
+    pub fn session(&self, date: NaiveDate, match_days: &[NaiveDate]) -> Option<TrainingSession> {
+        let plays = |days: i64| match_days.contains(&(date + Duration::days(days)));
+
+        if plays(0) {
+            return None;
+        }
+
+        if plays(-1) {
+            return Some(TrainingSession::recovery());
+        }
+
+        let matches_around = (-CONGESTION_DAYS..=CONGESTION_DAYS)
+            .filter(|days| plays(*days))
+            .count();
+
+        if matches_around >= CONGESTED_MATCHES {
+            return Some(TrainingSession::recovery());
+        }
+
+        let planned = self.planned(date.weekday());
+
+        if plays(1) && planned.is_workout() {
+            return Some(TrainingSession::new(
+                TrainingSessionType::SetPieces,
+                TrainingIntensity::Low,
+            ));
+        }
+
+        Some(planned)
+    }
+}
+
+impl Default for WeeklyTrainingPlan {
+    fn default() -> Self {
+        Self::balanced()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::club::staff::staff_stub::StaffStub;
+
+    fn date(day: u32) -> NaiveDate {
+        // 2024-08-05 is a Monday
+        NaiveDate::from_ymd_opt(2024, 8, day).unwrap()
+    }
+
+    #[test]
+    fn sessions_make_way_for_matches() {
+        let plan = WeeklyTrainingPlan::balanced();
+
+        // a quiet week follows the plan
+        assert_eq!(
+            plan.planned(Weekday::Tue),
+            plan.session(date(6), &[]).unwrap()
+        );
+
+        // Saturday match: light work on Friday, nothing on the day, recovery on Sunday
+        let weekend = [date(10)];
+        let friday = plan.session(date(9), &weekend).unwrap();
+
+        assert_eq!(TrainingSessionType::SetPieces, friday.session_type);
+        assert_eq!(TrainingIntensity::Low, friday.intensity);
+        assert_eq!(None, plan.session(date(10), &weekend));
+        assert_eq!(
+            Some(TrainingSession::recovery()),
+            plan.session(date(11), &weekend)
+        );
+
+        // Saturday and Wednesday matches leave no room for hard work in between
+        let congested = [date(10), date(14)];
+
+        assert_eq!(
+            Some(TrainingSession::recovery()),
+            plan.session(date(12), &congested)
+        );
+    }
+
+    #[test]
+    fn coach_builds_the_week_and_intensity_drains_condition() {
+        let mut coach = StaffStub::default();
+        coach.staff_attributes.coaching.fitness = 18;
+        coach.staff_attributes.coaching.tactical = 8;
+        coach.staff_attributes.coaching.technical = 8;
+
+        let plan = WeeklyTrainingPlan::for_coach(&coach);
+
+        let physical_sessions = plan
+            .sessions
+            .iter()
+            .filter(|s| s.session_type == TrainingSessionType::Physical)
+            .count();
+
+        assert_eq!(2, physical_sessions);
+
+        coach.staff_attributes.coaching.fitness = 9;
+        assert_eq!(
+            WeeklyTrainingPlan::balanced(),
+            WeeklyTrainingPlan::for_coach(&coach)
+        );
+
+        let mut attributes = PlayerAttributes {
+            condition: CONDITION_MAX_VALUE,
+            ..Default::default()
+        };
+
+        let hard = TrainingSession::new(TrainingSessionType::Physical, TrainingIntensity::High);
+        let easy = TrainingSession::new(TrainingSessionType::Tactical, TrainingIntensity::Low);
+
+        hard.apply_load(&mut attributes);
+        let after_hard = attributes.condition;
+
+        assert!(after_hard < CONDITION_MAX_VALUE);
+        assert!(attributes.jadedness > 0);
+
+        easy.apply_load(&mut attributes);
+        assert!(after_hard - attributes.condition < CONDITION_MAX_VALUE - after_hard);
+
+        TrainingSession::rest().apply_load(&mut attributes);
+        assert_eq!(CONDITION_MAX_VALUE, attributes.condition);
+        assert_eq!(0, attributes.jadedness);
+
+        assert!(hard.injury_chance(1.0) > easy.injury_chance(1.0));
+        assert_eq!(0.0, TrainingSession::rest().injury_chance(1.0));
+    }
+}
@@ -1,9 +1,13 @@
+use crate::WeeklyTrainingPlan;
 use chrono::{NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TrainingSchedule {
     pub morning_time: NaiveTime,
     pub evening_time: NaiveTime,
+    pub plan: WeeklyTrainingPlan,
+    // the training coach picks the plan
     pub is_default: bool,
 }
 
@@ -12,6 +16,7 @@ impl TrainingSchedule {
         TrainingSchedule {
             morning_time,
             evening_time,
+            plan: WeeklyTrainingPlan::default(),
             is_default: true,
         }
     }
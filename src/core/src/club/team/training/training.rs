@@ -1,22 +1,71 @@
-use crate::{Team, TeamTrainingResult};
-use chrono::NaiveDateTime;
+use crate::training::result::PlayerTrainingResult;
+use crate::{
+    Injury, InjuryCause, InjuryRisk, PlayerDevelopment, Team, TeamTrainingResult,
+    TRAINING_INJURY_CHANCE,
+};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Weekday};
 
 #[derive(Debug)]
 pub struct TeamTraining {}
 
 impl TeamTraining {
-    pub fn train(team: &mut Team, date: NaiveDateTime) -> TeamTrainingResult {
-        if team.training_schedule.is_time(date) {
+    // The session the plan leaves for the day around the team's matches,
+    // a week of ageing for everyone every Monday
+    pub fn train(
+        team: &mut Team,
+        date: NaiveDateTime,
+        match_days: &[NaiveDate],
+    ) -> TeamTrainingResult {
+        let session = team.training_schedule.plan.session(date.date(), match_days);
+        let is_new_week = date.weekday() == Weekday::Mon;
+
+        if session.is_none() && !is_new_week {
             return TeamTrainingResult::empty();
         }
 
         let mut result = TeamTrainingResult::new();
 
         let coach = team.staffs.training_coach(&team.team_type);
+        let medical = &team.staffs.physio().staff_attributes.medical;
+
+        for player in team.players.players.iter_mut() {
+            let mut training_result = PlayerTrainingResult::new(player.id);
+
+            // injured players are with the physio instead
+            if let Some(session) = session.filter(|_| !player.player_attributes.is_injured) {
+                if session.is_workout() {
+                    training_result = player.train(coach, date);
+                    session.shape(&mut training_result);
+
+                    player.training_history.add_session(date);
+
+                    let natural_fitness = player.skills.physical.natural_fitness;
+
+                    if InjuryRisk::is_injured(
+                        session.injury_chance(TRAINING_INJURY_CHANCE),
+                        &player.player_attributes,
+                        natural_fitness,
+                        medical.sports_science,
+                    ) {
+                        player.injure(Injury::random(
+                            InjuryCause::NonContact,
+                            date.date(),
+                            natural_fitness,
+                            medical,
+                        ));
+                    }
+                }
+
+                session.apply_load(&mut player.player_attributes);
+            }
+
+            if is_new_week {
+                PlayerDevelopment::decline(player, date.date(), &mut training_result);
+            }
 
-        for player in &team.players.players {
-            let training_result = player.train(coach, date);
-            result.player_results.push(training_result);
+            if !training_result.is_empty() {
+                result.player_results.push(training_result);
+            }
         }
 
         result
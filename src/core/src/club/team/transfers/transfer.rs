@@ -1,8 +1,9 @@
 use crate::shared::CurrencyValue;
+use serde::{Deserialize, Serialize};
 
 const DEFAULT_TRANSFER_LIST_SIZE: usize = 10;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Transfers {
     items: Vec<TransferItem>,
 }
@@ -14,12 +15,26 @@ impl Transfers {
         }
     }
 
+    // A listed player keeps a single item with the latest asking price
     pub fn add(&mut self, item: TransferItem) {
+        self.remove(item.player_id);
         self.items.push(item);
     }
+
+    pub fn remove(&mut self, player_id: u32) {
+        self.items.retain(|item| item.player_id != player_id);
+    }
+
+    pub fn contains(&self, player_id: u32) -> bool {
+        self.items.iter().any(|item| item.player_id == player_id)
+    }
+
+    pub fn items(&self) -> &[TransferItem] {
+        &self.items
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TransferItem {
     pub player_id: u32,
     pub amount: CurrencyValue,
@@ -30,3 +45,30 @@ impl TransferItem {
         TransferItem { player_id, amount }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::Currency;
+
+    #[test]
+    fn transfer_list_keeps_latest_price() {
+        let mut transfers = Transfers::new();
+
+        transfers.add(TransferItem::new(
+            1,
+            CurrencyValue::new(100.0, Currency::Usd),
+        ));
+        transfers.add(TransferItem::new(
+            1,
+            CurrencyValue::new(200.0, Currency::Usd),
+        ));
+
+        assert_eq!(1, transfers.items().len());
+        assert_eq!(200.0, transfers.items()[0].amount.amount);
+
+        transfers.remove(1);
+
+        assert!(!transfers.contains(1));
+    }
+}
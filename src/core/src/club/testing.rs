@@ -0,0 +1,41 @@
+use crate::club::academy::ClubAcademy;
+use crate::shared::Location;
+use crate::{
+    Club, ClubBoard, ClubFinances, ClubMood, ClubStatus, Player, PlayerCollection,
+    StaffCollection, Team, TeamCollection, TeamReputation, TeamType, TrainingSchedule,
+};
+use chrono::NaiveTime;
+
+// Shared clubs of the world tests, named and slugged after their ids
+
+pub fn team(team_id: u32, league_id: u32, club_id: u32, players: Vec<Player>) -> Team {
+    Team::new(
+        team_id,
+        league_id,
+        club_id,
+        format!("Team {}", team_id),
+        format!("team-{}", team_id),
+        TeamType::Main,
+        TrainingSchedule::new(
+            NaiveTime::from_hms_opt(10, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        ),
+        TeamReputation::new(0, 0, 0),
+        PlayerCollection::new(players),
+        StaffCollection::new(Vec::new()),
+    )
+}
+
+pub fn club(club_id: u32, teams: Vec<Team>) -> Club {
+    Club {
+        id: club_id,
+        name: format!("Club {}", club_id),
+        location: Location { city_id: 1 },
+        mood: ClubMood::default(),
+        board: ClubBoard::new(),
+        status: ClubStatus::Professional,
+        finance: ClubFinances::new(1_000_000, Vec::new()),
+        academy: ClubAcademy::new(100),
+        teams: TeamCollection::new(teams),
+    }
+}
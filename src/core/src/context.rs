@@ -4,7 +4,9 @@ use crate::club::{BoardContext, ClubContext, ClubFinanceContext, PlayerContext,
 use crate::continent::ContinentContext;
 use crate::country::CountryContext;
 use crate::league::LeagueContext;
-use crate::TeamContext;
+use crate::r#match::MatchEngineMode;
+use crate::utils::{RandomGenerator, RandomStream};
+use crate::{MatchCalendar, TeamContext};
 
 #[derive(Clone)]
 pub struct GlobalContext<'gc> {
@@ -18,6 +20,7 @@ pub struct GlobalContext<'gc> {
     pub board: Option<BoardContext>,
     pub player: Option<PlayerContext>,
     pub staff: Option<StaffContext>,
+    pub match_calendar: Option<&'gc MatchCalendar>,
 }
 
 impl<'gc> GlobalContext<'gc> {
@@ -33,6 +36,7 @@ impl<'gc> GlobalContext<'gc> {
             board: None,
             player: None,
             staff: None,
+            match_calendar: None,
         }
     }
 
@@ -90,6 +94,12 @@ impl<'gc> GlobalContext<'gc> {
         ctx.finance = Some(ClubFinanceContext::new());
         ctx
     }
+
+    pub fn with_match_calendar(&self, match_calendar: &'gc MatchCalendar) -> Self {
+        let mut ctx = GlobalContext::clone(self);
+        ctx.match_calendar = Some(match_calendar);
+        ctx
+    }
 }
 
 #[derive(Clone)]
@@ -97,6 +107,9 @@ pub struct SimulationContext {
     pub date: NaiveDateTime,
     pub day: u8,
     pub hour: u8,
+    pub seed: u64,
+    // how matches are played where the competition doesn't say otherwise
+    pub match_engine: MatchEngineMode,
 }
 
 impl SimulationContext {
@@ -105,9 +118,26 @@ impl SimulationContext {
             date,
             day: date.day() as u8,
             hour: date.hour() as u8,
+            seed: 0,
+            match_engine: MatchEngineMode::Detailed,
         }
     }
 
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_match_engine(mut self, match_engine: MatchEngineMode) -> Self {
+        self.match_engine = match_engine;
+        self
+    }
+
+    #[inline]
+    pub fn random_seed(&self, stream: RandomStream, id: u64) -> u64 {
+        RandomGenerator::derive_seed(self.seed, stream, id)
+    }
+
     #[inline]
     pub fn is_week_beginning(&self) -> bool {
         self.date.weekday() == Weekday::Mon && self.date.hour() == 0
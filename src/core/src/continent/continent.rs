@@ -1,27 +1,109 @@
 use crate::context::GlobalContext;
-use crate::continent::ContinentResult;
+use crate::continent::{
+    ChampionLeague, ContinentResult, LeagueEurope, Tournament, TournamentContext,
+    TournamentResult,
+};
 use crate::country::CountryResult;
-use crate::utils::Logging;
-use crate::Country;
+use crate::utils::{Logging, RandomGenerator, RandomStream};
+use crate::{Country, MatchCalendar};
+use chrono::NaiveDate;
 pub use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize)]
 pub struct Continent {
     pub id: u32,
     pub name: String,
     pub countries: Vec<Country>,
+    pub champion_league: Option<ChampionLeague>,
+    pub league_europe: Option<LeagueEurope>,
 }
 
 impl Continent {
     pub fn simulate(&mut self, ctx: GlobalContext<'_>) -> ContinentResult {
+        let match_calendar = self.match_calendar(ctx.simulation.date.date());
+        let ctx = ctx.with_match_calendar(&match_calendar);
+
         let country_results: Vec<CountryResult> = self
             .countries
             .par_iter_mut()
             .map(|country| {
                 let message = &format!("simulate country: {}", &country.name);
-                Logging::estimate_result(|| country.simulate(ctx.with_country(country.id)), message)
+                let country_seed = ctx
+                    .simulation
+                    .random_seed(RandomStream::Country, country.id as u64);
+
+                Logging::estimate_result(
+                    || {
+                        RandomGenerator::scope(country_seed, || {
+                            country.simulate(ctx.with_country(country.id))
+                        })
+                    },
+                    message,
+                )
+            })
+            .collect();
+
+        let tournament_ctx = TournamentContext::new(&self.countries);
+
+        let tournaments: [Option<&mut dyn Tournament>; 2] = [
+            self.champion_league
+                .as_mut()
+                .map(|t| t as &mut dyn Tournament),
+            self.league_europe.as_mut().map(|t| t as &mut dyn Tournament),
+        ];
+
+        let tournament_results: Vec<TournamentResult> = tournaments
+            .into_iter()
+            .flatten()
+            .map(|tournament| {
+                let competition = tournament.competition();
+
+                let message = &format!("simulate tournament: {}", &competition.name);
+                let tournament_seed = ctx
+                    .simulation
+                    .random_seed(RandomStream::Tournament, competition.id as u64);
+
+                Logging::estimate_result(
+                    || {
+                        RandomGenerator::scope(tournament_seed, || {
+                            tournament.simulate(&tournament_ctx, ctx.clone())
+                        })
+                    },
+                    message,
+                )
             })
             .collect();
 
-        ContinentResult::new(country_results)
+        ContinentResult::new(country_results, tournament_results)
+    }
+
+    // Midweek fixtures of the continental tournaments, countries add their own
+    pub fn match_calendar(&self, date: NaiveDate) -> MatchCalendar {
+        let mut calendar = MatchCalendar::new();
+
+        let fixtures = self
+            .tournaments()
+            .into_iter()
+            .filter_map(|tournament| tournament.competition().season.as_ref())
+            .flat_map(|season| &season.fixtures);
+
+        calendar.add_fixtures(date, fixtures);
+
+        calendar
+    }
+
+    pub fn tournaments(&self) -> Vec<&dyn Tournament> {
+        let mut tournaments: Vec<&dyn Tournament> = Vec::new();
+
+        if let Some(champion_league) = &self.champion_league {
+            tournaments.push(champion_league);
+        }
+
+        if let Some(league_europe) = &self.league_europe {
+            tournaments.push(league_europe);
+        }
+
+        tournaments
     }
 }
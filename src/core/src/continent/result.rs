@@ -1,19 +1,28 @@
+use crate::continent::TournamentResult;
 use crate::country::CountryResult;
 use crate::simulator::SimulatorData;
 use crate::SimulationResult;
 
 pub struct ContinentResult {
     pub countries: Vec<CountryResult>,
+    pub tournaments: Vec<TournamentResult>,
 }
 
 impl ContinentResult {
-    pub fn new(countries: Vec<CountryResult>) -> Self {
-        ContinentResult { countries }
+    pub fn new(countries: Vec<CountryResult>, tournaments: Vec<TournamentResult>) -> Self {
+        ContinentResult {
+            countries,
+            tournaments,
+        }
     }
 
     pub fn process(self, data: &mut SimulatorData, result: &mut SimulationResult) {
         for country_result in self.countries {
             country_result.process(data, result);
         }
+
+        for tournament_result in self.tournaments {
+            tournament_result.process(data, result);
+        }
     }
 }
@@ -1,10 +1,60 @@
 use crate::context::GlobalContext;
-use crate::continent::{Tournament, TournamentContext};
+use crate::continent::{
+    CompetitionSettings, ContinentalCompetition, QualificationPlaces, Tournament,
+    TournamentContext, TournamentResult,
+};
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
 
-pub struct ChampionLeague {}
+pub const CHAMPION_LEAGUE_ID: u32 = 10_000;
 
-impl ChampionLeague {}
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChampionLeague {
+    pub competition: ContinentalCompetition,
+}
+
+impl ChampionLeague {
+    pub fn new() -> Self {
+        ChampionLeague {
+            competition: ContinentalCompetition::new(
+                CHAMPION_LEAGUE_ID,
+                String::from("Champions League"),
+                String::from("champions-league"),
+                CompetitionSettings {
+                    places: vec![
+                        QualificationPlaces::new(0, 4),
+                        QualificationPlaces::new(0, 4),
+                        QualificationPlaces::new(0, 4),
+                        QualificationPlaces::new(0, 4),
+                        QualificationPlaces::new(0, 3),
+                        QualificationPlaces::new(0, 2),
+                        QualificationPlaces::new(0, 1),
+                    ],
+                    match_day: Weekday::Tue,
+                    league_phase_rounds: 8,
+                    match_engine: None,
+                },
+            ),
+        }
+    }
+}
+
+impl Default for ChampionLeague {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Tournament for ChampionLeague {
-    fn simulate(&mut self, _: &mut TournamentContext, _: GlobalContext<'_>) {}
+    fn competition(&self) -> &ContinentalCompetition {
+        &self.competition
+    }
+
+    fn simulate(
+        &mut self,
+        tournament_ctx: &TournamentContext<'_>,
+        ctx: GlobalContext<'_>,
+    ) -> TournamentResult {
+        self.competition.simulate(tournament_ctx, &ctx)
+    }
 }
@@ -0,0 +1,664 @@
+use crate::context::GlobalContext;
+use crate::continent::{KnockoutTie, TournamentContext, TournamentResult};
+use crate::league::{LeagueTable, MatchStorage, RankingRule, ScheduleItem, ScheduleItemResult};
+use crate::r#match::{Match, MatchConfig, MatchEngineMode, MatchResult};
+use crate::utils::{DateUtils, Logging, RandomGenerator, RandomStream};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use rand::seq::SliceRandom;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+
+// (day, month)
+const QUALIFICATION_DATE: (u32, u32) = (1, 6);
+const DRAW_DATE: (u32, u32) = (28, 8);
+const LEAGUE_PHASE_START: (u32, u32) = (16, 9);
+const KNOCKOUT_START: (u32, u32) = (10, 2);
+
+const LEAGUE_PHASE_ROUND_DAYS: i64 = 14;
+const KNOCKOUT_ROUND_DAYS: i64 = 28;
+const KNOCKOUT_LEGS_DAYS: i64 = 14;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualificationPlaces {
+    pub from: u8,
+    pub count: u8,
+}
+
+impl QualificationPlaces {
+    pub fn new(from: u8, count: u8) -> Self {
+        QualificationPlaces { from, count }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompetitionSettings {
+    // domestic table places by country rank, the last entry applies to all remaining countries
+    pub places: Vec<QualificationPlaces>,
+    pub match_day: Weekday,
+    pub league_phase_rounds: u8,
+    // overrides the match engine of the simulation
+    pub match_engine: Option<MatchEngineMode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CompetitionStage {
+    LeaguePhase,
+    Knockout(u8),
+    Finished,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompetitionWinner {
+    pub year: u16,
+    pub team_id: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContinentalCompetition {
+    pub id: u32,
+    pub name: String,
+    pub slug: String,
+    pub settings: CompetitionSettings,
+    pub qualified: Vec<u32>,
+    pub season: Option<CompetitionSeason>,
+    pub matches: MatchStorage,
+    pub winners: Vec<CompetitionWinner>,
+}
+
+impl ContinentalCompetition {
+    pub fn new(id: u32, name: String, slug: String, settings: CompetitionSettings) -> Self {
+        ContinentalCompetition {
+            id,
+            name,
+            slug,
+            settings,
+            qualified: Vec::new(),
+            season: None,
+            matches: MatchStorage::new(),
+            winners: Vec::new(),
+        }
+    }
+
+    pub fn simulate(
+        &mut self,
+        tournament_ctx: &TournamentContext<'_>,
+        ctx: &GlobalContext<'_>,
+    ) -> TournamentResult {
+        let date = ctx.simulation.date.date();
+
+        if is_date(date, QUALIFICATION_DATE) {
+            self.qualified = self.qualify(tournament_ctx);
+        }
+
+        if is_date(date, DRAW_DATE) {
+            self.draw(date.year() as u16, tournament_ctx);
+        }
+
+        let match_results = self.play_matches(tournament_ctx, ctx);
+
+        if let Some(season) = &mut self.season {
+            for match_result in &match_results {
+                season.set_result(match_result);
+
+                // copy without position data, that is stored in a separate replay file
+                self.matches
+                    .push(match_result.copy_without_data_positions());
+            }
+
+            if let Some(team_id) =
+                season.advance(self.id, &self.slug, &self.settings, &match_results)
+            {
+                self.winners.push(CompetitionWinner {
+                    year: season.year,
+                    team_id,
+                });
+            }
+        }
+
+        TournamentResult::new(self.id, match_results)
+    }
+
+    pub fn qualify(&self, tournament_ctx: &TournamentContext<'_>) -> Vec<u32> {
+        let places = &self.settings.places;
+
+        tournament_ctx
+            .domestic_standings()
+            .iter()
+            .enumerate()
+            .flat_map(|(rank, standings)| {
+                let country_places = &places[rank.min(places.len() - 1)];
+
+                standings
+                    .iter()
+                    .skip(country_places.from as usize)
+                    .take(country_places.count as usize)
+                    .copied()
+                    .collect::<Vec<u32>>()
+            })
+            .collect()
+    }
+
+    fn draw(&mut self, year: u16, tournament_ctx: &TournamentContext<'_>) {
+        let mut participants = if self.qualified.is_empty() {
+            self.qualify(tournament_ctx)
+        } else {
+            std::mem::take(&mut self.qualified)
+        };
+
+        participants.retain(|team_id| tournament_ctx.team(*team_id).is_some());
+
+        if participants.len() < 2 {
+            self.season = None;
+            return;
+        }
+
+        participants.shuffle(&mut RandomGenerator::current());
+
+        self.season = Some(CompetitionSeason::new(
+            year,
+            self.id,
+            &self.slug,
+            &self.settings,
+            participants,
+        ));
+    }
+
+    fn play_matches(
+        &self,
+        tournament_ctx: &TournamentContext<'_>,
+        ctx: &GlobalContext<'_>,
+    ) -> Vec<MatchResult> {
+        let season = match &self.season {
+            Some(season) => season,
+            None => return Vec::new(),
+        };
+
+        let fixtures: Vec<&ScheduleItem> = season
+            .fixtures
+            .iter()
+            .filter(|f| f.date == ctx.simulation.date && f.result.is_none())
+            .collect();
+
+        let match_engine = self
+            .settings
+            .match_engine
+            .unwrap_or(ctx.simulation.match_engine);
+
+        fixtures
+            .par_iter()
+            .map(|fixture| {
+                let match_seed = ctx.simulation.random_seed(
+                    RandomStream::Match,
+                    ((fixture.home_team_id as u64) << 32) | fixture.away_team_id as u64,
+                );
+
+                RandomGenerator::scope(match_seed, || {
+                    self.play_match(fixture, tournament_ctx, match_engine)
+                })
+            })
+            .collect()
+    }
+
+    // League phase matches may end level, knockout ties are played to a finish
+    fn match_config(&self, fixture: &ScheduleItem) -> MatchConfig {
+        self.season
+            .as_ref()
+            .and_then(|season| {
+                season
+                    .ties
+                    .iter()
+                    .find(|tie| tie.legs.contains(&fixture.id))
+                    .map(|tie| tie.match_config(&fixture.id, &season.fixtures))
+            })
+            .unwrap_or_default()
+    }
+
+    fn play_match(
+        &self,
+        fixture: &ScheduleItem,
+        tournament_ctx: &TournamentContext<'_>,
+        match_engine: MatchEngineMode,
+    ) -> MatchResult {
+        let home_team = tournament_ctx.team(fixture.home_team_id).unwrap();
+        let away_team = tournament_ctx.team(fixture.away_team_id).unwrap();
+
+        let match_to_play = Match::make(
+            fixture.id.clone(),
+            self.id,
+            &self.slug,
+            home_team.get_match_squad(),
+            away_team.get_match_squad(),
+        )
+        .with_config(self.match_config(fixture).with_engine(match_engine));
+
+        let message = &format!(
+            "play {} match: {} - {}",
+            &self.name, &match_to_play.home_squad.team_name, &match_to_play.away_squad.team_name
+        );
+
+        Logging::estimate_result(|| match_to_play.play(), message)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompetitionSeason {
+    pub year: u16,
+    pub stage: CompetitionStage,
+    pub participants: Vec<u32>,
+    pub table: LeagueTable,
+    pub fixtures: Vec<ScheduleItem>,
+    pub seeds: Vec<u32>,
+    pub ties: Vec<KnockoutTie>,
+}
+
+impl CompetitionSeason {
+    pub fn new(
+        year: u16,
+        competition_id: u32,
+        competition_slug: &str,
+        settings: &CompetitionSettings,
+        participants: Vec<u32>,
+    ) -> Self {
+        let first_match_day =
+            DateUtils::next_weekday(date_of(year as i32, LEAGUE_PHASE_START), settings.match_day);
+
+        let fixtures = league_phase_pairs(&participants, settings.league_phase_rounds as usize)
+            .into_iter()
+            .enumerate()
+            .flat_map(|(round, pairs)| {
+                let date = midnight(
+                    first_match_day + Duration::days(round as i64 * LEAGUE_PHASE_ROUND_DAYS),
+                );
+
+                pairs.into_iter().map(move |(home_team_id, away_team_id)| {
+                    ScheduleItem::new(
+                        competition_id,
+                        String::from(competition_slug),
+                        home_team_id,
+                        away_team_id,
+                        date,
+                        None,
+                    )
+                })
+            })
+            .collect();
+
+        CompetitionSeason {
+            year,
+            stage: CompetitionStage::LeaguePhase,
+            table: LeagueTable::new(&participants),
+            participants,
+            fixtures,
+            seeds: Vec::new(),
+            ties: Vec::new(),
+        }
+    }
+
+    pub fn set_result(&mut self, match_result: &MatchResult) {
+        if let Some(fixture) = self.fixtures.iter_mut().find(|f| f.id == match_result.id) {
+            fixture.result = Some(ScheduleItemResult::from_match_result(match_result));
+        }
+    }
+
+    // Moves the season forward after a match day, returns the winner once the final is decided
+    pub fn advance(
+        &mut self,
+        competition_id: u32,
+        competition_slug: &str,
+        settings: &CompetitionSettings,
+        match_results: &[MatchResult],
+    ) -> Option<u32> {
+        match self.stage {
+            CompetitionStage::LeaguePhase => {
+                if match_results.is_empty() {
+                    return None;
+                }
+
+                self.table.update_from_results(match_results);
+                self.table.sort(&RankingRule::defaults());
+
+                if self.fixtures.iter().all(|f| f.result.is_some()) {
+                    let knockout_teams = knockout_size(self.participants.len());
+
+                    self.seeds = self
+                        .table
+                        .rows
+                        .iter()
+                        .take(knockout_teams)
+                        .map(|row| row.team_id)
+                        .collect();
+
+                    let teams: Vec<u32> = bracket_order(knockout_teams)
+                        .into_iter()
+                        .map(|seed| self.seeds[seed])
+                        .collect();
+
+                    self.start_knockout_round(
+                        0,
+                        &teams,
+                        competition_id,
+                        competition_slug,
+                        settings,
+                    );
+                }
+
+                None
+            }
+            CompetitionStage::Knockout(round) => {
+                for tie in self.ties.iter_mut().filter(|t| !t.is_decided()) {
+                    tie.decide(&self.fixtures);
+                }
+
+                if !self.ties.iter().all(|t| t.is_decided()) {
+                    return None;
+                }
+
+                let winners: Vec<u32> = self.ties.iter().filter_map(|t| t.winner()).collect();
+
+                if winners.len() == 1 {
+                    self.stage = CompetitionStage::Finished;
+                    return Some(winners[0]);
+                }
+
+                self.start_knockout_round(
+                    round + 1,
+                    &winners,
+                    competition_id,
+                    competition_slug,
+                    settings,
+                );
+
+                None
+            }
+            CompetitionStage::Finished => None,
+        }
+    }
+
+    // Pairs neighbouring teams, the better seed hosts the second leg
+    fn start_knockout_round(
+        &mut self,
+        round: u8,
+        teams: &[u32],
+        competition_id: u32,
+        competition_slug: &str,
+        settings: &CompetitionSettings,
+    ) {
+        let first_leg_day = DateUtils::next_weekday(
+            date_of(self.year as i32 + 1, KNOCKOUT_START)
+                + Duration::days(round as i64 * KNOCKOUT_ROUND_DAYS),
+            settings.match_day,
+        );
+
+        let is_final = teams.len() == 2;
+
+        self.ties = teams
+            .chunks(2)
+            .map(|pair| {
+                let (first_team_id, second_team_id) = if self.seed(pair[0]) < self.seed(pair[1]) {
+                    (pair[1], pair[0])
+                } else {
+                    (pair[0], pair[1])
+                };
+
+                let mut legs = Vec::with_capacity(2);
+
+                if is_final {
+                    legs.push(ScheduleItem::new(
+                        competition_id,
+                        String::from(competition_slug),
+                        second_team_id,
+                        first_team_id,
+                        midnight(first_leg_day),
+                        None,
+                    ));
+                } else {
+                    legs.push(ScheduleItem::new(
+                        competition_id,
+                        String::from(competition_slug),
+                        first_team_id,
+                        second_team_id,
+                        midnight(first_leg_day),
+                        None,
+                    ));
+                    legs.push(ScheduleItem::new(
+                        competition_id,
+                        String::from(competition_slug),
+                        second_team_id,
+                        first_team_id,
+                        midnight(first_leg_day + Duration::days(KNOCKOUT_LEGS_DAYS)),
+                        None,
+                    ));
+                }
+
+                let tie = KnockoutTie::new(
+                    first_team_id,
+                    second_team_id,
+                    legs.iter().map(|leg| leg.id.clone()).collect(),
+                );
+
+                self.fixtures.extend(legs);
+
+                tie
+            })
+            .collect();
+
+        self.stage = CompetitionStage::Knockout(round);
+    }
+
+    fn seed(&self, team_id: u32) -> usize {
+        self.seeds
+            .iter()
+            .position(|id| *id == team_id)
+            .unwrap_or(usize::MAX)
+    }
+}
+
+// Round-robin by the circle method, only the first `rounds` rounds are taken
+pub fn league_phase_pairs(teams: &[u32], rounds: usize) -> Vec<Vec<(u32, u32)>> {
+    let mut slots: Vec<Option<u32>> = teams.iter().map(|t| Some(*t)).collect();
+
+    if !slots.len().is_multiple_of(2) {
+        slots.push(None);
+    }
+
+    let slots_count = slots.len();
+
+    (0..rounds.min(slots_count - 1))
+        .map(|round| {
+            let pairs = (0..slots_count / 2)
+                .filter_map(|idx| {
+                    let home = slots[idx]?;
+                    let away = slots[slots_count - 1 - idx]?;
+
+                    if (round + idx) % 2 == 0 {
+                        Some((home, away))
+                    } else {
+                        Some((away, home))
+                    }
+                })
+                .collect();
+
+            slots[1..].rotate_right(1);
+
+            pairs
+        })
+        .collect()
+}
+
+// Largest power of two not above half of the participants
+pub fn knockout_size(participants: usize) -> usize {
+    let half = (participants / 2).max(2);
+
+    1 << (usize::BITS - 1 - half.leading_zeros())
+}
+
+// Seed positions so that the best seeds can only meet in the latest rounds
+pub fn bracket_order(size: usize) -> Vec<usize> {
+    let mut order = vec![0];
+
+    while order.len() < size {
+        let len = order.len() * 2;
+
+        order = order
+            .iter()
+            .flat_map(|&seed| [seed, len - 1 - seed])
+            .collect();
+    }
+
+    order
+}
+
+fn date_of(year: i32, (day, month): (u32, u32)) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn midnight(date: NaiveDate) -> NaiveDateTime {
+    NaiveDateTime::new(date, NaiveTime::from_hms_opt(0, 0, 0).unwrap())
+}
+
+fn is_date(date: NaiveDate, (day, month): (u32, u32)) -> bool {
+    date.day() == day && date.month() == month
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::{MatchResultRaw, Score, TeamScore};
+    use std::collections::HashSet;
+
+    fn settings() -> CompetitionSettings {
+        CompetitionSettings {
+            places: vec![QualificationPlaces::new(0, 4)],
+            match_day: Weekday::Tue,
+            league_phase_rounds: 6,
+            match_engine: None,
+        }
+    }
+
+    fn home_win(fixture: &ScheduleItem) -> MatchResult {
+        MatchResult {
+            id: fixture.id.clone(),
+            league_id: fixture.league_id,
+            league_slug: fixture.league_slug.clone(),
+            home_team_id: fixture.home_team_id,
+            away_team_id: fixture.away_team_id,
+            score: Score {
+                home_team: TeamScore::new_with_score(fixture.home_team_id, 2),
+                away_team: TeamScore::new_with_score(fixture.away_team_id, 0),
+                details: vec![],
+            },
+            details: None,
+        }
+    }
+
+    // Home wins leave two-legged ties level, the second leg then goes to penalties
+    fn second_leg_shootout(fixture: &ScheduleItem) -> MatchResult {
+        let mut details = MatchResultRaw::with_match_time(0);
+        details.extra_time = Some((0, 0));
+        details.penalty_shootout = Some((4, 3));
+
+        MatchResult {
+            details: Some(details),
+            ..home_win(fixture)
+        }
+    }
+
+    #[test]
+    fn league_phase_pairs_have_no_repeats() {
+        let teams: Vec<u32> = (1..=9).collect();
+
+        let rounds = league_phase_pairs(&teams, 6);
+
+        assert_eq!(6, rounds.len());
+
+        let mut met = HashSet::new();
+
+        for round in &rounds {
+            let mut playing = HashSet::new();
+
+            assert_eq!(4, round.len());
+
+            for (home, away) in round {
+                assert!(playing.insert(*home));
+                assert!(playing.insert(*away));
+                assert!(met.insert((*home.min(away), *home.max(away))));
+            }
+        }
+    }
+
+    #[test]
+    fn knockout_size_is_power_of_two() {
+        assert_eq!(2, knockout_size(2));
+        assert_eq!(2, knockout_size(6));
+        assert_eq!(4, knockout_size(12));
+        assert_eq!(16, knockout_size(36));
+    }
+
+    #[test]
+    fn bracket_keeps_top_seeds_apart() {
+        assert_eq!(vec![0, 1], bracket_order(2));
+        assert_eq!(vec![0, 3, 1, 2], bracket_order(4));
+        assert_eq!(vec![0, 7, 3, 4, 1, 6, 2, 5], bracket_order(8));
+    }
+
+    #[test]
+    fn season_is_played_through_to_a_winner() {
+        let settings = settings();
+        let participants: Vec<u32> = (1..=12).collect();
+
+        let mut season = CompetitionSeason::new(2024, 1, "cl", &settings, participants);
+
+        assert_eq!(36, season.fixtures.len());
+        assert!(season
+            .fixtures
+            .iter()
+            .all(|f| f.date.weekday() == Weekday::Tue));
+
+        let mut winner = None;
+        let mut match_days = 0;
+
+        while winner.is_none() {
+            let date = season
+                .fixtures
+                .iter()
+                .filter(|f| f.result.is_none())
+                .map(|f| f.date)
+                .min()
+                .expect("season stalled");
+
+            let results: Vec<MatchResult> = season
+                .fixtures
+                .iter()
+                .filter(|f| f.date == date)
+                .map(|f| {
+                    let is_second_leg = season
+                        .ties
+                        .iter()
+                        .any(|t| t.legs.len() == 2 && t.legs[1] == f.id);
+
+                    if is_second_leg {
+                        second_leg_shootout(f)
+                    } else {
+                        home_win(f)
+                    }
+                })
+                .collect();
+
+            for result in &results {
+                season.set_result(result);
+            }
+
+            winner = season.advance(1, "cl", &settings, &results);
+            match_days += 1;
+        }
+
+        // 6 league phase rounds, two-legged semi-finals and a single final
+        assert_eq!(9, match_days);
+        assert_eq!(CompetitionStage::Finished, season.stage);
+        assert!(season.seeds.contains(&winner.unwrap()));
+        assert!(season
+            .fixtures
+            .iter()
+            .all(|f| f.date.weekday() == Weekday::Tue));
+    }
+}
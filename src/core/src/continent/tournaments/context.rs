@@ -1,7 +1,87 @@
-pub struct TournamentContext {}
+use crate::{Country, Team, TeamType};
+use std::cmp::Ordering;
 
-impl TournamentContext {
-    pub fn new() -> Self {
-        TournamentContext {}
+pub struct TournamentContext<'t> {
+    pub countries: &'t [Country],
+}
+
+impl<'t> TournamentContext<'t> {
+    pub fn new(countries: &'t [Country]) -> Self {
+        TournamentContext { countries }
+    }
+
+    pub fn team(&self, team_id: u32) -> Option<&'t Team> {
+        self.countries
+            .iter()
+            .flat_map(|country| &country.clubs)
+            .flat_map(|club| &club.teams.teams)
+            .find(|team| team.id == team_id)
+    }
+
+    // Main teams of every country's top division in table order, strongest countries first
+    pub fn domestic_standings(&self) -> Vec<Vec<u32>> {
+        let mut countries: Vec<&Country> = self.countries.iter().collect();
+
+        countries.sort_by(|a, b| b.reputation.cmp(&a.reputation).then(a.id.cmp(&b.id)));
+
+        countries
+            .into_iter()
+            .filter_map(|country| self.country_standings(country))
+            .filter(|standings| !standings.is_empty())
+            .collect()
+    }
+
+    fn country_standings(&self, country: &Country) -> Option<Vec<u32>> {
+        let top_league = country
+            .leagues
+            .leagues
+            .iter()
+            .filter(|league| league.tier == 1)
+            .min_by_key(|league| league.id)?;
+
+        let main_teams: Vec<&Team> = country
+            .clubs
+            .iter()
+            .flat_map(|club| &club.teams.teams)
+            .filter(|team| team.league_id == top_league.id && team.team_type == TeamType::Main)
+            .collect();
+
+        let season_started = top_league.table.rows.iter().any(|row| row.played > 0);
+
+        if season_started {
+            return Some(
+                top_league
+                    .table
+                    .rows
+                    .iter()
+                    .filter(|row| main_teams.iter().any(|team| team.id == row.team_id))
+                    .map(|row| row.team_id)
+                    .collect(),
+            );
+        }
+
+        // no matches played yet, the previous season's final table decides
+        if let Some(last_season) = top_league.history.last() {
+            let standings: Vec<u32> = last_season
+                .table
+                .iter()
+                .filter(|row| main_teams.iter().any(|team| team.id == row.team_id))
+                .map(|row| row.team_id)
+                .collect();
+
+            if !standings.is_empty() {
+                return Some(standings);
+            }
+        }
+
+        // no history either, fall back to the clubs' reputation
+        let mut teams = main_teams;
+
+        teams.sort_by(|a, b| match b.reputation.world.cmp(&a.reputation.world) {
+            Ordering::Equal => a.id.cmp(&b.id),
+            other => other,
+        });
+
+        Some(teams.into_iter().map(|team| team.id).collect())
     }
 }
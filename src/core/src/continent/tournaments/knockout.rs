@@ -0,0 +1,330 @@
+use crate::league::ScheduleItem;
+use crate::r#match::MatchConfig;
+use serde::{Deserialize, Serialize};
+
+// goals of the first and the second team of a tie
+type TieGoals = (u8, u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TieResolution {
+    Aggregate,
+    AwayGoals,
+    Replay,
+    ExtraTime,
+    Penalties,
+    // level without a shootout, the better seed goes through
+    Seeding,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnockoutOutcome {
+    pub winner_team_id: u32,
+    pub resolution: TieResolution,
+    pub extra_time: Option<(u8, u8)>,
+    pub penalties: Option<(u8, u8)>,
+}
+
+// First team hosts the first leg, second team hosts the second leg (or the final)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnockoutTie {
+    pub first_team_id: u32,
+    pub second_team_id: u32,
+    pub legs: Vec<String>,
+    pub outcome: Option<KnockoutOutcome>,
+}
+
+impl KnockoutTie {
+    pub fn new(first_team_id: u32, second_team_id: u32, legs: Vec<String>) -> Self {
+        KnockoutTie {
+            first_team_id,
+            second_team_id,
+            legs,
+            outcome: None,
+        }
+    }
+
+    pub fn is_decided(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    pub fn winner(&self) -> Option<u32> {
+        self.outcome.as_ref().map(|o| o.winner_team_id)
+    }
+
+    // Goals of (first team, second team) over all legs, None until every leg is played
+    pub fn aggregate(&self, fixtures: &[ScheduleItem]) -> Option<(u16, u16)> {
+        self.goals(fixtures, |_| true)
+    }
+
+    pub fn away_goals(&self, fixtures: &[ScheduleItem]) -> Option<(u16, u16)> {
+        self.goals(fixtures, |fixture| {
+            fixture.home_team_id != self.first_team_id
+        })
+        .zip(self.goals(fixtures, |fixture| {
+            fixture.home_team_id == self.first_team_id
+        }))
+        .map(|((first, _), (_, second))| (first, second))
+    }
+
+    fn goals<F>(&self, fixtures: &[ScheduleItem], filter: F) -> Option<(u16, u16)>
+    where
+        F: Fn(&ScheduleItem) -> bool,
+    {
+        let mut first_goals = 0;
+        let mut second_goals = 0;
+
+        for leg in &self.legs {
+            let fixture = fixtures.iter().find(|f| &f.id == leg)?;
+            let result = fixture.result.as_ref()?;
+
+            if !filter(fixture) {
+                continue;
+            }
+
+            let (home, away) = (result.home.get() as u16, result.away.get() as u16);
+
+            if fixture.home_team_id == self.first_team_id {
+                first_goals += home;
+                second_goals += away;
+            } else {
+                first_goals += away;
+                second_goals += home;
+            }
+        }
+
+        Some((first_goals, second_goals))
+    }
+
+    // Decides the tie once every leg is played, the last leg is played to a finish
+    // with extra time and penalties by the match engine
+    pub fn decide(&mut self, fixtures: &[ScheduleItem]) -> Option<u32> {
+        if let Some(winner) = self.winner() {
+            return Some(winner);
+        }
+
+        let (first, second) = self.aggregate(fixtures)?;
+
+        // extra time and penalties played out in the last leg, as (first, second)
+        let (last_leg_extra_time, last_leg_penalties) = self.last_leg_decider(fixtures);
+
+        if first != second {
+            let resolution = if last_leg_extra_time.is_some() {
+                TieResolution::ExtraTime
+            } else {
+                TieResolution::Aggregate
+            };
+
+            return Some(self.finish(first > second, resolution, last_leg_extra_time, None));
+        }
+
+        if self.legs.len() > 1 {
+            let (first_away, second_away) = self.away_goals(fixtures)?;
+
+            if first_away != second_away {
+                return Some(self.finish(
+                    first_away > second_away,
+                    TieResolution::AwayGoals,
+                    last_leg_extra_time,
+                    None,
+                ));
+            }
+        }
+
+        // the match engine plays the last leg to a finish, should it still come back
+        // level without a shootout the second team, the better seed hosting it, goes
+        // through rather than the tie being left undecided
+        let (first_wins, resolution) = match last_leg_penalties {
+            Some((first_penalties, second_penalties)) => {
+                (first_penalties > second_penalties, TieResolution::Penalties)
+            }
+            None => (false, TieResolution::Seeding),
+        };
+
+        Some(self.finish(
+            first_wins,
+            resolution,
+            last_leg_extra_time,
+            last_leg_penalties,
+        ))
+    }
+
+    // The last leg is played to a finish, the first leg may end level. Away goals
+    // count for two-legged ties
+    pub fn match_config(&self, fixture_id: &str, fixtures: &[ScheduleItem]) -> MatchConfig {
+        if self.legs.last().map(String::as_str) != Some(fixture_id) {
+            return MatchConfig::default();
+        }
+
+        if self.legs.len() == 1 {
+            return MatchConfig::knockout();
+        }
+
+        let last_leg = fixtures.iter().find(|f| f.id == fixture_id);
+        let first_leg = fixtures.iter().find(|f| f.id == self.legs[0]);
+
+        match (last_leg, first_leg.and_then(|f| f.result.as_ref().map(|r| (f, r)))) {
+            (Some(last_leg), Some((first_leg, result))) => {
+                let (home, away) = (result.home.get(), result.away.get());
+
+                // goals of the last leg's home team come first
+                let first_leg_goals = if first_leg.home_team_id == last_leg.home_team_id {
+                    (home, away)
+                } else {
+                    (away, home)
+                };
+
+                MatchConfig::second_leg(first_leg_goals, true)
+            }
+            _ => MatchConfig::knockout(),
+        }
+    }
+
+    fn last_leg_decider(
+        &self,
+        fixtures: &[ScheduleItem],
+    ) -> (Option<TieGoals>, Option<TieGoals>) {
+        let last_leg = match self
+            .legs
+            .last()
+            .and_then(|id| fixtures.iter().find(|f| &f.id == id))
+        {
+            Some(last_leg) => last_leg,
+            None => return (None, None),
+        };
+
+        let result = match &last_leg.result {
+            Some(result) => result,
+            None => return (None, None),
+        };
+
+        let is_first_at_home = last_leg.home_team_id == self.first_team_id;
+
+        let orient = |goals: TieGoals| {
+            if is_first_at_home {
+                goals
+            } else {
+                (goals.1, goals.0)
+            }
+        };
+
+        (result.extra_time.map(orient), result.penalties.map(orient))
+    }
+
+    fn finish(
+        &mut self,
+        first_wins: bool,
+        resolution: TieResolution,
+        extra_time: Option<(u8, u8)>,
+        penalties: Option<(u8, u8)>,
+    ) -> u32 {
+        let winner_team_id = if first_wins {
+            self.first_team_id
+        } else {
+            self.second_team_id
+        };
+
+        self.outcome = Some(KnockoutOutcome {
+            winner_team_id,
+            resolution,
+            extra_time,
+            penalties,
+        });
+
+        winner_team_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::league::ScheduleItemResult;
+    use crate::r#match::TeamScore;
+    use chrono::NaiveDate;
+
+    fn fixture(day: u32, home_team_id: u32, away_team_id: u32, score: (u8, u8)) -> ScheduleItem {
+        ScheduleItem::new(
+            1,
+            String::from("champions-league"),
+            home_team_id,
+            away_team_id,
+            NaiveDate::from_ymd_opt(2025, 2, day)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+            Some(ScheduleItemResult::new(
+                &TeamScore::new_with_score(home_team_id, score.0),
+                &TeamScore::new_with_score(away_team_id, score.1),
+            )),
+        )
+    }
+
+    fn tie(fixtures: &[ScheduleItem]) -> KnockoutTie {
+        KnockoutTie::new(1, 2, fixtures.iter().map(|f| f.id.clone()).collect())
+    }
+
+    #[test]
+    fn tie_decided_on_aggregate() {
+        let fixtures = vec![fixture(11, 1, 2, (2, 0)), fixture(25, 2, 1, (2, 1))];
+        let mut tie = tie(&fixtures);
+
+        assert_eq!(Some((3, 2)), tie.aggregate(&fixtures));
+        assert_eq!(Some(1), tie.decide(&fixtures));
+        assert_eq!(
+            TieResolution::Aggregate,
+            tie.outcome.as_ref().unwrap().resolution
+        );
+    }
+
+    #[test]
+    fn tie_decided_on_away_goals() {
+        let fixtures = vec![fixture(11, 1, 2, (1, 2)), fixture(25, 2, 1, (0, 1))];
+        let mut tie = tie(&fixtures);
+
+        assert_eq!(Some((2, 2)), tie.aggregate(&fixtures));
+        assert_eq!(Some(2), tie.decide(&fixtures));
+        assert_eq!(
+            TieResolution::AwayGoals,
+            tie.outcome.as_ref().unwrap().resolution
+        );
+    }
+
+    #[test]
+    fn tie_not_decided_before_second_leg() {
+        let mut fixtures = vec![fixture(11, 1, 2, (1, 0)), fixture(25, 2, 1, (0, 0))];
+        fixtures[1].result = None;
+
+        let mut tie = tie(&fixtures);
+
+        assert_eq!(None, tie.decide(&fixtures));
+        assert!(!tie.is_decided());
+    }
+
+    #[test]
+    fn level_tie_goes_to_last_leg_shootout() {
+        let mut fixtures = vec![fixture(11, 1, 2, (1, 1)), fixture(25, 2, 1, (1, 1))];
+        let mut tie = tie(&fixtures);
+
+        let last_leg = fixtures[1].result.as_mut().unwrap();
+        last_leg.extra_time = Some((0, 0));
+        last_leg.penalties = Some((3, 4));
+
+        assert_eq!(Some(1), tie.decide(&fixtures));
+
+        let outcome = tie.outcome.as_ref().unwrap();
+
+        assert_eq!(TieResolution::Penalties, outcome.resolution);
+        assert_eq!(Some((4, 3)), outcome.penalties);
+    }
+
+    #[test]
+    fn level_tie_without_shootout_goes_to_better_seed() {
+        let fixtures = vec![fixture(11, 1, 2, (1, 1)), fixture(25, 2, 1, (1, 1))];
+        let mut tie = tie(&fixtures);
+
+        assert_eq!(Some(2), tie.decide(&fixtures));
+        assert_eq!(
+            TieResolution::Seeding,
+            tie.outcome.as_ref().unwrap().resolution
+        );
+    }
+}
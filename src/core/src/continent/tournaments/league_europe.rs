@@ -1,10 +1,61 @@
 use crate::context::GlobalContext;
-use crate::continent::{Tournament, TournamentContext};
+use crate::continent::{
+    CompetitionSettings, ContinentalCompetition, QualificationPlaces, Tournament,
+    TournamentContext, TournamentResult,
+};
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
 
-pub struct LeagueEurope {}
+pub const LEAGUE_EUROPE_ID: u32 = 10_001;
 
-impl LeagueEurope {}
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LeagueEurope {
+    pub competition: ContinentalCompetition,
+}
+
+impl LeagueEurope {
+    // Takes the places right below the Champions League ones
+    pub fn new() -> Self {
+        LeagueEurope {
+            competition: ContinentalCompetition::new(
+                LEAGUE_EUROPE_ID,
+                String::from("Europa League"),
+                String::from("europa-league"),
+                CompetitionSettings {
+                    places: vec![
+                        QualificationPlaces::new(4, 2),
+                        QualificationPlaces::new(4, 2),
+                        QualificationPlaces::new(4, 2),
+                        QualificationPlaces::new(4, 2),
+                        QualificationPlaces::new(3, 2),
+                        QualificationPlaces::new(2, 2),
+                        QualificationPlaces::new(1, 1),
+                    ],
+                    match_day: Weekday::Thu,
+                    league_phase_rounds: 8,
+                    match_engine: None,
+                },
+            ),
+        }
+    }
+}
+
+impl Default for LeagueEurope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Tournament for LeagueEurope {
-    fn simulate(&mut self, _: &mut TournamentContext, _: GlobalContext<'_>) {}
+    fn competition(&self) -> &ContinentalCompetition {
+        &self.competition
+    }
+
+    fn simulate(
+        &mut self,
+        tournament_ctx: &TournamentContext<'_>,
+        ctx: GlobalContext<'_>,
+    ) -> TournamentResult {
+        self.competition.simulate(tournament_ctx, &ctx)
+    }
 }
@@ -1,10 +1,24 @@
 mod champion_league;
+mod competition;
 mod context;
+mod knockout;
 mod league_europe;
+mod result;
 
 use crate::context::GlobalContext;
+pub use champion_league::*;
+pub use competition::*;
 pub use context::*;
+pub use knockout::*;
+pub use league_europe::*;
+pub use result::*;
 
 pub trait Tournament {
-    fn simulate(&mut self, tournament_ctx: &mut TournamentContext, ctx: GlobalContext<'_>);
+    fn competition(&self) -> &ContinentalCompetition;
+
+    fn simulate(
+        &mut self,
+        tournament_ctx: &TournamentContext<'_>,
+        ctx: GlobalContext<'_>,
+    ) -> TournamentResult;
 }
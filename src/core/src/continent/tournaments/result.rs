@@ -0,0 +1,26 @@
+use crate::r#match::MatchResult;
+use crate::simulator::SimulatorData;
+use crate::SimulationResult;
+
+pub struct TournamentResult {
+    pub tournament_id: u32,
+    pub match_results: Vec<MatchResult>,
+}
+
+impl TournamentResult {
+    pub fn new(tournament_id: u32, match_results: Vec<MatchResult>) -> Self {
+        TournamentResult {
+            tournament_id,
+            match_results,
+        }
+    }
+
+    pub fn process(self, data: &mut SimulatorData, result: &mut SimulationResult) {
+        for match_result in self.match_results {
+            data.add_match_history(&match_result);
+            data.add_player_statistics(&match_result);
+
+            result.match_results.push(match_result);
+        }
+    }
+}
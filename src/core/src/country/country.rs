@@ -1,11 +1,16 @@
 use crate::context::GlobalContext;
 use crate::country::CountryResult;
+use crate::cup::CupCollection;
 use crate::league::LeagueCollection;
-use crate::utils::Logging;
-use crate::{Club, ClubResult};
+use crate::transfers::TransferWindows;
+use crate::utils::{Logging, RandomGenerator, RandomStream};
+use crate::{Club, ClubResult, MatchCalendar};
+use chrono::NaiveDate;
 use rayon::iter::IntoParallelRefMutIterator;
 use rayon::iter::ParallelIterator;
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize)]
 pub struct Country {
     pub id: u32,
     pub code: String,
@@ -13,12 +18,16 @@ pub struct Country {
     pub name: String,
     pub continent_id: u32,
     pub leagues: LeagueCollection,
+    pub cups: CupCollection,
     pub clubs: Vec<Club>,
     pub reputation: u16,
+    pub transfer_windows: TransferWindows,
     pub generator_data: CountryGeneratorData,
 }
 
 impl Country {
+    // cups and transfer windows start out with the defaults
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: u32,
         code: String,
@@ -37,31 +46,74 @@ impl Country {
             name,
             continent_id,
             leagues,
+            cups: CupCollection::default(),
             clubs,
             reputation,
+            transfer_windows: TransferWindows::default(),
             generator_data,
         }
     }
 
     pub fn simulate(&mut self, ctx: GlobalContext<'_>) -> CountryResult {
-        let league_results = self.leagues.simulate(&self.clubs, &ctx);
+        let mut league_results = self.leagues.simulate(&self.clubs, &ctx);
+
+        let cup_results = self.cups.simulate(&self.clubs, &self.leagues, &ctx);
+
+        league_results.extend(self.leagues.end_season(&mut self.clubs, &ctx));
+
+        let match_calendar = self.match_calendar(ctx.simulation.date.date(), ctx.match_calendar);
+        let ctx = ctx.with_match_calendar(&match_calendar);
 
         let clubs_results: Vec<ClubResult> = self
             .clubs
             .par_iter_mut()
             .map(|club| {
                 let message = &format!("simulate club: {}", &club.name);
+                let club_seed = ctx.simulation.random_seed(RandomStream::Club, club.id as u64);
+
                 Logging::estimate_result(
-                    || club.simulate(ctx.with_club(club.id, &club.name.clone())),
+                    || {
+                        RandomGenerator::scope(club_seed, || {
+                            club.simulate(ctx.with_club(club.id, &club.name.clone()))
+                        })
+                    },
                     message,
                 )
             })
             .collect();
 
-        CountryResult::new(league_results, clubs_results)
+        CountryResult::new(league_results, cup_results, clubs_results)
+    }
+
+    // League and cup fixtures added to the continental ones
+    fn match_calendar(
+        &self,
+        date: NaiveDate,
+        continental: Option<&MatchCalendar>,
+    ) -> MatchCalendar {
+        let league_fixtures = self
+            .leagues
+            .leagues
+            .iter()
+            .flat_map(|league| &league.schedule.tours)
+            .flat_map(|tour| &tour.items);
+
+        let cup_fixtures = self
+            .cups
+            .cups
+            .iter()
+            .filter_map(|cup| cup.season.as_ref())
+            .flat_map(|season| &season.fixtures);
+
+        let mut calendar = continental.cloned().unwrap_or_default();
+
+        calendar.add_fixtures(date, league_fixtures.chain(cup_fixtures));
+
+        calendar
     }
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct CountryGeneratorData {
     pub people_names: PeopleNameGeneratorData,
 }
@@ -86,7 +138,100 @@ impl CountryGeneratorData {
     }
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct PeopleNameGeneratorData {
     pub first_names: Vec<String>,
     pub last_names: Vec<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::continent::{ChampionLeague, CompetitionSeason, Continent, CHAMPION_LEAGUE_ID};
+    use crate::league::{
+        DayMonthPeriod, League, LeagueSettings, RankingRule, ScheduleItem, ScheduleTour,
+    };
+
+    fn fixture(league_id: u32, home_team_id: u32, away_team_id: u32, day: u32) -> ScheduleItem {
+        ScheduleItem::new(
+            league_id,
+            format!("league-{}", league_id),
+            home_team_id,
+            away_team_id,
+            NaiveDate::from_ymd_opt(2024, 9, day)
+                .unwrap()
+                .and_hms_opt(20, 0, 0)
+                .unwrap(),
+            None,
+        )
+    }
+
+    #[test]
+    fn match_calendar_includes_continental_midweek_matches() {
+        let mut league = League::new(
+            1,
+            "League".to_string(),
+            "league".to_string(),
+            1,
+            0,
+            1,
+            LeagueSettings {
+                season_starting_half: DayMonthPeriod::new(1, 7, 31, 12),
+                season_ending_half: DayMonthPeriod::new(1, 1, 31, 5),
+                promotion: None,
+                ranking: RankingRule::defaults(),
+                match_engine: None,
+            },
+        );
+
+        // Saturday league matches either side of a Tuesday in the Champions League
+        league.schedule.tours.push(ScheduleTour {
+            num: 1,
+            items: vec![fixture(1, 10, 20, 14), fixture(1, 20, 10, 21)],
+        });
+
+        let mut champion_league = ChampionLeague::new();
+        let settings = &champion_league.competition.settings;
+
+        let mut season =
+            CompetitionSeason::new(2024, CHAMPION_LEAGUE_ID, "cl", settings, vec![10, 30]);
+        season.fixtures = vec![fixture(CHAMPION_LEAGUE_ID, 30, 10, 17)];
+
+        champion_league.competition.season = Some(season);
+
+        let country = Country::new(
+            1,
+            "cn".to_string(),
+            "country".to_string(),
+            "Country".to_string(),
+            1,
+            LeagueCollection::new(vec![league]),
+            Vec::new(),
+            0,
+            CountryGeneratorData::empty(),
+        );
+
+        let continent = Continent {
+            id: 1,
+            name: "Continent".to_string(),
+            countries: Vec::new(),
+            champion_league: Some(champion_league),
+            league_europe: None,
+        };
+
+        let date = NaiveDate::from_ymd_opt(2024, 9, 16).unwrap();
+
+        let continental = continent.match_calendar(date);
+        let calendar = country.match_calendar(date, Some(&continental));
+
+        let days = |days: &[u32]| -> Vec<NaiveDate> {
+            days.iter()
+                .map(|day| NaiveDate::from_ymd_opt(2024, 9, *day).unwrap())
+                .collect()
+        };
+
+        assert_eq!(days(&[17]), continental.match_days(10));
+        assert_eq!(days(&[17, 14, 21]), calendar.match_days(10));
+        assert_eq!(days(&[17]), calendar.match_days(30));
+    }
+}
@@ -1,15 +1,21 @@
+use crate::cup::CupResult;
 use crate::league::LeagueResult;
 use crate::simulator::SimulatorData;
 use crate::{ClubResult, SimulationResult};
 
 pub struct CountryResult {
     pub leagues: Vec<LeagueResult>,
+    pub cups: Vec<CupResult>,
     pub clubs: Vec<ClubResult>,
 }
 
 impl CountryResult {
-    pub fn new(leagues: Vec<LeagueResult>, clubs: Vec<ClubResult>) -> Self {
-        CountryResult { leagues, clubs }
+    pub fn new(leagues: Vec<LeagueResult>, cups: Vec<CupResult>, clubs: Vec<ClubResult>) -> Self {
+        CountryResult {
+            leagues,
+            cups,
+            clubs,
+        }
     }
 
     pub fn process(self, data: &mut SimulatorData, result: &mut SimulationResult) {
@@ -17,6 +23,10 @@ impl CountryResult {
             league_result.process(data, result);
         }
 
+        for cup_result in self.cups {
+            cup_result.process(data, result);
+        }
+
         for club_result in self.clubs {
             club_result.process(data, result);
         }
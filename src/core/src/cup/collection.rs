@@ -0,0 +1,37 @@
+use crate::context::GlobalContext;
+use crate::cup::{Cup, CupResult};
+use crate::league::LeagueCollection;
+use crate::utils::{Logging, RandomGenerator, RandomStream};
+use crate::Club;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CupCollection {
+    pub cups: Vec<Cup>,
+}
+
+impl CupCollection {
+    pub fn new(cups: Vec<Cup>) -> Self {
+        CupCollection { cups }
+    }
+
+    pub fn simulate(
+        &mut self,
+        clubs: &[Club],
+        leagues: &LeagueCollection,
+        ctx: &GlobalContext<'_>,
+    ) -> Vec<CupResult> {
+        self.cups
+            .iter_mut()
+            .map(|cup| {
+                let message = &format!("simulate cup: {}", &cup.name);
+                let cup_seed = ctx.simulation.random_seed(RandomStream::Cup, cup.id as u64);
+
+                Logging::estimate_result(
+                    || RandomGenerator::scope(cup_seed, || cup.simulate(clubs, leagues, ctx)),
+                    message,
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,245 @@
+use crate::context::GlobalContext;
+use crate::cup::{CupResult, CupSeason, CupSeasonContext};
+use crate::league::{DayMonthPeriod, LeagueCollection, MatchStorage, ScheduleItem};
+use crate::r#match::{Match, MatchConfig, MatchEngineMode, MatchResult};
+use crate::utils::{Logging, RandomGenerator, RandomStream};
+use crate::{Club, Team, TeamType};
+use chrono::{Datelike, NaiveDate, Weekday};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Cup {
+    pub id: u32,
+    pub name: String,
+    pub slug: String,
+    pub country_id: u32,
+    pub reputation: u16,
+    pub settings: CupSettings,
+    pub season: Option<CupSeason>,
+    pub matches: MatchStorage,
+    pub winners: Vec<CupWinner>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CupSettings {
+    // from - first round date (the draw happens on that day), to - the final
+    pub season: DayMonthPeriod,
+    pub match_day: Weekday,
+    pub replays: bool,
+    // overrides the match engine of the simulation
+    pub match_engine: Option<MatchEngineMode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CupWinner {
+    pub year: u16,
+    pub team_id: u32,
+}
+
+impl Cup {
+    pub fn new(
+        id: u32,
+        name: String,
+        slug: String,
+        country_id: u32,
+        reputation: u16,
+        settings: CupSettings,
+    ) -> Self {
+        Cup {
+            id,
+            name,
+            slug,
+            country_id,
+            reputation,
+            settings,
+            season: None,
+            matches: MatchStorage::new(),
+            winners: Vec::new(),
+        }
+    }
+
+    pub fn simulate(
+        &mut self,
+        clubs: &[Club],
+        leagues: &LeagueCollection,
+        ctx: &GlobalContext<'_>,
+    ) -> CupResult {
+        let today = ctx.simulation.date.date();
+
+        let is_busy =
+            |team_id: u32, date: NaiveDate| Self::has_league_match(leagues, team_id, date);
+
+        let season_ctx = CupSeasonContext {
+            cup_id: self.id,
+            cup_slug: &self.slug,
+            match_day: self.settings.match_day,
+            replays: self.settings.replays,
+            is_busy: &is_busy,
+        };
+
+        let season_period = &self.settings.season;
+
+        if today.day() == season_period.from_day as u32
+            && today.month() == season_period.from_month as u32
+        {
+            let teams = Self::participants(clubs, leagues);
+
+            self.season = if teams.len() > 1 {
+                let final_year = if season_period.to_month < season_period.from_month {
+                    today.year() + 1
+                } else {
+                    today.year()
+                };
+
+                let final_round = NaiveDate::from_ymd_opt(
+                    final_year,
+                    season_period.to_month as u32,
+                    season_period.to_day as u32,
+                )
+                .unwrap();
+
+                Some(CupSeason::new(
+                    today.year() as u16,
+                    &teams,
+                    today,
+                    final_round,
+                    &season_ctx,
+                ))
+            } else {
+                None
+            };
+        }
+
+        let match_results = self.play_matches(clubs, ctx);
+
+        if let Some(season) = &mut self.season {
+            for match_result in &match_results {
+                season.set_result(match_result);
+
+                // copy without position data, that is stored in a separate replay file
+                self.matches
+                    .push(match_result.copy_without_data_positions());
+            }
+
+            if !match_results.is_empty() {
+                if let Some(team_id) = season.advance(today, &season_ctx) {
+                    self.winners.push(CupWinner {
+                        year: season.year,
+                        team_id,
+                    });
+                }
+            }
+        }
+
+        CupResult::new(self.id, match_results)
+    }
+
+    // Main teams from every division, ordered from the top flight down
+    fn participants(clubs: &[Club], leagues: &LeagueCollection) -> Vec<u32> {
+        let mut teams: Vec<(&Team, u16)> = clubs
+            .iter()
+            .flat_map(|club| &club.teams.teams)
+            .filter(|team| team.team_type == TeamType::Main)
+            .filter_map(|team| {
+                leagues
+                    .leagues
+                    .iter()
+                    .find(|league| league.id == team.league_id)
+                    .map(|league| (team, league.reputation))
+            })
+            .collect();
+
+        teams.sort_by(|(a, a_league), (b, b_league)| {
+            b_league
+                .cmp(a_league)
+                .then(b.reputation.national.cmp(&a.reputation.national))
+                .then(a.id.cmp(&b.id))
+        });
+
+        teams.into_iter().map(|(team, _)| team.id).collect()
+    }
+
+    fn play_matches(&self, clubs: &[Club], ctx: &GlobalContext<'_>) -> Vec<MatchResult> {
+        let season = match &self.season {
+            Some(season) => season,
+            None => return Vec::new(),
+        };
+
+        let fixtures: Vec<&ScheduleItem> = season
+            .fixtures
+            .iter()
+            .filter(|f| f.date == ctx.simulation.date && f.result.is_none())
+            .collect();
+
+        let match_engine = self
+            .settings
+            .match_engine
+            .unwrap_or(ctx.simulation.match_engine);
+
+        fixtures
+            .par_iter()
+            .map(|fixture| {
+                let match_seed = ctx.simulation.random_seed(
+                    RandomStream::Match,
+                    ((fixture.home_team_id as u64) << 32) | fixture.away_team_id as u64,
+                );
+
+                RandomGenerator::scope(match_seed, || {
+                    self.play_match(fixture, clubs, match_engine)
+                })
+            })
+            .collect()
+    }
+
+    fn play_match(
+        &self,
+        fixture: &ScheduleItem,
+        clubs: &[Club],
+        match_engine: MatchEngineMode,
+    ) -> MatchResult {
+        let home_team = Self::find_team(clubs, fixture.home_team_id).unwrap();
+        let away_team = Self::find_team(clubs, fixture.away_team_id).unwrap();
+
+        let config = self
+            .season
+            .as_ref()
+            .map(|season| season.match_config(&fixture.id, self.settings.replays))
+            .unwrap_or_else(MatchConfig::knockout);
+
+        let match_to_play = Match::make(
+            fixture.id.clone(),
+            self.id,
+            &self.slug,
+            home_team.get_match_squad(),
+            away_team.get_match_squad(),
+        )
+        .with_config(config.with_engine(match_engine));
+
+        let message = &format!(
+            "play {} match: {} - {}",
+            &self.name, &match_to_play.home_squad.team_name, &match_to_play.away_squad.team_name
+        );
+
+        Logging::estimate_result(|| match_to_play.play(), message)
+    }
+
+    fn find_team(clubs: &[Club], team_id: u32) -> Option<&Team> {
+        clubs
+            .iter()
+            .flat_map(|c| &c.teams.teams)
+            .find(|team| team.id == team_id)
+    }
+
+    fn has_league_match(leagues: &LeagueCollection, team_id: u32, date: NaiveDate) -> bool {
+        leagues
+            .leagues
+            .iter()
+            .flat_map(|league| &league.schedule.tours)
+            .flat_map(|tour| &tour.items)
+            .any(|item| {
+                item.date.date() == date
+                    && (item.home_team_id == team_id || item.away_team_id == team_id)
+            })
+    }
+}
@@ -0,0 +1,9 @@
+mod collection;
+mod cup;
+mod result;
+mod season;
+
+pub use collection::*;
+pub use cup::*;
+pub use result::*;
+pub use season::*;
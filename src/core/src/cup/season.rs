@@ -0,0 +1,510 @@
+use crate::continent::{KnockoutOutcome, TieResolution};
+use crate::league::{ScheduleItem, ScheduleItemResult};
+use crate::r#match::{MatchConfig, MatchResult};
+use crate::utils::{DateUtils, RandomGenerator};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+const REPLAY_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CupTie {
+    pub home_team_id: u32,
+    pub away_team_id: u32,
+    pub matches: Vec<String>,
+    pub outcome: Option<KnockoutOutcome>,
+}
+
+impl CupTie {
+    pub fn new(home_team_id: u32, away_team_id: u32, match_id: String) -> Self {
+        CupTie {
+            home_team_id,
+            away_team_id,
+            matches: vec![match_id],
+            outcome: None,
+        }
+    }
+
+    pub fn is_decided(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    pub fn winner(&self) -> Option<u32> {
+        self.outcome.as_ref().map(|o| o.winner_team_id)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CupSeason {
+    pub year: u16,
+    pub round: u8,
+    pub round_dates: Vec<NaiveDate>,
+    pub byes: Vec<u32>,
+    pub fixtures: Vec<ScheduleItem>,
+    pub ties: Vec<CupTie>,
+    pub winner: Option<u32>,
+}
+
+// Everything a cup season needs to know about the world around it
+pub struct CupSeasonContext<'c> {
+    pub cup_id: u32,
+    pub cup_slug: &'c str,
+    pub match_day: Weekday,
+    pub replays: bool,
+    pub is_busy: &'c dyn Fn(u32, NaiveDate) -> bool,
+}
+
+impl CupSeason {
+    // Teams are ordered from the strongest, the weakest ones play the preliminary round
+    pub fn new(
+        year: u16,
+        teams: &[u32],
+        first_round: NaiveDate,
+        final_round: NaiveDate,
+        ctx: &CupSeasonContext<'_>,
+    ) -> Self {
+        let rounds = rounds_count(teams.len());
+
+        let round_dates = round_dates(first_round, final_round, rounds);
+
+        let preliminary_teams = preliminary_teams_count(teams.len());
+        let (byes, playing) = teams.split_at(teams.len() - preliminary_teams);
+
+        let mut season = CupSeason {
+            year,
+            round: 0,
+            round_dates,
+            byes: byes.to_vec(),
+            fixtures: Vec::new(),
+            ties: Vec::new(),
+            winner: None,
+        };
+
+        season.draw_round(playing.to_vec(), first_round, ctx);
+
+        season
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.winner.is_some()
+    }
+
+    pub fn set_result(&mut self, match_result: &MatchResult) {
+        if let Some(fixture) = self.fixtures.iter_mut().find(|f| f.id == match_result.id) {
+            fixture.result = Some(ScheduleItemResult::from_match_result(match_result));
+        }
+    }
+
+    // Decides played ties and draws the next round, returns the winner after the final
+    pub fn advance(&mut self, today: NaiveDate, ctx: &CupSeasonContext<'_>) -> Option<u32> {
+        if self.is_finished() {
+            return None;
+        }
+
+        let mut replays = Vec::new();
+
+        for tie in self.ties.iter_mut().filter(|t| !t.is_decided()) {
+            let last_match = match tie.matches.last() {
+                Some(id) => self.fixtures.iter().find(|f| &f.id == id).unwrap(),
+                None => continue,
+            };
+
+            let result = match &last_match.result {
+                Some(result) => result,
+                None => continue,
+            };
+
+            let (home_goals, away_goals) = (result.home.get(), result.away.get());
+
+            let resolution = if tie.matches.len() > 1 {
+                TieResolution::Replay
+            } else if result.extra_time.is_some() {
+                TieResolution::ExtraTime
+            } else {
+                TieResolution::Aggregate
+            };
+
+            if home_goals != away_goals {
+                let winner_team_id = if home_goals > away_goals {
+                    last_match.home_team_id
+                } else {
+                    last_match.away_team_id
+                };
+
+                tie.outcome = Some(KnockoutOutcome {
+                    winner_team_id,
+                    resolution,
+                    extra_time: result.extra_time,
+                    penalties: None,
+                });
+
+                continue;
+            }
+
+            if let Some((home_penalties, away_penalties)) = result.penalties {
+                tie.outcome = Some(KnockoutOutcome {
+                    winner_team_id: if home_penalties > away_penalties {
+                        last_match.home_team_id
+                    } else {
+                        last_match.away_team_id
+                    },
+                    resolution: TieResolution::Penalties,
+                    extra_time: result.extra_time,
+                    penalties: Some((home_penalties, away_penalties)),
+                });
+
+                continue;
+            }
+
+            // a level first match is replayed where the cup allows it, any other
+            // match is played to a finish by the match engine, should one still come
+            // back level without a shootout it is replayed rather than left undecided
+            let replay_date = free_date(
+                last_match.date.date() + Duration::days(REPLAY_DAYS),
+                ctx.match_day,
+                &[tie.home_team_id, tie.away_team_id],
+                ctx,
+            );
+
+            let replay = ScheduleItem::new(
+                ctx.cup_id,
+                String::from(ctx.cup_slug),
+                last_match.away_team_id,
+                last_match.home_team_id,
+                midnight(replay_date),
+                None,
+            );
+
+            tie.matches.push(replay.id.clone());
+            replays.push(replay);
+        }
+
+        self.fixtures.extend(replays);
+
+        if !self.ties.iter().all(|t| t.is_decided()) {
+            return None;
+        }
+
+        let mut teams: Vec<u32> = std::mem::take(&mut self.byes);
+        teams.extend(self.ties.iter().filter_map(|t| t.winner()));
+
+        if teams.len() == 1 {
+            self.winner = Some(teams[0]);
+            return self.winner;
+        }
+
+        self.round += 1;
+
+        let planned_date = self
+            .round_dates
+            .get(self.round as usize)
+            .copied()
+            .unwrap_or(today);
+
+        self.draw_round(teams, planned_date.max(today.succ_opt().unwrap()), ctx);
+
+        None
+    }
+
+    // Only a first match that may be replayed can end level, any other cup
+    // match is played to a finish
+    pub fn match_config(&self, fixture_id: &str, replays: bool) -> MatchConfig {
+        let is_first_match = self
+            .ties
+            .iter()
+            .find(|t| t.matches.iter().any(|id| id == fixture_id))
+            .is_some_and(|t| t.matches.len() == 1);
+
+        if replays && is_first_match {
+            MatchConfig::default()
+        } else {
+            MatchConfig::knockout()
+        }
+    }
+
+    fn draw_round(&mut self, mut teams: Vec<u32>, date: NaiveDate, ctx: &CupSeasonContext<'_>) {
+        teams.shuffle(&mut RandomGenerator::current());
+
+        // the team left over without an opponent goes through to the next round
+        if !teams.len().is_multiple_of(2) {
+            self.byes.extend(teams.pop());
+        }
+
+        let round_date = free_date(date, ctx.match_day, &teams, ctx);
+
+        self.ties = teams
+            .chunks(2)
+            .map(|pair| {
+                let fixture = ScheduleItem::new(
+                    ctx.cup_id,
+                    String::from(ctx.cup_slug),
+                    pair[0],
+                    pair[1],
+                    midnight(round_date),
+                    None,
+                );
+
+                let tie = CupTie::new(pair[0], pair[1], fixture.id.clone());
+
+                self.fixtures.push(fixture);
+
+                tie
+            })
+            .collect();
+    }
+}
+
+// First match day on or after `date` when none of the teams has a league match
+fn free_date(
+    date: NaiveDate,
+    match_day: Weekday,
+    teams: &[u32],
+    ctx: &CupSeasonContext<'_>,
+) -> NaiveDate {
+    let mut candidate = DateUtils::next_weekday(date, match_day);
+
+    while teams
+        .iter()
+        .any(|team_id| (ctx.is_busy)(*team_id, candidate))
+    {
+        candidate = DateUtils::next_weekday(candidate.succ_opt().unwrap(), match_day);
+    }
+
+    candidate
+}
+
+pub fn rounds_count(teams: usize) -> usize {
+    let mut rounds = 0;
+
+    while (1 << rounds) < teams {
+        rounds += 1;
+    }
+
+    rounds
+}
+
+// Teams that can't get a bye when the field isn't a power of two
+pub fn preliminary_teams_count(teams: usize) -> usize {
+    if teams < 2 {
+        return 0;
+    }
+
+    let bracket = 1 << (rounds_count(teams) - 1);
+
+    if bracket * 2 == teams {
+        return teams;
+    }
+
+    (teams - bracket) * 2
+}
+
+// Rounds spread evenly between the first round and the final
+pub fn round_dates(
+    first_round: NaiveDate,
+    final_round: NaiveDate,
+    rounds: usize,
+) -> Vec<NaiveDate> {
+    if rounds <= 1 {
+        return vec![first_round; rounds];
+    }
+
+    let interval = (final_round - first_round).num_days() / (rounds as i64 - 1);
+
+    (0..rounds)
+        .map(|round| first_round + Duration::days(round as i64 * interval))
+        .collect()
+}
+
+fn midnight(date: NaiveDate) -> NaiveDateTime {
+    NaiveDateTime::new(date, NaiveTime::from_hms_opt(0, 0, 0).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::{Score, TeamScore};
+    use chrono::Datelike;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn result(fixture: &ScheduleItem, home_goals: u8, away_goals: u8) -> MatchResult {
+        MatchResult {
+            id: fixture.id.clone(),
+            league_id: fixture.league_id,
+            league_slug: fixture.league_slug.clone(),
+            home_team_id: fixture.home_team_id,
+            away_team_id: fixture.away_team_id,
+            score: Score {
+                home_team: TeamScore::new_with_score(fixture.home_team_id, home_goals),
+                away_team: TeamScore::new_with_score(fixture.away_team_id, away_goals),
+                details: vec![],
+            },
+            details: None,
+        }
+    }
+
+    #[test]
+    fn preliminary_round_evens_the_bracket() {
+        assert_eq!(0, preliminary_teams_count(1));
+        assert_eq!(8, preliminary_teams_count(8));
+        assert_eq!(4, preliminary_teams_count(10));
+        assert_eq!(14, preliminary_teams_count(15));
+        assert_eq!(4, rounds_count(10));
+    }
+
+    #[test]
+    fn round_dates_end_with_final() {
+        let dates = round_dates(date(2024, 8, 28), date(2025, 5, 21), 4);
+
+        assert_eq!(4, dates.len());
+        assert_eq!(date(2024, 8, 28), dates[0]);
+        assert!(dates[3] <= date(2025, 5, 21));
+        assert!(dates.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn cup_is_played_until_a_single_winner() {
+        let teams: Vec<u32> = (1..=10).collect();
+
+        let is_busy = |_: u32, date: NaiveDate| date.weekday() == Weekday::Sat;
+
+        let ctx = CupSeasonContext {
+            cup_id: 1,
+            cup_slug: "cup",
+            match_day: Weekday::Wed,
+            replays: true,
+            is_busy: &is_busy,
+        };
+
+        let mut season = CupSeason::new(2024, &teams, date(2024, 8, 28), date(2025, 5, 21), &ctx);
+
+        assert_eq!(6, season.byes.len());
+        assert_eq!(2, season.ties.len());
+
+        let mut match_days = 0;
+
+        while !season.is_finished() {
+            let today = season
+                .fixtures
+                .iter()
+                .filter(|f| f.result.is_none())
+                .map(|f| f.date)
+                .min()
+                .expect("cup stalled");
+
+            let results: Vec<MatchResult> = season
+                .fixtures
+                .iter()
+                .filter(|f| f.date == today)
+                .map(|f| {
+                    if season
+                        .ties
+                        .iter()
+                        .any(|t| t.matches.len() == 1 && t.matches[0] == f.id)
+                    {
+                        // every first match is a draw
+                        result(f, 1, 1)
+                    } else {
+                        result(f, 2, 1)
+                    }
+                })
+                .collect();
+
+            for result in &results {
+                season.set_result(result);
+            }
+
+            season.advance(today.date(), &ctx);
+
+            match_days += 1;
+        }
+
+        let winner = season.winner.unwrap();
+
+        assert!(teams.contains(&winner));
+        assert!(season
+            .fixtures
+            .iter()
+            .all(|f| f.date.weekday() == Weekday::Wed));
+        // 4 rounds, each followed by a replay
+        assert_eq!(8, match_days);
+        assert!(season
+            .ties
+            .iter()
+            .all(|t| t.outcome.as_ref().unwrap().resolution == TieResolution::Replay));
+    }
+
+    #[test]
+    fn level_match_without_shootout_is_replayed_and_round_advances() {
+        let teams: Vec<u32> = (1..=4).collect();
+
+        let is_busy = |_: u32, _: NaiveDate| false;
+
+        let ctx = CupSeasonContext {
+            cup_id: 1,
+            cup_slug: "cup",
+            match_day: Weekday::Wed,
+            replays: false,
+            is_busy: &is_busy,
+        };
+
+        let mut season = CupSeason::new(2024, &teams, date(2024, 8, 28), date(2025, 5, 21), &ctx);
+
+        assert_eq!(2, season.ties.len());
+
+        let level = result(&season.fixtures[0], 1, 1);
+        let decided = result(&season.fixtures[1], 2, 1);
+
+        season.set_result(&level);
+        season.set_result(&decided);
+        season.advance(date(2024, 8, 28), &ctx);
+
+        assert_eq!(0, season.round);
+        assert_eq!(2, season.ties[0].matches.len());
+
+        let replay = season.fixtures.last().unwrap();
+        let replay_date = replay.date.date();
+        let replay_result = result(replay, 2, 1);
+
+        season.set_result(&replay_result);
+        season.advance(replay_date, &ctx);
+
+        assert_eq!(1, season.round);
+        assert_eq!(1, season.ties.len());
+    }
+
+    #[test]
+    fn odd_team_out_gets_a_bye() {
+        let is_busy = |_: u32, _: NaiveDate| false;
+
+        let ctx = CupSeasonContext {
+            cup_id: 1,
+            cup_slug: "cup",
+            match_day: Weekday::Wed,
+            replays: false,
+            is_busy: &is_busy,
+        };
+
+        let mut season = CupSeason::new(2024, &[1, 2], date(2024, 8, 28), date(2025, 5, 21), &ctx);
+
+        season.fixtures.clear();
+        season.draw_round(vec![1, 2, 3], date(2024, 8, 28), &ctx);
+
+        assert_eq!(1, season.ties.len());
+        assert_eq!(1, season.byes.len());
+
+        let bye_team_id = season.byes[0];
+
+        let match_result = result(&season.fixtures[0], 2, 1);
+        season.set_result(&match_result);
+        season.advance(date(2024, 8, 28), &ctx);
+
+        let final_tie = &season.ties[0];
+
+        assert_eq!(1, season.round);
+        assert!(final_tie.home_team_id == bye_team_id || final_tie.away_team_id == bye_team_id);
+    }
+}
@@ -1,9 +1,12 @@
 use crate::context::GlobalContext;
 use crate::league::{League, LeagueResult};
+use crate::utils::{RandomGenerator, RandomStream};
 use crate::{Club, Logging};
 use rayon::iter::IntoParallelRefMutIterator;
 use rayon::iter::ParallelIterator;
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize)]
 pub struct LeagueCollection {
     pub leagues: Vec<League>,
 }
@@ -33,12 +36,18 @@ impl LeagueCollection {
 
                     let league_slug = String::from(&league.slug);
 
+                    let league_seed = ctx
+                        .simulation
+                        .random_seed(RandomStream::League, league.id as u64);
+
                     Logging::estimate_result(
                         || {
-                            league.simulate(
-                                clubs,
-                                ctx.with_league(league.id, league_slug, &league_team_ids),
-                            )
+                            RandomGenerator::scope(league_seed, || {
+                                league.simulate(
+                                    clubs,
+                                    ctx.with_league(league.id, league_slug, &league_team_ids),
+                                )
+                            })
                         },
                         message,
                     )
@@ -0,0 +1,40 @@
+use crate::league::{LeagueTableRow, Season};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LeagueHistory {
+    pub items: Vec<LeagueHistoryItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LeagueHistoryItem {
+    pub season: Season,
+    pub table: Vec<LeagueTableRow>,
+    pub champion_team_id: Option<u32>,
+}
+
+impl LeagueHistory {
+    pub fn new() -> Self {
+        LeagueHistory { items: Vec::new() }
+    }
+
+    pub fn add(&mut self, season: Season, table: Vec<LeagueTableRow>) {
+        let champion_team_id = table.first().map(|row| row.team_id);
+
+        self.items.push(LeagueHistoryItem {
+            season,
+            table,
+            champion_team_id,
+        });
+    }
+
+    pub fn last(&self) -> Option<&LeagueHistoryItem> {
+        self.items.last()
+    }
+}
+
+impl Default for LeagueHistory {
+    fn default() -> Self {
+        LeagueHistory::new()
+    }
+}
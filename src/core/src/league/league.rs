@@ -1,14 +1,16 @@
 use crate::context::{GlobalContext, SimulationContext};
 use crate::league::{
-    LeagueMatch, LeagueMatchResultResult, LeagueResult, LeagueTable, MatchStorage, Schedule,
+    LeagueHistory, LeagueMatch, LeagueMatchResultResult, LeagueResult, LeagueTable, MatchStorage,
+    RankingRule, Schedule, Season,
 };
-use crate::r#match::{Match, MatchResult};
-use crate::utils::Logging;
+use crate::r#match::{Match, MatchConfig, MatchEngineMode, MatchResult};
+use crate::utils::{Logging, RandomGenerator, RandomStream};
 use crate::{Club, Team};
 use chrono::{Datelike, NaiveDate};
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct League {
     pub id: u32,
     pub name: String,
@@ -19,6 +21,8 @@ pub struct League {
     pub settings: LeagueSettings,
     pub matches: MatchStorage,
     pub reputation: u16,
+    pub tier: u8,
+    pub history: LeagueHistory,
 }
 
 impl League {
@@ -28,6 +32,7 @@ impl League {
         slug: String,
         country_id: u32,
         reputation: u16,
+        tier: u8,
         settings: LeagueSettings,
     ) -> Self {
         League {
@@ -40,6 +45,8 @@ impl League {
             matches: MatchStorage::new(),
             settings,
             reputation,
+            tier,
+            history: LeagueHistory::new(),
         }
     }
 
@@ -57,11 +64,13 @@ impl League {
         );
 
         if schedule_result.is_match_scheduled() {
-            let match_results = self.play_matches(&mut schedule_result.scheduled_matches, clubs);
+            let match_results =
+                self.play_matches(&mut schedule_result.scheduled_matches, clubs, &ctx);
             self.table.update_from_results(&match_results);
+            self.table.sort(&self.settings.ranking);
 
             match_results.iter().for_each(|mr| {
-                // copy without match details, that store in separate gzipped file
+                // copy without position data, that is stored in a separate replay file
                 self.matches.push(mr.copy_without_data_positions());
             });
 
@@ -75,37 +84,61 @@ impl League {
         &mut self,
         scheduled_matches: &mut Vec<LeagueMatch>,
         clubs: &[Club],
+        ctx: &GlobalContext<'_>,
     ) -> Vec<MatchResult> {
+        let match_engine = self
+            .settings
+            .match_engine
+            .unwrap_or(ctx.simulation.match_engine);
+
         scheduled_matches
             .par_iter_mut()
             .map(|scheduled_match| {
-                let home_team = self.get_team(clubs, scheduled_match.home_team_id);
-                let away_team = self.get_team(clubs, scheduled_match.away_team_id);
-
-                let match_to_play = Match::make(
-                    scheduled_match.id.clone(),
-                    scheduled_match.league_id,
-                    &scheduled_match.league_slug,
-                    home_team.get_match_squad(),
-                    away_team.get_match_squad(),
+                let match_seed = ctx.simulation.random_seed(
+                    RandomStream::Match,
+                    ((scheduled_match.home_team_id as u64) << 32)
+                        | scheduled_match.away_team_id as u64,
                 );
 
-                let message = &format!(
-                    "play match: {} - {}",
-                    &match_to_play.home_squad.team_name, &match_to_play.away_squad.team_name
-                );
+                RandomGenerator::scope(match_seed, || {
+                    self.play_match(scheduled_match, clubs, match_engine)
+                })
+            })
+            .collect::<Vec<MatchResult>>()
+    }
+
+    fn play_match(
+        &self,
+        scheduled_match: &mut LeagueMatch,
+        clubs: &[Club],
+        match_engine: MatchEngineMode,
+    ) -> MatchResult {
+        let home_team = self.get_team(clubs, scheduled_match.home_team_id);
+        let away_team = self.get_team(clubs, scheduled_match.away_team_id);
 
-                let match_result = Logging::estimate_result(|| match_to_play.play(), message);
+        let match_to_play = Match::make(
+            scheduled_match.id.clone(),
+            scheduled_match.league_id,
+            &scheduled_match.league_slug,
+            home_team.get_match_squad(),
+            away_team.get_match_squad(),
+        )
+        .with_config(MatchConfig::default().with_engine(match_engine));
 
-                // Set match result in schedule
-                scheduled_match.result = Some(LeagueMatchResultResult::new(
-                    &match_result.score.home_team,
-                    &match_result.score.away_team,
-                ));
+        let message = &format!(
+            "play match: {} - {}",
+            &match_to_play.home_squad.team_name, &match_to_play.away_squad.team_name
+        );
 
-                match_result
-            })
-            .collect::<Vec<MatchResult>>()
+        let match_result = Logging::estimate_result(|| match_to_play.play(), message);
+
+        // Set match result in schedule
+        scheduled_match.result = Some(LeagueMatchResultResult::new(
+            &match_result.score.home_team,
+            &match_result.score.away_team,
+        ));
+
+        match_result
     }
 
     fn get_team<'c>(&self, clubs: &'c [Club], id: u32) -> &'c Team {
@@ -117,7 +150,7 @@ impl League {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DayMonthPeriod {
     pub from_day: u8,
     pub from_month: u8,
@@ -135,12 +168,47 @@ impl DayMonthPeriod {
             to_month,
         }
     }
+
+    // Inclusive on both ends, a period may wrap over the new year
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        let day = (date.month() as u8, date.day() as u8);
+
+        let from = (self.from_month, self.from_day);
+        let to = (self.to_month, self.to_day);
+
+        if from <= to {
+            from <= day && day <= to
+        } else {
+            day >= from || day <= to
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LeagueSettings {
     pub season_starting_half: DayMonthPeriod,
     pub season_ending_half: DayMonthPeriod,
+    // exchange of teams with the league one tier above
+    pub promotion: Option<PromotionSettings>,
+    // tie-breakers for teams level on points
+    pub ranking: Vec<RankingRule>,
+    // overrides the match engine of the simulation
+    pub match_engine: Option<MatchEngineMode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromotionSettings {
+    pub places: u8,
+    pub playoff_places: u8,
+}
+
+impl PromotionSettings {
+    pub fn new(places: u8, playoff_places: u8) -> Self {
+        PromotionSettings {
+            places,
+            playoff_places,
+        }
+    }
 }
 
 impl LeagueSettings {
@@ -152,6 +220,26 @@ impl LeagueSettings {
         (NaiveDate::day(&date) as u8) == season_starting_date.from_day
             && (date.month() as u8) == season_starting_date.from_month
     }
+
+    pub fn is_time_for_season_end(&self, context: &SimulationContext) -> bool {
+        let season_ending_date = &self.season_ending_half;
+
+        let date = context.date.date();
+
+        (NaiveDate::day(&date) as u8) == season_ending_date.to_day
+            && (date.month() as u8) == season_ending_date.to_month
+    }
+
+    // Season that finishes in the year of `date`
+    pub fn ending_season(&self, date: NaiveDate) -> Season {
+        let end_year = date.year() as u16;
+
+        if self.season_starting_half.from_month > self.season_ending_half.to_month {
+            Season::TwoYear(end_year - 1, end_year)
+        } else {
+            Season::OneYear(end_year)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -174,6 +262,9 @@ mod tests {
                 to_day: 0,
                 to_month: 0,
             },
+            promotion: None,
+            ranking: RankingRule::defaults(),
+            match_engine: None,
         };
 
         let context = SimulationContext {
@@ -184,6 +275,8 @@ mod tests {
             // Add other fields as needed
             day: 0,
             hour: 0,
+            seed: 0,
+            match_engine: MatchEngineMode::Detailed,
         };
 
         assert!(settings.is_time_for_new_schedule(&context));
@@ -204,6 +297,9 @@ mod tests {
                 to_day: 0,
                 to_month: 0,
             },
+            promotion: None,
+            ranking: RankingRule::defaults(),
+            match_engine: None,
         };
 
         let context = SimulationContext {
@@ -214,8 +310,48 @@ mod tests {
             // Add other fields as needed
             day: 0,
             hour: 0,
+            seed: 0,
+            match_engine: MatchEngineMode::Detailed,
         };
 
         assert!(!settings.is_time_for_new_schedule(&context));
     }
+
+    #[test]
+    fn test_season_end_and_ending_season() {
+        let settings = LeagueSettings {
+            season_starting_half: DayMonthPeriod::new(1, 7, 5, 12),
+            season_ending_half: DayMonthPeriod::new(1, 3, 31, 5),
+            promotion: None,
+            ranking: RankingRule::defaults(),
+            match_engine: None,
+        };
+
+        let date = NaiveDate::from_ymd_opt(2025, 5, 31).unwrap();
+
+        let context = SimulationContext {
+            date: date.and_hms_opt(0, 0, 0).unwrap(),
+            day: 31,
+            hour: 0,
+            seed: 0,
+            match_engine: MatchEngineMode::Detailed,
+        };
+
+        assert!(settings.is_time_for_season_end(&context));
+        assert_eq!(Season::TwoYear(2024, 2025), settings.ending_season(date));
+    }
+
+    #[test]
+    fn day_month_period_contains() {
+        let summer = DayMonthPeriod::new(1, 6, 31, 8);
+        let winter = DayMonthPeriod::new(15, 12, 15, 1);
+
+        assert!(summer.contains(NaiveDate::from_ymd_opt(2024, 6, 1).unwrap()));
+        assert!(summer.contains(NaiveDate::from_ymd_opt(2024, 8, 31).unwrap()));
+        assert!(!summer.contains(NaiveDate::from_ymd_opt(2024, 9, 1).unwrap()));
+
+        assert!(winter.contains(NaiveDate::from_ymd_opt(2024, 12, 20).unwrap()));
+        assert!(winter.contains(NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()));
+        assert!(!winter.contains(NaiveDate::from_ymd_opt(2025, 2, 1).unwrap()));
+    }
 }
@@ -1,17 +1,23 @@
 mod collection;
 mod context;
+mod history;
 mod league;
+mod promotion;
 pub mod result;
 pub mod schedule;
 mod season;
+mod season_end;
 pub mod storages;
 pub mod table;
 
 pub use collection::*;
 pub use context::*;
+pub use history::*;
 pub use league::*;
+pub use promotion::*;
 pub use result::*;
 pub use schedule::*;
 pub use season::*;
+pub use season_end::*;
 pub use storages::*;
 pub use table::*;
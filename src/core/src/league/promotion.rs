@@ -0,0 +1,292 @@
+use crate::context::GlobalContext;
+use crate::league::{League, LeagueCollection, PromotionSettings, ScheduleItem};
+use crate::r#match::{Match, MatchConfig, MatchResult};
+use crate::utils::{Logging, RandomGenerator, RandomStream};
+use crate::{Club, Team, TeamType};
+
+#[derive(Debug, PartialEq)]
+pub struct PromotionPlan {
+    pub promoted: Vec<u32>,
+    pub relegated: Vec<u32>,
+    // (upper league team, lower league team)
+    pub playoffs: Vec<(u32, u32)>,
+}
+
+impl PromotionPlan {
+    // Both standings are final tables, the lower one only holds teams allowed to go up
+    pub fn new(upper: &[u32], lower: &[u32], settings: &PromotionSettings) -> Self {
+        let places = (settings.places as usize).min(lower.len()).min(upper.len());
+
+        let playoff_places = (settings.playoff_places as usize)
+            .min(lower.len() - places)
+            .min(upper.len() - places);
+
+        let relegation_position = upper.len() - places;
+
+        PromotionPlan {
+            promoted: lower[..places].to_vec(),
+            relegated: upper[relegation_position..].to_vec(),
+            playoffs: upper[relegation_position - playoff_places..relegation_position]
+                .iter()
+                .rev()
+                .zip(&lower[places..places + playoff_places])
+                .map(|(upper_team, lower_team)| (*upper_team, *lower_team))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamMove {
+    pub team_id: u32,
+    pub from_league_id: u32,
+    pub to_league_id: u32,
+}
+
+impl LeagueCollection {
+    // Team moves between tiers of the ending leagues, with play-off matches grouped by league
+    pub fn promotion_moves(
+        &self,
+        ending_leagues: &[u32],
+        clubs: &[Club],
+        ctx: &GlobalContext<'_>,
+    ) -> (Vec<TeamMove>, Vec<(u32, Vec<MatchResult>)>) {
+        let mut moves = Vec::new();
+        let mut playoff_results = Vec::new();
+
+        for lower in self
+            .leagues
+            .iter()
+            .filter(|league| ending_leagues.contains(&league.id))
+        {
+            let settings = match &lower.settings.promotion {
+                Some(settings) => settings,
+                None => continue,
+            };
+
+            let upper = self.leagues.iter().find(|league| {
+                league.tier + 1 == lower.tier && ending_leagues.contains(&league.id)
+            });
+
+            let upper = match upper {
+                Some(upper) => upper,
+                None => continue,
+            };
+
+            let eligible: Vec<u32> = lower
+                .table
+                .team_ids()
+                .into_iter()
+                .filter(|team_id| {
+                    find_team(clubs, *team_id).is_some_and(|team| team.team_type == TeamType::Main)
+                })
+                .collect();
+
+            let plan = PromotionPlan::new(&upper.table.team_ids(), &eligible, settings);
+
+            let mut promoted = plan.promoted;
+            let mut relegated = plan.relegated;
+
+            let mut results = Vec::with_capacity(plan.playoffs.len());
+
+            for (upper_team_id, lower_team_id) in plan.playoffs {
+                let (match_result, winner_team_id) =
+                    play_playoff(upper, upper_team_id, lower_team_id, clubs, ctx);
+
+                if winner_team_id == lower_team_id {
+                    promoted.push(lower_team_id);
+                    relegated.push(upper_team_id);
+                }
+
+                results.push(match_result);
+            }
+
+            moves.extend(promoted.into_iter().map(|team_id| TeamMove {
+                team_id,
+                from_league_id: lower.id,
+                to_league_id: upper.id,
+            }));
+
+            moves.extend(relegated.into_iter().map(|team_id| TeamMove {
+                team_id,
+                from_league_id: upper.id,
+                to_league_id: lower.id,
+            }));
+
+            if !results.is_empty() {
+                playoff_results.push((upper.id, results));
+            }
+        }
+
+        (moves, playoff_results)
+    }
+
+    // Teams level on every ranking rule meet in a single match for the title and the relegation line
+    pub fn play_deciders(
+        &mut self,
+        league_idx: usize,
+        clubs: &[Club],
+        ctx: &GlobalContext<'_>,
+    ) -> Vec<MatchResult> {
+        let league = &self.leagues[league_idx];
+
+        let mut positions = vec![1];
+
+        let relegated_places = self
+            .leagues
+            .iter()
+            .filter(|lower| lower.tier == league.tier + 1)
+            .find_map(|lower| lower.settings.promotion.as_ref())
+            .map(|promotion| promotion.places as usize);
+
+        if let Some(places) = relegated_places {
+            positions.push(league.table.rows.len().saturating_sub(places));
+            positions.dedup();
+        }
+
+        let mut results = Vec::new();
+
+        loop {
+            let league = &self.leagues[league_idx];
+
+            let pairs = league
+                .table
+                .required_play_offs(&league.settings.ranking, &positions);
+
+            if pairs.is_empty() {
+                return results;
+            }
+
+            let played: Vec<(MatchResult, u32, u32)> = pairs
+                .into_iter()
+                .map(|(first_team_id, second_team_id)| {
+                    let (match_result, winner_team_id) =
+                        play_playoff(league, first_team_id, second_team_id, clubs, ctx);
+
+                    let loser_team_id = if winner_team_id == first_team_id {
+                        second_team_id
+                    } else {
+                        first_team_id
+                    };
+
+                    (match_result, winner_team_id, loser_team_id)
+                })
+                .collect();
+
+            let league = &mut self.leagues[league_idx];
+
+            for (match_result, winner_team_id, loser_team_id) in played {
+                league.table.add_play_off(winner_team_id, loser_team_id);
+                results.push(match_result);
+            }
+
+            league.table.sort(&league.settings.ranking);
+        }
+    }
+}
+
+// Single match at the first team's ground, played to a finish with extra time and penalties
+fn play_playoff(
+    league: &League,
+    home_team_id: u32,
+    away_team_id: u32,
+    clubs: &[Club],
+    ctx: &GlobalContext<'_>,
+) -> (MatchResult, u32) {
+    let match_seed = ctx.simulation.random_seed(
+        RandomStream::Match,
+        ((home_team_id as u64) << 32) | away_team_id as u64,
+    );
+
+    RandomGenerator::scope(match_seed, || {
+        let home_team = find_team(clubs, home_team_id).unwrap();
+        let away_team = find_team(clubs, away_team_id).unwrap();
+
+        let schedule_item = ScheduleItem::new(
+            league.id,
+            String::from(&league.slug),
+            home_team_id,
+            away_team_id,
+            ctx.simulation.date,
+            None,
+        );
+
+        let match_to_play = Match::make(
+            schedule_item.id,
+            league.id,
+            &league.slug,
+            home_team.get_match_squad(),
+            away_team.get_match_squad(),
+        )
+        .with_config(MatchConfig::knockout().with_engine(
+            league
+                .settings
+                .match_engine
+                .unwrap_or(ctx.simulation.match_engine),
+        ));
+
+        let message = &format!(
+            "play {} play-off: {} - {}",
+            &league.name, &home_team.name, &away_team.name
+        );
+
+        let match_result = Logging::estimate_result(|| match_to_play.play(), message);
+
+        let (home_goals, away_goals) = (
+            match_result.score.home_team.get(),
+            match_result.score.away_team.get(),
+        );
+
+        // extra time goals are already in the score, a level score went to penalties
+        let home_wins = match match_result.penalty_shootout() {
+            Some((home_penalties, away_penalties)) if home_goals == away_goals => {
+                home_penalties > away_penalties
+            }
+            _ => home_goals > away_goals,
+        };
+
+        let winner_team_id = if home_wins {
+            home_team_id
+        } else {
+            away_team_id
+        };
+
+        (match_result, winner_team_id)
+    })
+}
+
+fn find_team(clubs: &[Club], team_id: u32) -> Option<&Team> {
+    clubs
+        .iter()
+        .flat_map(|c| &c.teams.teams)
+        .find(|team| team.id == team_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn promotion_plan_swaps_bottom_and_top() {
+        let upper = [1, 2, 3, 4, 5, 6, 7, 8];
+        let lower = [11, 12, 13, 14, 15, 16];
+
+        let plan = PromotionPlan::new(&upper, &lower, &PromotionSettings::new(2, 2));
+
+        assert_eq!(vec![11, 12], plan.promoted);
+        assert_eq!(vec![7, 8], plan.relegated);
+        assert_eq!(vec![(6, 13), (5, 14)], plan.playoffs);
+    }
+
+    #[test]
+    fn promotion_plan_is_limited_by_eligible_teams() {
+        let upper = [1, 2, 3, 4];
+        let lower = [11];
+
+        let plan = PromotionPlan::new(&upper, &lower, &PromotionSettings::new(2, 2));
+
+        assert_eq!(vec![11], plan.promoted);
+        assert_eq!(vec![4], plan.relegated);
+        assert!(plan.playoffs.is_empty());
+    }
+}
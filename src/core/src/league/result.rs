@@ -1,13 +1,14 @@
-use crate::league::{LeagueTableResult, ScheduleItem};
+use crate::league::{LeagueTableResult, ScheduleItem, SeasonSummary};
 use crate::r#match::{MatchResult, TeamScore};
 use crate::simulator::SimulatorData;
-use crate::{MatchHistoryItem, SimulationResult};
+use crate::SimulationResult;
 use chrono::NaiveDateTime;
 
 pub struct LeagueResult {
     pub league_id: u32,
     pub table_result: LeagueTableResult,
     pub match_results: Option<Vec<MatchResult>>,
+    pub season_summary: Option<SeasonSummary>,
 }
 
 impl LeagueResult {
@@ -16,6 +17,7 @@ impl LeagueResult {
             league_id,
             table_result,
             match_results: None,
+            season_summary: None,
         }
     }
 
@@ -28,6 +30,7 @@ impl LeagueResult {
             league_id,
             table_result,
             match_results: Some(match_results),
+            season_summary: None,
         }
     }
 
@@ -39,11 +42,13 @@ impl LeagueResult {
                 result.match_results.push(match_result);
             }
         }
+
+        if let Some(season_summary) = self.season_summary {
+            result.season_summaries.push(season_summary);
+        }
     }
 
     fn process_match_results(result: &MatchResult, data: &mut SimulatorData) {
-        let now = data.date;
-
         let league = data.league_mut(result.league_id).unwrap();
 
         league.schedule.update_match_result(
@@ -52,52 +57,8 @@ impl LeagueResult {
             &result.score.away_team,
         );
 
-        let home_team = data.team_mut(result.score.home_team.team_id).unwrap();
-        home_team.match_history.add(MatchHistoryItem::new(
-            now,
-            result.score.home_team.team_id,
-            (
-                TeamScore::from(&result.score.home_team),
-                TeamScore::from(&result.score.away_team),
-            ),
-        ));
-
-        let away_team = data.team_mut(result.score.away_team.team_id).unwrap();
-        away_team.match_history.add(MatchHistoryItem::new(
-            now,
-            result.score.away_team.team_id,
-            (
-                TeamScore::from(&result.score.away_team),
-                TeamScore::from(&result.score.home_team),
-            ),
-        ));
-
-        // process_match_events(result, data);
-        //
-        // fn process_match_events(result: &MatchResult, data: &mut SimulatorData) {
-        //     for match_event in &result.details.as_ref().unwrap().events {
-        //         match match_event {
-        //             MatchEvent::MatchPlayed(player_id, is_start_squad, _minutes_played) => {
-        //                 let mut player = data.player_mut(*player_id).unwrap();
-        //
-        //                 if *is_start_squad {
-        //                     player.statistics.played += 1;
-        //                 } else {
-        //                     player.statistics.played_subs += 1;
-        //                 }
-        //             }
-        //             MatchEvent::Goal(player_id) => {
-        //                 let mut player = data.player_mut(*player_id).unwrap();
-        //                 player.statistics.goals += 1;
-        //             }
-        //             MatchEvent::Assist(player_id) => {
-        //                 let mut player = data.player_mut(*player_id).unwrap();
-        //                 player.statistics.assists += 1;
-        //             }
-        //             MatchEvent::Injury(player_id) => {}
-        //         }
-        //     }
-        // }
+        data.add_match_history(result);
+        data.add_player_statistics(result);
     }
 }
 
@@ -152,7 +152,7 @@ fn rotate(clubs: &mut Vec<(u32, u32)>) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::league::DayMonthPeriod;
+    use crate::league::{DayMonthPeriod, RankingRule};
 
     #[test]
     fn generate_schedule_is_correct() {
@@ -165,6 +165,9 @@ mod tests {
         let league_settings = LeagueSettings {
             season_starting_half: DayMonthPeriod::new(1, 1, 30, 6),
             season_ending_half: DayMonthPeriod::new(1, 7, 1, 12),
+            promotion: None,
+            ranking: RankingRule::defaults(),
+            match_engine: None,
         };
 
         let schedule_tours = schedule
@@ -1,22 +1,23 @@
 use crate::context::GlobalContext;
 use crate::league::round::RoundSchedule;
 use crate::league::{LeagueMatch, LeagueSettings, ScheduleGenerator, ScheduleResult, Season};
-use crate::r#match::TeamScore;
+use crate::r#match::{MatchResult, TeamScore};
 use chrono::{Datelike, NaiveDate, NaiveDateTime};
 use log::error;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Schedule {
     pub tours: Vec<ScheduleTour>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScheduleTour {
     pub num: u8,
     pub items: Vec<ScheduleItem>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScheduleItem {
     pub id: String,
 
@@ -31,10 +32,13 @@ pub struct ScheduleItem {
     pub result: Option<ScheduleItemResult>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScheduleItemResult {
     pub home: TeamScore,
     pub away: TeamScore,
+    // knockout matches level after normal time: extra time goals and the shootout
+    pub extra_time: Option<(u8, u8)>,
+    pub penalties: Option<(u8, u8)>,
 }
 
 impl Schedule {
@@ -112,18 +116,13 @@ impl Schedule {
             .flat_map(|t| &t.items)
             .filter(|s| s.home_team_id == team_id || s.away_team_id == team_id)
             .map(|s| {
-                let res = match &s.result {
-                    Some(result) => Some(ScheduleItemResult::new(&result.home, &result.away)),
-                    None => None,
-                };
-
                 ScheduleItem::new(
                     s.league_id,
                     String::from(&s.league_slug),
                     s.home_team_id,
                     s.away_team_id,
                     s.date,
-                    res,
+                    s.result.clone(),
                 )
             })
             .collect()
@@ -188,6 +187,16 @@ impl ScheduleItemResult {
         ScheduleItemResult {
             home: TeamScore::from(home_team),
             away: TeamScore::from(away_team),
+            extra_time: None,
+            penalties: None,
+        }
+    }
+
+    pub fn from_match_result(match_result: &MatchResult) -> Self {
+        ScheduleItemResult {
+            extra_time: match_result.extra_time(),
+            penalties: match_result.penalty_shootout(),
+            ..ScheduleItemResult::new(&match_result.score.home_team, &match_result.score.away_team)
         }
     }
 }
@@ -250,6 +259,8 @@ mod tests {
             result: Some(ScheduleItemResult {
                 home: TeamScore::new_with_score(0, 0),
                 away: TeamScore::new_with_score(0, 0),
+                extra_time: None,
+                penalties: None,
             }),
         };
         let item2 = ScheduleItem {
@@ -265,6 +276,8 @@ mod tests {
             result: Some(ScheduleItemResult {
                 home: TeamScore::new_with_score(0, 0),
                 away: TeamScore::new_with_score(0, 0),
+                extra_time: None,
+                penalties: None,
             }),
         };
         let mut items_with_results = Vec::new();
@@ -315,6 +328,8 @@ mod tests {
             result: Some(ScheduleItemResult {
                 home: TeamScore::new_with_score(0, 0),
                 away: TeamScore::new_with_score(0, 0),
+                extra_time: None,
+                penalties: None,
             }),
         };
         let item2 = ScheduleItem {
@@ -330,6 +345,8 @@ mod tests {
             result: Some(ScheduleItemResult {
                 home: TeamScore::new_with_score(0, 0),
                 away: TeamScore::new_with_score(0, 0),
+                extra_time: None,
+                penalties: None,
             }),
         };
         let schedule_tour = ScheduleTour {
@@ -354,6 +371,8 @@ mod tests {
             result: Some(ScheduleItemResult {
                 home: TeamScore::new_with_score(0, 0),
                 away: TeamScore::new_with_score(0, 0),
+                extra_time: None,
+                penalties: None,
             }),
         };
         let item2 = ScheduleItem {
@@ -368,6 +387,8 @@ mod tests {
             result: Some(ScheduleItemResult {
                 home: TeamScore::new_with_score(0, 0),
                 away: TeamScore::new_with_score(0, 0),
+                extra_time: None,
+                penalties: None,
             }),
         };
         let schedule_tour = ScheduleTour {
@@ -0,0 +1,436 @@
+use crate::context::GlobalContext;
+use crate::league::{League, LeagueCollection, LeagueResult, LeagueTableResult, Season};
+use crate::r#match::MatchResult;
+use crate::{Club, Team};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct SeasonSummary {
+    pub league_id: u32,
+    pub season: Season,
+    pub champion_team_id: Option<u32>,
+    // teams that left the league for the tier above or below
+    pub promoted: Vec<u32>,
+    pub relegated: Vec<u32>,
+    pub top_scorer: Option<SeasonTopScorer>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeasonTopScorer {
+    pub player_id: u32,
+    pub team_id: u32,
+    pub goals: u16,
+}
+
+impl LeagueCollection {
+    // Freezes final tables and player statistics, exchanges teams between tiers
+    // and prepares leagues for the next season
+    pub fn end_season(&mut self, clubs: &mut [Club], ctx: &GlobalContext<'_>) -> Vec<LeagueResult> {
+        let ending_leagues: Vec<u32> = self
+            .leagues
+            .iter()
+            .filter(|league| league.settings.is_time_for_season_end(&ctx.simulation))
+            .map(|league| league.id)
+            .collect();
+
+        if ending_leagues.is_empty() {
+            return Vec::new();
+        }
+
+        let date = ctx.simulation.date.date();
+
+        let mut match_results: Vec<(u32, Vec<MatchResult>)> = Vec::new();
+
+        for idx in 0..self.leagues.len() {
+            if !ending_leagues.contains(&self.leagues[idx].id) {
+                continue;
+            }
+
+            let results = self.play_deciders(idx, clubs, ctx);
+
+            if !results.is_empty() {
+                match_results.push((self.leagues[idx].id, results));
+            }
+        }
+
+        let (moves, playoff_results) = self.promotion_moves(&ending_leagues, clubs, ctx);
+
+        match_results.extend(playoff_results);
+
+        let mut summaries = Vec::with_capacity(ending_leagues.len());
+
+        for league in self
+            .leagues
+            .iter()
+            .filter(|league| ending_leagues.contains(&league.id))
+        {
+            let tier_of = |league_id: u32| {
+                self.leagues
+                    .iter()
+                    .find(|l| l.id == league_id)
+                    .map(|l| l.tier)
+                    .unwrap_or(league.tier)
+            };
+
+            let leaving = |upwards: bool| {
+                moves
+                    .iter()
+                    .filter(|m| m.from_league_id == league.id)
+                    .filter(|m| (tier_of(m.to_league_id) < league.tier) == upwards)
+                    .map(|m| m.team_id)
+                    .collect()
+            };
+
+            summaries.push(SeasonSummary {
+                league_id: league.id,
+                season: league.settings.ending_season(date),
+                champion_team_id: league.table.rows.first().map(|row| row.team_id),
+                promoted: leaving(true),
+                relegated: leaving(false),
+                top_scorer: top_scorer(league),
+            });
+        }
+
+        // before the moves, every team is archived with the season of the league it played in
+        for team in clubs.iter_mut().flat_map(|c| &mut c.teams.teams) {
+            if let Some(summary) = summaries.iter().find(|s| s.league_id == team.league_id) {
+                archive_statistics(team, &summary.season);
+            }
+        }
+
+        for team in clubs.iter_mut().flat_map(|c| &mut c.teams.teams) {
+            if let Some(team_move) = moves.iter().find(|m| m.team_id == team.id) {
+                team.league_id = team_move.to_league_id;
+            }
+        }
+
+        for league in self
+            .leagues
+            .iter_mut()
+            .filter(|league| ending_leagues.contains(&league.id))
+        {
+            let season = league.settings.ending_season(date);
+
+            league.history.add(season, league.table.rows.clone());
+
+            // the table is rebuilt for the new team set on the next simulation day,
+            // the schedule once the new season starts
+            league.table.reset();
+        }
+
+        summaries
+            .into_iter()
+            .map(|summary| {
+                let league_id = summary.league_id;
+
+                let mut league_result = LeagueResult::new(league_id, LeagueTableResult {});
+
+                if let Some(idx) = match_results.iter().position(|(id, _)| *id == league_id) {
+                    let (_, results) = match_results.remove(idx);
+
+                    if let Some(league) = self.leagues.iter_mut().find(|l| l.id == league_id) {
+                        for result in &results {
+                            league.matches.push(result.copy_without_data_positions());
+                        }
+                    }
+
+                    league_result.match_results = Some(results);
+                }
+
+                league_result.season_summary = Some(summary);
+
+                league_result
+            })
+            .collect()
+    }
+}
+
+// Moves the season's statistics of every player of the team into their history
+fn archive_statistics(team: &mut Team, season: &Season) {
+    let club_id = team.club_id;
+
+    for player in team.players.players.iter_mut() {
+        let statistics = std::mem::take(&mut player.statistics);
+
+        player
+            .statistics_history
+            .add(season.clone(), club_id, statistics);
+    }
+}
+
+// Goals of the season's league fixtures only, player statistics also hold cup and continental goals
+fn top_scorer(league: &League) -> Option<SeasonTopScorer> {
+    let mut goals: HashMap<(u32, u32), u16> = HashMap::new();
+
+    let player_results = league
+        .schedule
+        .tours
+        .iter()
+        .flat_map(|tour| &tour.items)
+        .filter_map(|item| league.matches.get(&item.id))
+        .filter_map(|match_result| match_result.details.as_ref())
+        .flat_map(|details| &details.player_results);
+
+    for result in player_results.filter(|result| result.goals > 0) {
+        *goals.entry((result.player_id, result.team_id)).or_default() += result.goals as u16;
+    }
+
+    goals
+        .into_iter()
+        .map(|((player_id, team_id), goals)| SeasonTopScorer {
+            player_id,
+            team_id,
+            goals,
+        })
+        .max_by(|a, b| {
+            a.goals
+                .cmp(&b.goals)
+                .then(b.player_id.cmp(&a.player_id))
+                .then(b.team_id.cmp(&a.team_id))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::club::testing;
+    use crate::context::SimulationContext;
+    use crate::league::{
+        DayMonthPeriod, LeagueSettings, LeagueTable, RankingRule, ScheduleItem, ScheduleTour,
+    };
+    use crate::r#match::statistics::MatchPlayerResult;
+    use crate::r#match::{MatchResultRaw, Score};
+    use crate::{PlayerGenerator, PlayerPositionType};
+    use chrono::NaiveDate;
+
+    fn settings(ending_month: u8) -> LeagueSettings {
+        LeagueSettings {
+            season_starting_half: DayMonthPeriod::new(1, 7, 31, 12),
+            season_ending_half: DayMonthPeriod::new(1, 1, 31, ending_month),
+            promotion: None,
+            ranking: RankingRule::defaults(),
+            match_engine: None,
+        }
+    }
+
+    fn league(id: u32, ending_month: u8, team_ids: &[u32]) -> League {
+        let mut league = League::new(
+            id,
+            format!("League {}", id),
+            format!("league-{}", id),
+            1,
+            0,
+            1,
+            settings(ending_month),
+        );
+
+        league.table = LeagueTable::new(team_ids);
+        league
+    }
+
+    // team ids are club_id * 10 + index, player ids team_id * 10 + index
+    fn club(club_id: u32, league_ids: &[u32]) -> Club {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+
+        let teams = league_ids
+            .iter()
+            .zip(club_id * 10..)
+            .map(|(league_id, team_id)| {
+                let players = (0..2)
+                    .map(|idx| {
+                        let mut player =
+                            PlayerGenerator::generate(1, date, PlayerPositionType::Striker, 15);
+                        player.id = team_id * 10 + idx;
+                        player.statistics.played = 30;
+                        player.statistics.goals = 10;
+                        player
+                    })
+                    .collect();
+
+                testing::team(team_id, *league_id, club_id, players)
+            })
+            .collect();
+
+        testing::club(club_id, teams)
+    }
+
+    // scorers are (player_id, team_id, goals)
+    fn league_match(id: &str, home: u32, away: u32, scorers: &[(u32, u32, u8)]) -> MatchResult {
+        let mut details = MatchResultRaw::with_match_time(0);
+
+        let score = Score::new(home, away);
+
+        for (player_id, team_id, goals) in scorers {
+            for _ in 0..*goals {
+                if *team_id == home {
+                    score.increment_home_goals();
+                } else {
+                    score.increment_away_goals();
+                }
+            }
+
+            details.player_results.push(MatchPlayerResult {
+                player_id: *player_id,
+                team_id: *team_id,
+                goals: *goals,
+                ..Default::default()
+            });
+        }
+
+        MatchResult {
+            id: id.to_string(),
+            league_id: 1,
+            league_slug: "league-1".to_string(),
+            home_team_id: home,
+            away_team_id: away,
+            details: Some(details),
+            score,
+        }
+    }
+
+    fn season_end() -> GlobalContext<'static> {
+        let date = NaiveDate::from_ymd_opt(2025, 5, 31)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+
+        GlobalContext::new(SimulationContext::new(date))
+    }
+
+    #[test]
+    fn summary_counts_league_goals_only() {
+        let mut league = league(1, 5, &[10, 20]);
+
+        let item = ScheduleItem::new(
+            1,
+            "league-1".to_string(),
+            10,
+            20,
+            NaiveDate::from_ymd_opt(2025, 5, 1)
+                .unwrap()
+                .and_hms_opt(15, 0, 0)
+                .unwrap(),
+            None,
+        );
+
+        let fixture = league_match(&item.id, 10, 20, &[(100, 10, 1), (200, 20, 2)]);
+
+        league.schedule.tours.push(ScheduleTour {
+            num: 1,
+            items: vec![item],
+        });
+
+        league
+            .table
+            .update_from_results(std::slice::from_ref(&fixture));
+        league.table.sort(&league.settings.ranking);
+
+        league.matches.push(fixture);
+        // last season's match is still stored
+        league
+            .matches
+            .push(league_match("old", 10, 20, &[(100, 10, 3)]));
+
+        let mut leagues = LeagueCollection::new(vec![league]);
+        let mut clubs = vec![club(1, &[1]), club(2, &[1])];
+
+        let results = leagues.end_season(&mut clubs, &season_end());
+        let summary = results[0].season_summary.as_ref().unwrap();
+
+        assert_eq!(1, summary.league_id);
+        assert_eq!(Season::TwoYear(2024, 2025), summary.season);
+        assert_eq!(Some(20), summary.champion_team_id);
+        assert!(summary.promoted.is_empty() && summary.relegated.is_empty());
+
+        // player statistics, cup goals included, have ten goals each
+        assert_eq!(
+            Some(SeasonTopScorer {
+                player_id: 200,
+                team_id: 20,
+                goals: 2
+            }),
+            summary.top_scorer
+        );
+    }
+
+    #[test]
+    fn statistics_are_archived_with_the_team_league_season() {
+        // the reserve team 11 plays in a league that ends in November
+        let mut leagues =
+            LeagueCollection::new(vec![league(1, 5, &[10, 20]), league(2, 11, &[11])]);
+        let mut clubs = vec![club(1, &[1, 2]), club(2, &[1])];
+
+        leagues.end_season(&mut clubs, &season_end());
+
+        let team = |team_id: u32| {
+            clubs
+                .iter()
+                .flat_map(|c| &c.teams.teams)
+                .find(|t| t.id == team_id)
+                .unwrap()
+        };
+
+        for player in &team(10).players.players {
+            let archived = &player.statistics_history.items;
+
+            assert_eq!(0, player.statistics.played);
+            assert_eq!(1, archived.len());
+            assert_eq!(Season::TwoYear(2024, 2025), archived[0].season);
+            assert_eq!(1, archived[0].club_id);
+            assert_eq!(30, archived[0].statistics.played);
+        }
+
+        for player in &team(11).players.players {
+            assert_eq!(30, player.statistics.played);
+            assert!(player.statistics_history.items.is_empty());
+        }
+
+        let ended = &leagues.leagues[0];
+
+        assert_eq!(1, ended.history.items.len());
+        assert_eq!(Season::TwoYear(2024, 2025), ended.history.items[0].season);
+        assert!(ended.table.rows.is_empty());
+
+        assert!(leagues.leagues[1].history.items.is_empty());
+    }
+
+    #[test]
+    fn schedule_is_generated_once_after_rollover() {
+        let mut leagues = LeagueCollection::new(vec![league(1, 5, &[10, 11, 20, 21])]);
+        let mut clubs = vec![club(1, &[1, 1]), club(2, &[1, 1])];
+
+        // last season's schedule
+        leagues.leagues[0].schedule.tours.push(ScheduleTour {
+            num: 1,
+            items: Vec::new(),
+        });
+
+        leagues.end_season(&mut clubs, &season_end());
+
+        let league = &mut leagues.leagues[0];
+        let team_ids = [10, 11, 20, 21];
+
+        // from the day after the season end up to the new season start on 1 July
+        let generated = NaiveDate::from_ymd_opt(2025, 6, 1)
+            .unwrap()
+            .iter_days()
+            .take_while(|date| *date <= NaiveDate::from_ymd_opt(2025, 7, 1).unwrap())
+            .filter(|date| {
+                let ctx = GlobalContext::new(SimulationContext::new(
+                    date.and_hms_opt(0, 0, 0).unwrap(),
+                ));
+
+                league
+                    .schedule
+                    .simulate(
+                        &league.settings,
+                        ctx.with_league(1, String::from("league-1"), &team_ids),
+                    )
+                    .generated
+            })
+            .count();
+
+        assert_eq!(1, generated);
+        assert!(!league.schedule.tours.is_empty());
+    }
+}
@@ -1,7 +1,8 @@
 use crate::r#match::MatchResult;
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MatchStorage {
     results: HashMap<String, MatchResult>,
 }
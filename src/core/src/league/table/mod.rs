@@ -1,5 +1,7 @@
+pub mod ranking;
 pub mod result;
 pub mod table;
 
+pub use ranking::*;
 pub use result::*;
 pub use table::*;
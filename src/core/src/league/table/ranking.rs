@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+// Rules that separate teams level on points, applied in order
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RankingRule {
+    GoalDifference,
+    GoalsScored,
+    Wins,
+    AwayGoals,
+    HeadToHeadPoints,
+    HeadToHeadGoalDifference,
+    HeadToHeadGoals,
+    HeadToHeadAwayGoals,
+    FairPlay,
+    PlayOff,
+}
+
+impl RankingRule {
+    pub fn defaults() -> Vec<RankingRule> {
+        vec![RankingRule::GoalDifference, RankingRule::GoalsScored]
+    }
+}
+
+impl FromStr for RankingRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "goal_difference" => Ok(RankingRule::GoalDifference),
+            "goals_scored" => Ok(RankingRule::GoalsScored),
+            "wins" => Ok(RankingRule::Wins),
+            "away_goals" => Ok(RankingRule::AwayGoals),
+            "head_to_head_points" => Ok(RankingRule::HeadToHeadPoints),
+            "head_to_head_goal_difference" => Ok(RankingRule::HeadToHeadGoalDifference),
+            "head_to_head_goals" => Ok(RankingRule::HeadToHeadGoals),
+            "head_to_head_away_goals" => Ok(RankingRule::HeadToHeadAwayGoals),
+            "fair_play" => Ok(RankingRule::FairPlay),
+            "play_off" => Ok(RankingRule::PlayOff),
+            _ => Err(format!("unknown ranking rule: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranking_rule_from_str() {
+        assert_eq!(
+            Ok(RankingRule::HeadToHeadGoalDifference),
+            RankingRule::from_str("head_to_head_goal_difference")
+        );
+        assert_eq!(Ok(RankingRule::PlayOff), RankingRule::from_str("play_off"));
+        assert!(RankingRule::from_str("coin_toss").is_err());
+    }
+}
@@ -1,17 +1,29 @@
 use crate::context::GlobalContext;
-use crate::league::LeagueTableResult;
+use crate::league::{LeagueTableResult, RankingRule};
 use crate::r#match::MatchResult;
-use std::cmp::Ordering;
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
 
-#[derive(Debug)]
+// disciplinary points of the fair play ranking, fewer is better
+const FAIR_PLAY_YELLOW_CARD: u16 = 1;
+const FAIR_PLAY_RED_CARD: u16 = 3;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LeagueTable {
     pub rows: Vec<LeagueTableRow>,
+    // played matches, needed for head-to-head and away goals rules
+    pub matches: Vec<LeagueTableMatch>,
+    pub adjustments: Vec<PointsAdjustment>,
+    pub play_offs: Vec<LeagueTablePlayOff>,
 }
 
 impl LeagueTable {
     pub fn new(teams: &[u32]) -> Self {
         LeagueTable {
             rows: Self::generate_for_teams(teams),
+            matches: Vec::new(),
+            adjustments: Vec::new(),
+            play_offs: Vec::new(),
         }
     }
 
@@ -37,6 +49,7 @@ impl LeagueTable {
                 goal_scored: 0,
                 goal_concerned: 0,
                 points: 0,
+                fair_play: 0,
             };
 
             rows.push(table_row)
@@ -79,7 +92,8 @@ impl LeagueTable {
         team.points += 1;
     }
 
-    pub fn update_from_results(&mut self, match_result: &Vec<MatchResult>) {
+    // Accumulates results, call `sort` afterwards to bring rows into table order
+    pub fn update_from_results(&mut self, match_result: &[MatchResult]) {
         for result in match_result {
             match Ord::cmp(&result.score.home_team.get(), &result.score.away_team.get()) {
                 Ordering::Equal => {
@@ -119,25 +133,231 @@ impl LeagueTable {
                     );
                 }
             }
-        }
 
-        self.rows.sort_by(|a, b| {
-            match b.points.cmp(&a.points) {
-                Ordering::Equal => match (b.goal_scored - b.goal_concerned).cmp(&(a.goal_scored - a.goal_concerned)) {
-                    Ordering::Equal => b.goal_scored.cmp(&a.goal_scored),
-                    other => other,
-                },
-                other => other,
+            self.matches.push(LeagueTableMatch {
+                home_team_id: result.score.home_team.team_id,
+                away_team_id: result.score.away_team.team_id,
+                home_goals: result.score.home_team.get(),
+                away_goals: result.score.away_team.get(),
+            });
+
+            if let Some(details) = &result.details {
+                for team_id in [result.home_team_id, result.away_team_id] {
+                    let points = details
+                        .player_results
+                        .iter()
+                        .filter(|player| player.team_id == team_id)
+                        .map(|player| {
+                            player.yellow_cards as u16 * FAIR_PLAY_YELLOW_CARD
+                                + player.red_cards as u16 * FAIR_PLAY_RED_CARD
+                        })
+                        .sum();
+
+                    self.add_fair_play_points(team_id, points);
+                }
             }
+        }
+    }
+
+    pub fn sort(&mut self, rules: &[RankingRule]) {
+        self.rows = self.standings(rules);
+    }
+
+    // Rows in table order: points first, then every rule in turn for teams still level
+    pub fn standings(&self, rules: &[RankingRule]) -> Vec<LeagueTableRow> {
+        self.rank(rules, &mut Vec::new())
+    }
+
+    // Pairs of level teams across the given positions that only a play-off match can separate.
+    // Position `n` is the line between the n-th and (n + 1)-th place, 1 for the title
+    pub fn required_play_offs(
+        &self,
+        rules: &[RankingRule],
+        positions: &[usize],
+    ) -> Vec<(u32, u32)> {
+        let mut play_off_groups = Vec::new();
+
+        let rows = self.rank(rules, &mut play_off_groups);
+
+        positions
+            .iter()
+            .filter(|position| **position > 0 && **position < rows.len())
+            .map(|position| (rows[position - 1].team_id, rows[*position].team_id))
+            .filter(|(first, second)| {
+                play_off_groups
+                    .iter()
+                    .any(|group| group.contains(first) && group.contains(second))
+                    && !self.play_offs.iter().any(|p| {
+                        (p.winner_team_id == *first && p.loser_team_id == *second)
+                            || (p.winner_team_id == *second && p.loser_team_id == *first)
+                    })
+            })
+            .collect()
+    }
+
+    pub fn add_play_off(&mut self, winner_team_id: u32, loser_team_id: u32) {
+        self.play_offs.push(LeagueTablePlayOff {
+            winner_team_id,
+            loser_team_id,
         });
     }
 
+    // Explicit points change, negative for deductions. Teams not in the table are ignored
+    pub fn adjust_points(&mut self, team_id: u32, points: i16, reason: &str) {
+        let Some(row) = self.rows.iter_mut().find(|row| row.team_id == team_id) else {
+            return;
+        };
+
+        row.points += points;
+
+        self.adjustments.push(PointsAdjustment {
+            team_id,
+            points,
+            reason: String::from(reason),
+        });
+    }
+
+    pub fn add_fair_play_points(&mut self, team_id: u32, points: u16) {
+        if let Some(row) = self.rows.iter_mut().find(|row| row.team_id == team_id) {
+            row.fair_play += points;
+        }
+    }
+
+    fn rank(
+        &self,
+        rules: &[RankingRule],
+        play_off_groups: &mut Vec<Vec<u32>>,
+    ) -> Vec<LeagueTableRow> {
+        let mut rows = self.rows.clone();
+
+        rows.sort_by_key(|row| Reverse(row.points));
+
+        for group in rows.chunk_by_mut(|a, b| a.points == b.points) {
+            self.rank_group(group, rules, play_off_groups);
+        }
+
+        rows
+    }
+
+    fn rank_group(
+        &self,
+        group: &mut [LeagueTableRow],
+        rules: &[RankingRule],
+        play_off_groups: &mut Vec<Vec<u32>>,
+    ) {
+        if group.len() < 2 {
+            return;
+        }
+
+        let (rule, next_rules) = match rules.split_first() {
+            Some(rules) => rules,
+            None => return,
+        };
+
+        let team_ids: Vec<u32> = group.iter().map(|row| row.team_id).collect();
+
+        if *rule == RankingRule::PlayOff {
+            play_off_groups.push(team_ids.clone());
+        }
+
+        group.sort_by_cached_key(|row| Reverse(self.rank_value(*rule, row, &team_ids)));
+
+        let values: Vec<i32> = group
+            .iter()
+            .map(|row| self.rank_value(*rule, row, &team_ids))
+            .collect();
+
+        let mut start = 0;
+
+        while start < group.len() {
+            let end = start
+                + values[start..]
+                    .iter()
+                    .take_while(|v| **v == values[start])
+                    .count();
+
+            self.rank_group(&mut group[start..end], next_rules, play_off_groups);
+
+            start = end;
+        }
+    }
+
+    // Bigger is better, head-to-head rules only count matches inside the level group
+    fn rank_value(&self, rule: RankingRule, row: &LeagueTableRow, group: &[u32]) -> i32 {
+        let team_id = row.team_id;
+
+        match rule {
+            RankingRule::GoalDifference => row.goal_scored - row.goal_concerned,
+            RankingRule::GoalsScored => row.goal_scored,
+            RankingRule::Wins => row.win as i32,
+            RankingRule::AwayGoals => self
+                .matches
+                .iter()
+                .filter(|m| m.away_team_id == team_id)
+                .map(|m| m.away_goals as i32)
+                .sum(),
+            RankingRule::HeadToHeadPoints => self.head_to_head(team_id, group).points,
+            RankingRule::HeadToHeadGoalDifference => {
+                let head_to_head = self.head_to_head(team_id, group);
+                head_to_head.goal_scored - head_to_head.goal_concerned
+            }
+            RankingRule::HeadToHeadGoals => self.head_to_head(team_id, group).goal_scored,
+            RankingRule::HeadToHeadAwayGoals => self.head_to_head(team_id, group).away_goals,
+            RankingRule::FairPlay => -(row.fair_play as i32),
+            RankingRule::PlayOff => self
+                .play_offs
+                .iter()
+                .filter(|p| p.winner_team_id == team_id && group.contains(&p.loser_team_id))
+                .count() as i32,
+        }
+    }
+
+    fn head_to_head(&self, team_id: u32, group: &[u32]) -> HeadToHead {
+        let mut head_to_head = HeadToHead::default();
+
+        for table_match in self
+            .matches
+            .iter()
+            .filter(|m| group.contains(&m.home_team_id) && group.contains(&m.away_team_id))
+        {
+            let (scored, concerned) = if table_match.home_team_id == team_id {
+                (table_match.home_goals as i32, table_match.away_goals as i32)
+            } else if table_match.away_team_id == team_id {
+                head_to_head.away_goals += table_match.away_goals as i32;
+                (table_match.away_goals as i32, table_match.home_goals as i32)
+            } else {
+                continue;
+            };
+
+            head_to_head.goal_scored += scored;
+            head_to_head.goal_concerned += concerned;
+            head_to_head.points += match scored.cmp(&concerned) {
+                Ordering::Greater => 3,
+                Ordering::Equal => 1,
+                Ordering::Less => 0,
+            };
+        }
+
+        head_to_head
+    }
+
     pub fn get(&self) -> &[LeagueTableRow] {
         &self.rows
     }
+
+    pub fn team_ids(&self) -> Vec<u32> {
+        self.rows.iter().map(|row| row.team_id).collect()
+    }
+
+    pub fn reset(&mut self) {
+        self.rows.clear();
+        self.matches.clear();
+        self.adjustments.clear();
+        self.play_offs.clear();
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeagueTableRow {
     pub team_id: u32,
     pub played: u8,
@@ -146,21 +366,53 @@ pub struct LeagueTableRow {
     pub lost: u8,
     pub goal_scored: i32,
     pub goal_concerned: i32,
-    pub points: u8,
+    pub points: i16,
+    // disciplinary points, lower is better
+    pub fair_play: u16,
 }
 
 impl LeagueTableRow {}
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeagueTableMatch {
+    pub home_team_id: u32,
+    pub away_team_id: u32,
+    pub home_goals: u8,
+    pub away_goals: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointsAdjustment {
+    pub team_id: u32,
+    pub points: i16,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeagueTablePlayOff {
+    pub winner_team_id: u32,
+    pub loser_team_id: u32,
+}
+
+#[derive(Default)]
+struct HeadToHead {
+    points: i32,
+    goal_scored: i32,
+    goal_concerned: i32,
+    away_goals: i32,
+}
+
 impl Default for LeagueTable {
     fn default() -> Self {
-        LeagueTable { rows: Vec::new() }
+        LeagueTable::new(&[])
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::r#match::{Score, TeamScore};
+    use crate::r#match::statistics::MatchPlayerResult;
+    use crate::r#match::{MatchResultRaw, Score, TeamScore};
 
     #[test]
     fn table_draft() {
@@ -327,4 +579,129 @@ mod tests {
         assert_eq!(0, away.goal_concerned);
         assert_eq!(3, away.points);
     }
+
+    fn result(home_team_id: u32, away_team_id: u32, home_goals: u8, away_goals: u8) -> MatchResult {
+        MatchResult {
+            league_id: 0,
+            id: format!("{}_{}", home_team_id, away_team_id),
+            league_slug: "slug".to_string(),
+            home_team_id,
+            away_team_id,
+            score: Score {
+                home_team: TeamScore::new_with_score(home_team_id, home_goals),
+                away_team: TeamScore::new_with_score(away_team_id, away_goals),
+                details: vec![],
+            },
+            details: None,
+        }
+    }
+
+    // 1 and 2 finish level on points, 1 has the better goal difference, 2 is better head-to-head
+    fn level_table() -> LeagueTable {
+        let mut table = LeagueTable::new(&[1, 2, 3]);
+
+        table.update_from_results(&[
+            result(1, 2, 0, 1),
+            result(2, 1, 0, 0),
+            result(1, 3, 6, 0),
+            result(3, 1, 0, 6),
+            result(2, 3, 1, 0),
+            result(3, 2, 1, 0),
+        ]);
+
+        table
+    }
+
+    fn positions(rows: &[LeagueTableRow]) -> Vec<u32> {
+        rows.iter().map(|row| row.team_id).collect()
+    }
+
+    #[test]
+    fn standings_by_goal_difference() {
+        let table = level_table();
+
+        let rules = [RankingRule::GoalDifference, RankingRule::HeadToHeadPoints];
+
+        assert_eq!(vec![1, 2, 3], positions(&table.standings(&rules)));
+    }
+
+    #[test]
+    fn standings_by_head_to_head() {
+        let table = level_table();
+
+        let rules = [RankingRule::HeadToHeadPoints, RankingRule::GoalDifference];
+
+        assert_eq!(vec![2, 1, 3], positions(&table.standings(&rules)));
+    }
+
+    #[test]
+    fn standings_with_points_deduction() {
+        let mut table = level_table();
+
+        table.adjust_points(1, -7, "financial irregularities");
+
+        let rows = table.standings(&RankingRule::defaults());
+
+        assert_eq!(vec![2, 3, 1], positions(&rows));
+        assert_eq!(0, rows[2].points);
+        assert_eq!(1, table.adjustments.len());
+    }
+
+    #[test]
+    fn play_off_required_when_level_on_every_rule() {
+        let mut table = LeagueTable::new(&[1, 2]);
+
+        table.update_from_results(&[result(1, 2, 1, 0), result(2, 1, 1, 0)]);
+
+        let rules = [RankingRule::HeadToHeadPoints, RankingRule::PlayOff];
+
+        assert_eq!(vec![(1, 2)], table.required_play_offs(&rules, &[1]));
+        assert!(table
+            .required_play_offs(&RankingRule::defaults(), &[1])
+            .is_empty());
+
+        table.add_play_off(2, 1);
+
+        assert!(table.required_play_offs(&rules, &[1]).is_empty());
+        assert_eq!(vec![2, 1], positions(&table.standings(&rules)));
+    }
+
+    #[test]
+    fn fair_play_points_come_from_cards() {
+        let mut table = LeagueTable::new(&[1, 2]);
+
+        let mut booked = result(1, 2, 0, 0);
+        let mut details = MatchResultRaw::with_match_time(0);
+
+        details.player_results = vec![
+            MatchPlayerResult {
+                player_id: 10,
+                team_id: 1,
+                yellow_cards: 2,
+                red_cards: 1,
+                ..Default::default()
+            },
+            MatchPlayerResult {
+                player_id: 20,
+                team_id: 2,
+                yellow_cards: 1,
+                ..Default::default()
+            },
+        ];
+        booked.details = Some(details);
+
+        table.update_from_results(&[booked]);
+
+        let rows = table.standings(&[RankingRule::FairPlay]);
+
+        assert_eq!(vec![2, 1], positions(&rows));
+        assert_eq!((1, 5), (rows[0].fair_play, rows[1].fair_play));
+
+        // teams from outside the league are left alone
+        table.adjust_points(3, -3, "unknown team");
+        table.add_fair_play_points(3, 1);
+
+        assert!(table.adjustments.is_empty());
+        assert_eq!(2, table.rows.len());
+    }
 }
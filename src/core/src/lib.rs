@@ -5,11 +5,13 @@ pub mod club;
 pub mod context;
 pub mod continent;
 pub mod country;
+pub mod cup;
 pub mod league;
 pub mod r#match;
 pub mod transfers;
 
 pub mod shared;
+pub mod snapshot;
 pub mod utils;
 
 pub use club::*;
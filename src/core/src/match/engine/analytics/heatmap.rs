@@ -0,0 +1,166 @@
+use crate::r#match::statistics::MatchPlayerResult;
+use crate::r#match::{MatchAnalytics, MatchFieldSize};
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const HEATMAP_COLUMNS: usize = 12;
+pub const HEATMAP_ROWS: usize = 8;
+
+// Time spent in each zone of the pitch, row by row from the top left corner.
+// The pitch is always seen as the team attacking to the right
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Heatmap {
+    pub columns: usize,
+    pub rows: usize,
+    // milliseconds
+    pub cells: Vec<u64>,
+}
+
+impl Heatmap {
+    pub fn new() -> Self {
+        Heatmap {
+            columns: HEATMAP_COLUMNS,
+            rows: HEATMAP_ROWS,
+            cells: vec![0; HEATMAP_COLUMNS * HEATMAP_ROWS],
+        }
+    }
+
+    pub fn add_time(&mut self, position: &Vector3<f32>, field_size: &MatchFieldSize, time: u64) {
+        let column = Self::zone(position.x, field_size.width, self.columns);
+        let row = Self::zone(position.y, field_size.height, self.rows);
+
+        self.cells[row * self.columns + column] += time;
+    }
+
+    pub fn merge(&mut self, other: &Heatmap) {
+        for (cell, time) in self.cells.iter_mut().zip(&other.cells) {
+            *cell += time;
+        }
+    }
+
+    pub fn total_time(&self) -> u64 {
+        self.cells.iter().sum()
+    }
+
+    pub fn cell(&self, column: usize, row: usize) -> u64 {
+        self.cells[row * self.columns + column]
+    }
+
+    fn zone(value: f32, length: usize, zones: usize) -> usize {
+        let zone = (value.max(0.0) / length as f32 * zones as f32) as usize;
+
+        zone.min(zones - 1)
+    }
+}
+
+impl Default for Heatmap {
+    fn default() -> Self {
+        Heatmap::new()
+    }
+}
+
+// Heatmaps of a played match by player and by team. They go with the replay,
+// the results kept for the season leave them out
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchHeatmaps {
+    pub players: HashMap<u32, Heatmap>,
+    pub teams: HashMap<u32, Heatmap>,
+}
+
+impl MatchHeatmaps {
+    pub fn new(player_results: &[MatchPlayerResult], analytics: &MatchAnalytics) -> Self {
+        let mut heatmaps = MatchHeatmaps::default();
+
+        for result in player_results {
+            let Some(movement) = analytics.movement(result.player_id) else {
+                continue;
+            };
+
+            heatmaps
+                .teams
+                .entry(result.team_id)
+                .or_default()
+                .merge(&movement.heatmap);
+
+            heatmaps
+                .players
+                .insert(result.player_id, movement.heatmap.clone());
+        }
+
+        heatmaps
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::testing::squad_with;
+    use crate::r#match::{MatchField, MatchResultRaw, MatchTimeline};
+    use crate::PlayerPositionType;
+
+    #[test]
+    fn time_goes_to_zone_of_position() {
+        let field_size = MatchFieldSize::new(840, 545);
+        let mut heatmap = Heatmap::new();
+
+        heatmap.add_time(&Vector3::new(0.0, 0.0, 0.0), &field_size, 10);
+        heatmap.add_time(&Vector3::new(839.0, 544.0, 0.0), &field_size, 20);
+        // players may step off the pitch
+        heatmap.add_time(&Vector3::new(900.0, -5.0, 0.0), &field_size, 30);
+
+        assert_eq!(10, heatmap.cell(0, 0));
+        assert_eq!(20, heatmap.cell(HEATMAP_COLUMNS - 1, HEATMAP_ROWS - 1));
+        assert_eq!(30, heatmap.cell(HEATMAP_COLUMNS - 1, 0));
+
+        let mut team = Heatmap::new();
+        team.merge(&heatmap);
+        team.merge(&heatmap);
+
+        assert_eq!(120, team.total_time());
+    }
+
+    #[test]
+    fn match_heatmaps_stay_out_of_the_kept_results() {
+        let positions = [
+            PlayerPositionType::Goalkeeper,
+            PlayerPositionType::ForwardLeft,
+        ];
+        let mut field = MatchField::new(
+            840,
+            545,
+            squad_with(1, &positions),
+            squad_with(2, &positions),
+        );
+
+        let mut analytics = MatchAnalytics::new();
+        let mut timeline = MatchTimeline::new();
+
+        for tick in 0..=10 {
+            analytics.track(&mut field, &mut timeline, tick * 10, false);
+        }
+
+        let player_results: Vec<MatchPlayerResult> = field
+            .players
+            .iter()
+            .map(|player| MatchPlayerResult {
+                player_id: player.id,
+                team_id: player.team_id,
+                ..Default::default()
+            })
+            .collect();
+
+        let mut result = MatchResultRaw::with_match_time(100);
+        result.heatmaps = MatchHeatmaps::new(&player_results, &analytics);
+
+        assert_eq!(4, result.heatmaps.players.len());
+        assert_eq!(100, result.heatmaps.players[&100].total_time());
+        assert_eq!(200, result.heatmaps.teams[&1].total_time());
+
+        assert!(result.copy_without_data_positions().heatmaps.is_empty());
+    }
+}
@@ -0,0 +1,346 @@
+use crate::r#match::{
+    Heatmap, MatchField, MatchTimeline, PlayerSide, TimelineEventType, VectorExtensions,
+    PITCH_LENGTH_METERS,
+};
+use nalgebra::Vector3;
+use std::collections::HashMap;
+
+// Longer steps in one tick are players put in place for a restart or the
+// change of ends, not running
+const MAX_STEP: f32 = 5.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct PlayerMovement {
+    // meters
+    pub distance: f32,
+    pub heatmap: Heatmap,
+    last_position: Option<Vector3<f32>>,
+}
+
+// Follows the ball and the players through the match for what the events alone
+// don't tell: who has the ball, whether passes arrive, how much players run
+#[derive(Debug, Clone, Default)]
+pub struct MatchAnalytics {
+    // milliseconds on the ball by team
+    possession: HashMap<u32, u64>,
+    corners: HashMap<u32, u16>,
+    movements: HashMap<u32, PlayerMovement>,
+
+    last_owner: Option<u32>,
+    possession_team: Option<u32>,
+    pending_pass: Option<u32>,
+    // passer and receiver of the last pass, while the receiver keeps the ball
+    last_pass: Option<(u32, u32)>,
+    pending_shot: Option<u32>,
+    // taker of the last shot if it was a penalty, while nobody has the ball
+    penalty_taker: Option<u32>,
+    last_time: u64,
+}
+
+impl MatchAnalytics {
+    pub fn new() -> Self {
+        MatchAnalytics::default()
+    }
+
+    // The pass is judged once somebody has the ball again
+    pub fn pass_played(&mut self, player_id: u32) {
+        self.pending_pass = Some(player_id);
+        self.last_owner = None;
+    }
+
+    // A shot on target the keeper gets hold of is a save
+    pub fn shot_on_target(&mut self, player_id: u32) {
+        self.pending_shot = Some(player_id);
+    }
+
+    pub fn shot_taken(&mut self, player_id: u32, is_penalty: bool) {
+        self.penalty_taker = is_penalty.then_some(player_id);
+    }
+
+    // A goal is scored from the spot if nobody touched the ball after the penalty
+    pub fn is_penalty_goal(&self, scorer_id: u32) -> bool {
+        self.penalty_taker == Some(scorer_id)
+    }
+
+    // The scorer was set up by the last pass if nobody else touched the ball since
+    pub fn assist_for(&self, scorer_id: u32) -> Option<u32> {
+        self.last_pass
+            .filter(|(_, receiver_id)| *receiver_id == scorer_id)
+            .map(|(passer_id, _)| passer_id)
+    }
+
+    pub fn corner_awarded(&mut self, team_id: u32) {
+        *self.corners.entry(team_id).or_default() += 1;
+    }
+
+    pub fn corners(&self, team_id: u32) -> u16 {
+        self.corners.get(&team_id).copied().unwrap_or(0)
+    }
+
+    // Share of the time on the ball, in percent
+    pub fn possession(&self, team_id: u32) -> f32 {
+        let total: u64 = self.possession.values().sum();
+
+        if total == 0 {
+            return 0.0;
+        }
+
+        self.possession.get(&team_id).copied().unwrap_or(0) as f32 * 100.0 / total as f32
+    }
+
+    pub fn movement(&self, player_id: u32) -> Option<&PlayerMovement> {
+        self.movements.get(&player_id)
+    }
+
+    pub fn track(
+        &mut self,
+        field: &mut MatchField,
+        timeline: &mut MatchTimeline,
+        time: u64,
+        is_stoppage: bool,
+    ) {
+        let elapsed = time.saturating_sub(self.last_time);
+        self.last_time = time;
+
+        self.track_ball(field, timeline, time, is_stoppage);
+
+        if let Some(team_id) = self.possession_team {
+            *self.possession.entry(team_id).or_default() += elapsed;
+        }
+
+        self.track_players(field, elapsed);
+    }
+
+    fn track_ball(
+        &mut self,
+        field: &mut MatchField,
+        timeline: &mut MatchTimeline,
+        time: u64,
+        is_stoppage: bool,
+    ) {
+        let owner_id = match field.ball.current_owner {
+            Some(owner_id) if Some(owner_id) != self.last_owner => owner_id,
+            _ => return,
+        };
+
+        self.last_owner = Some(owner_id);
+
+        let team_of = |id: u32| field.players.iter().find(|p| p.id == id).map(|p| p.team_id);
+
+        let owner_team = match team_of(owner_id) {
+            Some(team_id) => team_id,
+            None => return,
+        };
+
+        let is_goalkeeper = field
+            .players
+            .iter()
+            .find(|p| p.id == owner_id)
+            .is_some_and(|p| p.tactical_position.current_position.is_goalkeeper());
+
+        if let Some(shooter_id) = self.pending_shot.take().filter(|_| !is_stoppage) {
+            if is_goalkeeper && team_of(shooter_id) != Some(owner_team) {
+                timeline.add(
+                    time,
+                    TimelineEventType::Save {
+                        goalkeeper_id: owner_id,
+                        shooter_id,
+                    },
+                );
+            }
+        }
+
+        self.last_pass = None;
+        self.penalty_taker = None;
+
+        // a pass ending in a stoppage is neither completed nor intercepted
+        if let Some(passer_id) = self.pending_pass.take().filter(|_| !is_stoppage) {
+            match team_of(passer_id) {
+                Some(team_id) if team_id == owner_team && passer_id != owner_id => {
+                    if let Some(passer) = field.get_player_mut(passer_id) {
+                        passer.statistics.add_completed_pass();
+                    }
+
+                    self.last_pass = Some((passer_id, owner_id));
+                }
+                Some(team_id) if team_id != owner_team => {
+                    if let Some(owner) = field.get_player_mut(owner_id) {
+                        owner.statistics.add_interception();
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.possession_team = Some(owner_team);
+    }
+
+    fn track_players(&mut self, field: &MatchField, elapsed: u64) {
+        let size = &field.size;
+        let scale = PITCH_LENGTH_METERS / size.width as f32;
+
+        for player in &field.players {
+            let movement = self.movements.entry(player.id).or_default();
+
+            if let Some(last_position) = movement.last_position {
+                let step = player.position.distance_to(&last_position);
+
+                if step <= MAX_STEP {
+                    movement.distance += step * scale;
+                }
+            }
+
+            movement.last_position = Some(player.position);
+
+            // every team is drawn attacking to the right
+            let position = if player.side == Some(PlayerSide::Right) {
+                Vector3::new(
+                    size.width as f32 - player.position.x,
+                    size.height as f32 - player.position.y,
+                    0.0,
+                )
+            } else {
+                player.position
+            };
+
+            movement.heatmap.add_time(&position, size, elapsed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::testing::squad_with;
+    use crate::r#match::TeamSquad;
+    use crate::PlayerPositionType;
+
+    fn field() -> MatchField {
+        MatchField::new(840, 545, squad(1), squad(2))
+    }
+
+    fn squad(team_id: u32) -> TeamSquad {
+        squad_with(
+            team_id,
+            &[PlayerPositionType::Goalkeeper, PlayerPositionType::ForwardLeft],
+        )
+    }
+
+    #[test]
+    fn passes_are_completed_or_intercepted() {
+        let mut field = field();
+        let mut analytics = MatchAnalytics::new();
+        let mut timeline = MatchTimeline::new();
+
+        field.ball.current_owner = Some(100);
+        analytics.track(&mut field, &mut timeline, 10, false);
+
+        analytics.pass_played(100);
+        field.ball.current_owner = None;
+        analytics.track(&mut field, &mut timeline, 20, false);
+
+        field.ball.current_owner = Some(101);
+        analytics.track(&mut field, &mut timeline, 30, false);
+
+        analytics.pass_played(101);
+        field.ball.current_owner = Some(201);
+        analytics.track(&mut field, &mut timeline, 60, false);
+
+        // passes into touch only stop play
+        analytics.pass_played(201);
+        field.ball.current_owner = Some(100);
+        analytics.track(&mut field, &mut timeline, 70, true);
+
+        let player = |id: u32| field.players.iter().find(|p| p.id == id).unwrap();
+
+        assert_eq!(1, player(100).statistics.passes_completed);
+        assert_eq!(0, player(101).statistics.passes_completed);
+        assert_eq!(1, player(201).statistics.interceptions);
+        assert_eq!(0, player(100).statistics.interceptions);
+
+        // 40 ms on the ball for the first team, 30 ms for the second
+        assert!((analytics.possession(1) - 400.0 / 7.0).abs() < 0.001);
+        assert!((analytics.possession(1) + analytics.possession(2) - 100.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn assists_and_saves_follow_the_ball() {
+        let mut field = field();
+        let mut analytics = MatchAnalytics::new();
+        let mut timeline = MatchTimeline::new();
+
+        field.ball.current_owner = Some(100);
+        analytics.track(&mut field, &mut timeline, 10, false);
+
+        analytics.pass_played(100);
+        field.ball.current_owner = Some(101);
+        analytics.track(&mut field, &mut timeline, 20, false);
+
+        assert_eq!(Some(100), analytics.assist_for(101));
+        assert_eq!(None, analytics.assist_for(100));
+
+        analytics.shot_on_target(101);
+        field.ball.current_owner = None;
+        analytics.track(&mut field, &mut timeline, 30, false);
+
+        field.ball.current_owner = Some(200);
+        analytics.track(&mut field, &mut timeline, 40, false);
+
+        assert_eq!(None, analytics.assist_for(101));
+        assert_eq!(
+            vec![TimelineEventType::Save {
+                goalkeeper_id: 200,
+                shooter_id: 101,
+            }],
+            timeline.events.iter().map(|e| e.event.clone()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn penalty_goal_needs_the_ball_untouched_since_the_kick() {
+        let mut field = field();
+        let mut analytics = MatchAnalytics::new();
+        let mut timeline = MatchTimeline::new();
+
+        analytics.shot_taken(101, true);
+        field.ball.current_owner = None;
+        analytics.track(&mut field, &mut timeline, 10, false);
+
+        assert!(analytics.is_penalty_goal(101));
+
+        // the keeper holds it
+        field.ball.current_owner = Some(200);
+        analytics.track(&mut field, &mut timeline, 20, false);
+
+        assert!(!analytics.is_penalty_goal(101));
+
+        analytics.shot_taken(101, false);
+
+        assert!(!analytics.is_penalty_goal(101));
+    }
+
+    #[test]
+    fn running_counts_but_repositioning_does_not() {
+        let mut field = field();
+        let mut analytics = MatchAnalytics::new();
+        let mut timeline = MatchTimeline::new();
+
+        let start = Vector3::new(400.0, 200.0, 0.0);
+        field.players[1].position = start;
+
+        for tick in 0..=10 {
+            field.players[1].position = start + Vector3::new(tick as f32, 0.0, 0.0);
+            analytics.track(&mut field, &mut timeline, tick * 10, false);
+        }
+
+        // put in place for a restart
+        field.players[1].position = Vector3::new(100.0, 100.0, 0.0);
+        analytics.track(&mut field, &mut timeline, 110, false);
+
+        let movement = analytics.movement(field.players[1].id).unwrap();
+        let meters_per_unit = PITCH_LENGTH_METERS / 840.0;
+
+        assert!((movement.distance - 10.0 * meters_per_unit).abs() < 0.001);
+        assert_eq!(110, movement.heatmap.total_time());
+    }
+}
@@ -0,0 +1,9 @@
+pub mod heatmap;
+pub mod match_analytics;
+pub mod shot;
+pub mod team_statistics;
+
+pub use heatmap::*;
+pub use match_analytics::*;
+pub use shot::*;
+pub use team_statistics::*;
@@ -0,0 +1,120 @@
+use crate::r#match::{GoalPosition, MatchFieldSize, GOAL_WIDTH};
+use nalgebra::Vector3;
+
+pub const PITCH_LENGTH_METERS: f32 = 105.0;
+
+const GOAL_WIDTH_METERS: f32 = 7.32;
+
+// Share of penalties scored
+const PENALTY_EXPECTED_GOALS: f32 = 0.76;
+
+// Logistic model on the distance and the angle the goal opens up to the shooter
+const XG_INTERCEPT: f32 = -1.0;
+const XG_ANGLE_WEIGHT: f32 = 1.5;
+const XG_DISTANCE_WEIGHT: f32 = 0.09;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shot {
+    pub expected_goals: f32,
+    pub is_on_target: bool,
+}
+
+impl Shot {
+    pub fn new(
+        from: Vector3<f32>,
+        target: Vector3<f32>,
+        goals: &GoalPosition,
+        field_size: &MatchFieldSize,
+        is_penalty: bool,
+    ) -> Self {
+        // the goal aimed at
+        let goal = if (target.x - goals.left.x).abs() < (target.x - goals.right.x).abs() {
+            goals.left
+        } else {
+            goals.right
+        };
+
+        let expected_goals = if is_penalty {
+            PENALTY_EXPECTED_GOALS
+        } else {
+            Self::expected_goals(from, goal, field_size)
+        };
+
+        Shot {
+            expected_goals,
+            is_on_target: Self::is_on_target(from, target, goal),
+        }
+    }
+
+    fn expected_goals(from: Vector3<f32>, goal: Vector3<f32>, field_size: &MatchFieldSize) -> f32 {
+        let scale = PITCH_LENGTH_METERS / field_size.width as f32;
+
+        let dx = (goal.x - from.x).abs() * scale;
+        let dy = (goal.y - from.y) * scale;
+
+        let distance = (dx * dx + dy * dy).sqrt();
+
+        let half_goal = GOAL_WIDTH_METERS / 2.0;
+        let angle = ((dy + half_goal).atan2(dx) - (dy - half_goal).atan2(dx)).abs();
+
+        let logit = XG_INTERCEPT + XG_ANGLE_WEIGHT * angle - XG_DISTANCE_WEIGHT * distance;
+
+        1.0 / (1.0 + (-logit).exp())
+    }
+
+    // The line of the shot crosses the goal line between the posts
+    fn is_on_target(from: Vector3<f32>, target: Vector3<f32>, goal: Vector3<f32>) -> bool {
+        let direction = target - from;
+
+        if direction.x == 0.0 {
+            return false;
+        }
+
+        let t = (goal.x - from.x) / direction.x;
+
+        if t <= 0.0 {
+            return false;
+        }
+
+        let y = from.y + direction.y * t;
+
+        (y - goal.y).abs() <= GOAL_WIDTH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shot(from: Vector3<f32>, target: Vector3<f32>) -> Shot {
+        let field_size = MatchFieldSize::new(840, 545);
+        let goals = GoalPosition::from(&field_size);
+
+        Shot::new(from, target, &goals, &field_size, false)
+    }
+
+    #[test]
+    fn closer_and_central_shots_are_better_chances() {
+        let target = Vector3::new(840.0, 272.0, 0.0);
+
+        let close = shot(Vector3::new(790.0, 272.0, 0.0), target);
+        let far = shot(Vector3::new(640.0, 272.0, 0.0), target);
+        let wide = shot(Vector3::new(790.0, 80.0, 0.0), target);
+
+        assert!(close.expected_goals > far.expected_goals);
+        assert!(close.expected_goals > wide.expected_goals);
+        assert!(far.expected_goals > 0.0 && close.expected_goals < 1.0);
+    }
+
+    #[test]
+    fn shot_wide_of_the_posts_is_off_target() {
+        let from = Vector3::new(700.0, 272.0, 0.0);
+
+        assert!(shot(from, Vector3::new(840.0, 300.0, 0.0)).is_on_target);
+        assert!(!shot(from, Vector3::new(840.0, 400.0, 0.0)).is_on_target);
+        // shots at the left goal are judged against it
+        let at_left_goal = shot(Vector3::new(100.0, 272.0, 0.0), Vector3::new(0.0, 250.0, 0.0));
+
+        assert!(at_left_goal.is_on_target);
+    }
+}
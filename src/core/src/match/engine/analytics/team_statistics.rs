@@ -0,0 +1,71 @@
+use crate::r#match::statistics::MatchPlayerResult;
+use crate::r#match::MatchAnalytics;
+use serde::{Deserialize, Serialize};
+
+// Aggregates of a team in a played match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchTeamStatistics {
+    pub team_id: u32,
+    // share of the time on the ball, in percent
+    pub possession: f32,
+    pub shots: u16,
+    pub shots_on_target: u16,
+    pub expected_goals: f32,
+    pub passes: u16,
+    pub passes_completed: u16,
+    pub tackles: u16,
+    pub interceptions: u16,
+    pub fouls: u16,
+    pub corners: u16,
+    pub offsides: u16,
+    // meters
+    pub distance_covered: f32,
+}
+
+impl MatchTeamStatistics {
+    pub fn new(
+        team_id: u32,
+        player_results: &[MatchPlayerResult],
+        analytics: &MatchAnalytics,
+    ) -> Self {
+        let mut statistics = MatchTeamStatistics {
+            team_id,
+            possession: analytics.possession(team_id),
+            shots: 0,
+            shots_on_target: 0,
+            expected_goals: 0.0,
+            passes: 0,
+            passes_completed: 0,
+            tackles: 0,
+            interceptions: 0,
+            fouls: 0,
+            corners: analytics.corners(team_id),
+            offsides: 0,
+            distance_covered: 0.0,
+        };
+
+        for result in player_results.iter().filter(|r| r.team_id == team_id) {
+            statistics.shots += result.shots;
+            statistics.shots_on_target += result.shots_on_target;
+            statistics.expected_goals += result.expected_goals;
+            statistics.passes += result.passes;
+            statistics.passes_completed += result.passes_completed;
+            statistics.tackles += result.tackles;
+            statistics.interceptions += result.interceptions;
+            statistics.fouls += result.fouls;
+            statistics.offsides += result.offsides;
+            statistics.distance_covered += result.distance_covered;
+        }
+
+        statistics
+    }
+
+    // Completed passes in percent
+    pub fn pass_accuracy(&self) -> f32 {
+        if self.passes == 0 {
+            return 0.0;
+        }
+
+        self.passes_completed as f32 * 100.0 / self.passes as f32
+    }
+}
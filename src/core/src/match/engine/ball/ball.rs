@@ -1,7 +1,7 @@
 use crate::r#match::ball::events::BallEvent;
 use crate::r#match::events::EventCollection;
 use crate::r#match::result::VectorExtensions;
-use crate::r#match::{GameTickContext, MatchContext, MatchPlayer};
+use crate::r#match::{BoundaryLine, GameTickContext, MatchContext, MatchPlayer, PlayerSide};
 use nalgebra::Vector3;
 
 pub struct Ball {
@@ -17,6 +17,7 @@ pub struct Ball {
 
     pub previous_owner: Option<u32>,
     pub current_owner: Option<u32>,
+    pub last_touched_by: Option<u32>,
     pub take_ball_notified_player: Option<u32>,
 }
 
@@ -48,6 +49,7 @@ impl Ball {
             flags: BallFlags::default(),
             previous_owner: None,
             current_owner: None,
+            last_touched_by: None,
             take_ball_notified_player: None,
         }
     }
@@ -59,12 +61,27 @@ impl Ball {
         tick_context: &GameTickContext,
         events: &mut EventCollection,
     ) {
+        if self.current_owner.is_some() {
+            self.last_touched_by = self.current_owner;
+        }
+
         self.update_velocity();
-        self.check_goal(context, events);
-        self.check_boundary_collision(context);
+
+        let crossed_line = self.check_boundary_collision(context);
+
+        if !self.check_goal(context, events) {
+            if let Some(line) = crossed_line {
+                events.add_ball_event(BallEvent::OutOfPlay(
+                    line,
+                    self.position,
+                    self.last_touched_by,
+                ));
+
+                return;
+            }
+        }
 
         self.try_intercept(players, events);
-        self.try_notify_standing_ball(players, events);
 
         self.process_ownership(context, players, events);
 
@@ -87,21 +104,6 @@ impl Ball {
         self.flags.running_for_ball = self.is_players_running_to_ball(players);
     }
 
-    pub fn try_notify_standing_ball(
-        &mut self,
-        players: &[MatchPlayer],
-        events: &mut EventCollection,
-    ) {
-        if self.is_stands_outside()
-            && self.take_ball_notified_player.is_none()
-            && self.current_owner.is_none()
-        {
-            if let Some(notified_player) = self.notify_nearest_player(players, events) {
-                self.take_ball_notified_player = Some(notified_player);
-            }
-        }
-    }
-
     pub fn try_intercept(&mut self, _players: &[MatchPlayer], _events: &mut EventCollection) {
         if self.current_owner.is_some() {
             return;
@@ -122,55 +124,33 @@ impl Ball {
             || self.position.y >= self.field_height
     }
 
-    fn notify_nearest_player(
-        &self,
-        players: &[MatchPlayer],
-        events: &mut EventCollection,
-    ) -> Option<u32> {
-        let ball_position = self.position;
-
-        // Find the nearest player to the ball
-        let nearest_player = players.iter().min_by(|a, b| {
-            let dist_a = a.position.distance_to(&ball_position);
-            let dist_b = b.position.distance_to(&ball_position);
-            dist_a
-                .partial_cmp(&dist_b)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+    // Parks the ball on the line it crossed, the goal lines take precedence at the corners
+    fn check_boundary_collision(&mut self, context: &MatchContext) -> Option<BoundaryLine> {
+        let field_width = context.field_size.width as f32;
+        let field_height = context.field_size.height as f32;
 
-        if let Some(player) = nearest_player {
-            events.add_ball_event(BallEvent::TakeMe(player.id));
+        let mut crossed_line = None;
 
-            return Some(player.id);
+        if self.position.y <= 0.0 || self.position.y >= field_height {
+            self.position.y = self.position.y.clamp(0.0, field_height);
+            crossed_line = Some(BoundaryLine::Touchline);
         }
 
-        None
-    }
-
-    fn check_boundary_collision(&mut self, context: &MatchContext) {
-        let field_width = context.field_size.width as f32;
-        let field_height = context.field_size.height as f32;
-
-        // Check if ball hits the boundary and reverse its velocity if it does
         if self.position.x <= 0.0 {
             self.position.x = 0.0;
-            self.velocity = Vector3::zeros();
+            crossed_line = Some(BoundaryLine::GoalLine(PlayerSide::Left));
         }
 
         if self.position.x >= field_width {
             self.position.x = field_width;
-            self.velocity = Vector3::zeros();
+            crossed_line = Some(BoundaryLine::GoalLine(PlayerSide::Right));
         }
 
-        if self.position.y <= 0.0 {
-            self.position.y = 0.0;
+        if crossed_line.is_some() {
             self.velocity = Vector3::zeros();
         }
 
-        if self.position.y >= field_height {
-            self.position.y = field_height;
-            self.velocity = Vector3::zeros();
-        }
+        crossed_line
     }
 
     fn is_players_running_to_ball(&self, players: &[MatchPlayer]) -> bool {
@@ -269,11 +249,15 @@ impl Ball {
             + physical_skills.agility * agility_weight
     }
 
-    fn check_goal(&mut self, context: &MatchContext, result: &mut EventCollection) {
+    fn check_goal(&mut self, context: &MatchContext, result: &mut EventCollection) -> bool {
         if let Some(goal_side) = context.goal_positions.is_goal(self.position) {
             result.add_ball_event(BallEvent::Goal(goal_side, self.previous_owner));
             self.reset();
+
+            return true;
         }
+
+        false
     }
 
     fn update_velocity(&mut self) {
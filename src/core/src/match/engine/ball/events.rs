@@ -1,7 +1,10 @@
 use crate::r#match::events::Event;
 use crate::r#match::player::events::PlayerEvent;
-use crate::r#match::{MatchContext, MatchField};
+use crate::r#match::{
+    BoundaryLine, MatchContext, MatchField, PlayerSide, SetPiece, SetPieceProcessor,
+};
 use log::{debug};
+use nalgebra::Vector3;
 
 #[derive(Copy, Clone, Debug)]
 pub enum BallEvent {
@@ -9,6 +12,7 @@ pub enum BallEvent {
     Claimed(u32),
     Gained(u32),
     TakeMe(u32),
+    OutOfPlay(BoundaryLine, Vector3<f32>, Option<u32>),
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -23,7 +27,7 @@ impl BallEventDispatcher {
     pub fn dispatch(
         event: BallEvent,
         field: &mut MatchField,
-        context: &MatchContext,
+        context: &mut MatchContext,
     ) -> Vec<Event> {
         let mut remaining_events = Vec::new();
 
@@ -31,9 +35,32 @@ impl BallEventDispatcher {
 
         match event {
             BallEvent::Goal(side, goalscorer_player_id) => {
-                match side {
-                    GoalSide::Home => context.score.increment_home_goals(),
-                    GoalSide::Away => context.score.increment_away_goals(),
+                field.ball.previous_owner = None;
+                field.ball.current_owner = None;
+
+                // shootout goals only count for the shootout
+                if let Some(shootout) = context.penalty_shootout.as_mut() {
+                    shootout.score_pending_kick();
+                    return remaining_events;
+                }
+
+                // the home goal is defended by the left side
+                let conceding_side = match side {
+                    GoalSide::Home => PlayerSide::Left,
+                    GoalSide::Away => PlayerSide::Right,
+                };
+
+                let is_home_conceded = field
+                    .players
+                    .iter()
+                    .find(|p| p.side == Some(conceding_side))
+                    .map(|p| p.team_id == context.score.home_team.team_id)
+                    .unwrap_or(conceding_side == PlayerSide::Left);
+
+                if is_home_conceded {
+                    context.score.increment_away_goals();
+                } else {
+                    context.score.increment_home_goals();
                 }
 
                 if let Some(goalscorer_player_id) = goalscorer_player_id {
@@ -41,6 +68,11 @@ impl BallEventDispatcher {
                 }
 
                 field.reset_players_positions();
+
+                // the team that conceded kicks off
+                if let Some(kick_off) = SetPiece::kick_off(conceding_side, field) {
+                    SetPieceProcessor::start(kick_off, field, context);
+                }
             }
             BallEvent::Claimed(player_id) => {
                 remaining_events.push(Event::PlayerEvent(PlayerEvent::ClaimBall(player_id)));
@@ -51,6 +83,18 @@ impl BallEventDispatcher {
             BallEvent::TakeMe(player_id) => {
                 remaining_events.push(Event::PlayerEvent(PlayerEvent::TakeBall(player_id)));
             }
+            BallEvent::OutOfPlay(line, position, last_touched_by) => {
+                // a shootout kick that goes out is simply missed
+                if context.penalty_shootout.is_some() {
+                    return remaining_events;
+                }
+
+                if let Some(set_piece) =
+                    SetPiece::from_out_of_play(line, position, last_touched_by, field, context)
+                {
+                    SetPieceProcessor::start(set_piece, field, context);
+                }
+            }
         }
 
         remaining_events
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+// How a match is played: tick by tick on the pitch, or resolved at once by the
+// statistical model, which is what bulk simulation of whole seasons wants
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchEngineMode {
+    #[default]
+    Detailed,
+    Statistical,
+}
+
+// How a match may end: league matches allow draws, knockout matches go to
+// extra time and penalties while the tie is level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchConfig {
+    pub allow_draw: bool,
+    // goals of (home, away) team of this match scored in the first leg
+    pub first_leg: Option<(u8, u8)>,
+    pub away_goals: bool,
+    // changes per team and the stoppages they may be made at, breaks excluded
+    pub substitutions: u8,
+    pub substitution_windows: u8,
+    pub engine: MatchEngineMode,
+}
+
+const SUBSTITUTIONS: u8 = 5;
+const SUBSTITUTION_WINDOWS: u8 = 3;
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        MatchConfig {
+            allow_draw: true,
+            first_leg: None,
+            away_goals: false,
+            substitutions: SUBSTITUTIONS,
+            substitution_windows: SUBSTITUTION_WINDOWS,
+            engine: MatchEngineMode::Detailed,
+        }
+    }
+}
+
+impl MatchConfig {
+    pub fn knockout() -> Self {
+        MatchConfig {
+            allow_draw: false,
+            first_leg: None,
+            away_goals: false,
+            substitutions: SUBSTITUTIONS,
+            substitution_windows: SUBSTITUTION_WINDOWS,
+            engine: MatchEngineMode::Detailed,
+        }
+    }
+
+    pub fn second_leg(first_leg: (u8, u8), away_goals: bool) -> Self {
+        MatchConfig {
+            allow_draw: false,
+            first_leg: Some(first_leg),
+            away_goals,
+            substitutions: SUBSTITUTIONS,
+            substitution_windows: SUBSTITUTION_WINDOWS,
+            engine: MatchEngineMode::Detailed,
+        }
+    }
+
+    pub fn with_engine(mut self, engine: MatchEngineMode) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    pub fn is_decided(&self, home_goals: u8, away_goals: u8) -> bool {
+        if self.allow_draw {
+            return true;
+        }
+
+        let (first_leg_home, first_leg_away) = self.first_leg.unwrap_or((0, 0));
+
+        let home_total = home_goals as u16 + first_leg_home as u16;
+        let away_total = away_goals as u16 + first_leg_away as u16;
+
+        if home_total != away_total {
+            return true;
+        }
+
+        // the home team of the second leg scored its away goals in the first leg
+        self.away_goals && self.first_leg.is_some() && first_leg_home != away_goals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn league_match_is_always_decided() {
+        assert!(MatchConfig::default().is_decided(1, 1));
+    }
+
+    #[test]
+    fn knockout_draw_is_not_decided() {
+        assert!(!MatchConfig::knockout().is_decided(2, 2));
+        assert!(MatchConfig::knockout().is_decided(2, 1));
+    }
+
+    #[test]
+    fn second_leg_counts_aggregate_and_away_goals() {
+        // lost 1:2 away in the first leg, 1:0 now is 2:2 on aggregate
+        let config = MatchConfig::second_leg((1, 2), false);
+
+        assert!(!config.is_decided(1, 0));
+        assert!(config.is_decided(2, 0));
+
+        // the away goal of the first leg against none now
+        let config = MatchConfig::second_leg((1, 2), true);
+
+        assert!(config.is_decided(1, 0));
+        assert!(!config.is_decided(2, 1));
+    }
+}
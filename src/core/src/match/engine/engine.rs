@@ -4,12 +4,16 @@ use crate::r#match::events::EventDispatcher;
 use crate::r#match::field::MatchField;
 use crate::r#match::result::ResultMatchPositionData;
 use crate::r#match::squad::TeamSquad;
+use crate::r#match::statistics::MatchPlayerResult;
 use crate::r#match::{
-    GameState, GameTickContext, GoalDetail, MatchPlayer, MatchResultRaw, Score, StateManager,
+    GameState, GameTickContext, GoalDetail, MatchAnalytics, MatchConfig, MatchHeatmaps,
+    MatchPlayer, MatchResultRaw, MatchState, MatchTeamStatistics, PenaltyKick, PenaltyShootout,
+    PlayerSide, MatchTimeline, Referee, Score, SetPiece, SetPieceProcessor, StateManager,
+    SubstitutionCoach, Substitutions, TacticalChanges, TacticalCoach, TimelineEventType,
 };
-use crate::Tactics;
+use crate::{InjuryCause, Tactics, MATCH_MINUTE_INJURY_CHANCE};
 use nalgebra::Vector3;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 pub struct FootballEngine<const W: usize, const H: usize> {}
 
@@ -18,7 +22,11 @@ impl<const W: usize, const H: usize> FootballEngine<W, H> {
         FootballEngine {}
     }
 
-    pub fn play(left_squad: TeamSquad, right_squad: TeamSquad) -> MatchResultRaw {
+    pub fn play(
+        left_squad: TeamSquad,
+        right_squad: TeamSquad,
+        config: MatchConfig,
+    ) -> MatchResultRaw {
         let score = Score::new(left_squad.team_id, right_squad.team_id);
 
         let players = MatchPlayerCollection::from_squads(&left_squad, &right_squad);
@@ -29,28 +37,86 @@ impl<const W: usize, const H: usize> FootballEngine<W, H> {
 
         let mut context = MatchContext::new(&field, players, score);
 
+        context.substitutions = Substitutions::new(&config);
+        context.config = config;
+
         let mut state_manager = StateManager::new();
 
-        while let Some(state) = state_manager.next() {
+        let mut normal_time_score = None;
+        let mut playing_time = 0;
+
+        while let Some(state) = state_manager.next(context.is_decided()) {
             context.state.set(state);
 
-            let play_state_result =
-                Self::play_inner(&mut field, &mut context, &mut match_position_data);
+            if state.is_extra_time() && normal_time_score.is_none() {
+                normal_time_score = Some(context.goals());
+
+                context.substitutions.extend_for_extra_time();
+            }
+
+            let play_state_result = if state == MatchState::PenaltyShootout {
+                Self::play_penalty_shootout(&mut field, &mut context, &mut match_position_data)
+            } else {
+                Self::play_inner(&mut field, &mut context, &mut match_position_data)
+            };
+
+            if state.duration() > 0 {
+                playing_time = context.time.time;
+            }
+
+            if state.duration() > 0 || state == MatchState::PenaltyShootout {
+                context
+                    .timeline
+                    .add(context.time.time, TimelineEventType::PeriodEnd { period: state });
+            }
 
             StateManager::handle_state_finish(&mut context, &mut field, play_state_result);
         }
 
-        let mut result = MatchResultRaw::with_match_time(MATCH_HALF_TIME_MS);
+        let mut result = MatchResultRaw::with_match_time(context.time.time);
 
         context.fill_details();
 
         result.score = Some(context.score.clone());
 
+        // goals scored in extra time are part of the score, reported apart as well
+        result.extra_time = normal_time_score.map(|(home, away)| {
+            let (home_total, away_total) = context.goals();
+            (home_total - home, away_total - away)
+        });
+
+        result.penalty_shootout = context
+            .penalty_shootout
+            .as_ref()
+            .map(|shootout| shootout.score());
+
+        result.player_results = MatchPlayerResult::from_players(
+            field
+                .players
+                .iter()
+                .chain(field.sent_off.iter())
+                .chain(field.substituted.iter()),
+            &context.score,
+            (playing_time / 60_000) as u8,
+            &context.analytics,
+        );
+
+        result.team_statistics = [context.score.home_team.team_id, context.score.away_team.team_id]
+            .into_iter()
+            .map(|team_id| {
+                MatchTeamStatistics::new(team_id, &result.player_results, &context.analytics)
+            })
+            .collect();
+
+        result.heatmaps = MatchHeatmaps::new(&result.player_results, &context.analytics);
+
         result.left_team_players = field.left_side_players.expect("left team players");
         result.right_team_players = field.right_side_players.expect("right team players");
 
         result.position_data = match_position_data;
 
+        result.timeline = context.timeline;
+
         result
     }
 
@@ -61,13 +127,215 @@ impl<const W: usize, const H: usize> FootballEngine<W, H> {
     ) -> PlayMatchStateResult {
         let result = PlayMatchStateResult::default();
 
+        let state = context.state.match_state;
+
+        context.time.start_period(state.duration());
+
+        if state.duration() > 0 {
+            // the team talk comes before the players line up again
+            if state != MatchState::FirstHalf {
+                TacticalCoach::review(field, context, true);
+            }
+
+            Self::kick_off(state, field, context);
+
+            // changes at the break are made before the restart
+            if state != MatchState::FirstHalf {
+                SubstitutionCoach::review(field, context, true);
+            }
+        }
+
         while context.increment_time() {
             Self::game_tick(field, context, match_data);
+
+            if context.time.is_new_minute() {
+                Self::play_minute(field, context);
+            }
+
+            if context.set_piece.is_some() && context.substitutions.has_pending() {
+                SubstitutionCoach::make_substitutions(field, context);
+            }
         }
 
         result
     }
 
+    fn play_minute(field: &mut MatchField, context: &mut MatchContext) {
+        field.players.iter_mut().for_each(|player| player.tire());
+
+        let injured: Vec<u32> = field
+            .players
+            .iter()
+            .filter(|player| player.is_injured_by(MATCH_MINUTE_INJURY_CHANCE))
+            .map(|player| player.id)
+            .collect();
+
+        for player_id in injured {
+            field.injure(player_id, InjuryCause::NonContact, context.time.time);
+
+            context
+                .timeline
+                .add(context.time.time, TimelineEventType::Injury { player_id });
+        }
+
+        TacticalCoach::review(field, context, false);
+        SubstitutionCoach::review(field, context, false);
+    }
+
+    // Home team kicks off the first half of normal and extra time, the away team the second
+    fn kick_off(state: MatchState, field: &mut MatchField, context: &mut MatchContext) {
+        let team_id = match state {
+            MatchState::FirstHalf | MatchState::ExtraTimeFirstHalf => {
+                context.score.home_team.team_id
+            }
+            _ => context.score.away_team.team_id,
+        };
+
+        context
+            .timeline
+            .add(context.time.time, TimelineEventType::KickOff { period: state, team_id });
+
+        let side = field
+            .players
+            .iter()
+            .find(|p| p.team_id == team_id)
+            .and_then(|p| p.side);
+
+        if let Some(kick_off) = side.and_then(|side| SetPiece::kick_off(side, field)) {
+            SetPieceProcessor::start(kick_off, field, context);
+        }
+    }
+
+    // Kicks are played out by the engine: the taker shoots from the spot and the keeper
+    // tries to save it, the score is kept apart from the match score
+    fn play_penalty_shootout(
+        field: &mut MatchField,
+        context: &mut MatchContext,
+        match_data: &mut ResultMatchPositionData,
+    ) -> PlayMatchStateResult {
+        context.penalty_shootout = Some(PenaltyShootout::new(
+            context.score.home_team.team_id,
+            context.score.away_team.team_id,
+            field,
+        ));
+
+        while let Some(kick) = context
+            .penalty_shootout
+            .as_mut()
+            .and_then(|shootout| shootout.take_kick())
+        {
+            Self::play_penalty_kick(kick, field, context, match_data);
+
+            let kick = context
+                .penalty_shootout
+                .as_mut()
+                .and_then(|shootout| shootout.finish_kick());
+
+            if let Some(kick) = kick {
+                context.timeline.add(
+                    context.time.time,
+                    TimelineEventType::ShootoutKick {
+                        player_id: kick.taker_id,
+                        team_id: kick.team_id,
+                        is_scored: kick.is_scored,
+                    },
+                );
+            }
+        }
+
+        context.set_piece = None;
+
+        PlayMatchStateResult::default()
+    }
+
+    fn play_penalty_kick(
+        kick: PenaltyKick,
+        field: &mut MatchField,
+        context: &mut MatchContext,
+        match_data: &mut ResultMatchPositionData,
+    ) {
+        let defending_side = match field
+            .players
+            .iter()
+            .find(|p| p.id == kick.taker_id)
+            .and_then(|p| p.side)
+        {
+            Some(PlayerSide::Left) => PlayerSide::Right,
+            Some(PlayerSide::Right) => PlayerSide::Left,
+            None => return,
+        };
+
+        let keeper_id = field
+            .players
+            .iter()
+            .find(|p| {
+                p.side == Some(defending_side)
+                    && p.tactical_position.current_position.is_goalkeeper()
+            })
+            .map(|p| p.id);
+
+        let penalty =
+            SetPiece::penalty(kick.team_id, defending_side, context).with_taker(kick.taker_id);
+
+        SetPieceProcessor::start(penalty, field, context);
+
+        for _ in 0..PENALTY_KICK_MAX_TICKS {
+            context.time.increment(MATCH_TIME_INCREMENT_MS);
+
+            if context.set_piece.is_some() {
+                Self::game_tick(field, context, match_data);
+            } else {
+                Self::penalty_tick(keeper_id, field, context, match_data);
+            }
+
+            let is_scored = context
+                .penalty_shootout
+                .as_ref()
+                .is_some_and(|shootout| shootout.is_pending_kick_scored());
+
+            // saved, missed or scored
+            let is_ball_dead = context.set_piece.is_none()
+                && (field.ball.current_owner.is_some() || field.ball.velocity == Vector3::zeros());
+
+            if is_scored || is_ball_dead {
+                break;
+            }
+        }
+    }
+
+    // Once the kick is taken only the ball and the keeper are in play, nobody may follow up
+    fn penalty_tick(
+        keeper_id: Option<u32>,
+        field: &mut MatchField,
+        context: &mut MatchContext,
+        match_data: &mut ResultMatchPositionData,
+    ) {
+        let game_tick_context = GameTickContext::new(field);
+
+        let mut events = EventCollection::new();
+
+        let keepers: Vec<MatchPlayer> = field
+            .players
+            .iter()
+            .filter(|p| Some(p.id) == keeper_id)
+            .cloned()
+            .collect();
+
+        field
+            .ball
+            .update(context, &keepers, &game_tick_context, &mut events);
+
+        field
+            .players
+            .iter_mut()
+            .filter(|p| Some(p.id) == keeper_id)
+            .for_each(|keeper| keeper.update(context, &game_tick_context, &mut events));
+
+        EventDispatcher::dispatch(events.to_vec(), field, context, true);
+
+        Self::write_match_positions(field, context.time.time, match_data);
+    }
+
     pub fn game_tick(
         field: &mut MatchField,
         context: &mut MatchContext,
@@ -77,12 +345,31 @@ impl<const W: usize, const H: usize> FootballEngine<W, H> {
 
         let mut events = EventCollection::new();
 
-        Self::play_ball(field, context, &game_tick_context, &mut events);
-        Self::play_players(field, context, &game_tick_context, &mut events);
+        // play stands still until the restart is taken
+        if context.set_piece.is_some() {
+            SetPieceProcessor::process(field, context, &mut events);
+        } else {
+            Self::play_ball(field, context, &game_tick_context, &mut events);
+            Self::play_players(field, context, &game_tick_context, &mut events);
+        }
+
+        let is_set_piece_taken = SetPieceProcessor::is_finished(context);
 
         // dispatch events
         EventDispatcher::dispatch(events.to_vec(), field, context, true);
 
+        // a new stoppage during dispatch replaces the finished set piece
+        if is_set_piece_taken && SetPieceProcessor::is_finished(context) {
+            context.set_piece = None;
+        }
+
+        context.analytics.track(
+            field,
+            &mut context.timeline,
+            context.time.time,
+            context.set_piece.is_some(),
+        );
+
         Self::write_match_positions(field, context.time.time, match_data);
     }
 
@@ -94,6 +381,7 @@ impl<const W: usize, const H: usize> FootballEngine<W, H> {
         // player positions
         field.players.iter().for_each(|player| {
             match_data.add_player_positions(player.id, timestamp, player.position);
+            match_data.add_player_state(player.id, timestamp, player.state);
         });
 
         // player positions
@@ -144,12 +432,22 @@ pub struct MatchContext {
     pub field_size: MatchFieldSize,
     pub players: MatchPlayerCollection,
     pub goal_positions: GoalPosition,
+    pub referee: Referee,
+    pub set_piece: Option<SetPiece>,
+    pub config: MatchConfig,
+    pub penalty_shootout: Option<PenaltyShootout>,
+    pub substitutions: Substitutions,
+    pub analytics: MatchAnalytics,
+    pub timeline: MatchTimeline,
+    pub tactical_changes: TacticalChanges,
 
     pub tactics: TeamsTactics,
 }
 
 impl MatchContext {
     pub fn new(field: &MatchField, players: MatchPlayerCollection, score: Score) -> Self {
+        let team_ids = [score.home_team.team_id, score.away_team.team_id];
+
         MatchContext {
             state: GameState::new(),
             time: MatchTime::new(),
@@ -157,12 +455,30 @@ impl MatchContext {
             field_size: MatchFieldSize::clone(&field.size),
             players,
             goal_positions: GoalPosition::from(&field.size),
+            referee: Referee::random(),
+            set_piece: None,
+            config: MatchConfig::default(),
+            penalty_shootout: None,
+            substitutions: Substitutions::new(&MatchConfig::default()),
+            analytics: MatchAnalytics::new(),
+            timeline: MatchTimeline::new(),
+            tactical_changes: TacticalChanges::from_field(field, team_ids),
             tactics: TeamsTactics::from_field(&field),
         }
     }
 
     pub fn increment_time(&mut self) -> bool {
-        self.time.increment(MATCH_TIME_INCREMENT_MS) < MATCH_HALF_TIME_MS
+        self.time.increment(MATCH_TIME_INCREMENT_MS) < self.time.period_end
+    }
+
+    pub fn goals(&self) -> (u8, u8) {
+        (self.score.home_team.get(), self.score.away_team.get())
+    }
+
+    pub fn is_decided(&self) -> bool {
+        let (home_goals, away_goals) = self.goals();
+
+        self.config.is_decided(home_goals, away_goals)
     }
 
     pub fn add_time(&mut self, time: u64) {
@@ -333,12 +649,12 @@ impl MatchFieldSize {
 }
 
 pub struct MatchPlayerCollection {
-    pub players: HashMap<u32, MatchPlayer>,
+    pub players: BTreeMap<u32, MatchPlayer>,
 }
 
 impl MatchPlayerCollection {
     pub fn from_squads(home_squad: &TeamSquad, away_squad: &TeamSquad) -> Self {
-        let mut result = HashMap::new();
+        let mut result = BTreeMap::new();
 
         // home_main
         for hs_m in &home_squad.main_squad {
@@ -374,6 +690,9 @@ impl MatchPlayerCollection {
 
 const MATCH_TIME_INCREMENT_MS: u64 = 10;
 
+// A kick that is neither in, saved nor out after this long counts as missed
+const PENALTY_KICK_MAX_TICKS: usize = 1000;
+
 #[cfg(debug_assertions)]
 pub const MATCH_HALF_TIME_MS: u64 = 1 * 60 * 1000;
 #[cfg(not(debug_assertions))]
@@ -381,13 +700,27 @@ pub const MATCH_HALF_TIME_MS: u64 = 45 * 60 * 1000;
 
 pub const MATCH_TIME_MS: u64 = MATCH_HALF_TIME_MS * 2;
 
+pub const EXTRA_TIME_HALF_MS: u64 = MATCH_HALF_TIME_MS / 3;
+
+// one minute of a 90 minute match whatever the length of a half is
+pub const MATCH_MINUTE_MS: u64 = MATCH_TIME_MS / 90;
+
 pub struct MatchTime {
     pub time: u64,
+    // the clock stops the running period here
+    pub period_end: u64,
 }
 
 impl MatchTime {
     pub fn new() -> Self {
-        MatchTime { time: 0 }
+        MatchTime {
+            time: 0,
+            period_end: 0,
+        }
+    }
+
+    pub fn start_period(&mut self, duration: u64) {
+        self.period_end = self.time + duration;
     }
 
     #[inline]
@@ -396,6 +729,15 @@ impl MatchTime {
         self.time
     }
 
+    pub fn minute(&self) -> u64 {
+        self.time / MATCH_MINUTE_MS
+    }
+
+    // the clock has just passed a full minute
+    pub fn is_new_minute(&self) -> bool {
+        self.time % MATCH_MINUTE_MS < MATCH_TIME_INCREMENT_MS
+    }
+
     pub fn is_running_out(&self) -> bool {
         self.time > (2 * MATCH_TIME_MS / 3)
     }
@@ -428,4 +770,19 @@ mod tests {
         assert_eq!(match_time.time, 15);
         assert_eq!(incremented_time_again, 15);
     }
+
+    #[test]
+    fn test_minutes_of_a_match() {
+        let mut match_time = MatchTime::new();
+        let mut minutes = 0;
+
+        while match_time.increment(MATCH_TIME_INCREMENT_MS) <= MATCH_TIME_MS {
+            if match_time.is_new_minute() {
+                minutes += 1;
+            }
+        }
+
+        assert_eq!(90, minutes);
+        assert_eq!(90, match_time.minute());
+    }
 }
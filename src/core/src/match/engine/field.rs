@@ -1,9 +1,11 @@
 ﻿use crate::r#match::ball::Ball;
 use crate::r#match::{
-    FieldSquad, MatchFieldSize, MatchPlayer, PlayerSide, PositionType, TeamSquad,
+    FieldSquad, MatchFieldSize, MatchPlayer, PlayerSide, PositionType, Substitution, TeamSquad,
     POSITION_POSITIONING,
 };
-use crate::Tactics;
+use crate::{
+    InjuryCause, PlayerFieldPositionGroup, PlayerPositionType, Tactics, TeamInstructions,
+};
 use nalgebra::Vector3;
 
 pub struct MatchField {
@@ -11,6 +13,8 @@ pub struct MatchField {
     pub ball: Ball,
     pub players: Vec<MatchPlayer>,
     pub substitutes: Vec<MatchPlayer>,
+    pub sent_off: Vec<MatchPlayer>,
+    pub substituted: Vec<MatchPlayer>,
 
     pub left_side_players: Option<FieldSquad>,
     pub left_team_tactics: Tactics,
@@ -33,13 +37,15 @@ impl MatchField {
         let right_tactics = right_team_squad.tactics.clone();
 
         let (players_on_field, substitutes) =
-            setup_player_on_field(left_team_squad, right_team_squad);
+            setup_player_on_field(left_team_squad, right_team_squad, height as f32);
 
         MatchField {
             size: MatchFieldSize::new(width, height),
             ball: Ball::with_coord(width as f32, height as f32),
             players: players_on_field,
             substitutes,
+            sent_off: Vec::new(),
+            substituted: Vec::new(),
             left_side_players: Some(left_squad),
             left_team_tactics: left_tactics,
             right_side_players: Some(away_squad),
@@ -56,8 +62,12 @@ impl MatchField {
         });
     }
 
+    // Teams change ends: every player takes the mirrored start position on the
+    // other half, the squads keep reporting the sides they started on
     pub fn swap_squads(&mut self) {
-        std::mem::swap(&mut self.left_side_players, &mut self.right_side_players);
+        std::mem::swap(&mut self.left_team_tactics, &mut self.right_team_tactics);
+
+        let (width, height) = (self.size.width as f32, self.size.height as f32);
 
         self.players.iter_mut().for_each(|p| {
             if let Some(side) = &p.side {
@@ -66,25 +76,170 @@ impl MatchField {
                     PlayerSide::Right => PlayerSide::Left,
                 })
             }
+
+            p.start_position = Vector3::new(
+                width - p.start_position.x,
+                height - p.start_position.y,
+                0.0,
+            );
         });
+
+        self.reset_players_positions();
+        self.ball.reset();
+        self.ball.previous_owner = None;
+        self.ball.current_owner = None;
     }
 
     pub fn get_player_mut(&mut self, id: u32) -> Option<&mut MatchPlayer> {
         self.players.iter_mut().find(|p| p.id == id)
     }
+
+    pub fn team_side(&self, team_id: u32) -> Option<PlayerSide> {
+        self.players
+            .iter()
+            .chain(self.substitutes.iter())
+            .find(|p| p.team_id == team_id)
+            .and_then(|p| p.side)
+    }
+
+    pub fn team_tactics(&self, side: PlayerSide) -> &Tactics {
+        match side {
+            PlayerSide::Left => &self.left_team_tactics,
+            PlayerSide::Right => &self.right_team_tactics,
+        }
+    }
+
+    // A new plan from the coach: the players move to their new positions, those
+    // changing part of the pitch take up the role that goes with it
+    pub fn change_tactics(
+        &mut self,
+        side: PlayerSide,
+        tactics: Tactics,
+        positions: &[(u32, PlayerPositionType)],
+    ) {
+        let field_height = self.size.height as f32;
+
+        for (player_id, position) in positions {
+            if let Some(player) = self.get_player_mut(*player_id) {
+                let previous_group = player.tactical_position.current_position.position_group();
+
+                player.tactical_position.current_position = *position;
+                player.tactical_position.role = tactics.role(*position);
+
+                if let Some(start_position) = get_player_position(player, side).map(|start| {
+                    apply_instructions(player, side, start, &tactics.instructions, field_height)
+                }) {
+                    player.start_position = start_position;
+                }
+
+                if position.position_group() != previous_group {
+                    player.set_default_state();
+                }
+            }
+        }
+
+        match side {
+            PlayerSide::Left => self.left_team_tactics = tactics,
+            PlayerSide::Right => self.right_team_tactics = tactics,
+        }
+    }
+
+    // The team plays on one player short, the ball is dropped if he had it
+    pub fn send_off(&mut self, id: u32) {
+        if let Some(idx) = self.players.iter().position(|p| p.id == id) {
+            let player = self.players.remove(idx);
+
+            if self.ball.current_owner == Some(id) {
+                self.ball.current_owner = None;
+            }
+
+            self.sent_off.push(player);
+        }
+    }
+
+    // An injured player lets go of the ball and waits to be replaced
+    pub fn injure(&mut self, id: u32, cause: InjuryCause, time: u64) {
+        if let Some(player) = self.get_player_mut(id) {
+            player.injure(cause, time);
+
+            if self.ball.current_owner == Some(id) {
+                self.ball.current_owner = None;
+            }
+        }
+    }
+
+    // The player coming on takes over the place and the role of the one going off
+    pub fn substitute(&mut self, substitution: &Substitution, time: u64) -> bool {
+        let out_idx = self
+            .players
+            .iter()
+            .position(|p| p.id == substitution.player_out_id);
+
+        let in_idx = self
+            .substitutes
+            .iter()
+            .position(|p| p.id == substitution.player_in_id);
+
+        let (out_idx, in_idx) = match (out_idx, in_idx) {
+            (Some(out_idx), Some(in_idx)) => (out_idx, in_idx),
+            _ => return false,
+        };
+
+        let mut player_out = self.players.remove(out_idx);
+        let mut player_in = self.substitutes.remove(in_idx);
+
+        player_in.side = player_out.side;
+        player_in.tactical_position = player_out.tactical_position.clone();
+        player_in.position = player_out.position;
+        player_in.start_position = player_out.start_position;
+        player_in.velocity = Vector3::zeros();
+        player_in.set_default_state();
+        player_in.statistics.add_substituted_on(time);
+
+        player_out.statistics.add_substituted_off(time);
+
+        if self.ball.previous_owner == Some(player_out.id) {
+            self.ball.previous_owner = None;
+        }
+
+        let squads = [&mut self.left_side_players, &mut self.right_side_players];
+
+        if let Some(squad) = squads
+            .into_iter()
+            .flatten()
+            .find(|squad| squad.contains(player_out.id))
+        {
+            squad.substitutions.push(Substitution {
+                time,
+                ..substitution.clone()
+            });
+        }
+
+        self.players.push(player_in);
+        self.substituted.push(player_out);
+
+        true
+    }
 }
 
 fn setup_player_on_field(
     left_team_squad: TeamSquad,
     right_team_squad: TeamSquad,
+    field_height: f32,
 ) -> (Vec<MatchPlayer>, Vec<MatchPlayer>) {
     let setup_squad = |squad: TeamSquad, side: PlayerSide| {
         let mut players = Vec::new();
         let mut subs = Vec::new();
 
+        let instructions = squad.tactics.instructions;
+
         for mut player in squad.main_squad {
             player.side = Some(side);
-            if let Some(position) = get_player_position(&player, side) {
+            player.tactical_position.role =
+                squad.tactics.role(player.tactical_position.current_position);
+            if let Some(position) = get_player_position(&player, side).map(|position| {
+                apply_instructions(&player, side, position, &instructions, field_height)
+            }) {
                 player.position = position;
                 player.start_position = position;
                 players.push(player);
@@ -93,6 +248,8 @@ fn setup_player_on_field(
 
         for mut player in squad.substitutes {
             player.side = Some(side);
+            player.tactical_position.role =
+                squad.tactics.role(player.tactical_position.current_position);
             player.position = Vector3::new(1.0, 1.0, 0.0);
             subs.push(player);
         }
@@ -130,4 +287,36 @@ fn get_player_position(player: &MatchPlayer, side: PlayerSide) -> Option<Vector3
             }
         })
         .map(|(x, y)| Vector3::new(x, y, 0.0))
+}
+
+// The line height moves the back line up or down the pitch and the width
+// spreads the outfield players away from the middle
+fn apply_instructions(
+    player: &MatchPlayer,
+    side: PlayerSide,
+    position: Vector3<f32>,
+    instructions: &TeamInstructions,
+    field_height: f32,
+) -> Vector3<f32> {
+    let group = player.tactical_position.current_position.position_group();
+
+    if group == PlayerFieldPositionGroup::Goalkeeper {
+        return position;
+    }
+
+    let mut position = position;
+
+    if group == PlayerFieldPositionGroup::Defender {
+        position.x += match side {
+            PlayerSide::Left => instructions.defensive_line.offset(),
+            PlayerSide::Right => -instructions.defensive_line.offset(),
+        };
+    }
+
+    let center = field_height / 2.0;
+
+    position.y = (center + (position.y - center) * instructions.width.spread())
+        .clamp(0.0, field_height);
+
+    position
 }
\ No newline at end of file
@@ -1,18 +1,30 @@
-﻿pub mod ball;
+﻿pub mod analytics;
+pub mod ball;
+pub mod config;
 pub mod engine;
 pub mod events;
 pub mod field;
 pub mod player;
 pub mod raycast;
+pub mod referee;
 pub mod result;
+pub mod set_pieces;
 pub mod state;
+pub mod substitutions;
 pub mod tactics;
+pub mod timeline;
 
+pub use analytics::*;
 pub use ball::*;
+pub use config::*;
 pub use engine::*;
 pub use field::*;
 pub use player::*;
 pub use raycast::*;
+pub use referee::*;
 pub use result::*;
+pub use set_pieces::*;
 pub use state::*;
-pub use tactics::*;
\ No newline at end of file
+pub use substitutions::*;
+pub use tactics::*;
+pub use timeline::*;
\ No newline at end of file
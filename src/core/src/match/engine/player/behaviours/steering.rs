@@ -1,4 +1,5 @@
 ﻿use crate::r#match::MatchPlayer;
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::Rng;
 
@@ -159,7 +160,7 @@ impl SteeringBehavior {
                 distance,
                 angle: _,
             } => {
-                let mut rng = rand::thread_rng();
+                let mut rng = RandomGenerator::current();
 
                 let angle = rng.gen::<f32>() * std::f32::consts::PI * 2.0;
 
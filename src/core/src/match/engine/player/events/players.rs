@@ -1,7 +1,12 @@
 use crate::r#match::events::Event;
 use crate::r#match::player::events::{PassingEventContext, ShootingEventContext};
 use crate::r#match::statistics::MatchStatisticType;
-use crate::r#match::{GoalDetail, MatchContext, MatchField};
+use crate::r#match::referee::{Card, Foul, FreeKickType, Offside};
+use crate::r#match::{
+    GoalDetail, MatchContext, MatchField, MatchState, SetPiece, SetPieceProcessor, SetPieceType,
+    Shot, TimelineEventType,
+};
+use crate::{InjuryCause, FOUL_INJURY_CHANCE, TACKLE_INJURY_CHANCE};
 use log::{debug};
 use nalgebra::Vector3;
 
@@ -24,7 +29,7 @@ pub enum PlayerEvent {
     ClaimBall(u32),
     GainBall(u32),
     CaughtBall(u32),
-    CommitFoul,
+    CommitFoul(u32, u32),
     RequestHeading(u32, Vector3<f32>),
     RequestShot(u32, Vector3<f32>),
     RequestBallReceive(u32),
@@ -54,13 +59,13 @@ impl PlayerEventDispatcher {
                 Self::handle_ball_collision_event(player_id, field);
             }
             PlayerEvent::TacklingBall(player_id) => {
-                Self::handle_tackling_ball_event(player_id, field);
+                Self::handle_tackling_ball_event(player_id, field, context);
             }
             PlayerEvent::BallOwnerChange(player_id) => {
                 Self::handle_ball_owner_change_event(player_id, field);
             }
             PlayerEvent::PassTo(pass_event_model) => {
-                Self::handle_pass_to_event(pass_event_model, field);
+                Self::handle_pass_to_event(pass_event_model, field, context);
             }
             PlayerEvent::ClaimBall(player_id) => {
                 Self::handle_claim_ball_event(player_id, field);
@@ -72,7 +77,7 @@ impl PlayerEventDispatcher {
                 Self::handle_gain_ball_event(player_id, field);
             }
             PlayerEvent::Shoot(shoot_event_model) => {
-                Self::handle_shoot_event(shoot_event_model, field);
+                Self::handle_shoot_event(shoot_event_model, field, context);
             }
             PlayerEvent::CaughtBall(player_id) => {
                 Self::handle_caught_ball_event(player_id, field);
@@ -83,6 +88,9 @@ impl PlayerEventDispatcher {
             PlayerEvent::TakeBall(player_id) => {
                 Self::handle_take_ball_event(player_id, field);
             }
+            PlayerEvent::CommitFoul(offender_id, fouled_player_id) => {
+                Self::handle_commit_foul_event(offender_id, fouled_player_id, field, context);
+            }
             _ => {} // Ignore unsupported events
         }
 
@@ -90,8 +98,17 @@ impl PlayerEventDispatcher {
     }
 
     fn handle_goal_event(player_id: u32, field: &mut MatchField, context: &mut MatchContext) {
-        let player = field.get_player_mut(player_id).unwrap();
-        player.statistics.add_goal(context.time.time);
+        let mut team_id = None;
+
+        if let Some(player) = field.get_player_mut(player_id) {
+            player.statistics.add_goal(context.time.time);
+
+            if context.analytics.is_penalty_goal(player_id) {
+                player.statistics.add_penalty_goal();
+            }
+
+            team_id = Some(player.team_id);
+        }
 
         context.score.add_goal_detail(GoalDetail {
             player_id,
@@ -99,33 +116,62 @@ impl PlayerEventDispatcher {
             time: context.time.time,
         });
 
-        field.ball.previous_owner = None;
-        field.ball.current_owner = None;
+        let assist_player_id = context.analytics.assist_for(player_id);
+
+        if let Some(assist_player_id) = assist_player_id {
+            Self::handle_assist_event(assist_player_id, field, context);
+        }
+
+        if let Some(team_id) = team_id {
+            context.timeline.add(
+                context.time.time,
+                TimelineEventType::Goal {
+                    player_id,
+                    team_id,
+                    assist_player_id,
+                },
+            );
+        }
     }
 
     fn handle_assist_event(player_id: u32, field: &mut MatchField, context: &mut MatchContext) {
-        let player = field.get_player_mut(player_id).unwrap();
-
         context.score.add_goal_detail(GoalDetail {
             player_id,
             stat_type: MatchStatisticType::Assist,
             time: context.time.time,
         });
 
-        player.statistics.add_assist(context.time.time);
+        if let Some(player) = field.get_player_mut(player_id) {
+            player.statistics.add_assist(context.time.time);
+        }
     }
 
     fn handle_ball_collision_event(player_id: u32, field: &mut MatchField) {
-        let player = field.get_player_mut(player_id).unwrap();
-
-        if player.skills.technical.first_touch > 10.0 {
-            // Handle player gaining control of the ball after collision
+        if let Some(player) = field.get_player_mut(player_id) {
+            if player.skills.technical.first_touch > 10.0 {
+                // Handle player gaining control of the ball after collision
+            }
         }
     }
 
-    fn handle_tackling_ball_event(player_id: u32, field: &mut MatchField) {
+    fn handle_tackling_ball_event(
+        player_id: u32,
+        field: &mut MatchField,
+        context: &mut MatchContext,
+    ) {
+        if let Some(player) = field.get_player_mut(player_id) {
+            player.statistics.add_tackle();
+        }
+
+        let tackled_id = field.ball.current_owner.filter(|id| *id != player_id);
+
         field.ball.previous_owner = field.ball.current_owner;
         field.ball.current_owner = Some(player_id);
+
+        // the player losing the ball may get hurt in the challenge
+        if let Some(tackled_id) = tackled_id {
+            Self::try_injure(tackled_id, TACKLE_INJURY_CHANCE, field, context);
+        }
     }
 
     fn handle_ball_owner_change_event(player_id: u32, field: &mut MatchField) {
@@ -133,7 +179,36 @@ impl PlayerEventDispatcher {
         field.ball.current_owner = Some(player_id);
     }
 
-    fn handle_pass_to_event(event_model: PassingEventContext, field: &mut MatchField) {
+    fn handle_pass_to_event(
+        event_model: PassingEventContext,
+        field: &mut MatchField,
+        context: &mut MatchContext,
+    ) {
+        if let Some(player) = field.get_player_mut(event_model.from_player_id) {
+            player.statistics.add_pass();
+        }
+
+        field.ball.last_touched_by = Some(event_model.from_player_id);
+
+        let is_offside_exempt = context
+            .set_piece
+            .as_ref()
+            .is_some_and(|set_piece| set_piece.set_piece_type.is_offside_exempt());
+
+        if is_offside_exempt {
+            // no offside straight from a throw-in, corner or goal kick
+        } else if let Some(offside) = Offside::check(
+            event_model.from_player_id,
+            event_model.to_player_id,
+            field,
+            context,
+        ) {
+            Self::handle_offside(offside, field, context);
+            return;
+        }
+
+        context.analytics.pass_played(event_model.from_player_id);
+
         let ball_pass_vector = event_model.pass_target - field.ball.position;
         let direction = ball_pass_vector.normalize();
         let pass_force = event_model.pass_force;
@@ -170,7 +245,47 @@ impl PlayerEventDispatcher {
         field.ball.flags.in_flight_state = 100;
     }
 
-    fn handle_shoot_event(shoot_event_model: ShootingEventContext, field: &mut MatchField) {
+    fn handle_shoot_event(
+        shoot_event_model: ShootingEventContext,
+        field: &mut MatchField,
+        context: &mut MatchContext,
+    ) {
+        // kicks of a shootout aren't part of the match statistics
+        if context.state.match_state != MatchState::PenaltyShootout {
+            let is_penalty = context
+                .set_piece
+                .as_ref()
+                .is_some_and(|set_piece| set_piece.set_piece_type == SetPieceType::Penalty);
+
+            let shot = Shot::new(
+                field.ball.position,
+                shoot_event_model.target,
+                &context.goal_positions,
+                &field.size,
+                is_penalty,
+            );
+
+            if let Some(player) = field.get_player_mut(shoot_event_model.from_player_id) {
+                player.statistics.add_shot();
+                player.statistics.add_shot_quality(&shot);
+            }
+
+            context.analytics.shot_taken(shoot_event_model.from_player_id, is_penalty);
+
+            if shot.is_on_target {
+                context.analytics.shot_on_target(shoot_event_model.from_player_id);
+            }
+
+            context.timeline.add(
+                context.time.time,
+                TimelineEventType::Shot {
+                    player_id: shoot_event_model.from_player_id,
+                    is_on_target: shot.is_on_target,
+                    expected_goals: shot.expected_goals,
+                },
+            );
+        }
+
         let ball_pass_vector = shoot_event_model.target - field.ball.position;
         let direction = ball_pass_vector.normalize();
 
@@ -178,6 +293,7 @@ impl PlayerEventDispatcher {
         let initial_velocity = direction * shoot_event_model.force as f32;
 
         field.ball.previous_owner = Some(shoot_event_model.from_player_id);
+        field.ball.last_touched_by = Some(shoot_event_model.from_player_id);
         field.ball.current_owner = None;
         field.ball.velocity = initial_velocity;
 
@@ -191,12 +307,309 @@ impl PlayerEventDispatcher {
     }
 
     fn handle_move_player_event(player_id: u32, position: Vector3<f32>, field: &mut MatchField) {
-        let player = field.get_player_mut(player_id).unwrap();
-        player.position = position;
+        if let Some(player) = field.get_player_mut(player_id) {
+            player.position = position;
+        }
     }
 
     fn handle_take_ball_event(player_id: u32, field: &mut MatchField) {
-        let player = field.get_player_mut(player_id).unwrap();
-        player.run_for_ball();
+        if let Some(player) = field.get_player_mut(player_id) {
+            player.run_for_ball();
+        }
+    }
+
+    fn handle_commit_foul_event(
+        offender_id: u32,
+        fouled_player_id: u32,
+        field: &mut MatchField,
+        context: &mut MatchContext,
+    ) {
+        let foul = match Foul::new(offender_id, fouled_player_id, field, context) {
+            Some(foul) => foul,
+            None => return,
+        };
+
+        let decision = context.referee.judge(&foul);
+
+        debug!("Referee decision: {:?} for {:?}", decision, foul);
+
+        if let Some(offender) = field.get_player_mut(offender_id) {
+            offender.statistics.add_foul();
+
+            match decision.card {
+                Some(Card::Yellow) => offender.statistics.add_yellow_card(context.time.time),
+                Some(Card::SecondYellow) => {
+                    offender.statistics.add_yellow_card(context.time.time);
+                    offender.statistics.add_red_card(context.time.time);
+                }
+                Some(Card::Red) => offender.statistics.add_red_card(context.time.time),
+                None => {}
+            }
+        }
+
+        let time = context.time.time;
+
+        context.timeline.add(
+            time,
+            TimelineEventType::Foul {
+                offender_id,
+                fouled_player_id,
+            },
+        );
+
+        match decision.card {
+            Some(Card::Yellow) => context
+                .timeline
+                .add(time, TimelineEventType::YellowCard { player_id: offender_id }),
+            Some(Card::SecondYellow) => {
+                context
+                    .timeline
+                    .add(time, TimelineEventType::YellowCard { player_id: offender_id });
+                context.timeline.add(
+                    time,
+                    TimelineEventType::RedCard {
+                        player_id: offender_id,
+                        is_second_yellow: true,
+                    },
+                );
+            }
+            Some(Card::Red) => context.timeline.add(
+                time,
+                TimelineEventType::RedCard {
+                    player_id: offender_id,
+                    is_second_yellow: false,
+                },
+            ),
+            None => {}
+        }
+
+        if decision.card.is_some_and(|card| card.is_sending_off()) {
+            field.send_off(offender_id);
+        }
+
+        // reckless fouls hurt more often
+        Self::try_injure(
+            fouled_player_id,
+            FOUL_INJURY_CHANCE * (0.5 + foul.aggression),
+            field,
+            context,
+        );
+
+        let set_piece = match decision.free_kick {
+            Some(FreeKickType::Penalty) => Self::penalty(&foul, field, context),
+            Some(FreeKickType::Direct) => SetPiece::for_team_of(
+                SetPieceType::DirectFreeKick,
+                fouled_player_id,
+                foul.position,
+                field,
+            ),
+            Some(FreeKickType::Indirect) => SetPiece::for_team_of(
+                SetPieceType::IndirectFreeKick,
+                fouled_player_id,
+                foul.position,
+                field,
+            ),
+            None => None,
+        };
+
+        if let Some(set_piece) = set_piece {
+            if set_piece.set_piece_type == SetPieceType::Penalty {
+                context.timeline.add(
+                    time,
+                    TimelineEventType::PenaltyAwarded {
+                        team_id: set_piece.team_id,
+                    },
+                );
+            }
+
+            SetPieceProcessor::start(set_piece, field, context);
+        }
+    }
+
+    fn try_injure(
+        player_id: u32,
+        base_chance: f32,
+        field: &mut MatchField,
+        context: &mut MatchContext,
+    ) {
+        let is_injured = field
+            .players
+            .iter()
+            .find(|p| p.id == player_id)
+            .is_some_and(|p| p.is_injured_by(base_chance));
+
+        if is_injured {
+            debug!("Player {} injured", player_id);
+
+            field.injure(player_id, InjuryCause::Contact, context.time.time);
+
+            context
+                .timeline
+                .add(context.time.time, TimelineEventType::Injury { player_id });
+        }
+    }
+
+    // Play stops before the pass arrives, the defending team takes an indirect
+    // free kick from where the offside player stood
+    fn handle_offside(offside: Offside, field: &mut MatchField, context: &mut MatchContext) {
+        debug!("Offside: {:?}", offside);
+
+        if let Some(player) = field.get_player_mut(offside.player_id) {
+            player.statistics.add_offside();
+        }
+
+        context.timeline.add(
+            context.time.time,
+            TimelineEventType::Offside {
+                player_id: offside.player_id,
+            },
+        );
+
+        let defender_id = field
+            .players
+            .iter()
+            .find(|p| p.team_id != offside.team_id)
+            .map(|p| p.id);
+
+        if let Some(set_piece) = defender_id.and_then(|id| {
+            SetPiece::for_team_of(SetPieceType::IndirectFreeKick, id, offside.position, field)
+        }) {
+            SetPieceProcessor::start(set_piece, field, context);
+        }
+    }
+
+    // Penalty from the spot in front of the offender's goal
+    fn penalty(foul: &Foul, field: &MatchField, context: &MatchContext) -> Option<SetPiece> {
+        let fouled_player = field
+            .players
+            .iter()
+            .find(|p| p.id == foul.fouled_player_id)?;
+
+        Some(SetPiece::penalty(
+            fouled_player.team_id,
+            foul.offender_side,
+            context,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::testing::{match_at, squad};
+    use crate::r#match::goalkeepers::states::state::GoalkeeperState;
+    use crate::r#match::player::state::PlayerState;
+    use crate::r#match::statistics::MatchPlayerResult;
+    use crate::r#match::PENALTY_SPOT_DISTANCE;
+    use crate::r#match::Referee;
+
+    // Left team defends x = 0, the right team's striker is brought down by the
+    // left team's defender with the rest of the left team upfield
+    fn setup(fouled_player_position: Vector3<f32>) -> (MatchField, MatchContext) {
+        let (mut field, mut context) = match_at(squad(1), squad(2), 0);
+
+        context.referee = Referee::new(10.0);
+
+        for player in field.players.iter_mut().filter(|p| p.team_id == 1) {
+            player.position = Vector3::new(800.0, 272.0, 0.0);
+        }
+
+        field.get_player_mut(101).unwrap().position = fouled_player_position;
+        field.get_player_mut(100).unwrap().position = Vector3::new(0.0, 272.0, 0.0);
+        field.get_player_mut(210).unwrap().position = fouled_player_position;
+        field.ball.current_owner = Some(210);
+
+        (field, context)
+    }
+
+    #[test]
+    fn last_man_foul_sends_offender_off() {
+        let (mut field, mut context) = setup(Vector3::new(100.0, 272.0, 0.0));
+
+        PlayerEventDispatcher::dispatch(
+            PlayerEvent::CommitFoul(101, 210),
+            &mut field,
+            &mut context,
+        );
+
+        assert!(field.get_player_mut(101).is_none());
+        assert_eq!(1, field.sent_off.len());
+
+        let results = MatchPlayerResult::from_players(
+            field.players.iter().chain(field.sent_off.iter()),
+            &context.score,
+            90,
+            &context.analytics,
+        );
+
+        let offender = results.iter().find(|r| r.player_id == 101).unwrap();
+
+        assert_eq!(1, offender.red_cards);
+        assert_eq!(1, offender.fouls);
+        assert_eq!(0, offender.minutes);
+    }
+
+    #[test]
+    fn foul_in_penalty_area_awards_penalty() {
+        let (mut field, mut context) = setup(Vector3::new(20.0, 272.0, 0.0));
+
+        PlayerEventDispatcher::dispatch(
+            PlayerEvent::CommitFoul(101, 210),
+            &mut field,
+            &mut context,
+        );
+
+        let taker_id = field.ball.current_owner.unwrap();
+        let taker = field.players.iter().find(|p| p.id == taker_id).unwrap();
+
+        assert_eq!(2, taker.team_id);
+        let goal = context.goal_positions.left;
+
+        assert_eq!(
+            Vector3::new(goal.x + PENALTY_SPOT_DISTANCE, goal.y, 0.0),
+            field.ball.position
+        );
+        assert_eq!(
+            PlayerState::Goalkeeper(GoalkeeperState::PenaltySave),
+            field.get_player_mut(100).unwrap().state
+        );
+
+        // the penalty restores the chance, so it's a booking only
+        assert_eq!(
+            1,
+            field.get_player_mut(101).unwrap().statistics.yellow_cards()
+        );
+    }
+
+    #[test]
+    fn pass_to_player_behind_defence_is_offside() {
+        let (mut field, mut context) = setup(Vector3::new(100.0, 272.0, 0.0));
+
+        field.get_player_mut(209).unwrap().position = Vector3::new(300.0, 272.0, 0.0);
+        field.get_player_mut(210).unwrap().position = Vector3::new(80.0, 272.0, 0.0);
+        field.ball.current_owner = Some(209);
+        field.ball.position = Vector3::new(300.0, 272.0, 0.0);
+
+        let pass = PassingEventContext::build()
+            .with_from_player_id(209)
+            .with_to_player_id(210)
+            .with_target(Vector3::new(80.0, 272.0, 0.0))
+            .with_force(1.0)
+            .build();
+
+        PlayerEventDispatcher::dispatch(PlayerEvent::PassTo(pass), &mut field, &mut context);
+
+        assert_eq!(1, field.get_player_mut(210).unwrap().statistics.offsides);
+        assert_eq!(1, field.get_player_mut(209).unwrap().statistics.passes);
+
+        // the defending team restarts from where the offside player stood
+        let set_piece = context.set_piece.as_ref().unwrap();
+        let taker_id = field.ball.current_owner.unwrap();
+
+        assert_eq!(SetPieceType::IndirectFreeKick, set_piece.set_piece_type);
+        assert_eq!(Some(taker_id), set_piece.taker_id);
+        assert_eq!(1, field.get_player_mut(taker_id).unwrap().team_id);
+        assert_eq!(Vector3::new(80.0, 272.0, 0.0), field.ball.position);
+        assert_eq!(Vector3::zeros(), field.ball.velocity);
     }
 }
@@ -7,13 +7,19 @@ use crate::r#match::player::state::{PlayerMatchState, PlayerState};
 use crate::r#match::player::statistics::MatchPlayerStatistics;
 use crate::r#match::{GameTickContext, MatchContext, StateProcessingContext};
 use crate::{
-    PersonAttributes, Player, PlayerAttributes, PlayerFieldPositionGroup, PlayerPositionType,
-    PlayerSkills,
+    InjuryCause, InjuryRisk, PersonAttributes, Player, PlayerAttributes,
+    PlayerFieldPositionGroup, PlayerPositionType, PlayerSkills,
 };
 use nalgebra::Vector3;
 use std::fmt::*;
 use crate::r#match::engine::tactics::{TacticalPositions};
 
+const MAX_FATIGUE_PER_MINUTE: f32 = 60.0;
+const FATIGUE_RESISTANCE: f32 = 0.8;
+
+// the club's medical staff isn't known on the pitch, an average one is assumed
+const MATCH_SPORTS_SCIENCE: u8 = 10;
+
 #[derive(Debug, Clone)]
 pub struct MatchPlayer {
     pub id: u32,
@@ -23,6 +29,8 @@ pub struct MatchPlayer {
     pub team_id: u32,
     pub player_attributes: PlayerAttributes,
     pub skills: PlayerSkills,
+    // average rating this season, zero before the first match
+    pub form: f32,
     pub tactical_position: TacticalPositions,
     pub velocity: Vector3<f32>,
     pub side: Option<PlayerSide>,
@@ -53,6 +61,7 @@ impl MatchPlayer {
             team_id,
             player_attributes: player.player_attributes,
             skills: player.skills,
+            form: player.statistics.average_rating,
             velocity: Vector3::new(0.0, 0.0, 0.0),
             tactical_position: TacticalPositions::new(position),
             side: None,
@@ -91,8 +100,48 @@ impl MatchPlayer {
         }
     }
 
+    // Condition lost in a minute of play, fit players with stamina tire slower
+    pub fn tire(&mut self) {
+        let physical = &self.skills.physical;
+
+        let fatigue = (MAX_FATIGUE_PER_MINUTE
+            - (physical.stamina + physical.natural_fitness) * FATIGUE_RESISTANCE)
+            .max(0.0);
+
+        self.player_attributes.condition =
+            (self.player_attributes.condition - fatigue as i16).max(0);
+    }
+
+    // Rolls for an injury at a challenge or a minute of running
+    pub fn is_injured_by(&self, base_chance: f32) -> bool {
+        !self.player_attributes.is_injured
+            && InjuryRisk::is_injured(
+                base_chance,
+                &self.player_attributes,
+                self.skills.physical.natural_fitness,
+                MATCH_SPORTS_SCIENCE,
+            )
+    }
+
+    // A hurt player goes down and stays there until replaced
+    pub fn injure(&mut self, cause: InjuryCause, time: u64) {
+        if self.player_attributes.is_injured {
+            return;
+        }
+
+        self.player_attributes.is_injured = true;
+        self.statistics.add_injury(cause, time);
+
+        self.velocity = Vector3::zeros();
+        self.state = PlayerState::Injured;
+    }
+
     pub fn set_default_state(&mut self) {
-        self.state = Self::default_state(self.tactical_position.current_position);
+        self.state = if self.player_attributes.is_injured {
+            PlayerState::Injured
+        } else {
+            Self::default_state(self.tactical_position.current_position)
+        };
     }
 
     fn default_state(position: PlayerPositionType) -> PlayerState {
@@ -1,15 +1,100 @@
+use crate::r#match::{MatchAnalytics, MatchPlayer, Score, Shot};
+use crate::{InjuryCause, PlayerFieldPositionGroup};
+use serde::{Deserialize, Serialize};
+
+const BASE_RATING: f32 = 6.0;
+const MIN_RATING: f32 = 1.0;
+const MAX_RATING: f32 = 10.0;
+
 #[derive(Debug, Clone)]
 pub struct MatchPlayerStatistics {
     pub items: Vec<MatchPlayerStatisticsItem>,
+    pub shots: u16,
+    pub shots_on_target: u16,
+    pub expected_goals: f32,
+    pub passes: u16,
+    pub passes_completed: u16,
+    pub tackles: u16,
+    pub interceptions: u16,
+    pub fouls: u16,
+    pub offsides: u16,
+    pub penalty_goals: u8,
+    pub injury: Option<InjuryCause>,
 }
 
 impl MatchPlayerStatistics {
     pub fn new() -> Self {
         MatchPlayerStatistics {
             items: Vec::with_capacity(5),
+            shots: 0,
+            shots_on_target: 0,
+            expected_goals: 0.0,
+            passes: 0,
+            passes_completed: 0,
+            tackles: 0,
+            interceptions: 0,
+            fouls: 0,
+            offsides: 0,
+            penalty_goals: 0,
+            injury: None,
         }
     }
 
+    pub fn count(&self, stat_type: MatchStatisticType) -> u8 {
+        self.items
+            .iter()
+            .filter(|item| item.stat_type == stat_type)
+            .count() as u8
+    }
+
+    pub fn add_shot(&mut self) {
+        self.shots += 1;
+    }
+
+    pub fn add_shot_quality(&mut self, shot: &Shot) {
+        self.expected_goals += shot.expected_goals;
+
+        if shot.is_on_target {
+            self.shots_on_target += 1;
+        }
+    }
+
+    pub fn add_pass(&mut self) {
+        self.passes += 1;
+    }
+
+    pub fn add_completed_pass(&mut self) {
+        self.passes_completed += 1;
+    }
+
+    pub fn add_tackle(&mut self) {
+        self.tackles += 1;
+    }
+
+    pub fn add_interception(&mut self) {
+        self.interceptions += 1;
+    }
+
+    pub fn add_foul(&mut self) {
+        self.fouls += 1;
+    }
+
+    pub fn add_offside(&mut self) {
+        self.offsides += 1;
+    }
+
+    pub fn add_penalty_goal(&mut self) {
+        self.penalty_goals += 1;
+    }
+
+    pub fn yellow_cards(&self) -> u8 {
+        self.count(MatchStatisticType::YellowCard)
+    }
+
+    pub fn red_cards(&self) -> u8 {
+        self.count(MatchStatisticType::RedCard)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.items.is_empty()
     }
@@ -27,6 +112,73 @@ impl MatchPlayerStatistics {
             match_second,
         })
     }
+
+    pub fn add_yellow_card(&mut self, match_second: u64) {
+        self.items.push(MatchPlayerStatisticsItem {
+            stat_type: MatchStatisticType::YellowCard,
+            match_second,
+        })
+    }
+
+    pub fn add_red_card(&mut self, match_second: u64) {
+        self.items.push(MatchPlayerStatisticsItem {
+            stat_type: MatchStatisticType::RedCard,
+            match_second,
+        })
+    }
+
+    pub fn add_substituted_on(&mut self, match_second: u64) {
+        self.items.push(MatchPlayerStatisticsItem {
+            stat_type: MatchStatisticType::SubstitutedOn,
+            match_second,
+        })
+    }
+
+    pub fn add_injury(&mut self, cause: InjuryCause, match_second: u64) {
+        self.injury = Some(cause);
+
+        self.items.push(MatchPlayerStatisticsItem {
+            stat_type: MatchStatisticType::Injury,
+            match_second,
+        })
+    }
+
+    pub fn add_substituted_off(&mut self, match_second: u64) {
+        self.items.push(MatchPlayerStatisticsItem {
+            stat_type: MatchStatisticType::SubstitutedOff,
+            match_second,
+        })
+    }
+
+    // Minute of the sending-off, if any
+    pub fn sent_off_minute(&self) -> Option<u8> {
+        self.minute_of(MatchStatisticType::RedCard)
+    }
+
+    pub fn is_substitute(&self) -> bool {
+        self.count(MatchStatisticType::SubstitutedOn) > 0
+    }
+
+    // From coming on, or the kick-off, until going off or the final whistle
+    pub fn minutes_played(&self, match_minutes: u8) -> u8 {
+        let on = self
+            .minute_of(MatchStatisticType::SubstitutedOn)
+            .unwrap_or(0);
+
+        let off = self
+            .sent_off_minute()
+            .or_else(|| self.minute_of(MatchStatisticType::SubstitutedOff))
+            .unwrap_or(match_minutes);
+
+        off.saturating_sub(on)
+    }
+
+    fn minute_of(&self, stat_type: MatchStatisticType) -> Option<u8> {
+        self.items
+            .iter()
+            .find(|item| item.stat_type == stat_type)
+            .map(|item| (item.match_second / 60_000) as u8)
+    }
 }
 
 impl Default for MatchPlayerStatistics {
@@ -41,10 +193,150 @@ pub struct MatchPlayerStatisticsItem {
     pub match_second: u64,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum MatchStatisticType {
     Goal,
     Assist,
+    YellowCard,
+    RedCard,
+    SubstitutedOn,
+    SubstitutedOff,
+    Injury,
+}
+
+// Final line of a player in a played match
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MatchPlayerResult {
+    pub player_id: u32,
+    pub team_id: u32,
+    pub minutes: u8,
+    pub substitute: bool,
+    pub goals: u8,
+    // goals from penalties, already counted in the goals
+    pub penalty_goals: u8,
+    pub assists: u8,
+    pub shots: u16,
+    pub shots_on_target: u16,
+    pub expected_goals: f32,
+    pub passes: u16,
+    pub passes_completed: u16,
+    pub tackles: u16,
+    pub interceptions: u16,
+    pub fouls: u16,
+    pub offsides: u16,
+    // meters
+    pub distance_covered: f32,
+    pub yellow_cards: u8,
+    pub red_cards: u8,
+    pub rating: f32,
+    pub player_of_the_match: bool,
+    pub injury: Option<InjuryCause>,
+}
+
+impl MatchPlayerResult {
+    pub fn from_players<'p>(
+        players: impl Iterator<Item = &'p MatchPlayer>,
+        score: &Score,
+        minutes: u8,
+        analytics: &MatchAnalytics,
+    ) -> Vec<Self> {
+        let mut results: Vec<MatchPlayerResult> = players
+            .map(|player| {
+                let (scored, conceded) = if player.team_id == score.home_team.team_id {
+                    (score.home_team.get(), score.away_team.get())
+                } else {
+                    (score.away_team.get(), score.home_team.get())
+                };
+
+                let mut result = MatchPlayerResult {
+                    player_id: player.id,
+                    team_id: player.team_id,
+                    minutes: player.statistics.minutes_played(minutes),
+                    substitute: player.statistics.is_substitute(),
+                    goals: player.statistics.count(MatchStatisticType::Goal),
+                    penalty_goals: player.statistics.penalty_goals,
+                    assists: player.statistics.count(MatchStatisticType::Assist),
+                    shots: player.statistics.shots,
+                    shots_on_target: player.statistics.shots_on_target,
+                    expected_goals: player.statistics.expected_goals,
+                    passes: player.statistics.passes,
+                    passes_completed: player.statistics.passes_completed,
+                    tackles: player.statistics.tackles,
+                    interceptions: player.statistics.interceptions,
+                    fouls: player.statistics.fouls,
+                    offsides: player.statistics.offsides,
+                    distance_covered: analytics
+                        .movement(player.id)
+                        .map_or(0.0, |m| m.distance),
+                    yellow_cards: player.statistics.yellow_cards(),
+                    red_cards: player.statistics.red_cards(),
+                    rating: 0.0,
+                    player_of_the_match: false,
+                    injury: player.statistics.injury,
+                };
+
+                result.rating = result.calculate_rating(
+                    player.tactical_position.current_position.position_group(),
+                    scored,
+                    conceded,
+                );
+
+                result
+            })
+            .collect();
+
+        let best = results
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| {
+                a.rating
+                    .total_cmp(&b.rating)
+                    .then(b.player_id.cmp(&a.player_id))
+            })
+            .map(|(idx, _)| idx);
+
+        if let Some(idx) = best {
+            results[idx].player_of_the_match = true;
+        }
+
+        results
+    }
+
+    fn calculate_rating(
+        &self,
+        position_group: PlayerFieldPositionGroup,
+        scored: u8,
+        conceded: u8,
+    ) -> f32 {
+        let mut rating = BASE_RATING
+            + self.goals as f32
+            + self.assists as f32 * 0.6
+            + self.shots as f32 * 0.1
+            + self.tackles as f32 * 0.1
+            + self.passes as f32 * 0.01
+            - self.yellow_cards as f32 * 0.3
+            - self.red_cards as f32 * 1.5;
+
+        rating += match scored.cmp(&conceded) {
+            std::cmp::Ordering::Greater => 0.4,
+            std::cmp::Ordering::Equal => 0.0,
+            std::cmp::Ordering::Less => -0.4,
+        };
+
+        // goals against weigh on the defensive line only
+        match position_group {
+            PlayerFieldPositionGroup::Goalkeeper | PlayerFieldPositionGroup::Defender => {
+                if conceded == 0 {
+                    rating += 0.5;
+                }
+
+                rating -= conceded as f32 * 0.25;
+            }
+            _ => {}
+        }
+
+        rating.clamp(MIN_RATING, MAX_RATING)
+    }
 }
 
 #[cfg(test)]
@@ -80,6 +372,77 @@ mod tests {
         assert!(!stats.is_empty());
     }
 
+    #[test]
+    fn test_count_and_counters() {
+        let mut stats = MatchPlayerStatistics::new();
+        stats.add_goal(10);
+        stats.add_goal(20);
+        stats.add_assist(30);
+        stats.add_shot();
+        stats.add_pass();
+        stats.add_pass();
+        stats.add_tackle();
+        stats.add_foul();
+        stats.add_offside();
+        stats.add_yellow_card(40);
+
+        assert_eq!(2, stats.count(MatchStatisticType::Goal));
+        assert_eq!(1, stats.count(MatchStatisticType::Assist));
+        assert_eq!(1, stats.shots);
+        assert_eq!(2, stats.passes);
+        assert_eq!(1, stats.tackles);
+        assert_eq!(1, stats.fouls);
+        assert_eq!(1, stats.offsides);
+        assert_eq!(1, stats.yellow_cards());
+        assert_eq!(0, stats.red_cards());
+        assert_eq!(None, stats.sent_off_minute());
+
+        stats.add_red_card(61 * 60_000);
+
+        assert_eq!(Some(61), stats.sent_off_minute());
+        assert_eq!(61, stats.minutes_played(90));
+    }
+
+    #[test]
+    fn test_minutes_played_by_substitutes() {
+        let mut replaced = MatchPlayerStatistics::new();
+        replaced.add_substituted_off(70 * 60_000);
+
+        let mut substitute = MatchPlayerStatistics::new();
+        substitute.add_substituted_on(70 * 60_000);
+
+        assert_eq!(70, replaced.minutes_played(90));
+        assert!(!replaced.is_substitute());
+        assert_eq!(20, substitute.minutes_played(90));
+        assert!(substitute.is_substitute());
+
+        substitute.add_red_card(85 * 60_000);
+
+        assert_eq!(15, substitute.minutes_played(90));
+    }
+
+    #[test]
+    fn test_rating_rewards_goals_and_clean_sheets() {
+        let result = |goals: u8| MatchPlayerResult {
+            player_id: 1,
+            team_id: 1,
+            minutes: 90,
+            goals,
+            ..Default::default()
+        };
+
+        let scorer = result(2).calculate_rating(PlayerFieldPositionGroup::Forward, 2, 0);
+        let keeper = result(0).calculate_rating(PlayerFieldPositionGroup::Goalkeeper, 2, 0);
+        let beaten_keeper = result(0).calculate_rating(PlayerFieldPositionGroup::Goalkeeper, 0, 5);
+
+        assert!(scorer > keeper);
+        assert!(keeper > BASE_RATING);
+        assert!(beaten_keeper < BASE_RATING);
+        assert!(
+            result(30).calculate_rating(PlayerFieldPositionGroup::Forward, 30, 0) <= MAX_RATING
+        );
+    }
+
     #[test]
     fn test_is_empty() {
         let stats = MatchPlayerStatistics::new();
@@ -3,7 +3,8 @@ use crate::r#match::{
     MatchPlayer, MatchPlayerLite, PlayerDistanceFromStartPosition, PlayerSide,
     StateProcessingContext,
 };
-use crate::PlayerSkills;
+use crate::{PlayerSkills, TacticalRole};
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::Rng;
 
@@ -36,6 +37,11 @@ impl<'p> PlayerOperationsImpl<'p> {
         }
     }
 
+    // What the coach asks of the player in their position
+    pub fn role(&self) -> TacticalRole {
+        self.ctx.player.tactical_position.role
+    }
+
     pub fn skills(&self, player_id: u32) -> &PlayerSkills {
         let player = self.ctx.context.players.by_id(player_id).unwrap();
         &player.skills
@@ -63,7 +69,7 @@ impl<'p> PlayerOperationsImpl<'p> {
         // Calculate the maximum deviation in the y-direction based on the goal height and player skills
         let max_y_deviation = goal_height * 0.15 * (1.0 - technique_factor * accuracy_factor);
 
-        let mut rng = rand::thread_rng();
+        let mut rng = RandomGenerator::current();
         let y_offset = rng.gen_range(-max_y_deviation..max_y_deviation);
 
         let mut shooting_target = goal_position;
@@ -113,7 +119,7 @@ impl<'p> PlayerOperationsImpl<'p> {
 
         let base_power = min_power + (max_power - min_power) * pass_skill * distance_factor;
 
-        let random_factor = rand::thread_rng().gen_range(0.9..1.1);
+        let random_factor = RandomGenerator::current().gen_range(0.9..1.1);
 
         let pass_power = base_power * random_factor;
 
@@ -1,5 +1,5 @@
 use crate::r#match::{PlayerSide, StateProcessingContext};
-use crate::Tactics;
+use crate::{Tactics, TeamInstructions};
 
 pub struct TeamOperationsImpl<'b> {
     ctx: &'b StateProcessingContext<'b>,
@@ -26,6 +26,10 @@ impl<'b> TeamOperationsImpl<'b> {
         }
     }
 
+    pub fn instructions(&self) -> &TeamInstructions {
+        &self.tactics().instructions
+    }
+
     pub fn is_control_ball(&self) -> bool {
         let current_player_team_id = self.ctx.player.team_id;
 
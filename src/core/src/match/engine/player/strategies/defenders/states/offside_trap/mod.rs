@@ -1,5 +1,6 @@
 use crate::r#match::defenders::states::DefenderState;
 use crate::r#match::{ConditionContext, MatchPlayer, MatchPlayerLite, PlayerSide, StateChangeResult, StateProcessingContext, StateProcessingHandler};
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::Rng;
 
@@ -149,7 +150,7 @@ impl DefenderOffsideTrapState {
         // Calculate the success probability based on teamwork and concentration
         let teamwork = ctx.player.skills.mental.teamwork as f32 / 20.0;
         let concentration = ctx.player.skills.mental.concentration as f32 / 20.0;
-        let mut rng = rand::thread_rng();
+        let mut rng = RandomGenerator::current();
         let success_probability = (teamwork + concentration) / 2.0;
 
         // Determine the offside trap outcome
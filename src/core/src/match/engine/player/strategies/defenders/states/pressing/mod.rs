@@ -34,7 +34,9 @@ impl StateProcessingHandler for DefenderPressingState {
             }
 
             // 5. If the opponent is too far away, stop pressing
-            if distance_to_opponent > PRESSING_DISTANCE_THRESHOLD {
+            if distance_to_opponent
+                > PRESSING_DISTANCE_THRESHOLD * ctx.team().instructions().pressing.distance_factor()
+            {
                 // Transition back to HoldingLine or appropriate state
                 return Some(StateChangeResult::with_defender_state(
                     DefenderState::HoldingLine,
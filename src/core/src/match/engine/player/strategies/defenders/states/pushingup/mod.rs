@@ -84,7 +84,9 @@ impl StateProcessingHandler for DefenderPushingUpState {
 impl DefenderPushingUpState {
     fn should_retreat(&self, ctx: &StateProcessingContext) -> bool {
         let field_width = ctx.context.field_size.width as f32;
-        let max_push_up_x = field_width * (MAX_PUSH_UP_DISTANCE + PUSH_UP_HYSTERESIS);
+        let max_push_up = MAX_PUSH_UP_DISTANCE
+            * ctx.team().instructions().defensive_line.push_up_factor();
+        let max_push_up_x = field_width * (max_push_up + PUSH_UP_HYSTERESIS);
 
         ctx.player.position.x > max_push_up_x || self.is_last_defender(ctx)
     }
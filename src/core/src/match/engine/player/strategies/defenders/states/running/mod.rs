@@ -88,7 +88,14 @@ impl StateProcessingHandler for DefenderRunningState {
 
 impl DefenderRunningState {
     pub fn should_clear(&self, ctx: &StateProcessingContext) -> bool {
-        ctx.ball().in_own_penalty_area() && ctx.players().opponents().exists(100.0)
+        // ball-playing defenders only clear when an opponent is right on them
+        let pressure_distance = if ctx.player().role().role.plays_out_from_back() {
+            30.0
+        } else {
+            100.0
+        };
+
+        ctx.ball().in_own_penalty_area() && ctx.players().opponents().exists(pressure_distance)
     }
 
     pub fn should_pass(&self, ctx: &StateProcessingContext) -> bool {
@@ -2,6 +2,7 @@ use crate::r#match::defenders::states::DefenderState;
 use crate::r#match::events::Event;
 use crate::r#match::player::events::PlayerEvent;
 use crate::r#match::{ConditionContext, MatchPlayerLite, StateChangeResult, StateProcessingContext, StateProcessingHandler};
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::Rng;
 
@@ -67,7 +68,7 @@ impl StateProcessingHandler for DefenderSlidingTackleState {
                 // Generate a foul event
                 state_change
                     .events
-                    .add(Event::PlayerEvent(PlayerEvent::CommitFoul));
+                    .add(Event::PlayerEvent(PlayerEvent::CommitFoul(ctx.player.id, opponent.id)));
 
                 // Transition to appropriate state (e.g., ReactingToFoul)
                 // You may need to define additional states for handling fouls
@@ -123,7 +124,7 @@ impl DefenderSlidingTackleState {
         ctx: &StateProcessingContext,
         _opponent: &MatchPlayerLite,
     ) -> (bool, bool) {
-        let mut rng = rand::thread_rng();
+        let mut rng = RandomGenerator::current();
 
         // Get defender's tackling-related skills
         let tackling_skill = ctx.player.skills.technical.tackling  / 20.0; // Normalize to [0,1]
@@ -128,12 +128,13 @@ impl DefenderStandingState {
             < ctx.context.field_size.width as f32 * FIELD_THIRD_THRESHOLD;
         let team_in_possession = ctx.team().is_control_ball();
         let defender_not_last_man = !self.is_last_defender(ctx);
+        let max_distance =
+            ctx.context.field_size.width as f32 * 0.25 * player_ops.role().duty.advance_factor();
 
         ball_in_attacking_third
             && team_in_possession
             && defender_not_last_man
-            && player_ops.distance_from_start_position()
-                < ctx.context.field_size.width as f32 * 0.25
+            && player_ops.distance_from_start_position() < max_distance
     }
 
     fn should_hold_defensive_line(&self, ctx: &StateProcessingContext) -> bool {
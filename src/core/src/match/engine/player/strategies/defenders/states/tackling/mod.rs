@@ -5,6 +5,7 @@ use crate::r#match::{
     ConditionContext, MatchPlayerLite, StateChangeResult,
     StateProcessingContext, StateProcessingHandler, SteeringBehavior,
 };
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::Rng;
 
@@ -41,7 +42,7 @@ impl StateProcessingHandler for DefenderTacklingState {
             } else if committed_foul {
                 return Some(StateChangeResult::with_defender_state_and_event(
                     DefenderState::Standing,
-                    Event::PlayerEvent(PlayerEvent::CommitFoul),
+                    Event::PlayerEvent(PlayerEvent::CommitFoul(ctx.player.id, opponent.id)),
                 ));
             } else {
                 Some(StateChangeResult::with_defender_state(
@@ -85,7 +86,7 @@ impl DefenderTacklingState {
         ctx: &StateProcessingContext,
         opponent: &MatchPlayerLite,
     ) -> (bool, bool) {
-        let mut rng = rand::thread_rng();
+        let mut rng = RandomGenerator::current();
 
         let tackling_skill = ctx.player.skills.technical.tackling / 20.0;
         let aggression = ctx.player.skills.mental.aggression / 20.0;
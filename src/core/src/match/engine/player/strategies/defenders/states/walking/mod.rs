@@ -45,7 +45,11 @@ impl StateProcessingHandler for DefenderWalkingState {
 
         // Press opponent only if they have the ball and are close
         if let Some(opponent_to_press) = ctx.players().opponents().with_ball().next() {
-            if ctx.player.position.distance_to(&opponent_to_press.position) < PRESSING_DISTANCE {
+            let pressing_distance = PRESSING_DISTANCE
+                * ctx.team().instructions().pressing.distance_factor()
+                * ctx.player().role().role.pressing_factor();
+
+            if ctx.player.position.distance_to(&opponent_to_press.position) < pressing_distance {
                 return Some(StateChangeResult::with_defender_state(
                     DefenderState::Pressing,
                 ));
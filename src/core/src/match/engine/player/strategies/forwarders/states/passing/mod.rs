@@ -5,6 +5,7 @@ use crate::r#match::{
     ConditionContext, MatchPlayerLite, PlayerSide, StateChangeResult, StateProcessingContext,
     StateProcessingHandler,
 };
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::prelude::IteratorRandom;
 
@@ -134,7 +135,7 @@ impl ForwardPassingState {
         teammates
             .nearby(300.0)
             .filter(|p| !p.tactical_positions.is_forward() && !p.tactical_positions.is_goalkeeper())
-            .choose(&mut rand::thread_rng())
+            .choose(&mut RandomGenerator::current())
     }
 
     fn find_best_pass_option_defensive_third(
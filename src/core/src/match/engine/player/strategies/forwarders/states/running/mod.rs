@@ -67,7 +67,11 @@ impl StateProcessingHandler for ForwardRunningState {
             if let Some(opponent_with_ball) = ctx.players().opponents().with_ball().next() {
                 let opponent_distance = ctx.player().distance_to_player(opponent_with_ball.id);
 
-                if opponent_distance < PRESSING_DISTANCE_THRESHOLD {
+                let pressing_distance = PRESSING_DISTANCE_THRESHOLD
+                    * ctx.team().instructions().pressing.distance_factor()
+                    * ctx.player().role().role.pressing_factor();
+
+                if opponent_distance < pressing_distance {
                     return Some(StateChangeResult::with_forward_state(
                         ForwardState::Pressing,
                     ));
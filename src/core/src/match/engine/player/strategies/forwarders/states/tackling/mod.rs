@@ -5,6 +5,7 @@ use crate::r#match::{
     ConditionContext, MatchPlayerLite, StateChangeResult, StateProcessingContext,
     StateProcessingHandler,
 };
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::Rng;
 
@@ -56,7 +57,7 @@ impl StateProcessingHandler for ForwardTacklingState {
                 } else if committed_foul {
                     return Some(StateChangeResult::with_forward_state_and_event(
                         ForwardState::Standing,
-                        Event::PlayerEvent(PlayerEvent::CommitFoul),
+                        Event::PlayerEvent(PlayerEvent::CommitFoul(ctx.player.id, opponent.id)),
                     ));
                 }
             }
@@ -87,7 +88,7 @@ impl ForwardTacklingState {
         ctx: &StateProcessingContext,
         opponent: &MatchPlayerLite,
     ) -> (bool, bool) {
-        let mut rng = rand::thread_rng();
+        let mut rng = RandomGenerator::current();
 
         let tackling_skill = ctx.player.skills.technical.tackling / 20.0;
         let aggression = ctx.player.skills.mental.aggression / 20.0;
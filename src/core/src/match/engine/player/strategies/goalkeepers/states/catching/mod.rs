@@ -3,6 +3,8 @@ use crate::r#match::player::events::PlayerEvent;
 use crate::r#match::{
     ConditionContext, StateChangeResult, StateProcessingContext, StateProcessingHandler,
 };
+use crate::utils::RandomGenerator;
+use rand::Rng;
 use nalgebra::Vector3;
 
 #[derive(Default)]
@@ -61,7 +63,7 @@ impl GoalkeeperCatchingState {
         let clamped_catch_probability = catch_probability.clamp(0.0, 1.0);
 
         // Random number between 0 and 1
-        let random_factor = rand::random::<f32>();
+        let random_factor = RandomGenerator::current().gen::<f32>();
 
         clamped_catch_probability > random_factor
     }
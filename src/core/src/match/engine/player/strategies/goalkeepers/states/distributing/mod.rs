@@ -4,6 +4,7 @@ use crate::r#match::player::events::{PassingEventContext, PlayerEvent};
 use crate::r#match::{
     ConditionContext, StateChangeResult, StateProcessingContext, StateProcessingHandler,
 };
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::prelude::IteratorRandom;
 
@@ -59,7 +60,7 @@ impl GoalkeeperDistributingState {
         if let Some((teammate_id, _)) = players
             .teammates()
             .nearby_ids(500.0)
-            .choose(&mut rand::thread_rng())
+            .choose(&mut RandomGenerator::current())
         {
             return Some(teammate_id);
         }
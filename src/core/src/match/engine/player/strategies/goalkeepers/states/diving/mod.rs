@@ -4,6 +4,8 @@ use crate::r#match::player::events::PlayerEvent;
 use crate::r#match::{
     ConditionContext, StateChangeResult, StateProcessingContext, StateProcessingHandler,
 };
+use crate::utils::RandomGenerator;
+use rand::Rng;
 use nalgebra::Vector3;
 
 const DIVE_DURATION: f32 = 1.0; // Duration of dive in seconds
@@ -103,7 +105,7 @@ impl GoalkeeperDivingState {
         let ball_distance = ctx.ball().distance();
         let catch_probability = ctx.player.skills.technical.first_touch / 20.0; // Using first_touch as a proxy for catching skill
 
-        ball_distance < 1.0 && rand::random::<f32>() < catch_probability
+        ball_distance < 1.0 && RandomGenerator::current().gen::<f32>() < catch_probability
     }
 
     fn is_ball_nearby(&self, ctx: &StateProcessingContext) -> bool {
@@ -2,6 +2,7 @@ use crate::r#match::events::Event;
 use crate::r#match::goalkeepers::states::state::GoalkeeperState;
 use crate::r#match::player::events::{PassingEventContext, PlayerEvent};
 use crate::r#match::{ConditionContext, MatchPlayerLite, StateChangeResult, StateProcessingContext, StateProcessingHandler};
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::prelude::IteratorRandom;
 
@@ -71,7 +72,7 @@ impl GoalkeeperPassingState {
                 })
                 .cloned()
         } else {
-            teammates.nearby(300.0).choose(&mut rand::thread_rng())
+            teammates.nearby(300.0).choose(&mut RandomGenerator::current())
         }
     }
 
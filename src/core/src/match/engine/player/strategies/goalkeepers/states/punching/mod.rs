@@ -3,6 +3,8 @@ use crate::r#match::player::events::PlayerEvent;
 use crate::r#match::{
     ConditionContext, StateChangeResult, StateProcessingContext, StateProcessingHandler,
 };
+use crate::utils::RandomGenerator;
+use rand::Rng;
 use nalgebra::Vector3;
 
 const PUNCHING_DISTANCE_THRESHOLD: f32 = 2.0; // Maximum distance to attempt punching
@@ -21,7 +23,7 @@ impl StateProcessingHandler for GoalkeeperPunchingState {
         }
 
         // 2. Attempt to punch the ball
-        let punch_success = rand::random::<f32>() < PUNCH_SUCCESS_PROBABILITY;
+        let punch_success = RandomGenerator::current().gen::<f32>() < PUNCH_SUCCESS_PROBABILITY;
         if punch_success {
             // Punch is successful
             let mut state_change =
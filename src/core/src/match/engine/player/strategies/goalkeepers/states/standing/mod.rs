@@ -19,7 +19,7 @@ impl StateProcessingHandler for GoalkeeperStandingState {
             ));
         }
         else {
-            if ctx.ball().distance() < 150.0 {
+            if ctx.ball().distance() < 150.0 * ctx.player().role().role.coming_out_factor() {
                 return Some(StateChangeResult::with_goalkeeper_state(
                     GoalkeeperState::ComingOut
                 ));
@@ -5,6 +5,7 @@ use crate::r#match::{
     ConditionContext, StateChangeResult, StateProcessingContext,
     StateProcessingHandler,
 };
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::Rng;
 
@@ -62,7 +63,7 @@ impl StateProcessingHandler for GoalkeeperTacklingState {
                 // Generate a foul event
                 state_change
                     .events
-                    .add_player_event(PlayerEvent::CommitFoul);
+                    .add_player_event(PlayerEvent::CommitFoul(ctx.player.id, opponent.id));
 
                 // Transition to appropriate state (e.g., ReactingToFoul)
                 // You may need to define additional states for handling fouls
@@ -113,7 +114,7 @@ impl StateProcessingHandler for GoalkeeperTacklingState {
 impl GoalkeeperTacklingState {
     /// Attempts a tackle and returns whether it was successful and if a foul was committed.
     fn attempt_tackle(&self, ctx: &StateProcessingContext) -> (bool, bool) {
-        let mut rng = rand::thread_rng();
+        let mut rng = RandomGenerator::current();
 
         // Get goalkeeper's tackling-related skills
         let tackling_skill = ctx.player.skills.technical.tackling as f32 / 20.0; // Normalize to [0,1]
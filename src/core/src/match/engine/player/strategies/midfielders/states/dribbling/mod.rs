@@ -2,6 +2,7 @@ use crate::r#match::midfielders::states::MidfielderState;
 use crate::r#match::{
     ConditionContext, StateChangeResult, StateProcessingContext, StateProcessingHandler,
 };
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::prelude::IteratorRandom;
 
@@ -24,7 +25,11 @@ impl StateProcessingHandler for MidfielderDribblingState {
                 ));
             }
 
-            if ctx.in_state_time > 100 {
+            let max_dribbling_time = (100.0
+                * ctx.team().instructions().tempo.possession_time_factor()
+                * ctx.player().role().role.carry_factor()) as u64;
+
+            if ctx.in_state_time > max_dribbling_time {
                 return Some(StateChangeResult::with_midfielder_state(
                     MidfielderState::Passing
                 ));
@@ -71,7 +76,7 @@ impl MidfielderDribblingState {
 
         let teammates = teammates.nearby_ids(150.0);
 
-        if let Some((teammate_id, _)) = teammates.choose(&mut rand::thread_rng()) {
+        if let Some((teammate_id, _)) = teammates.choose(&mut RandomGenerator::current()) {
             return Some(teammate_id)
         }
 
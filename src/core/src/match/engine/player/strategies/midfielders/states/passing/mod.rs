@@ -73,7 +73,10 @@ impl MidfielderPassingState {
     ) -> Option<MatchPlayerLite> {
         let players = ctx.players();
         let teammates = players.teammates();
-        let vision_range = ctx.player.skills.mental.vision * 20.0;
+        let vision_range = ctx.player.skills.mental.vision
+            * 20.0
+            * ctx.team().instructions().passing.pass_range_factor()
+            * ctx.player().role().role.pass_range_factor();
 
         teammates
             .nearby(vision_range)
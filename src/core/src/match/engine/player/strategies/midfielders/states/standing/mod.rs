@@ -31,7 +31,7 @@ impl StateProcessingHandler for MidfielderStandingState {
             }
             else {
 
-                if ctx.ball().distance() < PRESSING_DISTANCE_THRESHOLD {
+                if ctx.ball().distance() < self.pressing_distance(ctx) {
                     // Transition to Tackling state to try and win the ball
                     return Some(StateChangeResult::with_midfielder_state(
                         MidfielderState::Pressing,
@@ -100,17 +100,26 @@ impl MidfielderStandingState {
     fn is_opponent_nearby(&self, ctx: &StateProcessingContext) -> bool {
         ctx.players()
             .opponents()
-            .exists(PRESSING_DISTANCE_THRESHOLD)
+            .exists(self.pressing_distance(ctx))
+    }
+
+    fn pressing_distance(&self, ctx: &StateProcessingContext) -> f32 {
+        PRESSING_DISTANCE_THRESHOLD
+            * ctx.team().instructions().pressing.distance_factor()
+            * ctx.player().role().role.pressing_factor()
     }
 
     /// Determines if the midfielder should support an attacking play.
     fn should_support_attack(&self, ctx: &StateProcessingContext) -> bool {
         // For simplicity, assume the midfielder supports the attack if the ball is in the attacking third
         let field_length = ctx.context.field_size.width as f32;
+        let support_line = ctx.team().instructions().mentality.support_line()
+            + ctx.player().role().duty.support_line_shift();
+
         let attacking_third_start = if ctx.player.side == Some(PlayerSide::Left) {
-            field_length * (2.0 / 3.0)
+            field_length * support_line
         } else {
-            field_length / 3.0
+            field_length * (1.0 - support_line)
         };
 
         let ball_position_x = ctx.tick_context.positions.ball.position.x;
@@ -5,6 +5,7 @@ use crate::r#match::{
     ConditionContext, MatchPlayerLite, StateChangeResult, StateProcessingContext,
     StateProcessingHandler, SteeringBehavior,
 };
+use crate::utils::RandomGenerator;
 use nalgebra::Vector3;
 use rand::Rng;
 
@@ -58,7 +59,7 @@ impl StateProcessingHandler for MidfielderTacklingState {
                 } else if committed_foul {
                     return Some(StateChangeResult::with_midfielder_state_and_event(
                         MidfielderState::Standing,
-                        Event::PlayerEvent(PlayerEvent::CommitFoul),
+                        Event::PlayerEvent(PlayerEvent::CommitFoul(ctx.player.id, opponent.id)),
                     ));
                 }
             }
@@ -99,7 +100,7 @@ impl MidfielderTacklingState {
         ctx: &StateProcessingContext,
         opponent: &MatchPlayerLite,
     ) -> (bool, bool) {
-        let mut rng = rand::thread_rng();
+        let mut rng = RandomGenerator::current();
 
         let tackling_skill = ctx.player.skills.technical.tackling / 20.0;
         let aggression = ctx.player.skills.mental.aggression / 20.0;
@@ -0,0 +1,85 @@
+use crate::r#match::{MatchContext, MatchField, PlayerSide, VectorExtensions};
+use nalgebra::Vector3;
+
+// Distance to goal from which a foul on the ball carrier can deny an obvious scoring chance
+const GOAL_SCORING_CHANCE_DISTANCE: f32 = 150.0;
+
+#[derive(Debug, Clone)]
+pub struct Foul {
+    pub offender_id: u32,
+    pub fouled_player_id: u32,
+    pub offender_side: PlayerSide,
+    pub position: Vector3<f32>,
+
+    // offender's aggression normalized to [0, 1]
+    pub aggression: f32,
+    pub is_offender_booked: bool,
+
+    // contact with the ball carrier is a direct free kick, impeding anyone else is indirect
+    pub is_on_ball_carrier: bool,
+    pub is_in_penalty_area: bool,
+    // the fouled team still has the ball in the opponent half
+    pub is_advantage_possible: bool,
+    pub denies_goal_scoring_chance: bool,
+}
+
+impl Foul {
+    pub fn new(
+        offender_id: u32,
+        fouled_player_id: u32,
+        field: &MatchField,
+        context: &MatchContext,
+    ) -> Option<Self> {
+        let offender = field.players.iter().find(|p| p.id == offender_id)?;
+        let fouled_player = field.players.iter().find(|p| p.id == fouled_player_id)?;
+
+        let offender_side = offender.side?;
+        let position = fouled_player.position;
+
+        // the offender defends the goal on his own side of the field
+        let own_goal = match offender_side {
+            PlayerSide::Left => context.goal_positions.left,
+            PlayerSide::Right => context.goal_positions.right,
+        };
+
+        let is_in_own_half = match offender_side {
+            PlayerSide::Left => position.x < context.field_size.half_width as f32,
+            PlayerSide::Right => position.x > context.field_size.half_width as f32,
+        };
+
+        let is_on_ball_carrier = field.ball.current_owner == Some(fouled_player_id);
+
+        let is_ball_kept = field
+            .ball
+            .current_owner
+            .and_then(|owner_id| field.players.iter().find(|p| p.id == owner_id))
+            .is_some_and(|owner| owner.team_id == fouled_player.team_id);
+
+        let goal_distance = position.distance_to(&own_goal);
+
+        // no outfield defender left between the ball carrier and the goal
+        let is_last_man = !field.players.iter().any(|player| {
+            player.team_id == offender.team_id
+                && player.id != offender_id
+                && !player.tactical_position.current_position.is_goalkeeper()
+                && player.position.distance_to(&own_goal) < goal_distance
+        });
+
+        Some(Foul {
+            offender_id,
+            fouled_player_id,
+            offender_side,
+            position,
+            aggression: offender.skills.mental.aggression / 20.0,
+            is_offender_booked: offender.statistics.yellow_cards() > 0,
+            is_on_ball_carrier,
+            is_in_penalty_area: context
+                .penalty_area(offender_side == PlayerSide::Left)
+                .contains(&position),
+            is_advantage_possible: is_ball_kept && is_in_own_half,
+            denies_goal_scoring_chance: is_on_ball_carrier
+                && is_last_man
+                && goal_distance < GOAL_SCORING_CHANCE_DISTANCE,
+        })
+    }
+}
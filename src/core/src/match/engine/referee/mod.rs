@@ -0,0 +1,7 @@
+pub mod foul;
+pub mod offside;
+pub mod referee;
+
+pub use foul::*;
+pub use offside::*;
+pub use referee::*;
@@ -0,0 +1,152 @@
+use crate::r#match::{MatchContext, MatchField, PlayerSide};
+use nalgebra::Vector3;
+
+// A player caught offside when the pass is played
+#[derive(Debug, Clone)]
+pub struct Offside {
+    pub player_id: u32,
+    pub team_id: u32,
+    pub position: Vector3<f32>,
+}
+
+impl Offside {
+    pub fn check(
+        passer_id: u32,
+        receiver_id: u32,
+        field: &MatchField,
+        context: &MatchContext,
+    ) -> Option<Self> {
+        let passer = field.players.iter().find(|p| p.id == passer_id)?;
+        let receiver = field.players.iter().find(|p| p.id == receiver_id)?;
+
+        // passing back to yourself or to the other team can't be offside
+        if passer.id == receiver.id || passer.team_id != receiver.team_id {
+            return None;
+        }
+
+        let side = receiver.side?;
+
+        let defenders_x: Vec<f32> = field
+            .players
+            .iter()
+            .filter(|p| p.team_id != receiver.team_id)
+            .map(|p| p.position.x)
+            .collect();
+
+        let is_offside = Self::is_offside_position(
+            side,
+            receiver.position.x,
+            field.ball.position.x,
+            &defenders_x,
+            context.field_size.half_width as f32,
+        );
+
+        is_offside.then_some(Offside {
+            player_id: receiver.id,
+            team_id: receiver.team_id,
+            position: receiver.position,
+        })
+    }
+
+    // In the opponent half and nearer to their goal line than both the ball
+    // and the second-last opponent, level counts as onside
+    fn is_offside_position(
+        side: PlayerSide,
+        position_x: f32,
+        ball_x: f32,
+        defenders_x: &[f32],
+        half_width: f32,
+    ) -> bool {
+        // depth towards the opponent goal line, the left team attacks to the right
+        let depth = |x: f32| match side {
+            PlayerSide::Left => x,
+            PlayerSide::Right => 2.0 * half_width - x,
+        };
+
+        let player_depth = depth(position_x);
+
+        if player_depth <= half_width || player_depth <= depth(ball_x) {
+            return false;
+        }
+
+        let mut defenders_depth: Vec<f32> = defenders_x.iter().map(|x| depth(*x)).collect();
+        defenders_depth.sort_by(|a, b| b.total_cmp(a));
+
+        match defenders_depth.get(1) {
+            Some(second_last) => player_depth > *second_last,
+            // fewer than two opponents left, only the goal line plays him onside
+            None => player_depth > defenders_depth.first().copied().unwrap_or(f32::MAX),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALF_WIDTH: f32 = 420.0;
+
+    #[test]
+    fn behind_second_last_defender_is_offside() {
+        // keeper on the line and a defender at 700
+        let defenders = [835.0, 700.0, 600.0];
+
+        assert!(Offside::is_offside_position(
+            PlayerSide::Left,
+            720.0,
+            500.0,
+            &defenders,
+            HALF_WIDTH
+        ));
+
+        assert!(!Offside::is_offside_position(
+            PlayerSide::Left,
+            700.0,
+            500.0,
+            &defenders,
+            HALF_WIDTH
+        ));
+    }
+
+    #[test]
+    fn own_half_and_behind_ball_are_onside() {
+        let defenders = [835.0, 300.0];
+
+        assert!(!Offside::is_offside_position(
+            PlayerSide::Left,
+            400.0,
+            200.0,
+            &defenders,
+            HALF_WIDTH
+        ));
+
+        assert!(!Offside::is_offside_position(
+            PlayerSide::Left,
+            720.0,
+            750.0,
+            &[835.0, 700.0],
+            HALF_WIDTH
+        ));
+    }
+
+    #[test]
+    fn right_team_attacks_towards_left_goal() {
+        let defenders = [5.0, 140.0, 300.0];
+
+        assert!(Offside::is_offside_position(
+            PlayerSide::Right,
+            120.0,
+            400.0,
+            &defenders,
+            HALF_WIDTH
+        ));
+
+        assert!(!Offside::is_offside_position(
+            PlayerSide::Right,
+            160.0,
+            400.0,
+            &defenders,
+            HALF_WIDTH
+        ));
+    }
+}
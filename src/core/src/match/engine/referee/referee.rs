@@ -0,0 +1,214 @@
+use crate::r#match::referee::Foul;
+use crate::utils::{FloatUtils, RandomGenerator};
+use rand::Rng;
+
+const MIN_STRICTNESS: f32 = 5.0;
+const MAX_STRICTNESS: f32 = 18.0;
+
+const YELLOW_CARD_BASE_CHANCE: f32 = 0.1;
+const RED_CARD_BASE_CHANCE: f32 = 0.005;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FreeKickType {
+    Direct,
+    Indirect,
+    Penalty,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Card {
+    Yellow,
+    SecondYellow,
+    Red,
+}
+
+impl Card {
+    pub fn is_sending_off(&self) -> bool {
+        matches!(self, Card::SecondYellow | Card::Red)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefereeDecision {
+    // no free kick when the referee plays the advantage
+    pub free_kick: Option<FreeKickType>,
+    pub card: Option<Card>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Referee {
+    // 1..20, how readily fouls are punished with cards
+    pub strictness: f32,
+}
+
+impl Referee {
+    pub fn new(strictness: f32) -> Self {
+        Referee { strictness }
+    }
+
+    pub fn random() -> Self {
+        Referee::new(FloatUtils::random(MIN_STRICTNESS, MAX_STRICTNESS))
+    }
+
+    pub fn judge(&self, foul: &Foul) -> RefereeDecision {
+        self.decide(foul, RandomGenerator::current().gen())
+    }
+
+    fn decide(&self, foul: &Foul, card_roll: f32) -> RefereeDecision {
+        let free_kick = if foul.is_in_penalty_area && foul.is_on_ball_carrier {
+            Some(FreeKickType::Penalty)
+        } else if foul.is_advantage_possible {
+            None
+        } else if foul.is_on_ball_carrier {
+            Some(FreeKickType::Direct)
+        } else {
+            Some(FreeKickType::Indirect)
+        };
+
+        let card = match self.card(foul, card_roll) {
+            Some(Card::Yellow) if foul.is_offender_booked => Some(Card::SecondYellow),
+            card => card,
+        };
+
+        RefereeDecision { free_kick, card }
+    }
+
+    // Denying an obvious goal-scoring chance is a sending-off, unless the penalty already
+    // restores it; other fouls are booked depending on recklessness and strictness
+    fn card(&self, foul: &Foul, card_roll: f32) -> Option<Card> {
+        if foul.denies_goal_scoring_chance {
+            return if foul.is_in_penalty_area {
+                Some(Card::Yellow)
+            } else {
+                Some(Card::Red)
+            };
+        }
+
+        let strictness = self.strictness / 20.0;
+
+        let red_chance = RED_CARD_BASE_CHANCE + strictness * 0.01 + foul.aggression * 0.015;
+        let yellow_chance = YELLOW_CARD_BASE_CHANCE + strictness * 0.2 + foul.aggression * 0.15;
+
+        if card_roll < red_chance {
+            Some(Card::Red)
+        } else if card_roll < red_chance + yellow_chance {
+            Some(Card::Yellow)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::PlayerSide;
+    use nalgebra::Vector3;
+
+    fn foul() -> Foul {
+        Foul {
+            offender_id: 1,
+            fouled_player_id: 2,
+            offender_side: PlayerSide::Left,
+            position: Vector3::new(300.0, 200.0, 0.0),
+            aggression: 0.5,
+            is_offender_booked: false,
+            is_on_ball_carrier: true,
+            is_in_penalty_area: false,
+            is_advantage_possible: false,
+            denies_goal_scoring_chance: false,
+        }
+    }
+
+    #[test]
+    fn foul_on_ball_carrier_is_direct_free_kick() {
+        let referee = Referee::new(10.0);
+
+        let decision = referee.decide(&foul(), 0.99);
+
+        assert_eq!(Some(FreeKickType::Direct), decision.free_kick);
+        assert_eq!(None, decision.card);
+
+        let impeding = Foul {
+            is_on_ball_carrier: false,
+            ..foul()
+        };
+
+        assert_eq!(
+            Some(FreeKickType::Indirect),
+            referee.decide(&impeding, 0.99).free_kick
+        );
+    }
+
+    #[test]
+    fn foul_in_penalty_area_is_penalty_even_with_advantage() {
+        let referee = Referee::new(10.0);
+
+        let penalty = Foul {
+            is_in_penalty_area: true,
+            is_advantage_possible: true,
+            ..foul()
+        };
+
+        assert_eq!(
+            Some(FreeKickType::Penalty),
+            referee.decide(&penalty, 0.99).free_kick
+        );
+    }
+
+    #[test]
+    fn advantage_is_played_but_card_still_shown() {
+        let referee = Referee::new(10.0);
+
+        let advantage = Foul {
+            is_advantage_possible: true,
+            ..foul()
+        };
+
+        let decision = referee.decide(&advantage, 0.1);
+
+        assert_eq!(None, decision.free_kick);
+        assert_eq!(Some(Card::Yellow), decision.card);
+    }
+
+    #[test]
+    fn second_yellow_sends_player_off() {
+        let booked = Foul {
+            is_offender_booked: true,
+            ..foul()
+        };
+
+        let card = Referee::new(10.0).decide(&booked, 0.1).card.unwrap();
+
+        assert_eq!(Card::SecondYellow, card);
+        assert!(card.is_sending_off());
+    }
+
+    #[test]
+    fn denying_goal_scoring_chance_is_red_outside_penalty_area() {
+        let referee = Referee::new(1.0);
+
+        let last_man = Foul {
+            denies_goal_scoring_chance: true,
+            ..foul()
+        };
+
+        assert_eq!(Some(Card::Red), referee.decide(&last_man, 0.99).card);
+
+        let in_area = Foul {
+            is_in_penalty_area: true,
+            ..last_man
+        };
+
+        assert_eq!(Some(Card::Yellow), referee.decide(&in_area, 0.99).card);
+    }
+
+    #[test]
+    fn strict_referee_books_more() {
+        let lenient = Referee::new(1.0);
+        let strict = Referee::new(20.0);
+
+        assert_eq!(None, lenient.decide(&foul(), 0.3).card);
+        assert_eq!(Some(Card::Yellow), strict.decide(&foul(), 0.3).card);
+    }
+}
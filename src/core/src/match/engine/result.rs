@@ -1,9 +1,13 @@
 ﻿use crate::league::LeagueMatch;
-use crate::r#match::statistics::MatchStatisticType;
-use crate::r#match::{ResultMatchPositionData, TeamSquad};
+use crate::r#match::statistics::{MatchPlayerResult, MatchStatisticType};
+use crate::r#match::{
+    MatchHeatmaps, MatchTeamStatistics, MatchTimeline, ResultMatchPositionData, Substitution,
+    TeamSquad,
+};
 use std::sync::atomic::{AtomicU8, Ordering};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MatchResultRaw {
     pub score: Option<Score>,
 
@@ -12,6 +16,15 @@ pub struct MatchResultRaw {
     pub left_team_players: FieldSquad,
     pub right_team_players: FieldSquad,
 
+    pub player_results: Vec<MatchPlayerResult>,
+    pub team_statistics: Vec<MatchTeamStatistics>,
+    pub heatmaps: MatchHeatmaps,
+    pub timeline: MatchTimeline,
+
+    // goals of (home, away) scored in extra time, already counted in the score
+    pub extra_time: Option<(u8, u8)>,
+    pub penalty_shootout: Option<(u8, u8)>,
+
     pub match_time_ms: u64,
     pub additional_time_ms: u64,
 }
@@ -23,6 +36,12 @@ impl Clone for MatchResultRaw {
             position_data: self.position_data.clone(),
             left_team_players: self.left_team_players.clone(),
             right_team_players: self.right_team_players.clone(),
+            player_results: self.player_results.clone(),
+            team_statistics: self.team_statistics.clone(),
+            heatmaps: self.heatmaps.clone(),
+            timeline: self.timeline.clone(),
+            extra_time: self.extra_time,
+            penalty_shootout: self.penalty_shootout,
             match_time_ms: self.match_time_ms,
             additional_time_ms: self.additional_time_ms,
         }
@@ -36,6 +55,12 @@ impl MatchResultRaw {
             position_data: ResultMatchPositionData::new(),
             left_team_players: FieldSquad::new(),
             right_team_players: FieldSquad::new(),
+            player_results: Vec::new(),
+            team_statistics: Vec::new(),
+            heatmaps: MatchHeatmaps::default(),
+            timeline: MatchTimeline::new(),
+            extra_time: None,
+            penalty_shootout: None,
             match_time_ms,
             additional_time_ms: 0,
         }
@@ -47,11 +72,21 @@ impl MatchResultRaw {
             position_data: ResultMatchPositionData::new(),
             left_team_players: self.left_team_players.clone(),
             right_team_players: self.right_team_players.clone(),
+            player_results: self.player_results.clone(),
+            team_statistics: self.team_statistics.clone(),
+            heatmaps: MatchHeatmaps::default(),
+            timeline: self.timeline.clone(),
+            extra_time: self.extra_time,
+            penalty_shootout: self.penalty_shootout,
             match_time_ms: self.match_time_ms,
             additional_time_ms: self.additional_time_ms,
         }
     }
 
+    pub fn team_statistics(&self, team_id: u32) -> Option<&MatchTeamStatistics> {
+        self.team_statistics.iter().find(|s| s.team_id == team_id)
+    }
+
     pub fn write_team_players(
         &mut self,
         home_team_players: &FieldSquad,
@@ -62,10 +97,11 @@ impl MatchResultRaw {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldSquad {
     pub main: Vec<u32>,
     pub substitutes: Vec<u32>,
+    pub substitutions: Vec<Substitution>,
 }
 
 impl FieldSquad {
@@ -73,6 +109,7 @@ impl FieldSquad {
         FieldSquad {
             main: Vec::new(),
             substitutes: Vec::new(),
+            substitutions: Vec::new(),
         }
     }
 
@@ -80,6 +117,7 @@ impl FieldSquad {
         FieldSquad {
             main: field_squad.main.to_vec(),
             substitutes: field_squad.substitutes.to_vec(),
+            substitutions: field_squad.substitutions.to_vec(),
         }
     }
 
@@ -87,22 +125,27 @@ impl FieldSquad {
         FieldSquad {
             main: squad.main_squad.iter().map(|p| p.id).collect(),
             substitutes: squad.substitutes.iter().map(|p| p.id).collect(),
+            substitutions: Vec::new(),
         }
     }
 
     pub fn count(&self) -> usize {
         self.main.len() + self.substitutes.len()
     }
+
+    pub fn contains(&self, player_id: u32) -> bool {
+        self.main.contains(&player_id) || self.substitutes.contains(&player_id)
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Score {
     pub home_team: TeamScore,
     pub away_team: TeamScore,
     pub details: Vec<GoalDetail>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TeamScore {
     pub team_id: u32,
     score: AtomicU8,
@@ -157,7 +200,7 @@ impl PartialOrd for TeamScore {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GoalDetail {
     pub player_id: u32,
     pub stat_type: MatchStatisticType,
@@ -191,7 +234,7 @@ impl Score {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchResult {
     pub id: String,
     pub league_id: u32,
@@ -203,6 +246,16 @@ pub struct MatchResult {
 }
 
 impl MatchResult {
+    pub fn extra_time(&self) -> Option<(u8, u8)> {
+        self.details.as_ref().and_then(|details| details.extra_time)
+    }
+
+    pub fn penalty_shootout(&self) -> Option<(u8, u8)> {
+        self.details
+            .as_ref()
+            .and_then(|details| details.penalty_shootout)
+    }
+
     pub fn copy_without_data_positions(&self) -> Self {
         MatchResult {
             id: String::from(&self.id),
@@ -0,0 +1,359 @@
+use crate::r#match::goalkeepers::states::state::GoalkeeperState;
+use crate::r#match::player::state::PlayerState;
+use crate::r#match::{
+    MatchContext, MatchField, MatchPlayer, PlayerSide, SetPiece, SetPieceType, VectorExtensions,
+    GOAL_WIDTH,
+};
+use nalgebra::Vector3;
+
+// Opponents keep 9.15 m from a free kick or corner, in field units
+const MIN_OPPONENT_DISTANCE: f32 = 73.0;
+// and 2 m from a throw-in
+const THROW_IN_OPPONENT_DISTANCE: f32 = 16.0;
+
+// Penalty area in field units: 16.5 m deep, 40.3 m wide
+const PENALTY_AREA_DEPTH: f32 = 132.0;
+const PENALTY_AREA_HALF_WIDTH: f32 = 161.0;
+
+// Free kicks nearer to goal than this get a defensive wall
+const WALL_DISTANCE_TO_GOAL: f32 = 250.0;
+const WALL_PLAYER_SPACING: f32 = 6.0;
+
+const CORNER_ATTACKERS: usize = 4;
+// Depth from the goal line and offset from the goal center of the attackers' spots
+const CORNER_ATTACKER_SPOTS: [(f32, f32); CORNER_ATTACKERS] =
+    [(40.0, -30.0), (55.0, 10.0), (75.0, -10.0), (90.0, 35.0)];
+
+const THROW_IN_SUPPORT_DISTANCE: f32 = 60.0;
+
+// Tactical layouts players take up before a restart, the set piece team is the
+// attacking team and its opponents the defending one
+pub struct SetPieceLayout;
+
+impl SetPieceLayout {
+    pub fn arrange(set_piece: &SetPiece, field: &mut MatchField, context: &MatchContext) {
+        match set_piece.set_piece_type {
+            SetPieceType::KickOff => Self::kick_off(field),
+            SetPieceType::ThrowIn => Self::throw_in(set_piece, field, context),
+            SetPieceType::Corner => Self::corner(set_piece, field, context),
+            SetPieceType::GoalKick => Self::goal_kick(set_piece, field, context),
+            SetPieceType::DirectFreeKick | SetPieceType::IndirectFreeKick => {
+                Self::free_kick(set_piece, field, context)
+            }
+            SetPieceType::Penalty => Self::penalty(set_piece, field, context),
+        }
+
+        if let Some(taker) = set_piece.taker_id.and_then(|id| field.get_player_mut(id)) {
+            place(taker, set_piece.position);
+        }
+    }
+
+    fn kick_off(field: &mut MatchField) {
+        field.reset_players_positions();
+    }
+
+    // Two nearest teammates offer short options up and down the line,
+    // opponents step back
+    fn throw_in(set_piece: &SetPiece, field: &mut MatchField, context: &MatchContext) {
+        let height = context.field_size.height as f32;
+        let width = context.field_size.width as f32;
+
+        let infield = if set_piece.position.y < height / 2.0 {
+            THROW_IN_SUPPORT_DISTANCE
+        } else {
+            -THROW_IN_SUPPORT_DISTANCE
+        };
+
+        let support_spots = [
+            Vector3::new(
+                set_piece.position.x + THROW_IN_SUPPORT_DISTANCE,
+                set_piece.position.y + infield,
+                0.0,
+            ),
+            Vector3::new(
+                set_piece.position.x - THROW_IN_SUPPORT_DISTANCE,
+                set_piece.position.y + infield / 2.0,
+                0.0,
+            ),
+        ];
+
+        let supporters = nearest_players(
+            field,
+            set_piece.position,
+            |p| {
+                p.team_id == set_piece.team_id
+                    && Some(p.id) != set_piece.taker_id
+                    && !is_goalkeeper(p)
+            },
+            support_spots.len(),
+        );
+
+        for (id, spot) in supporters.into_iter().zip(support_spots) {
+            if let Some(player) = field.get_player_mut(id) {
+                place(player, Vector3::new(spot.x.clamp(0.0, width), spot.y, 0.0));
+            }
+        }
+
+        keep_opponents_away(set_piece, field, THROW_IN_OPPONENT_DISTANCE);
+    }
+
+    // Best headers attack the box, each marked goal side by a defender,
+    // the keeper stays on his line
+    fn corner(set_piece: &SetPiece, field: &mut MatchField, context: &MatchContext) {
+        let goal = set_piece.attacking_goal(context);
+        let into_field = direction_into_field(goal, context);
+
+        // mirror the spots for corners from the other flag
+        let flag_side = if set_piece.position.y < goal.y {
+            -1.0
+        } else {
+            1.0
+        };
+
+        let mut headers: Vec<&MatchPlayer> = field
+            .players
+            .iter()
+            .filter(|p| {
+                p.team_id == set_piece.team_id
+                    && Some(p.id) != set_piece.taker_id
+                    && !is_goalkeeper(p)
+            })
+            .collect();
+
+        headers.sort_by(|a, b| {
+            b.skills
+                .technical
+                .heading
+                .total_cmp(&a.skills.technical.heading)
+        });
+
+        let attackers: Vec<(u32, Vector3<f32>)> = headers
+            .iter()
+            .zip(CORNER_ATTACKER_SPOTS)
+            .map(|(player, (depth, offset))| {
+                (
+                    player.id,
+                    Vector3::new(
+                        goal.x + into_field * depth,
+                        goal.y + flag_side * offset,
+                        0.0,
+                    ),
+                )
+            })
+            .collect();
+
+        let mut markers = Vec::with_capacity(attackers.len() + 1);
+
+        for (attacker_id, spot) in &attackers {
+            if let Some(player) = field.get_player_mut(*attacker_id) {
+                place(player, *spot);
+            }
+
+            let marker = nearest_players(
+                field,
+                *spot,
+                |p| p.team_id != set_piece.team_id && !is_goalkeeper(p) && !markers.contains(&p.id),
+                1,
+            );
+
+            if let Some(marker_id) = marker.first() {
+                markers.push(*marker_id);
+
+                if let Some(player) = field.get_player_mut(*marker_id) {
+                    place(player, Vector3::new(spot.x - into_field * 8.0, spot.y, 0.0));
+                }
+            }
+        }
+
+        // one more defender guards the near post
+        let near_post = Vector3::new(
+            goal.x + into_field * 4.0,
+            goal.y + flag_side * GOAL_WIDTH * 0.8,
+            0.0,
+        );
+
+        let post_guard = nearest_players(
+            field,
+            near_post,
+            |p| p.team_id != set_piece.team_id && !is_goalkeeper(p) && !markers.contains(&p.id),
+            1,
+        );
+
+        if let Some(player) = post_guard.first().and_then(|id| field.get_player_mut(*id)) {
+            place(player, near_post);
+        }
+
+        place_opponent_goalkeeper(set_piece, field, goal, into_field);
+    }
+
+    // Opponents leave the penalty area until the keeper has kicked the ball
+    fn goal_kick(set_piece: &SetPiece, field: &mut MatchField, context: &MatchContext) {
+        let own_goal = match set_piece.side {
+            PlayerSide::Left => context.goal_positions.left,
+            PlayerSide::Right => context.goal_positions.right,
+        };
+
+        let into_field = direction_into_field(own_goal, context);
+
+        clear_penalty_area(field, own_goal, into_field, |p| {
+            p.team_id != set_piece.team_id
+        });
+    }
+
+    // Defending team builds a wall 9.15 m away when the kick is within reach of
+    // goal, everybody else keeps the distance
+    fn free_kick(set_piece: &SetPiece, field: &mut MatchField, context: &MatchContext) {
+        keep_opponents_away(set_piece, field, MIN_OPPONENT_DISTANCE);
+
+        let goal = set_piece.attacking_goal(context);
+        let goal_distance = set_piece.position.distance_to(&goal);
+
+        if goal_distance >= WALL_DISTANCE_TO_GOAL {
+            return;
+        }
+
+        let wall_size = Self::wall_size(goal_distance);
+
+        let to_goal = (goal - set_piece.position).normalize();
+        let across = Vector3::new(-to_goal.y, to_goal.x, 0.0);
+        let wall_center = set_piece.position + to_goal * MIN_OPPONENT_DISTANCE;
+
+        let wall = nearest_players(
+            field,
+            wall_center,
+            |p| p.team_id != set_piece.team_id && !is_goalkeeper(p),
+            wall_size,
+        );
+
+        for (idx, id) in wall.into_iter().enumerate() {
+            let offset = (idx as f32 - (wall_size - 1) as f32 / 2.0) * WALL_PLAYER_SPACING;
+
+            if let Some(player) = field.get_player_mut(id) {
+                place(player, wall_center + across * offset);
+            }
+        }
+
+        place_opponent_goalkeeper(set_piece, field, goal, direction_into_field(goal, context));
+    }
+
+    // The nearer to goal, the more players in the wall
+    pub fn wall_size(goal_distance: f32) -> usize {
+        match goal_distance {
+            d if d < 150.0 => 5,
+            d if d < 200.0 => 4,
+            _ => 3,
+        }
+    }
+
+    // Only the taker and the keeper stay in the penalty area
+    fn penalty(set_piece: &SetPiece, field: &mut MatchField, context: &MatchContext) {
+        let goal = set_piece.attacking_goal(context);
+        let into_field = direction_into_field(goal, context);
+
+        clear_penalty_area(field, goal, into_field, |p| {
+            Some(p.id) != set_piece.taker_id
+                && !(p.team_id != set_piece.team_id && is_goalkeeper(p))
+        });
+
+        if let Some(goalkeeper) = field
+            .players
+            .iter_mut()
+            .find(|p| p.team_id != set_piece.team_id && is_goalkeeper(p))
+        {
+            place(goalkeeper, goal);
+            goalkeeper.state = PlayerState::Goalkeeper(GoalkeeperState::PenaltySave);
+        }
+    }
+}
+
+fn place(player: &mut MatchPlayer, position: Vector3<f32>) {
+    player.position = position;
+    player.velocity = Vector3::zeros();
+}
+
+fn is_goalkeeper(player: &MatchPlayer) -> bool {
+    player.tactical_position.current_position.is_goalkeeper()
+}
+
+// +1 when the goal is on the left goal line, -1 on the right one
+fn direction_into_field(goal: Vector3<f32>, context: &MatchContext) -> f32 {
+    if goal.x < context.field_size.half_width as f32 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+fn nearest_players(
+    field: &MatchField,
+    position: Vector3<f32>,
+    filter: impl Fn(&MatchPlayer) -> bool,
+    count: usize,
+) -> Vec<u32> {
+    let mut players: Vec<&MatchPlayer> = field.players.iter().filter(|p| filter(p)).collect();
+
+    players.sort_by(|a, b| {
+        a.position
+            .distance_to(&position)
+            .total_cmp(&b.position.distance_to(&position))
+    });
+
+    players.iter().take(count).map(|p| p.id).collect()
+}
+
+fn keep_opponents_away(set_piece: &SetPiece, field: &mut MatchField, min_distance: f32) {
+    for player in field
+        .players
+        .iter_mut()
+        .filter(|p| p.team_id != set_piece.team_id)
+    {
+        let distance = player.position.distance_to(&set_piece.position);
+
+        if distance < min_distance {
+            let away = if distance > 0.0 {
+                (player.position - set_piece.position).normalize()
+            } else {
+                Vector3::new(0.0, 1.0, 0.0)
+            };
+
+            place(player, set_piece.position + away * min_distance);
+        }
+    }
+}
+
+fn clear_penalty_area(
+    field: &mut MatchField,
+    goal: Vector3<f32>,
+    into_field: f32,
+    must_leave: impl Fn(&MatchPlayer) -> bool,
+) {
+    let area_edge_x = goal.x + into_field * (PENALTY_AREA_DEPTH + 1.0);
+
+    for player in field.players.iter_mut().filter(|p| must_leave(p)) {
+        let depth = (player.position.x - goal.x) * into_field;
+        let is_in_area = depth < PENALTY_AREA_DEPTH
+            && (player.position.y - goal.y).abs() < PENALTY_AREA_HALF_WIDTH;
+
+        if is_in_area {
+            place(player, Vector3::new(area_edge_x, player.position.y, 0.0));
+        }
+    }
+}
+
+fn place_opponent_goalkeeper(
+    set_piece: &SetPiece,
+    field: &mut MatchField,
+    goal: Vector3<f32>,
+    into_field: f32,
+) {
+    if let Some(goalkeeper) = field
+        .players
+        .iter_mut()
+        .find(|p| p.team_id != set_piece.team_id && is_goalkeeper(p))
+    {
+        place(
+            goalkeeper,
+            Vector3::new(goal.x + into_field * 4.0, goal.y, 0.0),
+        );
+    }
+}
@@ -0,0 +1,7 @@
+pub mod layouts;
+pub mod restart;
+pub mod set_piece;
+
+pub use layouts::*;
+pub use restart::*;
+pub use set_piece::*;
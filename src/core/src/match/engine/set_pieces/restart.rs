@@ -0,0 +1,380 @@
+use crate::r#match::events::EventCollection;
+use crate::r#match::player::events::{PassingEventContext, PlayerEvent, ShootingEventContext};
+use crate::r#match::{
+    MatchContext, MatchField, MatchPlayer, SetPiece, SetPieceLayout, SetPieceType,
+    TimelineEventType, VectorExtensions, GOAL_WIDTH,
+};
+use crate::utils::RandomGenerator;
+use log::debug;
+use nalgebra::Vector3;
+use rand::Rng;
+
+// Direct free kicks nearer to goal than this are shot at goal
+const FREE_KICK_SHOOTING_DISTANCE: f32 = 220.0;
+
+const MIN_KICK_POWER: f32 = 0.5;
+const MAX_KICK_POWER: f32 = 2.5;
+
+pub struct SetPieceProcessor;
+
+impl SetPieceProcessor {
+    // Stops play, lines players up for the restart and hands the ball to the taker
+    pub fn start(mut set_piece: SetPiece, field: &mut MatchField, context: &mut MatchContext) {
+        let taker_id = match set_piece
+            .taker_id
+            .or_else(|| Self::choose_taker(&set_piece, field))
+        {
+            Some(taker_id) => taker_id,
+            None => return,
+        };
+
+        set_piece.taker_id = Some(taker_id);
+
+        if set_piece.set_piece_type == SetPieceType::Corner {
+            context.analytics.corner_awarded(set_piece.team_id);
+            context.timeline.add(
+                context.time.time,
+                TimelineEventType::Corner {
+                    team_id: set_piece.team_id,
+                },
+            );
+        }
+
+        debug!("Set piece: {:?}", set_piece);
+
+        field.players.iter_mut().for_each(|p| {
+            p.velocity = Vector3::zeros();
+            p.set_default_state();
+        });
+
+        SetPieceLayout::arrange(&set_piece, field, context);
+
+        field.ball.position = set_piece.position;
+        field.ball.velocity = Vector3::zeros();
+        field.ball.previous_owner = None;
+        field.ball.current_owner = Some(taker_id);
+        field.ball.last_touched_by = Some(taker_id);
+        field.ball.take_ball_notified_player = None;
+        field.ball.flags.reset();
+
+        context.set_piece = Some(set_piece);
+    }
+
+    // Counts down while players stand in position, then the taker plays the ball
+    pub fn process(field: &MatchField, context: &mut MatchContext, events: &mut EventCollection) {
+        let set_piece = match context.set_piece.as_mut() {
+            Some(set_piece) => set_piece,
+            None => return,
+        };
+
+        set_piece.tick();
+
+        if !set_piece.is_ready() {
+            return;
+        }
+
+        let set_piece = set_piece.clone();
+
+        let taker = match set_piece
+            .taker_id
+            .and_then(|id| field.players.iter().find(|p| p.id == id))
+        {
+            Some(taker) => taker,
+            None => return,
+        };
+
+        if let Some(event) = Self::execute(&set_piece, taker, field, context) {
+            events.add_player_event(event);
+        }
+    }
+
+    pub fn is_finished(context: &MatchContext) -> bool {
+        context
+            .set_piece
+            .as_ref()
+            .is_some_and(|set_piece| set_piece.is_ready())
+    }
+
+    fn choose_taker(set_piece: &SetPiece, field: &MatchField) -> Option<u32> {
+        let team_players = field
+            .players
+            .iter()
+            .filter(|p| p.team_id == set_piece.team_id && !p.player_attributes.is_injured);
+
+        let is_goalkeeper = |p: &MatchPlayer| p.tactical_position.current_position.is_goalkeeper();
+
+        let best_by = |skill: fn(&MatchPlayer) -> f32| {
+            team_players
+                .clone()
+                .filter(|p| !is_goalkeeper(p))
+                .max_by(|a, b| skill(a).total_cmp(&skill(b)))
+                .map(|p| p.id)
+        };
+
+        match set_piece.set_piece_type {
+            SetPieceType::KickOff => team_players
+                .clone()
+                .filter(|p| !is_goalkeeper(p))
+                .min_by(|a, b| {
+                    a.position
+                        .distance_to(&set_piece.position)
+                        .total_cmp(&b.position.distance_to(&set_piece.position))
+                })
+                .map(|p| p.id),
+            SetPieceType::ThrowIn => best_by(|p| p.skills.technical.long_throws),
+            SetPieceType::Corner => best_by(|p| p.skills.technical.corners),
+            SetPieceType::GoalKick => team_players
+                .clone()
+                .find(|p| is_goalkeeper(p))
+                .map(|p| p.id)
+                .or_else(|| best_by(|p| p.skills.technical.passing)),
+            SetPieceType::DirectFreeKick | SetPieceType::IndirectFreeKick => {
+                best_by(|p| p.skills.technical.free_kicks)
+            }
+            SetPieceType::Penalty => best_by(|p| p.skills.technical.penalty_taking),
+        }
+    }
+
+    fn execute(
+        set_piece: &SetPiece,
+        taker: &MatchPlayer,
+        field: &MatchField,
+        context: &MatchContext,
+    ) -> Option<PlayerEvent> {
+        let technical = &taker.skills.technical;
+        let goal = set_piece.attacking_goal(context);
+
+        let is_shot = match set_piece.set_piece_type {
+            SetPieceType::Penalty => true,
+            SetPieceType::DirectFreeKick => {
+                set_piece.position.distance_to(&goal) < FREE_KICK_SHOOTING_DISTANCE
+            }
+            _ => false,
+        };
+
+        if is_shot {
+            let skill = match set_piece.set_piece_type {
+                SetPieceType::Penalty => technical.penalty_taking,
+                _ => technical.free_kicks,
+            };
+
+            let mut rng = RandomGenerator::current();
+
+            let target = Vector3::new(goal.x, goal.y + rng.gen_range(-0.8..0.8) * GOAL_WIDTH, 0.0);
+
+            let force = (1.5 + skill / 20.0) * rng.gen_range(0.9..1.1);
+
+            return Some(PlayerEvent::Shoot(
+                ShootingEventContext::build()
+                    .with_player_id(taker.id)
+                    .with_target(target)
+                    .with_force(force as f64)
+                    .build(),
+            ));
+        }
+
+        let receiver = Self::choose_receiver(set_piece, taker, field, goal)?;
+
+        let skill = match set_piece.set_piece_type {
+            SetPieceType::ThrowIn => technical.long_throws,
+            SetPieceType::Corner => technical.corners,
+            SetPieceType::DirectFreeKick | SetPieceType::IndirectFreeKick => technical.free_kicks,
+            SetPieceType::KickOff | SetPieceType::GoalKick | SetPieceType::Penalty => {
+                technical.passing
+            }
+        };
+
+        let distance = set_piece.position.distance_to(&receiver.position);
+
+        Some(PlayerEvent::PassTo(
+            PassingEventContext::build()
+                .with_from_player_id(taker.id)
+                .with_to_player_id(receiver.id)
+                .with_target(receiver.position)
+                .with_force(Self::kick_power(skill, distance, context))
+                .build(),
+        ))
+    }
+
+    // Corners are crossed to the best header in the box, goal kicks played long to
+    // the most advanced teammate, everything else goes to the nearest one
+    fn choose_receiver<'p>(
+        set_piece: &SetPiece,
+        taker: &MatchPlayer,
+        field: &'p MatchField,
+        goal: Vector3<f32>,
+    ) -> Option<&'p MatchPlayer> {
+        let teammates = field
+            .players
+            .iter()
+            .filter(|p| p.team_id == set_piece.team_id && p.id != taker.id);
+
+        match set_piece.set_piece_type {
+            SetPieceType::Corner => teammates
+                .filter(|p| !p.tactical_position.current_position.is_goalkeeper())
+                .max_by(|a, b| {
+                    let score = |p: &MatchPlayer| {
+                        p.skills.technical.heading - p.position.distance_to(&goal) / 10.0
+                    };
+
+                    score(a).total_cmp(&score(b))
+                }),
+            SetPieceType::GoalKick => teammates.min_by(|a, b| {
+                a.position
+                    .distance_to(&goal)
+                    .total_cmp(&b.position.distance_to(&goal))
+            }),
+            _ => teammates.min_by(|a, b| {
+                a.position
+                    .distance_to(&set_piece.position)
+                    .total_cmp(&b.position.distance_to(&set_piece.position))
+            }),
+        }
+    }
+
+    // Same scale as an open play pass: stronger for better takers and longer balls
+    fn kick_power(skill: f32, distance: f32, context: &MatchContext) -> f32 {
+        let max_kick_distance = context.field_size.width as f32 * 0.6;
+        let distance_factor = (distance / max_kick_distance).clamp(0.0, 1.0);
+
+        let base_power =
+            MIN_KICK_POWER + (MAX_KICK_POWER - MIN_KICK_POWER) * (skill / 20.0) * distance_factor;
+
+        base_power * RandomGenerator::current().gen_range(0.9..1.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::testing::{match_at, squad};
+    use crate::r#match::events::Event;
+    use crate::r#match::{BoundaryLine, PlayerSide};
+
+    // Team 1 plays on the left and defends x = 0
+    fn setup() -> (MatchField, MatchContext) {
+        match_at(squad(1), squad(2), 0)
+    }
+
+    #[test]
+    fn ball_over_touchline_is_throw_in_for_opponents() {
+        let (field, context) = setup();
+
+        let set_piece = SetPiece::from_out_of_play(
+            BoundaryLine::Touchline,
+            Vector3::new(300.0, 0.0, 0.0),
+            Some(105),
+            &field,
+            &context,
+        )
+        .unwrap();
+
+        assert_eq!(SetPieceType::ThrowIn, set_piece.set_piece_type);
+        assert_eq!(2, set_piece.team_id);
+        assert_eq!(300.0, set_piece.position.x);
+        assert!(set_piece.position.y > 0.0);
+    }
+
+    #[test]
+    fn ball_over_own_goal_line_is_corner_otherwise_goal_kick() {
+        let (field, context) = setup();
+
+        let corner = SetPiece::from_out_of_play(
+            BoundaryLine::GoalLine(PlayerSide::Left),
+            Vector3::new(0.0, 100.0, 0.0),
+            Some(102),
+            &field,
+            &context,
+        )
+        .unwrap();
+
+        assert_eq!(SetPieceType::Corner, corner.set_piece_type);
+        assert_eq!(2, corner.team_id);
+        assert!(corner.position.y < 10.0);
+
+        let goal_kick = SetPiece::from_out_of_play(
+            BoundaryLine::GoalLine(PlayerSide::Left),
+            Vector3::new(0.0, 100.0, 0.0),
+            Some(209),
+            &field,
+            &context,
+        )
+        .unwrap();
+
+        assert_eq!(SetPieceType::GoalKick, goal_kick.set_piece_type);
+        assert_eq!(1, goal_kick.team_id);
+    }
+
+    #[test]
+    fn corner_is_taken_by_best_corner_taker_after_delay() {
+        let (mut field, mut context) = setup();
+
+        field.get_player_mut(205).unwrap().skills.technical.corners = 19.0;
+
+        let corner = SetPiece::new(
+            SetPieceType::Corner,
+            2,
+            PlayerSide::Right,
+            Vector3::new(1.0, 1.0, 0.0),
+        );
+
+        SetPieceProcessor::start(corner, &mut field, &mut context);
+
+        assert_eq!(Some(205), field.ball.current_owner);
+        assert_eq!(Vector3::new(1.0, 1.0, 0.0), field.ball.position);
+
+        // the keeper guards his line
+        assert!(field.get_player_mut(100).unwrap().position.x < 10.0);
+
+        for _ in 1..SetPieceType::Corner.delay() {
+            let mut events = EventCollection::new();
+            SetPieceProcessor::process(&field, &mut context, &mut events);
+
+            assert!(events.to_vec().is_empty());
+        }
+
+        let mut events = EventCollection::new();
+        SetPieceProcessor::process(&field, &mut context, &mut events);
+
+        assert!(SetPieceProcessor::is_finished(&context));
+
+        match events.to_vec().pop() {
+            Some(Event::PlayerEvent(PlayerEvent::PassTo(pass))) => {
+                assert_eq!(205, pass.from_player_id);
+
+                let receiver = field.get_player_mut(pass.to_player_id).unwrap();
+                assert_eq!(2, receiver.team_id);
+            }
+            _ => panic!("corner should be crossed"),
+        }
+    }
+
+    #[test]
+    fn free_kick_near_goal_gets_wall_at_distance() {
+        let (mut field, mut context) = setup();
+
+        let position = Vector3::new(180.0, 272.0, 0.0);
+        let free_kick = SetPiece::new(SetPieceType::DirectFreeKick, 2, PlayerSide::Right, position);
+
+        SetPieceProcessor::start(free_kick, &mut field, &mut context);
+
+        let wall_size =
+            SetPieceLayout::wall_size(position.distance_to(&context.goal_positions.left));
+
+        let wall = field
+            .players
+            .iter()
+            .filter(|p| p.team_id == 1)
+            .filter(|p| (p.position.distance_to(&position) - 73.0).abs() < 1.0)
+            .filter(|p| p.position.x < position.x)
+            .count();
+
+        assert!(wall >= wall_size);
+
+        assert!(field
+            .players
+            .iter()
+            .filter(|p| p.team_id == 1)
+            .all(|p| p.position.distance_to(&position) >= 72.9));
+    }
+}
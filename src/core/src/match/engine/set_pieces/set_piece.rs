@@ -0,0 +1,249 @@
+use crate::r#match::{MatchContext, MatchField, PlayerSide, VectorExtensions};
+use nalgebra::Vector3;
+
+// Restarts are taken this far inside the field so the ball isn't out again at once
+const RESTART_INSET: f32 = 1.0;
+
+// Goal kicks are taken from the edge of the goal area, 5.5 m in field units
+const GOAL_AREA_DEPTH: f32 = 44.0;
+
+// Penalty spot distance from the goal line, 11 m in field units
+pub const PENALTY_SPOT_DISTANCE: f32 = 88.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetPieceType {
+    KickOff,
+    ThrowIn,
+    Corner,
+    GoalKick,
+    DirectFreeKick,
+    IndirectFreeKick,
+    Penalty,
+}
+
+impl SetPieceType {
+    // ticks the game stands still while players take up their positions
+    pub fn delay(&self) -> u32 {
+        match self {
+            SetPieceType::KickOff => 100,
+            SetPieceType::ThrowIn => 150,
+            SetPieceType::Corner | SetPieceType::GoalKick => 250,
+            SetPieceType::DirectFreeKick | SetPieceType::IndirectFreeKick => 250,
+            SetPieceType::Penalty => 300,
+        }
+    }
+
+    // the laws don't punish offside straight from these restarts
+    pub fn is_offside_exempt(&self) -> bool {
+        matches!(
+            self,
+            SetPieceType::ThrowIn | SetPieceType::Corner | SetPieceType::GoalKick
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundaryLine {
+    Touchline,
+    GoalLine(PlayerSide),
+}
+
+#[derive(Debug, Clone)]
+pub struct SetPiece {
+    pub set_piece_type: SetPieceType,
+    pub team_id: u32,
+    pub side: PlayerSide,
+    pub position: Vector3<f32>,
+    pub taker_id: Option<u32>,
+    pub remaining_ticks: u32,
+}
+
+impl SetPiece {
+    pub fn new(
+        set_piece_type: SetPieceType,
+        team_id: u32,
+        side: PlayerSide,
+        position: Vector3<f32>,
+    ) -> Self {
+        SetPiece {
+            set_piece_type,
+            team_id,
+            side,
+            position,
+            taker_id: None,
+            remaining_ticks: set_piece_type.delay(),
+        }
+    }
+
+    // Team restarting with the given set piece, looked up by one of its players
+    pub fn for_team_of(
+        set_piece_type: SetPieceType,
+        player_id: u32,
+        position: Vector3<f32>,
+        field: &MatchField,
+    ) -> Option<Self> {
+        let player = field.players.iter().find(|p| p.id == player_id)?;
+
+        Some(SetPiece::new(
+            set_piece_type,
+            player.team_id,
+            player.side?,
+            position,
+        ))
+    }
+
+    // Kick-off from the center spot for the team defending the given side
+    pub fn kick_off(side: PlayerSide, field: &MatchField) -> Option<Self> {
+        let team_id = field.players.iter().find(|p| p.side == Some(side))?.team_id;
+
+        let center = Vector3::new(
+            field.size.width as f32 / 2.0,
+            field.size.height as f32 / 2.0,
+            0.0,
+        );
+
+        Some(SetPiece::new(SetPieceType::KickOff, team_id, side, center))
+    }
+
+    // Restart for the team that didn't touch the ball last: throw-in over the touchline,
+    // corner or goal kick over the goal line depending on who put it out
+    pub fn from_out_of_play(
+        line: BoundaryLine,
+        position: Vector3<f32>,
+        last_touched_by: Option<u32>,
+        field: &MatchField,
+        context: &MatchContext,
+    ) -> Option<Self> {
+        let last_touched_by = last_touched_by.or_else(|| {
+            field
+                .players
+                .iter()
+                .min_by(|a, b| {
+                    a.position
+                        .distance_to(&position)
+                        .total_cmp(&b.position.distance_to(&position))
+                })
+                .map(|p| p.id)
+        })?;
+
+        let last_toucher = field.players.iter().find(|p| p.id == last_touched_by)?;
+        let last_toucher_side = last_toucher.side?;
+
+        let opponent = field
+            .players
+            .iter()
+            .find(|p| p.team_id != last_toucher.team_id)?;
+        let opponent_side = opponent.side?;
+
+        let width = context.field_size.width as f32;
+        let height = context.field_size.height as f32;
+
+        let set_piece = match line {
+            BoundaryLine::Touchline => {
+                let y = if position.y <= 0.0 {
+                    RESTART_INSET
+                } else {
+                    height - RESTART_INSET
+                };
+
+                SetPiece::new(
+                    SetPieceType::ThrowIn,
+                    opponent.team_id,
+                    opponent_side,
+                    Vector3::new(
+                        position.x.clamp(RESTART_INSET, width - RESTART_INSET),
+                        y,
+                        0.0,
+                    ),
+                )
+            }
+            BoundaryLine::GoalLine(goal_side) => {
+                let goal = match goal_side {
+                    PlayerSide::Left => context.goal_positions.left,
+                    PlayerSide::Right => context.goal_positions.right,
+                };
+
+                let line_x = if goal.x <= 0.0 {
+                    RESTART_INSET
+                } else {
+                    width - RESTART_INSET
+                };
+
+                if last_toucher_side == goal_side {
+                    let corner_y = if position.y < height / 2.0 {
+                        RESTART_INSET
+                    } else {
+                        height - RESTART_INSET
+                    };
+
+                    SetPiece::new(
+                        SetPieceType::Corner,
+                        opponent.team_id,
+                        opponent_side,
+                        Vector3::new(line_x, corner_y, 0.0),
+                    )
+                } else {
+                    let goal_kick_x = if goal.x <= 0.0 {
+                        GOAL_AREA_DEPTH
+                    } else {
+                        width - GOAL_AREA_DEPTH
+                    };
+
+                    SetPiece::new(
+                        SetPieceType::GoalKick,
+                        opponent.team_id,
+                        opponent_side,
+                        Vector3::new(goal_kick_x, goal.y, 0.0),
+                    )
+                }
+            }
+        };
+
+        Some(set_piece)
+    }
+
+    pub fn with_taker(mut self, taker_id: u32) -> Self {
+        self.taker_id = Some(taker_id);
+        self
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.remaining_ticks == 0
+    }
+
+    pub fn tick(&mut self) {
+        self.remaining_ticks = self.remaining_ticks.saturating_sub(1);
+    }
+
+    // Penalty from the spot in front of the goal the given side defends
+    pub fn penalty(
+        team_id: u32,
+        defending_side: PlayerSide,
+        context: &MatchContext,
+    ) -> Self {
+        let (goal, spot_offset) = match defending_side {
+            PlayerSide::Left => (context.goal_positions.left, PENALTY_SPOT_DISTANCE),
+            PlayerSide::Right => (context.goal_positions.right, -PENALTY_SPOT_DISTANCE),
+        };
+
+        let side = match defending_side {
+            PlayerSide::Left => PlayerSide::Right,
+            PlayerSide::Right => PlayerSide::Left,
+        };
+
+        SetPiece::new(
+            SetPieceType::Penalty,
+            team_id,
+            side,
+            Vector3::new(goal.x + spot_offset, goal.y, 0.0),
+        )
+    }
+
+    // Goal the set piece team attacks
+    pub fn attacking_goal(&self, context: &MatchContext) -> Vector3<f32> {
+        match self.side {
+            PlayerSide::Left => context.goal_positions.right,
+            PlayerSide::Right => context.goal_positions.left,
+        }
+    }
+}
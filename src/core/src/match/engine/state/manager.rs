@@ -7,7 +7,7 @@ pub struct StateManager {
 impl StateManager {
     pub fn new() -> Self {
         StateManager {
-            current_state: MatchState::FirstHalf,
+            current_state: MatchState::Initial,
         }
     }
 
@@ -15,8 +15,9 @@ impl StateManager {
         self.current_state
     }
 
-    pub fn next(&mut self) -> Option<MatchState> {
-        let next_state = Self::get_next_state(self.current_state);
+    // Extra time and the shootout are only played while the match is undecided
+    pub fn next(&mut self, is_decided: bool) -> Option<MatchState> {
+        let next_state = Self::get_next_state(self.current_state, is_decided);
 
         match next_state {
             MatchState::End => None,
@@ -27,13 +28,16 @@ impl StateManager {
         }
     }
 
-    fn get_next_state(current_state: MatchState) -> MatchState {
+    fn get_next_state(current_state: MatchState, is_decided: bool) -> MatchState {
         match current_state {
             MatchState::Initial => MatchState::FirstHalf,
             MatchState::FirstHalf => MatchState::HalfTime,
             MatchState::HalfTime => MatchState::SecondHalf,
-            MatchState::SecondHalf => MatchState::ExtraTime,
-            MatchState::ExtraTime => MatchState::PenaltyShootout,
+            MatchState::SecondHalf if is_decided => MatchState::End,
+            MatchState::SecondHalf => MatchState::ExtraTimeFirstHalf,
+            MatchState::ExtraTimeFirstHalf => MatchState::ExtraTimeSecondHalf,
+            MatchState::ExtraTimeSecondHalf if is_decided => MatchState::End,
+            MatchState::ExtraTimeSecondHalf => MatchState::PenaltyShootout,
             MatchState::PenaltyShootout => MatchState::End,
             MatchState::End => MatchState::End,
         }
@@ -46,6 +50,8 @@ impl StateManager {
     ) {
         if context.state.match_state.need_swap_squads() {
             field.swap_squads();
+
+            std::mem::swap(&mut context.tactics.left, &mut context.tactics.right);
         }
 
         if play_result.additional_time > 0 {
@@ -58,8 +64,11 @@ impl StateManager {
                 Self::play_rest_time(field);
             }
             MatchState::HalfTime => {}
-            MatchState::SecondHalf => {}
-            MatchState::ExtraTime => {}
+            MatchState::SecondHalf => {
+                Self::play_rest_time(field);
+            }
+            MatchState::ExtraTimeFirstHalf => {}
+            MatchState::ExtraTimeSecondHalf => {}
             MatchState::PenaltyShootout => {}
             _ => {}
         }
@@ -80,17 +89,39 @@ mod tests {
     #[test]
     fn test_state_manager_new() {
         let state_manager = StateManager::new();
-        assert_eq!(state_manager.current(), MatchState::FirstHalf);
+        assert_eq!(state_manager.current(), MatchState::Initial);
     }
 
     #[test]
     fn test_state_manager_next() {
         let mut state_manager = StateManager::new();
-        assert_eq!(state_manager.next(), Some(MatchState::HalfTime));
-        assert_eq!(state_manager.next(), Some(MatchState::SecondHalf));
-        assert_eq!(state_manager.next(), Some(MatchState::ExtraTime));
-        assert_eq!(state_manager.next(), Some(MatchState::PenaltyShootout));
-        assert_eq!(state_manager.next(), None); // End of match
-        assert_eq!(state_manager.next(), None); // No more states after match ends
+        assert_eq!(state_manager.next(false), Some(MatchState::FirstHalf));
+        assert_eq!(state_manager.next(false), Some(MatchState::HalfTime));
+        assert_eq!(state_manager.next(false), Some(MatchState::SecondHalf));
+        assert_eq!(state_manager.next(false), Some(MatchState::ExtraTimeFirstHalf));
+        assert_eq!(state_manager.next(false), Some(MatchState::ExtraTimeSecondHalf));
+        assert_eq!(state_manager.next(false), Some(MatchState::PenaltyShootout));
+        assert_eq!(state_manager.next(false), None); // End of match
+        assert_eq!(state_manager.next(false), None); // No more states after match ends
+    }
+
+    #[test]
+    fn test_state_manager_decided_match_ends_after_normal_time() {
+        let mut state_manager = StateManager::new();
+        assert_eq!(state_manager.next(true), Some(MatchState::FirstHalf));
+        assert_eq!(state_manager.next(true), Some(MatchState::HalfTime));
+        assert_eq!(state_manager.next(true), Some(MatchState::SecondHalf));
+        assert_eq!(state_manager.next(true), None);
+    }
+
+    #[test]
+    fn test_state_manager_decided_in_extra_time_skips_shootout() {
+        let mut state_manager = StateManager::new();
+        state_manager.next(false);
+        state_manager.next(false);
+        state_manager.next(false);
+        assert_eq!(state_manager.next(false), Some(MatchState::ExtraTimeFirstHalf));
+        assert_eq!(state_manager.next(true), Some(MatchState::ExtraTimeSecondHalf));
+        assert_eq!(state_manager.next(true), None);
     }
 }
@@ -1,5 +1,7 @@
 pub mod manager;
+pub mod shootout;
 pub mod state;
 
 pub use manager::*;
+pub use shootout::*;
 pub use state::*;
@@ -0,0 +1,248 @@
+use crate::r#match::{MatchField, MatchPlayer};
+
+// Kicks each team takes before sudden death
+const SHOOTOUT_ROUNDS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PenaltyKick {
+    pub team_id: u32,
+    pub taker_id: u32,
+    pub is_scored: bool,
+}
+
+// Home team kicks first, teams alternate and every player on the field kicks
+// once before anybody kicks again
+#[derive(Debug, Clone)]
+pub struct PenaltyShootout {
+    pub home_team_id: u32,
+    pub away_team_id: u32,
+    home_takers: Vec<u32>,
+    away_takers: Vec<u32>,
+    pub kicks: Vec<PenaltyKick>,
+    pending_kick: Option<PenaltyKick>,
+}
+
+impl PenaltyShootout {
+    pub fn new(home_team_id: u32, away_team_id: u32, field: &MatchField) -> Self {
+        Self::from_players(home_team_id, away_team_id, &field.players)
+    }
+
+    pub fn from_players(home_team_id: u32, away_team_id: u32, players: &[MatchPlayer]) -> Self {
+        PenaltyShootout {
+            home_team_id,
+            away_team_id,
+            home_takers: Self::takers(home_team_id, players),
+            away_takers: Self::takers(away_team_id, players),
+            kicks: Vec::new(),
+            pending_kick: None,
+        }
+    }
+
+    // Best penalty takers first, the keeper kicks last
+    fn takers(team_id: u32, players: &[MatchPlayer]) -> Vec<u32> {
+        let mut players: Vec<_> = players
+            .iter()
+            .filter(|p| p.team_id == team_id)
+            .collect();
+
+        players.sort_by(|a, b| {
+            let is_goalkeeper =
+                |p: &MatchPlayer| p.tactical_position.current_position.is_goalkeeper();
+
+            is_goalkeeper(a).cmp(&is_goalkeeper(b)).then(
+                b.skills
+                    .technical
+                    .penalty_taking
+                    .total_cmp(&a.skills.technical.penalty_taking),
+            )
+        });
+
+        players.iter().map(|p| p.id).collect()
+    }
+
+    // Next kick to play, None once the shootout is decided
+    pub fn take_kick(&mut self) -> Option<PenaltyKick> {
+        if self.is_decided() {
+            return None;
+        }
+
+        let (home_kicks, away_kicks) = self.kicks_taken();
+
+        let (team_id, takers, taken) = if home_kicks == away_kicks {
+            (self.home_team_id, &self.home_takers, home_kicks)
+        } else {
+            (self.away_team_id, &self.away_takers, away_kicks)
+        };
+
+        if takers.is_empty() {
+            return None;
+        }
+
+        let kick = PenaltyKick {
+            team_id,
+            taker_id: takers[taken % takers.len()],
+            is_scored: false,
+        };
+
+        self.pending_kick = Some(kick);
+
+        Some(kick)
+    }
+
+    pub fn is_kick_pending(&self) -> bool {
+        self.pending_kick.is_some()
+    }
+
+    pub fn is_pending_kick_scored(&self) -> bool {
+        self.pending_kick.is_some_and(|kick| kick.is_scored)
+    }
+
+    pub fn score_pending_kick(&mut self) {
+        if let Some(kick) = self.pending_kick.as_mut() {
+            kick.is_scored = true;
+        }
+    }
+
+    pub fn finish_kick(&mut self) -> Option<PenaltyKick> {
+        let kick = self.pending_kick.take()?;
+
+        self.kicks.push(kick);
+
+        Some(kick)
+    }
+
+    pub fn score(&self) -> (u8, u8) {
+        let scored = |team_id: u32| {
+            self.kicks
+                .iter()
+                .filter(|k| k.team_id == team_id && k.is_scored)
+                .count() as u8
+        };
+
+        (scored(self.home_team_id), scored(self.away_team_id))
+    }
+
+    fn kicks_taken(&self) -> (usize, usize) {
+        let taken = |team_id: u32| self.kicks.iter().filter(|k| k.team_id == team_id).count();
+
+        (taken(self.home_team_id), taken(self.away_team_id))
+    }
+
+    // Decided once a team can't catch up within the five rounds,
+    // afterwards in the first round of sudden death that isn't level
+    pub fn is_decided(&self) -> bool {
+        let (home_goals, away_goals) = self.score();
+        let (home_goals, away_goals) = (home_goals as usize, away_goals as usize);
+        let (home_kicks, away_kicks) = self.kicks_taken();
+
+        if home_kicks < SHOOTOUT_ROUNDS || away_kicks < SHOOTOUT_ROUNDS {
+            home_goals + (SHOOTOUT_ROUNDS - home_kicks.min(SHOOTOUT_ROUNDS)) < away_goals
+                || away_goals + (SHOOTOUT_ROUNDS - away_kicks.min(SHOOTOUT_ROUNDS)) < home_goals
+        } else {
+            home_kicks == away_kicks && home_goals != away_goals
+        }
+    }
+
+    pub fn winner(&self) -> Option<u32> {
+        if !self.is_decided() {
+            return None;
+        }
+
+        let (home_goals, away_goals) = self.score();
+
+        if home_goals > away_goals {
+            Some(self.home_team_id)
+        } else {
+            Some(self.away_team_id)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shootout() -> PenaltyShootout {
+        PenaltyShootout {
+            home_team_id: 1,
+            away_team_id: 2,
+            home_takers: (100..111).collect(),
+            away_takers: (200..211).collect(),
+            kicks: Vec::new(),
+            pending_kick: None,
+        }
+    }
+
+    fn play(shootout: &mut PenaltyShootout, results: &[bool]) {
+        for is_scored in results {
+            shootout.take_kick().unwrap();
+
+            if *is_scored {
+                shootout.score_pending_kick();
+            }
+
+            shootout.finish_kick();
+        }
+    }
+
+    #[test]
+    fn teams_alternate_with_home_team_first() {
+        let mut shootout = shootout();
+
+        let first = shootout.take_kick().unwrap();
+        shootout.finish_kick();
+        let second = shootout.take_kick().unwrap();
+        shootout.finish_kick();
+        let third = shootout.take_kick().unwrap();
+
+        assert_eq!((1, 100), (first.team_id, first.taker_id));
+        assert_eq!((2, 200), (second.team_id, second.taker_id));
+        assert_eq!((1, 101), (third.team_id, third.taker_id));
+    }
+
+    #[test]
+    fn ends_early_when_team_cannot_catch_up() {
+        let mut shootout = shootout();
+
+        // home scores three, away misses three
+        play(&mut shootout, &[true, false, true, false, true, false]);
+
+        assert!(shootout.is_decided());
+        assert_eq!((3, 0), shootout.score());
+        assert_eq!(Some(1), shootout.winner());
+        assert!(shootout.take_kick().is_none());
+    }
+
+    #[test]
+    fn level_after_five_rounds_goes_to_sudden_death() {
+        let mut shootout = shootout();
+
+        play(&mut shootout, &[true; 10]);
+
+        assert!(!shootout.is_decided());
+
+        // home scores, away still has its kick
+        play(&mut shootout, &[true]);
+        assert!(!shootout.is_decided());
+
+        play(&mut shootout, &[false]);
+        assert!(shootout.is_decided());
+        assert_eq!((6, 5), shootout.score());
+        assert_eq!(Some(1), shootout.winner());
+    }
+
+    #[test]
+    fn takers_repeat_after_everybody_kicked() {
+        let mut shootout = PenaltyShootout {
+            home_takers: vec![100, 101],
+            away_takers: vec![200, 201],
+            ..shootout()
+        };
+
+        play(&mut shootout, &[true, true, true, true]);
+
+        let kick = shootout.take_kick().unwrap();
+
+        assert_eq!(100, kick.taker_id);
+    }
+}
@@ -1,19 +1,42 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
+use crate::r#match::{EXTRA_TIME_HALF_MS, MATCH_HALF_TIME_MS};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum MatchState {
     Initial,
     FirstHalf,
     HalfTime,
     SecondHalf,
-    ExtraTime,
+    ExtraTimeFirstHalf,
+    ExtraTimeSecondHalf,
     PenaltyShootout,
     End,
 }
 
 impl MatchState {
+    // teams change ends after the first half of normal and of extra time
     pub fn need_swap_squads(&self) -> bool {
+        matches!(
+            *self,
+            MatchState::FirstHalf | MatchState::ExtraTimeFirstHalf
+        )
+    }
+
+    // playing time of the period, zero for breaks and the shootout
+    pub fn duration(&self) -> u64 {
         match *self {
-            MatchState::SecondHalf | MatchState::ExtraTime => true,
-            _ => false,
+            MatchState::FirstHalf | MatchState::SecondHalf => MATCH_HALF_TIME_MS,
+            MatchState::ExtraTimeFirstHalf | MatchState::ExtraTimeSecondHalf => {
+                EXTRA_TIME_HALF_MS
+            }
+            _ => 0,
         }
     }
+
+    pub fn is_extra_time(&self) -> bool {
+        matches!(
+            *self,
+            MatchState::ExtraTimeFirstHalf | MatchState::ExtraTimeSecondHalf
+        )
+    }
 }
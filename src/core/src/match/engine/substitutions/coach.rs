@@ -0,0 +1,426 @@
+use crate::r#match::{
+    MatchContext, MatchField, MatchPlayer, Substitution, SubstitutionReason, TimelineEventType,
+};
+use crate::PlayerFieldPositionGroup;
+use log::debug;
+
+// the coach looks over the team every few minutes, injuries are seen at once
+const REVIEW_INTERVAL_MINUTES: u64 = 5;
+
+// condition in percent below which a player is taken off tired, later in the
+// match the coach wants fresh legs sooner
+const FATIGUE_CONDITION: u32 = 60;
+const FRESH_LEGS_CONDITION: u32 = 80;
+const FRESH_LEGS_MINUTE: u64 = 60;
+
+// booked defenders and midfielders are spared a second yellow from here on
+const CARD_RISK_MINUTE: u64 = 60;
+
+// a team behind brings on fresh attackers, a team ahead fresh defenders
+const CHASING_MINUTE: u64 = 60;
+const PROTECTING_MINUTE: u64 = 75;
+const TACTICAL_CONDITION: u32 = 90;
+
+// changes apart from injuries the coach makes at one stoppage
+const MAX_CHANGES_PER_WINDOW: usize = 2;
+
+pub struct SubstitutionCoach;
+
+impl SubstitutionCoach {
+    // Both coaches ask for the changes they need, they are made at the next stoppage
+    pub fn review(field: &MatchField, context: &mut MatchContext, is_break: bool) {
+        let team_ids = [
+            context.score.home_team.team_id,
+            context.score.away_team.team_id,
+        ];
+
+        for team_id in team_ids {
+            for substitution in Self::decide(team_id, field, context, is_break) {
+                debug!("Substitution requested: {:?}", substitution);
+
+                context.substitutions.request(substitution, is_break);
+            }
+        }
+    }
+
+    pub fn decide(
+        team_id: u32,
+        field: &MatchField,
+        context: &MatchContext,
+        is_break: bool,
+    ) -> Vec<Substitution> {
+        let substitutions = &context.substitutions;
+
+        if !substitutions.can_substitute(team_id, is_break) {
+            return Vec::new();
+        }
+
+        let minute = context.time.minute();
+        let is_review = is_break || minute.is_multiple_of(REVIEW_INTERVAL_MINUTES);
+        let goal_difference = Self::goal_difference(team_id, context);
+
+        let mut candidates: Vec<(&MatchPlayer, SubstitutionReason)> = field
+            .players
+            .iter()
+            .filter(|p| p.team_id == team_id && !substitutions.is_pending(p.id))
+            .filter_map(|p| Self::reason(p, minute, goal_difference).map(|reason| (p, reason)))
+            .filter(|(_, reason)| is_review || *reason == SubstitutionReason::Injury)
+            .collect();
+
+        // injuries first, then the most tired
+        candidates.sort_by(|(a, a_reason), (b, b_reason)| {
+            Self::priority(*a_reason)
+                .cmp(&Self::priority(*b_reason))
+                .then(
+                    a.player_attributes
+                        .condition
+                        .cmp(&b.player_attributes.condition),
+                )
+        });
+
+        let mut bench: Vec<&MatchPlayer> = field
+            .substitutes
+            .iter()
+            .filter(|p| {
+                p.team_id == team_id
+                    && !p.player_attributes.is_injured
+                    && !substitutions.is_pending(p.id)
+            })
+            .collect();
+
+        let mut remaining = substitutions.remaining(team_id) as usize;
+        let mut changes = 0;
+
+        let mut result = Vec::new();
+
+        for (player, reason) in candidates {
+            if remaining == 0 {
+                break;
+            }
+
+            if reason != SubstitutionReason::Injury && changes >= MAX_CHANGES_PER_WINDOW {
+                continue;
+            }
+
+            if let Some(idx) = Self::replacement(player, reason, goal_difference, &bench) {
+                let replacement = bench.remove(idx);
+
+                result.push(Substitution {
+                    team_id,
+                    player_out_id: player.id,
+                    player_in_id: replacement.id,
+                    reason,
+                    time: context.time.time,
+                });
+
+                remaining -= 1;
+
+                if reason != SubstitutionReason::Injury {
+                    changes += 1;
+                }
+            }
+        }
+
+        result
+    }
+
+    // Requested changes are made while play is stopped, the player on the ball
+    // waits until the ball is played
+    pub fn make_substitutions(field: &mut MatchField, context: &mut MatchContext) {
+        let time = context.time.time;
+
+        for (substitution, is_break) in context.substitutions.take_pending() {
+            let is_on_ball = field.ball.current_owner == Some(substitution.player_out_id)
+                || context.set_piece.as_ref().is_some_and(|set_piece| {
+                    set_piece.taker_id == Some(substitution.player_out_id)
+                });
+
+            if is_on_ball {
+                context.substitutions.request(substitution, is_break);
+                continue;
+            }
+
+            // the player may have been sent off meanwhile
+            if field.substitute(&substitution, time) {
+                debug!("Substitution made: {:?}", substitution);
+
+                context.timeline.add(
+                    time,
+                    TimelineEventType::Substitution {
+                        team_id: substitution.team_id,
+                        player_out_id: substitution.player_out_id,
+                        player_in_id: substitution.player_in_id,
+                        reason: substitution.reason,
+                    },
+                );
+
+                context.substitutions.complete(substitution, is_break, time);
+            }
+        }
+    }
+
+    fn reason(
+        player: &MatchPlayer,
+        minute: u64,
+        goal_difference: i16,
+    ) -> Option<SubstitutionReason> {
+        if player.player_attributes.is_injured {
+            return Some(SubstitutionReason::Injury);
+        }
+
+        let position_group = player.tactical_position.current_position.position_group();
+
+        // keepers only come off hurt
+        if position_group == PlayerFieldPositionGroup::Goalkeeper {
+            return None;
+        }
+
+        let condition = player.player_attributes.condition_percentage();
+
+        if condition < FATIGUE_CONDITION
+            || (minute >= FRESH_LEGS_MINUTE && condition < FRESH_LEGS_CONDITION)
+        {
+            return Some(SubstitutionReason::Fatigue);
+        }
+
+        let is_defensive = matches!(
+            position_group,
+            PlayerFieldPositionGroup::Defender | PlayerFieldPositionGroup::Midfielder
+        );
+
+        if is_defensive && minute >= CARD_RISK_MINUTE && player.statistics.yellow_cards() > 0 {
+            return Some(SubstitutionReason::Card);
+        }
+
+        if condition < TACTICAL_CONDITION {
+            let is_chasing = goal_difference < 0
+                && minute >= CHASING_MINUTE
+                && position_group != PlayerFieldPositionGroup::Forward;
+
+            let is_protecting = goal_difference > 0
+                && minute >= PROTECTING_MINUTE
+                && position_group != PlayerFieldPositionGroup::Defender;
+
+            if is_chasing || is_protecting {
+                return Some(SubstitutionReason::Tactical);
+            }
+        }
+
+        None
+    }
+
+    fn priority(reason: SubstitutionReason) -> u8 {
+        match reason {
+            SubstitutionReason::Injury => 0,
+            SubstitutionReason::Fatigue => 1,
+            SubstitutionReason::Card => 2,
+            SubstitutionReason::Tactical => 3,
+        }
+    }
+
+    // Keepers are replaced by keepers, outfield players by the best fresh player
+    // for the same part of the pitch
+    fn replacement(
+        player: &MatchPlayer,
+        reason: SubstitutionReason,
+        goal_difference: i16,
+        bench: &[&MatchPlayer],
+    ) -> Option<usize> {
+        let position_group = player.tactical_position.current_position.position_group();
+
+        let preferred_group = match reason {
+            SubstitutionReason::Tactical if goal_difference < 0 => {
+                PlayerFieldPositionGroup::Forward
+            }
+            SubstitutionReason::Tactical => PlayerFieldPositionGroup::Defender,
+            _ => position_group,
+        };
+
+        let is_goalkeeper = |p: &MatchPlayer| p.tactical_position.current_position.is_goalkeeper();
+
+        bench
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| is_goalkeeper(p) == is_goalkeeper(player))
+            .map(|(idx, p)| {
+                let mut rating = p.player_attributes.current_ability as f32
+                    + p.player_attributes.condition_percentage() as f32 / 5.0;
+
+                if p.tactical_position.current_position.position_group() == preferred_group {
+                    rating += 100.0;
+                }
+
+                (idx, rating)
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(idx, _)| idx)
+    }
+
+    // Two-legged ties are judged on aggregate
+    pub fn goal_difference(team_id: u32, context: &MatchContext) -> i16 {
+        let (home_goals, away_goals) = context.goals();
+        let (first_leg_home, first_leg_away) = context.config.first_leg.unwrap_or((0, 0));
+
+        let difference = (home_goals as i16 + first_leg_home as i16)
+            - (away_goals as i16 + first_leg_away as i16);
+
+        if team_id == context.score.home_team.team_id {
+            difference
+        } else {
+            -difference
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::testing::{self, POSITIONS};
+    use crate::r#match::TeamSquad;
+    use crate::PlayerPositionType;
+
+    const BENCH: [PlayerPositionType; 3] = [
+        PlayerPositionType::Goalkeeper,
+        PlayerPositionType::DefenderCenter,
+        PlayerPositionType::Striker,
+    ];
+
+    fn squad(team_id: u32) -> TeamSquad {
+        let mut squad = testing::squad_with_substitutes(team_id, &POSITIONS, &BENCH);
+
+        for player in squad.main_squad.iter_mut().chain(squad.substitutes.iter_mut()) {
+            player.player_attributes.condition = 10000;
+            player.player_attributes.is_injured = false;
+        }
+
+        squad
+    }
+
+    fn setup(minute: u64) -> (MatchField, MatchContext) {
+        testing::match_at(squad(1), squad(2), minute)
+    }
+
+    #[test]
+    fn fresh_team_is_left_alone() {
+        let (field, context) = setup(60);
+
+        assert!(SubstitutionCoach::decide(1, &field, &context, false).is_empty());
+    }
+
+    #[test]
+    fn injured_keeper_is_replaced_by_keeper_at_once() {
+        let (mut field, context) = setup(33);
+
+        field
+            .get_player_mut(100)
+            .unwrap()
+            .player_attributes
+            .is_injured = true;
+
+        let substitutions = SubstitutionCoach::decide(1, &field, &context, false);
+
+        assert_eq!(1, substitutions.len());
+        assert_eq!(100, substitutions[0].player_out_id);
+        assert_eq!(120, substitutions[0].player_in_id);
+        assert_eq!(SubstitutionReason::Injury, substitutions[0].reason);
+    }
+
+    #[test]
+    fn tired_players_are_replaced_at_a_review() {
+        let (mut field, context) = setup(70);
+
+        field
+            .get_player_mut(102)
+            .unwrap()
+            .player_attributes
+            .condition = 4000;
+        field
+            .get_player_mut(110)
+            .unwrap()
+            .player_attributes
+            .condition = 5000;
+
+        let substitutions = SubstitutionCoach::decide(1, &field, &context, false);
+
+        assert_eq!(2, substitutions.len());
+        assert_eq!(
+            (102, 121),
+            (
+                substitutions[0].player_out_id,
+                substitutions[0].player_in_id
+            )
+        );
+        assert_eq!(
+            (110, 122),
+            (
+                substitutions[1].player_out_id,
+                substitutions[1].player_in_id
+            )
+        );
+
+        // between reviews only injuries are dealt with
+        let (mut field, context) = setup(71);
+
+        field
+            .get_player_mut(102)
+            .unwrap()
+            .player_attributes
+            .condition = 4000;
+
+        assert!(SubstitutionCoach::decide(1, &field, &context, false).is_empty());
+    }
+
+    #[test]
+    fn team_behind_brings_on_a_forward_late() {
+        let (mut field, context) = setup(65);
+
+        context.score.increment_away_goals();
+        field
+            .get_player_mut(106)
+            .unwrap()
+            .player_attributes
+            .condition = 8500;
+
+        let substitutions = SubstitutionCoach::decide(1, &field, &context, false);
+
+        assert_eq!(1, substitutions.len());
+        assert_eq!(SubstitutionReason::Tactical, substitutions[0].reason);
+        assert_eq!(
+            (106, 122),
+            (
+                substitutions[0].player_out_id,
+                substitutions[0].player_in_id
+            )
+        );
+    }
+
+    #[test]
+    fn substitute_takes_over_place_of_replaced_player() {
+        let (mut field, mut context) = setup(60);
+
+        field
+            .get_player_mut(105)
+            .unwrap()
+            .player_attributes
+            .condition = 3000;
+
+        SubstitutionCoach::review(&field, &mut context, false);
+        SubstitutionCoach::make_substitutions(&mut field, &mut context);
+
+        let player_in = field.players.iter().find(|p| p.id == 122).unwrap();
+
+        assert_eq!(
+            PlayerPositionType::MidfielderLeft,
+            player_in.tactical_position.current_position
+        );
+        assert!(player_in.statistics.is_substitute());
+        assert!(field.substituted.iter().any(|p| p.id == 105));
+        assert_eq!(1, context.substitutions.made_by(1));
+        assert_eq!(1, context.substitutions.windows_used(1));
+
+        let squad = field.left_side_players.as_ref().unwrap();
+
+        assert_eq!(1, squad.substitutions.len());
+        assert_eq!(105, squad.substitutions[0].player_out_id);
+    }
+}
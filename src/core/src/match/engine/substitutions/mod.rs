@@ -0,0 +1,5 @@
+pub mod coach;
+pub mod substitution;
+
+pub use coach::*;
+pub use substitution::*;
@@ -0,0 +1,168 @@
+use crate::r#match::MatchConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SubstitutionReason {
+    Injury,
+    Fatigue,
+    Card,
+    Tactical,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Substitution {
+    pub team_id: u32,
+    pub player_out_id: u32,
+    pub player_in_id: u32,
+    pub reason: SubstitutionReason,
+    pub time: u64,
+}
+
+// Changes of both teams in a match: the coach asks for them, they are made at the
+// next stoppage. Changes at a break don't use up one of the windows
+#[derive(Debug, Clone)]
+pub struct Substitutions {
+    pub max_substitutions: u8,
+    pub max_windows: u8,
+    pub made: Vec<Substitution>,
+    // requested changes and whether they were asked for at a break
+    pending: Vec<(Substitution, bool)>,
+    windows: Vec<(u32, u64)>,
+}
+
+impl Substitutions {
+    pub fn new(config: &MatchConfig) -> Self {
+        Substitutions {
+            max_substitutions: config.substitutions,
+            max_windows: config.substitution_windows,
+            made: Vec::new(),
+            pending: Vec::new(),
+            windows: Vec::new(),
+        }
+    }
+
+    // Extra time brings one more change and one more window
+    pub fn extend_for_extra_time(&mut self) {
+        self.max_substitutions += 1;
+        self.max_windows += 1;
+    }
+
+    pub fn made_by(&self, team_id: u32) -> u8 {
+        self.made.iter().filter(|s| s.team_id == team_id).count() as u8
+    }
+
+    pub fn remaining(&self, team_id: u32) -> u8 {
+        let pending = self
+            .pending
+            .iter()
+            .filter(|(s, _)| s.team_id == team_id)
+            .count() as u8;
+
+        self.max_substitutions
+            .saturating_sub(self.made_by(team_id) + pending)
+    }
+
+    pub fn windows_used(&self, team_id: u32) -> u8 {
+        self.windows.iter().filter(|(id, _)| *id == team_id).count() as u8
+    }
+
+    pub fn can_substitute(&self, team_id: u32, is_break: bool) -> bool {
+        self.remaining(team_id) > 0 && (is_break || self.windows_used(team_id) < self.max_windows)
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    pub fn is_pending(&self, player_id: u32) -> bool {
+        self.pending
+            .iter()
+            .any(|(s, _)| s.player_out_id == player_id || s.player_in_id == player_id)
+    }
+
+    pub fn request(&mut self, substitution: Substitution, is_break: bool) {
+        self.pending.push((substitution, is_break));
+    }
+
+    pub fn take_pending(&mut self) -> Vec<(Substitution, bool)> {
+        std::mem::take(&mut self.pending)
+    }
+
+    // A team opens a window with its first change at a stoppage
+    pub fn complete(&mut self, mut substitution: Substitution, is_break: bool, time: u64) {
+        let is_new_window = !self
+            .windows
+            .iter()
+            .any(|&(team_id, window)| team_id == substitution.team_id && window == time);
+
+        if is_new_window && !is_break {
+            self.windows.push((substitution.team_id, time));
+        }
+
+        substitution.time = time;
+
+        self.made.push(substitution);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn substitution(team_id: u32, player_out_id: u32, player_in_id: u32) -> Substitution {
+        Substitution {
+            team_id,
+            player_out_id,
+            player_in_id,
+            reason: SubstitutionReason::Fatigue,
+            time: 0,
+        }
+    }
+
+    #[test]
+    fn changes_at_one_stoppage_use_one_window() {
+        let mut substitutions = Substitutions::new(&MatchConfig::default());
+
+        substitutions.complete(substitution(1, 10, 20), false, 1000);
+        substitutions.complete(substitution(1, 11, 21), false, 1000);
+        substitutions.complete(substitution(2, 30, 40), false, 1000);
+
+        assert_eq!(1, substitutions.windows_used(1));
+        assert_eq!(2, substitutions.made_by(1));
+        assert_eq!(3, substitutions.remaining(1));
+
+        substitutions.complete(substitution(1, 12, 22), false, 2000);
+
+        assert_eq!(2, substitutions.windows_used(1));
+        assert_eq!(1, substitutions.windows_used(2));
+    }
+
+    #[test]
+    fn team_runs_out_of_windows_but_not_breaks() {
+        let mut substitutions = Substitutions::new(&MatchConfig::default());
+
+        for window in 1..=3 {
+            substitutions.complete(substitution(1, window, window + 10), false, window as u64);
+        }
+
+        assert!(!substitutions.can_substitute(1, false));
+        assert!(substitutions.can_substitute(1, true));
+
+        substitutions.request(substitution(1, 4, 14), true);
+
+        assert!(substitutions.is_pending(14));
+        assert_eq!(1, substitutions.remaining(1));
+
+        for (pending, is_break) in substitutions.take_pending() {
+            substitutions.complete(pending, is_break, 10);
+        }
+
+        assert_eq!(3, substitutions.windows_used(1));
+        assert_eq!(1, substitutions.remaining(1));
+
+        substitutions.extend_for_extra_time();
+
+        assert!(substitutions.can_substitute(1, false));
+        assert_eq!(2, substitutions.remaining(1));
+    }
+}
@@ -0,0 +1,486 @@
+use crate::r#match::{
+    MatchContext, MatchField, MatchPlayer, PlayerSide, PositionType, SubstitutionCoach,
+    TimelineEventType, POSITION_POSITIONING,
+};
+use crate::{
+    DefensiveLine, MatchTacticType, PassingDirectness, PlayerFieldPositionGroup,
+    PlayerPositionType, PressingIntensity, Tactics, Tempo, TACTICS_POSITIONS,
+};
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+// the coach rethinks the plan every quarter of an hour and at the breaks, and
+// straight away after conceding or losing a player
+const REVIEW_INTERVAL_MINUTES: u64 = 15;
+
+// a team behind from here on goes for it, earlier when two or more down
+const CHASING_MINUTE: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TacticalChangeReason {
+    Review,
+    Conceded,
+    RedCard,
+    LateGame,
+}
+
+// What the coach saw at the last look, to notice what happened since
+#[derive(Debug, Clone)]
+struct TeamTacticalState {
+    team_id: u32,
+    kick_off: Tactics,
+    goals_conceded: u8,
+    sent_off: usize,
+    is_late_game: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TacticalChanges {
+    teams: Vec<TeamTacticalState>,
+}
+
+impl TacticalChanges {
+    pub fn from_field(field: &MatchField, team_ids: [u32; 2]) -> Self {
+        TacticalChanges {
+            teams: team_ids
+                .iter()
+                .filter_map(|team_id| {
+                    field.team_side(*team_id).map(|side| TeamTacticalState {
+                        team_id: *team_id,
+                        kick_off: field.team_tactics(side).clone(),
+                        goals_conceded: 0,
+                        sent_off: 0,
+                        is_late_game: false,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    fn team_mut(&mut self, team_id: u32) -> Option<&mut TeamTacticalState> {
+        self.teams.iter_mut().find(|team| team.team_id == team_id)
+    }
+}
+
+pub struct TacticalCoach;
+
+impl TacticalCoach {
+    pub fn review(field: &mut MatchField, context: &mut MatchContext, is_break: bool) {
+        let team_ids = [
+            context.score.home_team.team_id,
+            context.score.away_team.team_id,
+        ];
+
+        for team_id in team_ids {
+            let Some(reason) = Self::reason(team_id, field, context, is_break) else {
+                continue;
+            };
+
+            let Some(side) = field.team_side(team_id) else {
+                continue;
+            };
+
+            let Some(kick_off) = context
+                .tactical_changes
+                .team_mut(team_id)
+                .map(|team| team.kick_off.clone())
+            else {
+                continue;
+            };
+
+            let current = field.team_tactics(side).clone();
+            let tactics = Self::plan(team_id, &kick_off, field, context);
+
+            // a player short the others close the gap even if the plan stays
+            if tactics.tactic_type == current.tactic_type
+                && tactics.instructions == current.instructions
+                && reason != TacticalChangeReason::RedCard
+            {
+                continue;
+            }
+
+            let players: Vec<&MatchPlayer> = field
+                .players
+                .iter()
+                .filter(|p| p.team_id == team_id)
+                .collect();
+
+            let positions = Self::assign_positions(&players, tactics.positions());
+
+            debug!(
+                "Tactical change for team {}: {} {:?} ({:?})",
+                team_id,
+                tactics.tactic_type.name(),
+                tactics.instructions.mentality,
+                reason
+            );
+
+            context.timeline.add(
+                context.time.time,
+                TimelineEventType::TacticalChange {
+                    team_id,
+                    reason,
+                    tactic_type: tactics.tactic_type,
+                    instructions: tactics.instructions,
+                },
+            );
+
+            match side {
+                PlayerSide::Left => context.tactics.left = tactics.clone(),
+                PlayerSide::Right => context.tactics.right = tactics.clone(),
+            }
+
+            field.change_tactics(side, tactics, &positions);
+        }
+    }
+
+    // Why the coach looks at the plan now, if at all
+    fn reason(
+        team_id: u32,
+        field: &MatchField,
+        context: &mut MatchContext,
+        is_break: bool,
+    ) -> Option<TacticalChangeReason> {
+        let (home_goals, away_goals) = context.goals();
+
+        let goals_conceded = if team_id == context.score.home_team.team_id {
+            away_goals
+        } else {
+            home_goals
+        };
+
+        let sent_off = field
+            .sent_off
+            .iter()
+            .filter(|p| p.team_id == team_id)
+            .count();
+
+        let is_late_game = context.time.is_running_out();
+        let is_interval = context
+            .time
+            .minute()
+            .is_multiple_of(REVIEW_INTERVAL_MINUTES);
+
+        let team = context.tactical_changes.team_mut(team_id)?;
+
+        let reason = if sent_off > team.sent_off {
+            Some(TacticalChangeReason::RedCard)
+        } else if goals_conceded > team.goals_conceded {
+            Some(TacticalChangeReason::Conceded)
+        } else if is_late_game && !team.is_late_game {
+            Some(TacticalChangeReason::LateGame)
+        } else if is_break || is_interval {
+            Some(TacticalChangeReason::Review)
+        } else {
+            None
+        };
+
+        team.goals_conceded = goals_conceded;
+        team.sent_off = sent_off;
+        team.is_late_game = is_late_game;
+
+        reason
+    }
+
+    // Starts from the plan the team kicked off with: a team behind pushes on, a
+    // team holding a narrow lead late on sits deeper, a man down shores up
+    fn plan(
+        team_id: u32,
+        kick_off: &Tactics,
+        field: &MatchField,
+        context: &MatchContext,
+    ) -> Tactics {
+        let goal_difference = SubstitutionCoach::goal_difference(team_id, context);
+        let minute = context.time.minute();
+        let is_late_game = context.time.is_running_out();
+
+        let players = field
+            .players
+            .iter()
+            .filter(|p| p.team_id == team_id)
+            .count();
+        let opponents = field.players.len() - players;
+
+        let mut tactics = kick_off.clone();
+        let instructions = &mut tactics.instructions;
+
+        let is_chasing = goal_difference < 0 && (minute >= CHASING_MINUTE || goal_difference <= -2);
+        let is_protecting = goal_difference == 1 && is_late_game;
+
+        if is_chasing {
+            instructions.mentality = instructions.mentality.more_attacking();
+            instructions.pressing = PressingIntensity::High;
+            instructions.defensive_line = DefensiveLine::High;
+            instructions.tempo = Tempo::Fast;
+
+            if is_late_game {
+                instructions.mentality = instructions.mentality.more_attacking();
+                instructions.passing = PassingDirectness::Direct;
+
+                tactics.tactic_type = Self::alternative(kick_off.tactic_type, true);
+            }
+        } else if is_protecting {
+            instructions.mentality = instructions.mentality.more_defensive();
+            instructions.pressing = PressingIntensity::Low;
+            instructions.defensive_line = DefensiveLine::Deep;
+            instructions.tempo = Tempo::Slow;
+
+            tactics.tactic_type = Self::alternative(kick_off.tactic_type, false);
+        }
+
+        if players < opponents && !(is_chasing && is_late_game) {
+            instructions.mentality = instructions.mentality.more_defensive();
+            instructions.pressing = PressingIntensity::Low;
+
+            tactics.tactic_type = Self::alternative(tactics.tactic_type, false);
+        } else if players > opponents && !is_protecting {
+            instructions.mentality = instructions.mentality.more_attacking();
+        }
+
+        tactics.instructions.width = tactics.tactic_type.natural_width();
+
+        tactics
+    }
+
+    // The formation with the same back line and the most, or fewest, players up front
+    fn alternative(tactic_type: MatchTacticType, is_attacking: bool) -> MatchTacticType {
+        let positions = Self::positions(tactic_type);
+        let back_line = Self::back_line(positions);
+        let attackers = Self::attackers(positions);
+
+        let mut best = (tactic_type, attackers);
+
+        for (candidate, positions) in TACTICS_POSITIONS {
+            if Self::back_line(positions) != back_line {
+                continue;
+            }
+
+            let candidate_attackers = Self::attackers(positions);
+
+            let is_better = if is_attacking {
+                candidate_attackers > best.1
+            } else {
+                candidate_attackers < best.1
+            };
+
+            if is_better {
+                best = (*candidate, candidate_attackers);
+            }
+        }
+
+        best.0
+    }
+
+    fn positions(tactic_type: MatchTacticType) -> &'static [PlayerPositionType; 11] {
+        TACTICS_POSITIONS
+            .iter()
+            .find(|(t, _)| *t == tactic_type)
+            .map(|(_, positions)| positions)
+            .expect("formation without positions")
+    }
+
+    fn back_line(positions: &[PlayerPositionType]) -> usize {
+        positions
+            .iter()
+            .filter(|p| {
+                p.position_group() == PlayerFieldPositionGroup::Defender
+                    && **p != PlayerPositionType::DefensiveMidfielder
+            })
+            .count()
+    }
+
+    fn attackers(positions: &[PlayerPositionType]) -> f32 {
+        positions
+            .iter()
+            .map(|position| match position {
+                PlayerPositionType::AttackingMidfielderLeft
+                | PlayerPositionType::AttackingMidfielderCenter
+                | PlayerPositionType::AttackingMidfielderRight => 0.5,
+                _ if position.position_group() == PlayerFieldPositionGroup::Forward => 1.0,
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    // Players keep their order from back to front and from one flank to the other,
+    // a side short leaves a central place in its busiest line empty
+    pub fn assign_positions(
+        players: &[&MatchPlayer],
+        formation: &[PlayerPositionType; 11],
+    ) -> Vec<(u32, PlayerPositionType)> {
+        let (keepers, mut outfield): (Vec<&MatchPlayer>, Vec<&MatchPlayer>) = players
+            .iter()
+            .partition(|p| p.tactical_position.current_position.is_goalkeeper());
+
+        let mut places: Vec<PlayerPositionType> = formation
+            .iter()
+            .filter(|p| !p.is_goalkeeper())
+            .copied()
+            .collect();
+
+        while places.len() > outfield.len() {
+            let busiest = [
+                PlayerFieldPositionGroup::Defender,
+                PlayerFieldPositionGroup::Midfielder,
+                PlayerFieldPositionGroup::Forward,
+            ]
+            .into_iter()
+            .max_by_key(|group| {
+                places
+                    .iter()
+                    .filter(|p| p.position_group() == *group)
+                    .count()
+            })
+            .unwrap();
+
+            let idx = places
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.position_group() == busiest)
+                .min_by(|(_, a), (_, b)| {
+                    Self::distance_from_middle(**a).total_cmp(&Self::distance_from_middle(**b))
+                })
+                .map(|(idx, _)| idx)
+                .unwrap();
+
+            places.remove(idx);
+        }
+
+        places.sort_by(|a, b| Self::order(*a).partial_cmp(&Self::order(*b)).unwrap());
+        outfield.sort_by(|a, b| {
+            Self::order(a.tactical_position.current_position)
+                .partial_cmp(&Self::order(b.tactical_position.current_position))
+                .unwrap()
+        });
+
+        keepers
+            .iter()
+            .map(|p| (p.id, PlayerPositionType::Goalkeeper))
+            .chain(
+                outfield
+                    .iter()
+                    .zip(places)
+                    .map(|(p, position)| (p.id, position)),
+            )
+            .collect()
+    }
+
+    // Line of the pitch first, then across it
+    fn order(position: PlayerPositionType) -> (u8, f32) {
+        let line = match position.position_group() {
+            PlayerFieldPositionGroup::Goalkeeper => 0,
+            PlayerFieldPositionGroup::Defender => 1,
+            PlayerFieldPositionGroup::Midfielder => 2,
+            PlayerFieldPositionGroup::Forward => 3,
+        };
+
+        (line, Self::home_position(position).1)
+    }
+
+    fn distance_from_middle(position: PlayerPositionType) -> f32 {
+        let (_, y) = Self::home_position(position);
+        let (_, middle) = Self::home_position(PlayerPositionType::Goalkeeper);
+
+        (y - middle).abs()
+    }
+
+    fn home_position(position: PlayerPositionType) -> (f32, f32) {
+        POSITION_POSITIONING
+            .iter()
+            .find(|(p, _, _)| *p == position)
+            .and_then(|(_, home, _)| match home {
+                PositionType::Home(x, y) => Some((*x as f32, *y as f32)),
+                PositionType::Away(_, _) => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::testing;
+    use crate::r#match::TeamSquad;
+    use crate::Mentality;
+    use std::collections::HashSet;
+
+    fn squad(team_id: u32) -> TeamSquad {
+        let mut squad =
+            testing::squad_with(team_id, TacticalCoach::positions(MatchTacticType::T451));
+        squad.tactics = Tactics::new(MatchTacticType::T451);
+
+        squad
+    }
+
+    fn setup(minute: u64) -> (MatchField, MatchContext) {
+        testing::match_at(squad(1), squad(2), minute)
+    }
+
+    fn forwards(field: &MatchField, team_id: u32) -> usize {
+        field
+            .players
+            .iter()
+            .filter(|p| p.team_id == team_id)
+            .filter(|p| {
+                p.tactical_position.current_position.position_group()
+                    == PlayerFieldPositionGroup::Forward
+            })
+            .count()
+    }
+
+    #[test]
+    fn team_behind_late_goes_for_it() {
+        let (mut field, mut context) = setup(70);
+
+        context.score.increment_away_goals();
+
+        TacticalCoach::review(&mut field, &mut context, false);
+
+        let side = field.team_side(1).unwrap();
+        let tactics = field.team_tactics(side);
+
+        assert_eq!(Mentality::VeryAttacking, tactics.instructions.mentality);
+        assert_ne!(MatchTacticType::T451, tactics.tactic_type);
+        assert!(forwards(&field, 1) > 1);
+
+        assert!(context.timeline.events.iter().any(|e| matches!(
+            e.event,
+            TimelineEventType::TacticalChange {
+                team_id: 1,
+                reason: TacticalChangeReason::Conceded,
+                ..
+            }
+        )));
+
+        // the winners hold on to what they have
+        let side = field.team_side(2).unwrap();
+
+        assert_eq!(
+            Mentality::Defensive,
+            field.team_tactics(side).instructions.mentality
+        );
+    }
+
+    #[test]
+    fn team_a_man_down_fills_the_gap() {
+        let (mut field, mut context) = setup(30);
+
+        field.send_off(105);
+
+        TacticalCoach::review(&mut field, &mut context, false);
+
+        let players: Vec<&MatchPlayer> = field.players.iter().filter(|p| p.team_id == 1).collect();
+
+        assert_eq!(10, players.len());
+        assert!(players
+            .iter()
+            .any(|p| p.tactical_position.current_position.is_goalkeeper()));
+
+        let positions: HashSet<PlayerPositionType> = players
+            .iter()
+            .map(|p| p.tactical_position.current_position)
+            .collect();
+
+        assert_eq!(10, positions.len());
+    }
+}
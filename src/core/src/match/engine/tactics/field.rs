@@ -44,12 +44,12 @@ pub const POSITION_POSITIONING: &[(PlayerPositionType, PositionType, PositionTyp
     (
         PlayerPositionType::WingbackLeft,
         PositionType::Home(235, 50),
-        PositionType::Away(625, 50),
+        PositionType::Away(625, 480),
     ),
     (
         PlayerPositionType::WingbackRight,
         PositionType::Home(235, 480),
-        PositionType::Away(625, 480),
+        PositionType::Away(625, 50),
     ),
     (
         PlayerPositionType::MidfielderLeft,
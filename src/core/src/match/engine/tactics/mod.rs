@@ -1,6 +1,8 @@
+pub mod adjustments;
 pub mod field;
 pub mod positions;
 pub mod paths;
 
+pub use adjustments::*;
 pub use field::*;
 pub use positions::*;
\ No newline at end of file
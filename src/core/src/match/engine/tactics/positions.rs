@@ -1,4 +1,4 @@
-use crate::PlayerPositionType;
+use crate::{PlayerPositionType, TacticalRole, TeamInstructions};
 
 #[derive(Debug, Clone)]
 pub struct MatchTacticalPosition {
@@ -9,6 +9,7 @@ pub struct MatchTacticalPosition {
 #[derive(Debug, Clone)]
 pub struct TacticalPositions {
     pub current_position: PlayerPositionType,
+    pub role: TacticalRole,
     pub tactical_positions: Vec<MatchTacticalPosition>
 }
 
@@ -16,6 +17,7 @@ impl TacticalPositions {
     pub fn new(current_position: PlayerPositionType) -> Self {
         TacticalPositions {
             current_position,
+            role: TacticalRole::for_position(current_position, &TeamInstructions::default()),
             tactical_positions: Vec::new()
         }
     }
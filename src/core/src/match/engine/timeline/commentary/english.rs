@@ -0,0 +1,165 @@
+use crate::r#match::{
+    CommentaryNames, MatchCommentary, MatchState, SubstitutionReason, TimelineEventType,
+};
+use crate::Mentality;
+
+// Shots above this are chances the crowd expects to go in
+const BIG_CHANCE_XG: f32 = 0.3;
+
+pub struct EnglishCommentary;
+
+impl MatchCommentary for EnglishCommentary {
+    fn comment(&self, event: &TimelineEventType, names: &CommentaryNames) -> Option<String> {
+        let text = match *event {
+            TimelineEventType::KickOff { period, team_id } => {
+                let team = names.team(team_id);
+
+                match period {
+                    MatchState::FirstHalf => format!("{} kick off the match.", team),
+                    MatchState::SecondHalf => format!("{} get the second half under way.", team),
+                    MatchState::ExtraTimeFirstHalf => {
+                        format!("Extra time begins, {} kick off.", team)
+                    }
+                    MatchState::ExtraTimeSecondHalf => {
+                        format!("{} start the second half of extra time.", team)
+                    }
+                    _ => return None,
+                }
+            }
+            TimelineEventType::PeriodEnd { period } => match period {
+                MatchState::FirstHalf => String::from("Half-time."),
+                MatchState::SecondHalf => String::from("The referee blows for full-time."),
+                MatchState::ExtraTimeFirstHalf => String::from("Half-time in extra time."),
+                MatchState::ExtraTimeSecondHalf => String::from("The end of extra time."),
+                MatchState::PenaltyShootout => String::from("The shootout is over."),
+                _ => return None,
+            },
+            TimelineEventType::Shot {
+                player_id,
+                is_on_target,
+                expected_goals,
+            } => {
+                let player = names.player(player_id);
+
+                if is_on_target {
+                    format!("{} tests the goalkeeper.", player)
+                } else if expected_goals >= BIG_CHANCE_XG {
+                    format!("{} misses a great chance!", player)
+                } else {
+                    format!("{} shoots wide.", player)
+                }
+            }
+            TimelineEventType::Save {
+                goalkeeper_id,
+                shooter_id,
+            } => format!(
+                "Save by {} from {}.",
+                names.player(goalkeeper_id),
+                names.player(shooter_id)
+            ),
+            TimelineEventType::Goal {
+                player_id,
+                team_id,
+                assist_player_id,
+            } => match assist_player_id {
+                Some(assist_id) => format!(
+                    "GOAL! {} scores for {}, set up by {}.",
+                    names.player(player_id),
+                    names.team(team_id),
+                    names.player(assist_id)
+                ),
+                None => format!(
+                    "GOAL! {} scores for {}!",
+                    names.player(player_id),
+                    names.team(team_id)
+                ),
+            },
+            TimelineEventType::Foul {
+                offender_id,
+                fouled_player_id,
+            } => format!(
+                "Foul by {} on {}.",
+                names.player(offender_id),
+                names.player(fouled_player_id)
+            ),
+            TimelineEventType::PenaltyAwarded { team_id } => {
+                format!("Penalty to {}!", names.team(team_id))
+            }
+            TimelineEventType::YellowCard { player_id } => {
+                format!("{} is booked.", names.player(player_id))
+            }
+            TimelineEventType::RedCard {
+                player_id,
+                is_second_yellow,
+            } => {
+                if is_second_yellow {
+                    format!("A second yellow, {} is sent off!", names.player(player_id))
+                } else {
+                    format!("Straight red card for {}!", names.player(player_id))
+                }
+            }
+            TimelineEventType::Offside { player_id } => {
+                format!("{} is caught offside.", names.player(player_id))
+            }
+            TimelineEventType::Corner { team_id } => {
+                format!("Corner to {}.", names.team(team_id))
+            }
+            TimelineEventType::Injury { player_id } => {
+                format!("{} is down injured.", names.player(player_id))
+            }
+            TimelineEventType::Substitution {
+                team_id,
+                player_out_id,
+                player_in_id,
+                reason,
+            } => {
+                let player_in = names.player(player_in_id);
+                let player_out = names.player(player_out_id);
+
+                match reason {
+                    SubstitutionReason::Injury => {
+                        format!("{} comes on for the injured {}.", player_in, player_out)
+                    }
+                    _ => format!(
+                        "Substitution for {}: {} replaces {}.",
+                        names.team(team_id),
+                        player_in,
+                        player_out
+                    ),
+                }
+            }
+            TimelineEventType::TacticalChange {
+                team_id,
+                tactic_type,
+                instructions,
+                ..
+            } => {
+                let team = names.team(team_id);
+                let shape = tactic_type.name();
+
+                match instructions.mentality {
+                    Mentality::Attacking | Mentality::VeryAttacking => {
+                        format!("{} push forward, now lined up in a {}.", team, shape)
+                    }
+                    Mentality::Defensive | Mentality::VeryDefensive => {
+                        format!("{} sit deeper in a {}.", team, shape)
+                    }
+                    Mentality::Balanced => format!("{} reorganise into a {}.", team, shape),
+                }
+            }
+            TimelineEventType::ShootoutKick {
+                player_id,
+                is_scored,
+                ..
+            } => {
+                if is_scored {
+                    format!("{} scores from the spot.", names.player(player_id))
+                } else {
+                    format!("{} misses the penalty!", names.player(player_id))
+                }
+            }
+        };
+
+        Some(text)
+    }
+}
@@ -0,0 +1,147 @@
+use crate::r#match::{EnglishCommentary, MatchTimeline, SpanishCommentary, TimelineEventType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Names the commentary refers to, filled in by whoever renders the match
+#[derive(Debug, Default)]
+pub struct CommentaryNames {
+    players: HashMap<u32, String>,
+    teams: HashMap<u32, String>,
+}
+
+impl CommentaryNames {
+    pub fn new() -> Self {
+        CommentaryNames::default()
+    }
+
+    pub fn add_player(&mut self, player_id: u32, name: String) {
+        self.players.insert(player_id, name);
+    }
+
+    pub fn add_team(&mut self, team_id: u32, name: String) {
+        self.teams.insert(team_id, name);
+    }
+
+    pub fn player(&self, player_id: u32) -> &str {
+        self.players.get(&player_id).map_or("?", String::as_str)
+    }
+
+    pub fn team(&self, team_id: u32) -> &str {
+        self.teams.get(&team_id).map_or("?", String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentaryLine {
+    pub minute: u64,
+    pub text: String,
+}
+
+// Turns timeline events into text, one implementation per language
+pub trait MatchCommentary {
+    // None for events not worth a line
+    fn comment(&self, event: &TimelineEventType, names: &CommentaryNames) -> Option<String>;
+
+    fn render(&self, timeline: &MatchTimeline, names: &CommentaryNames) -> Vec<CommentaryLine> {
+        timeline
+            .events
+            .iter()
+            .filter_map(|event| {
+                self.comment(&event.event, names)
+                    .map(|text| CommentaryLine {
+                        minute: event.minute(),
+                        text,
+                    })
+            })
+            .collect()
+    }
+}
+
+pub struct Commentary;
+
+impl Commentary {
+    // Commentary in the language of the code, English when there is none in it
+    pub fn for_language(language: &str) -> Box<dyn MatchCommentary + Send + Sync> {
+        match language.to_lowercase().as_str() {
+            "es" => Box::new(SpanishCommentary),
+            _ => Box::new(EnglishCommentary),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::{MatchState, SubstitutionReason};
+
+    fn names() -> CommentaryNames {
+        let mut names = CommentaryNames::new();
+
+        names.add_team(1, String::from("Reds"));
+        names.add_player(10, String::from("Smith"));
+        names.add_player(11, String::from("Jones"));
+        names.add_player(12, String::from("Brown"));
+
+        names
+    }
+
+    fn timeline() -> MatchTimeline {
+        let mut timeline = MatchTimeline::new();
+
+        timeline.add(
+            0,
+            TimelineEventType::KickOff {
+                period: MatchState::FirstHalf,
+                team_id: 1,
+            },
+        );
+        timeline.add(1000, TimelineEventType::Offside { player_id: 11 });
+        timeline.add(
+            1500,
+            TimelineEventType::Goal {
+                player_id: 10,
+                team_id: 1,
+                assist_player_id: Some(11),
+            },
+        );
+        timeline.add(
+            2000,
+            TimelineEventType::Substitution {
+                team_id: 1,
+                player_out_id: 11,
+                player_in_id: 12,
+                reason: SubstitutionReason::Injury,
+            },
+        );
+
+        timeline
+    }
+
+    #[test]
+    fn commentary_names_players_and_teams() {
+        let lines = Commentary::for_language("en").render(&timeline(), &names());
+
+        assert_eq!(4, lines.len());
+        assert_eq!(1, lines[0].minute);
+        assert!(lines[0].text.contains("Reds"));
+        assert!(lines[2].text.contains("Smith") && lines[2].text.contains("Jones"));
+        assert!(lines[3].text.contains("Brown"));
+    }
+
+    #[test]
+    fn commentary_is_localized_with_english_fallback() {
+        let spanish = Commentary::for_language("ES").render(&timeline(), &names());
+        let english = Commentary::for_language("en").render(&timeline(), &names());
+        let unknown = Commentary::for_language("xx").render(&timeline(), &names());
+
+        assert_ne!(english[2].text, spanish[2].text);
+        assert_eq!(english, unknown);
+
+        let mut nameless = CommentaryNames::new();
+        nameless.add_team(1, String::from("Reds"));
+
+        let lines = Commentary::for_language("en").render(&timeline(), &nameless);
+
+        assert!(lines[1].text.contains('?'));
+    }
+}
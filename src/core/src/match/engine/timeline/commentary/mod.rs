@@ -0,0 +1,7 @@
+pub mod english;
+pub mod generator;
+pub mod spanish;
+
+pub use english::*;
+pub use generator::*;
+pub use spanish::*;
@@ -0,0 +1,172 @@
+use crate::r#match::{
+    CommentaryNames, MatchCommentary, MatchState, SubstitutionReason, TimelineEventType,
+};
+use crate::Mentality;
+
+const BIG_CHANCE_XG: f32 = 0.3;
+
+pub struct SpanishCommentary;
+
+impl MatchCommentary for SpanishCommentary {
+    fn comment(&self, event: &TimelineEventType, names: &CommentaryNames) -> Option<String> {
+        let text = match *event {
+            TimelineEventType::KickOff { period, team_id } => {
+                let team = names.team(team_id);
+
+                match period {
+                    MatchState::FirstHalf => {
+                        format!("{} saca de centro, comienza el partido.", team)
+                    }
+                    MatchState::SecondHalf => format!("{} pone en juego la segunda parte.", team),
+                    MatchState::ExtraTimeFirstHalf => {
+                        format!("Comienza la prórroga, saca {}.", team)
+                    }
+                    MatchState::ExtraTimeSecondHalf => {
+                        format!("{} inicia la segunda parte de la prórroga.", team)
+                    }
+                    _ => return None,
+                }
+            }
+            TimelineEventType::PeriodEnd { period } => match period {
+                MatchState::FirstHalf => String::from("Descanso."),
+                MatchState::SecondHalf => String::from("Final del tiempo reglamentario."),
+                MatchState::ExtraTimeFirstHalf => String::from("Descanso de la prórroga."),
+                MatchState::ExtraTimeSecondHalf => String::from("Final de la prórroga."),
+                MatchState::PenaltyShootout => String::from("Termina la tanda de penaltis."),
+                _ => return None,
+            },
+            TimelineEventType::Shot {
+                player_id,
+                is_on_target,
+                expected_goals,
+            } => {
+                let player = names.player(player_id);
+
+                if is_on_target {
+                    format!("Disparo a puerta de {}.", player)
+                } else if expected_goals >= BIG_CHANCE_XG {
+                    format!("¡{} falla una ocasión clarísima!", player)
+                } else {
+                    format!("Disparo desviado de {}.", player)
+                }
+            }
+            TimelineEventType::Save {
+                goalkeeper_id,
+                shooter_id,
+            } => format!(
+                "Parada de {} ante {}.",
+                names.player(goalkeeper_id),
+                names.player(shooter_id)
+            ),
+            TimelineEventType::Goal {
+                player_id,
+                team_id,
+                assist_player_id,
+            } => match assist_player_id {
+                Some(assist_id) => format!(
+                    "¡GOL! {} marca para {} tras el pase de {}.",
+                    names.player(player_id),
+                    names.team(team_id),
+                    names.player(assist_id)
+                ),
+                None => format!(
+                    "¡GOL! {} marca para {}.",
+                    names.player(player_id),
+                    names.team(team_id)
+                ),
+            },
+            TimelineEventType::Foul {
+                offender_id,
+                fouled_player_id,
+            } => format!(
+                "Falta de {} sobre {}.",
+                names.player(offender_id),
+                names.player(fouled_player_id)
+            ),
+            TimelineEventType::PenaltyAwarded { team_id } => {
+                format!("¡Penalti a favor de {}!", names.team(team_id))
+            }
+            TimelineEventType::YellowCard { player_id } => {
+                format!("Tarjeta amarilla para {}.", names.player(player_id))
+            }
+            TimelineEventType::RedCard {
+                player_id,
+                is_second_yellow,
+            } => {
+                if is_second_yellow {
+                    format!(
+                        "¡Segunda amarilla, {} es expulsado!",
+                        names.player(player_id)
+                    )
+                } else {
+                    format!("¡Roja directa para {}!", names.player(player_id))
+                }
+            }
+            TimelineEventType::Offside { player_id } => {
+                format!("Fuera de juego de {}.", names.player(player_id))
+            }
+            TimelineEventType::Corner { team_id } => {
+                format!("Córner a favor de {}.", names.team(team_id))
+            }
+            TimelineEventType::Injury { player_id } => {
+                format!(
+                    "{} se queda tendido en el césped, lesionado.",
+                    names.player(player_id)
+                )
+            }
+            TimelineEventType::Substitution {
+                team_id,
+                player_out_id,
+                player_in_id,
+                reason,
+            } => {
+                let player_in = names.player(player_in_id);
+                let player_out = names.player(player_out_id);
+
+                match reason {
+                    SubstitutionReason::Injury => {
+                        format!("{} entra por el lesionado {}.", player_in, player_out)
+                    }
+                    _ => format!(
+                        "Cambio en {}: entra {} y sale {}.",
+                        names.team(team_id),
+                        player_in,
+                        player_out
+                    ),
+                }
+            }
+            TimelineEventType::TacticalChange {
+                team_id,
+                tactic_type,
+                instructions,
+                ..
+            } => {
+                let team = names.team(team_id);
+                let shape = tactic_type.name();
+
+                match instructions.mentality {
+                    Mentality::Attacking | Mentality::VeryAttacking => {
+                        format!("{} se lanza al ataque y pasa a un {}.", team, shape)
+                    }
+                    Mentality::Defensive | Mentality::VeryDefensive => {
+                        format!("{} se repliega con un {}.", team, shape)
+                    }
+                    Mentality::Balanced => format!("{} se reorganiza en un {}.", team, shape),
+                }
+            }
+            TimelineEventType::ShootoutKick {
+                player_id,
+                is_scored,
+                ..
+            } => {
+                if is_scored {
+                    format!("{} marca su penalti.", names.player(player_id))
+                } else {
+                    format!("¡{} falla el penalti!", names.player(player_id))
+                }
+            }
+        };
+
+        Some(text)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::r#match::{MatchState, SubstitutionReason, TacticalChangeReason, MATCH_MINUTE_MS};
+use crate::{MatchTacticType, TeamInstructions};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TimelineEventType {
+    KickOff {
+        period: MatchState,
+        team_id: u32,
+    },
+    PeriodEnd {
+        period: MatchState,
+    },
+    Shot {
+        player_id: u32,
+        is_on_target: bool,
+        expected_goals: f32,
+    },
+    Save {
+        goalkeeper_id: u32,
+        shooter_id: u32,
+    },
+    Goal {
+        player_id: u32,
+        team_id: u32,
+        assist_player_id: Option<u32>,
+    },
+    Foul {
+        offender_id: u32,
+        fouled_player_id: u32,
+    },
+    PenaltyAwarded {
+        team_id: u32,
+    },
+    YellowCard {
+        player_id: u32,
+    },
+    RedCard {
+        player_id: u32,
+        is_second_yellow: bool,
+    },
+    Offside {
+        player_id: u32,
+    },
+    Corner {
+        team_id: u32,
+    },
+    Injury {
+        player_id: u32,
+    },
+    Substitution {
+        team_id: u32,
+        player_out_id: u32,
+        player_in_id: u32,
+        reason: SubstitutionReason,
+    },
+    TacticalChange {
+        team_id: u32,
+        reason: TacticalChangeReason,
+        tactic_type: MatchTacticType,
+        instructions: TeamInstructions,
+    },
+    ShootoutKick {
+        player_id: u32,
+        team_id: u32,
+        is_scored: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineEvent {
+    // match milliseconds
+    pub time: u64,
+    pub event: TimelineEventType,
+}
+
+impl TimelineEvent {
+    // Minute as shown on the scoreboard, the first minute is 1'
+    pub fn minute(&self) -> u64 {
+        self.time / MATCH_MINUTE_MS + 1
+    }
+}
+
+// Everything worth telling about a match, in the order it happened
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MatchTimeline {
+    pub events: Vec<TimelineEvent>,
+}
+
+impl MatchTimeline {
+    pub fn new() -> Self {
+        MatchTimeline::default()
+    }
+
+    pub fn add(&mut self, time: u64, event: TimelineEventType) {
+        self.events.push(TimelineEvent { time, event });
+    }
+
+    pub fn goals(&self) -> impl Iterator<Item = &TimelineEvent> {
+        self.events
+            .iter()
+            .filter(|e| matches!(e.event, TimelineEventType::Goal { .. }))
+    }
+}
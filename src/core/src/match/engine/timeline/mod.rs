@@ -0,0 +1,5 @@
+pub mod commentary;
+pub mod match_timeline;
+
+pub use commentary::*;
+pub use match_timeline::*;
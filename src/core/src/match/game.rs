@@ -1,5 +1,5 @@
 use super::engine::FootballEngine;
-use crate::r#match::{MatchResult, TeamSquad};
+use crate::r#match::{MatchConfig, MatchEngineMode, MatchResult, StatisticalEngine, TeamSquad};
 use log::debug;
 
 #[derive(Debug, Clone)]
@@ -9,6 +9,7 @@ pub struct Match {
     league_slug: String,
     pub home_squad: TeamSquad,
     pub away_squad: TeamSquad,
+    pub config: MatchConfig,
 }
 
 impl Match {
@@ -25,9 +26,15 @@ impl Match {
             league_slug: String::from(league_slug),
             home_squad,
             away_squad,
+            config: MatchConfig::default(),
         }
     }
 
+    pub fn with_config(mut self, config: MatchConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     pub fn play(self) -> MatchResult {
         let home_team_id = self.home_squad.team_id;
         let home_team_name = String::from(&self.home_squad.team_name);
@@ -35,7 +42,14 @@ impl Match {
         let away_team_id = self.away_squad.team_id;
         let away_team_name = String::from(&self.away_squad.team_name);
 
-        let match_result = FootballEngine::<840, 545>::play(self.home_squad, self.away_squad);
+        let match_result = match self.config.engine {
+            MatchEngineMode::Detailed => {
+                FootballEngine::<840, 545>::play(self.home_squad, self.away_squad, self.config)
+            }
+            MatchEngineMode::Statistical => {
+                StatisticalEngine::play(self.home_squad, self.away_squad, self.config)
+            }
+        };
 
         let score = match_result.score.as_ref().expect("no score");
 
@@ -2,14 +2,21 @@ pub mod engine;
 
 pub mod game;
 
+pub mod replay;
 pub mod result;
 
 pub mod squad;
 pub mod state;
+pub mod statistical;
+
+#[cfg(test)]
+pub mod testing;
 
 pub use engine::*;
 pub use game::*;
 
+pub use replay::*;
 pub use result::*;
 pub use squad::*;
 pub use state::*;
+pub use statistical::*;
@@ -0,0 +1,162 @@
+use crate::r#match::ReplayError;
+use nalgebra::Vector3;
+
+// positions are stored in hundredths of a field unit
+const QUANTIZATION_SCALE: f32 = 100.0;
+
+fn quantize(value: f32) -> i32 {
+    (value * QUANTIZATION_SCALE).round() as i32
+}
+
+fn dequantize(value: i32) -> f32 {
+    value as f32 / QUANTIZATION_SCALE
+}
+
+// Little-endian primitives, LEB128 varints and zigzag signed varints
+pub(super) struct ReplayEncoder {
+    bytes: Vec<u8>,
+}
+
+impl ReplayEncoder {
+    pub fn new() -> Self {
+        ReplayEncoder { bytes: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+
+        self.bytes.push(value as u8);
+    }
+
+    pub fn write_signed(&mut self, value: i64) {
+        self.write_varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    pub fn write_string(&mut self, value: &str) {
+        self.write_varint(value.len() as u64);
+        self.write_bytes(value.as_bytes());
+    }
+}
+
+pub(super) struct ReplayDecoder<'d> {
+    bytes: &'d [u8],
+    offset: usize,
+}
+
+impl<'d> ReplayDecoder<'d> {
+    pub fn new(bytes: &'d [u8]) -> Self {
+        ReplayDecoder { bytes, offset: 0 }
+    }
+
+    pub fn at(bytes: &'d [u8], offset: usize) -> Self {
+        ReplayDecoder { bytes, offset }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offset >= self.bytes.len()
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'d [u8], ReplayError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(ReplayError::Corrupted)?;
+
+        let bytes = &self.bytes[self.offset..end];
+        self.offset = end;
+
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, ReplayError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, ReplayError> {
+        let bytes = self.read_bytes(2)?;
+
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn read_varint(&mut self) -> Result<u64, ReplayError> {
+        let mut value = 0u64;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(ReplayError::Corrupted)
+    }
+
+    pub fn read_signed(&mut self) -> Result<i64, ReplayError> {
+        let value = self.read_varint()?;
+
+        Ok(((value >> 1) as i64) ^ -((value & 1) as i64))
+    }
+
+    pub fn read_index(&mut self) -> Result<usize, ReplayError> {
+        usize::try_from(self.read_varint()?).map_err(|_| ReplayError::Corrupted)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, ReplayError> {
+        u32::try_from(self.read_varint()?).map_err(|_| ReplayError::Corrupted)
+    }
+
+    pub fn read_string(&mut self) -> Result<String, ReplayError> {
+        let len = self.read_index()?;
+        let bytes = self.read_bytes(len)?;
+
+        String::from_utf8(bytes.to_vec()).map_err(|_| ReplayError::Corrupted)
+    }
+}
+
+// frame kinds of the replay body
+pub(super) const DELTA_FRAME: u8 = 0;
+pub(super) const KEYFRAME: u8 = 1;
+
+pub(super) type QuantizedPosition = [i32; 3];
+
+pub(super) fn quantize_position(position: &Vector3<f32>) -> QuantizedPosition {
+    [
+        quantize(position.x),
+        quantize(position.y),
+        quantize(position.z),
+    ]
+}
+
+pub(super) fn dequantize_position(position: &QuantizedPosition) -> Vector3<f32> {
+    Vector3::new(
+        dequantize(position[0]),
+        dequantize(position[1]),
+        dequantize(position[2]),
+    )
+}
@@ -0,0 +1,31 @@
+use std::fmt::{Display, Formatter};
+use std::{error, io};
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    InvalidHeader,
+    UnsupportedVersion(u16),
+    Corrupted,
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "replay io error: {}", err),
+            ReplayError::InvalidHeader => write!(f, "not a match replay"),
+            ReplayError::UnsupportedVersion(version) => {
+                write!(f, "unsupported replay version: {}", version)
+            }
+            ReplayError::Corrupted => write!(f, "replay data is corrupted"),
+        }
+    }
+}
+
+impl error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> Self {
+        ReplayError::Io(err)
+    }
+}
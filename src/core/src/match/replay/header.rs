@@ -0,0 +1,82 @@
+use crate::r#match::replay::encoding::{ReplayDecoder, ReplayEncoder};
+use crate::r#match::{MatchResult, ReplayError};
+
+const REPLAY_MAGIC: &[u8; 4] = b"OFRP";
+
+// bump whenever the replay layout changes
+pub const REPLAY_VERSION: u16 = 1;
+
+// a full set of positions every this often, so playback can start anywhere
+pub const DEFAULT_KEYFRAME_INTERVAL_MS: u64 = 10_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayHeader {
+    pub match_id: String,
+    pub league_slug: String,
+    pub home_team_id: u32,
+    pub away_team_id: u32,
+    pub home_goals: u8,
+    pub away_goals: u8,
+    pub match_time_ms: u64,
+    pub keyframe_interval_ms: u64,
+}
+
+impl ReplayHeader {
+    pub fn from_result(result: &MatchResult) -> Self {
+        ReplayHeader {
+            match_id: result.id.clone(),
+            league_slug: result.league_slug.clone(),
+            home_team_id: result.home_team_id,
+            away_team_id: result.away_team_id,
+            home_goals: result.score.home_team.get(),
+            away_goals: result.score.away_team.get(),
+            match_time_ms: result
+                .details
+                .as_ref()
+                .map_or(0, |details| details.match_time_ms),
+            keyframe_interval_ms: DEFAULT_KEYFRAME_INTERVAL_MS,
+        }
+    }
+
+    // magic + version (u16 LE), then the match metadata
+    pub(super) fn write(&self, encoder: &mut ReplayEncoder) {
+        encoder.write_bytes(REPLAY_MAGIC);
+        encoder.write_u16(REPLAY_VERSION);
+
+        encoder.write_string(&self.match_id);
+        encoder.write_string(&self.league_slug);
+        encoder.write_varint(self.home_team_id as u64);
+        encoder.write_varint(self.away_team_id as u64);
+        encoder.write_u8(self.home_goals);
+        encoder.write_u8(self.away_goals);
+        encoder.write_varint(self.match_time_ms);
+        encoder.write_varint(self.keyframe_interval_ms);
+    }
+
+    pub(super) fn read(decoder: &mut ReplayDecoder<'_>) -> Result<Self, ReplayError> {
+        let magic = decoder
+            .read_bytes(REPLAY_MAGIC.len())
+            .map_err(|_| ReplayError::InvalidHeader)?;
+
+        if magic != REPLAY_MAGIC {
+            return Err(ReplayError::InvalidHeader);
+        }
+
+        let version = decoder.read_u16().map_err(|_| ReplayError::InvalidHeader)?;
+
+        if version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+
+        Ok(ReplayHeader {
+            match_id: decoder.read_string()?,
+            league_slug: decoder.read_string()?,
+            home_team_id: decoder.read_u32()?,
+            away_team_id: decoder.read_u32()?,
+            home_goals: decoder.read_u8()?,
+            away_goals: decoder.read_u8()?,
+            match_time_ms: decoder.read_varint()?,
+            keyframe_interval_ms: decoder.read_varint()?,
+        })
+    }
+}
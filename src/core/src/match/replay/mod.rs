@@ -0,0 +1,10 @@
+mod encoding;
+mod error;
+mod header;
+mod reader;
+mod writer;
+
+pub use error::*;
+pub use header::*;
+pub use reader::*;
+pub use writer::*;
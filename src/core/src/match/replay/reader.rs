@@ -0,0 +1,391 @@
+use crate::r#match::replay::encoding::{
+    dequantize_position, QuantizedPosition, ReplayDecoder, DELTA_FRAME, KEYFRAME,
+};
+use crate::r#match::{ReplayError, ReplayHeader, ReplayWriter, ResultMatchPositionData};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayKeyframe {
+    pub timestamp: u64,
+    offset: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct StateAnnotation {
+    timestamp: u64,
+    track: usize,
+    label: usize,
+}
+
+pub struct ReplayReader<'r> {
+    header: ReplayHeader,
+    player_ids: Vec<u32>,
+    labels: Vec<String>,
+    annotations: Vec<StateAnnotation>,
+    keyframes: Vec<ReplayKeyframe>,
+    body: &'r [u8],
+}
+
+impl<'r> ReplayReader<'r> {
+    pub fn new(bytes: &'r [u8]) -> Result<Self, ReplayError> {
+        let mut decoder = ReplayDecoder::new(bytes);
+
+        let header = ReplayHeader::read(&mut decoder)?;
+
+        let player_ids = (0..decoder.read_index()?)
+            .map(|_| decoder.read_u32())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let labels = (0..decoder.read_index()?)
+            .map(|_| decoder.read_string())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut annotations = Vec::new();
+        let mut timestamp = 0;
+
+        for _ in 0..decoder.read_index()? {
+            timestamp += decoder.read_varint()?;
+
+            let annotation = StateAnnotation {
+                timestamp,
+                track: decoder.read_index()?,
+                label: decoder.read_index()?,
+            };
+
+            if annotation.track == 0
+                || annotation.track > player_ids.len()
+                || annotation.label >= labels.len()
+            {
+                return Err(ReplayError::Corrupted);
+            }
+
+            annotations.push(annotation);
+        }
+
+        let mut keyframes = Vec::new();
+        let mut keyframe = ReplayKeyframe {
+            timestamp: 0,
+            offset: 0,
+        };
+
+        for _ in 0..decoder.read_index()? {
+            keyframe.timestamp += decoder.read_varint()?;
+            keyframe.offset += decoder.read_index()?;
+
+            keyframes.push(keyframe);
+        }
+
+        let body_len = decoder.read_index()?;
+        let body = decoder.read_bytes(body_len)?;
+
+        if keyframes
+            .iter()
+            .any(|keyframe| keyframe.offset >= body.len())
+        {
+            return Err(ReplayError::Corrupted);
+        }
+
+        Ok(ReplayReader {
+            header,
+            player_ids,
+            labels,
+            annotations,
+            keyframes,
+            body,
+        })
+    }
+
+    pub fn header(&self) -> &ReplayHeader {
+        &self.header
+    }
+
+    pub fn player_ids(&self) -> &[u32] {
+        &self.player_ids
+    }
+
+    pub fn keyframes(&self) -> &[ReplayKeyframe] {
+        &self.keyframes
+    }
+
+    pub fn read(&self) -> Result<ResultMatchPositionData, ReplayError> {
+        self.read_range(0, u64::MAX)
+    }
+
+    // Positions and states from `from` to `to` (inclusive), starting with where
+    // everybody stood and what they were doing at `from`
+    pub fn read_range(&self, from: u64, to: u64) -> Result<ResultMatchPositionData, ReplayError> {
+        let mut data = ResultMatchPositionData::new();
+
+        self.read_positions(from, to, &mut data)?;
+        self.read_states(from, to, &mut data);
+
+        Ok(data)
+    }
+
+    // A replay of its own holding only the window, for streaming a part of the match
+    pub fn slice(&self, from: u64, to: u64) -> Result<Vec<u8>, ReplayError> {
+        Ok(ReplayWriter::encode(
+            &self.header,
+            &self.read_range(from, to)?,
+        ))
+    }
+
+    fn read_positions(
+        &self,
+        from: u64,
+        to: u64,
+        data: &mut ResultMatchPositionData,
+    ) -> Result<(), ReplayError> {
+        let tracks = self.player_ids.len() + 1;
+
+        // decoding starts at the last keyframe before the window
+        let start = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.timestamp <= from)
+            .checked_sub(1)
+            .map_or(0, |idx| self.keyframes[idx].offset);
+
+        let mut decoder = ReplayDecoder::at(self.body, start);
+
+        let mut positions: Vec<Option<QuantizedPosition>> = vec![None; tracks];
+        let mut frame: Vec<Option<QuantizedPosition>> = vec![None; tracks];
+        let mut timestamp = 0;
+        let mut in_window = false;
+
+        while !decoder.is_empty() {
+            let kind = decoder.read_u8()?;
+
+            frame.fill(None);
+
+            match kind {
+                KEYFRAME => {
+                    timestamp = decoder.read_varint()?;
+
+                    for _ in 0..decoder.read_index()? {
+                        let idx = decoder.read_index()?;
+                        let position = Self::read_position(&mut decoder, [0; 3])?;
+
+                        *frame.get_mut(idx).ok_or(ReplayError::Corrupted)? = Some(position);
+                    }
+                }
+                DELTA_FRAME => {
+                    timestamp += decoder.read_varint()?;
+
+                    for _ in 0..decoder.read_index()? {
+                        let idx = decoder.read_index()?;
+                        let previous = positions
+                            .get(idx)
+                            .ok_or(ReplayError::Corrupted)?
+                            .unwrap_or_default();
+
+                        frame[idx] = Some(Self::read_position(&mut decoder, previous)?);
+                    }
+                }
+                _ => return Err(ReplayError::Corrupted),
+            }
+
+            if timestamp > to {
+                break;
+            }
+
+            if !in_window && timestamp >= from {
+                in_window = true;
+
+                for (idx, position) in positions.iter().enumerate() {
+                    if let Some(position) = position {
+                        self.push_position(data, idx, from, position);
+                    }
+                }
+            }
+
+            for (idx, position) in frame.iter().enumerate() {
+                if let Some(position) = position {
+                    if in_window && positions[idx] != Some(*position) {
+                        self.push_position(data, idx, timestamp, position);
+                    }
+
+                    positions[idx] = Some(*position);
+                }
+            }
+        }
+
+        // the window is past the last frame, so it only shows the final positions
+        if !in_window && from <= to {
+            for (idx, position) in positions.iter().enumerate() {
+                if let Some(position) = position {
+                    self.push_position(data, idx, from, position);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_position(
+        decoder: &mut ReplayDecoder<'_>,
+        base: QuantizedPosition,
+    ) -> Result<QuantizedPosition, ReplayError> {
+        let mut position = base;
+
+        for value in position.iter_mut() {
+            *value = i32::try_from(*value as i64 + decoder.read_signed()?)
+                .map_err(|_| ReplayError::Corrupted)?;
+        }
+
+        Ok(position)
+    }
+
+    fn push_position(
+        &self,
+        data: &mut ResultMatchPositionData,
+        track: usize,
+        timestamp: u64,
+        position: &QuantizedPosition,
+    ) {
+        let position = dequantize_position(position);
+
+        match track {
+            0 => data.add_ball_positions(timestamp, position),
+            _ => data.add_player_positions(self.player_ids[track - 1], timestamp, position),
+        }
+    }
+
+    fn read_states(&self, from: u64, to: u64, data: &mut ResultMatchPositionData) {
+        let mut current: Vec<Option<usize>> = vec![None; self.player_ids.len() + 1];
+
+        for annotation in self.annotations.iter().take_while(|a| a.timestamp <= to) {
+            if annotation.timestamp < from {
+                current[annotation.track] = Some(annotation.label);
+                continue;
+            }
+
+            // what the players were doing when the window opens
+            for (track, label) in current.iter_mut().enumerate() {
+                if let Some(label) = label.take() {
+                    self.push_state(data, track, from, label);
+                }
+            }
+
+            self.push_state(
+                data,
+                annotation.track,
+                annotation.timestamp,
+                annotation.label,
+            );
+        }
+
+        for (track, label) in current.iter().enumerate() {
+            if let Some(label) = label {
+                self.push_state(data, track, from, *label);
+            }
+        }
+    }
+
+    fn push_state(
+        &self,
+        data: &mut ResultMatchPositionData,
+        track: usize,
+        timestamp: u64,
+        label: usize,
+    ) {
+        data.add_player_state_label(
+            self.player_ids[track - 1],
+            timestamp,
+            self.labels[label].clone(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector3;
+
+    fn header() -> ReplayHeader {
+        ReplayHeader {
+            match_id: String::from("match-1"),
+            league_slug: String::from("premier-league"),
+            home_team_id: 1,
+            away_team_id: 2,
+            home_goals: 2,
+            away_goals: 1,
+            match_time_ms: 90_000,
+            keyframe_interval_ms: 1_000,
+        }
+    }
+
+    fn position_data() -> ResultMatchPositionData {
+        let mut data = ResultMatchPositionData::new();
+
+        for tick in 0..1_000u64 {
+            let timestamp = tick * 10;
+            let step = tick as f32 * 0.25;
+
+            data.add_ball_positions(timestamp, Vector3::new(420.0 + step, 272.5, 0.0));
+            data.add_player_positions(7, timestamp, Vector3::new(100.0 + step, 50.0 - step, 0.0));
+
+            // a player standing still is only written once
+            data.add_player_positions(9, timestamp, Vector3::new(10.0, 20.0, 0.0));
+        }
+
+        data.add_player_state_label(7, 0, String::from("Forward: Standing"));
+        data.add_player_state_label(7, 4_000, String::from("Forward: Running"));
+        data.add_player_state_label(9, 0, String::from("Goalkeeper: Standing"));
+
+        data
+    }
+
+    #[test]
+    fn replay_roundtrip_keeps_header_positions_and_states() {
+        let data = position_data();
+        let bytes = ReplayWriter::encode(&header(), &data);
+
+        let reader = ReplayReader::new(&bytes).unwrap();
+
+        assert_eq!(reader.header(), &header());
+        assert_eq!(reader.player_ids(), &[7, 9]);
+        assert_eq!(reader.keyframes().len(), 10);
+
+        let decoded = reader.read().unwrap();
+
+        assert_eq!(decoded.ball(), data.ball());
+        assert_eq!(decoded.players(), data.players());
+        assert_eq!(decoded.states(), data.states());
+
+        let raw = bincode::serialize(&data).unwrap();
+        assert!(bytes.len() * 3 < raw.len());
+    }
+
+    #[test]
+    fn replay_seeks_to_window() {
+        let bytes = ReplayWriter::encode(&header(), &position_data());
+        let reader = ReplayReader::new(&bytes).unwrap();
+
+        let window = reader.read_range(5_005, 6_000).unwrap();
+
+        let ball = window.ball();
+        assert_eq!(ball.first().unwrap().timestamp, 5_005);
+        assert_eq!(ball.first().unwrap().position.x, 420.0 + 500.0 * 0.25);
+        assert_eq!(ball.last().unwrap().timestamp, 6_000);
+
+        let standing = &window.players()[&9];
+        assert_eq!(standing.len(), 1);
+        assert_eq!(standing[0].timestamp, 5_005);
+
+        assert_eq!(window.states()[&7][0].state, "Forward: Running");
+        assert_eq!(window.states()[&7][0].timestamp, 5_005);
+
+        let slice = reader.slice(5_005, 6_000).unwrap();
+        assert!(slice.len() < bytes.len());
+        assert_eq!(
+            ReplayReader::new(&slice).unwrap().read().unwrap().ball(),
+            ball
+        );
+
+        let mut corrupted = bytes.clone();
+        corrupted[0] = b'X';
+        assert!(matches!(
+            ReplayReader::new(&corrupted),
+            Err(ReplayError::InvalidHeader)
+        ));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::r#match::replay::encoding::{
+    quantize_position, QuantizedPosition, ReplayEncoder, DELTA_FRAME, KEYFRAME,
+};
+use crate::r#match::{ReplayError, ReplayHeader, ResultMatchPositionData, ResultPositionDataItem};
+use std::collections::HashMap;
+use std::io::Write;
+
+// Layout after the header, all integers as varints:
+// tracks      - player ids, track 0 is the ball and track n + 1 the n-th player
+// states      - state labels, then (timestamp delta, track, label) annotations
+// keyframes   - (timestamp delta, body offset delta) of every keyframe
+// body        - length, then frames in time order
+//
+// A frame starts with its kind. Delta frames carry the timestamp delta and the
+// position deltas of the tracks that moved, keyframes the absolute timestamp and
+// the absolute positions of every track seen so far
+pub struct ReplayWriter;
+
+impl ReplayWriter {
+    pub fn write<W: Write>(
+        header: &ReplayHeader,
+        data: &ResultMatchPositionData,
+        mut writer: W,
+    ) -> Result<(), ReplayError> {
+        writer.write_all(&Self::encode(header, data))?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    pub fn encode(header: &ReplayHeader, data: &ResultMatchPositionData) -> Vec<u8> {
+        let mut player_ids: Vec<u32> = data
+            .players()
+            .keys()
+            .chain(data.states().keys())
+            .copied()
+            .collect();
+
+        player_ids.sort_unstable();
+        player_ids.dedup();
+
+        let mut encoder = ReplayEncoder::new();
+
+        header.write(&mut encoder);
+
+        encoder.write_varint(player_ids.len() as u64);
+        player_ids
+            .iter()
+            .for_each(|player_id| encoder.write_varint(*player_id as u64));
+
+        Self::write_states(&mut encoder, data, &player_ids);
+
+        let empty = Vec::new();
+
+        let tracks: Vec<&[ResultPositionDataItem]> = std::iter::once(data.ball())
+            .chain(
+                player_ids
+                    .iter()
+                    .map(|player_id| data.players().get(player_id).unwrap_or(&empty).as_slice()),
+            )
+            .collect();
+
+        let (keyframes, body) = Self::encode_body(&tracks, header.keyframe_interval_ms.max(1));
+
+        encoder.write_varint(keyframes.len() as u64);
+
+        let mut last_keyframe = (0, 0);
+        for (timestamp, offset) in keyframes {
+            encoder.write_varint(timestamp - last_keyframe.0);
+            encoder.write_varint((offset - last_keyframe.1) as u64);
+
+            last_keyframe = (timestamp, offset);
+        }
+
+        encoder.write_varint(body.len() as u64);
+        encoder.write_bytes(&body);
+
+        encoder.into_bytes()
+    }
+
+    fn write_states(
+        encoder: &mut ReplayEncoder,
+        data: &ResultMatchPositionData,
+        player_ids: &[u32],
+    ) {
+        let mut labels: Vec<&str> = Vec::new();
+        let mut label_indexes: HashMap<&str, usize> = HashMap::new();
+
+        let mut annotations: Vec<(u64, usize, usize)> = Vec::new();
+
+        for (idx, player_id) in player_ids.iter().enumerate() {
+            for item in data.states().get(player_id).into_iter().flatten() {
+                let label = *label_indexes.entry(&item.state).or_insert_with(|| {
+                    labels.push(&item.state);
+                    labels.len() - 1
+                });
+
+                annotations.push((item.timestamp, idx + 1, label));
+            }
+        }
+
+        annotations.sort_unstable();
+
+        encoder.write_varint(labels.len() as u64);
+        labels.iter().for_each(|label| encoder.write_string(label));
+
+        encoder.write_varint(annotations.len() as u64);
+
+        let mut last_timestamp = 0;
+        for (timestamp, track, label) in annotations {
+            encoder.write_varint(timestamp - last_timestamp);
+            encoder.write_varint(track as u64);
+            encoder.write_varint(label as u64);
+
+            last_timestamp = timestamp;
+        }
+    }
+
+    // Merges the per-track samples into frames, returns the keyframe index and the body
+    fn encode_body(
+        tracks: &[&[ResultPositionDataItem]],
+        keyframe_interval_ms: u64,
+    ) -> (Vec<(u64, usize)>, Vec<u8>) {
+        let mut encoder = ReplayEncoder::new();
+        let mut keyframes = Vec::new();
+
+        let mut cursors = vec![0usize; tracks.len()];
+        let mut positions: Vec<Option<QuantizedPosition>> = vec![None; tracks.len()];
+
+        let mut changes: Vec<(usize, QuantizedPosition)> = Vec::new();
+        let mut last_timestamp = 0;
+        let mut next_keyframe = 0;
+
+        while let Some(timestamp) = tracks
+            .iter()
+            .zip(&cursors)
+            .filter_map(|(track, cursor)| track.get(*cursor).map(|item| item.timestamp))
+            .min()
+        {
+            changes.clear();
+
+            for (idx, track) in tracks.iter().enumerate() {
+                let mut latest = None;
+
+                while let Some(item) = track.get(cursors[idx]).filter(|i| i.timestamp == timestamp)
+                {
+                    latest = Some(quantize_position(&item.position));
+                    cursors[idx] += 1;
+                }
+
+                if let Some(position) = latest.filter(|position| positions[idx] != Some(*position))
+                {
+                    changes.push((idx, position));
+                }
+            }
+
+            if changes.is_empty() {
+                continue;
+            }
+
+            if timestamp >= next_keyframe {
+                for (idx, position) in &changes {
+                    positions[*idx] = Some(*position);
+                }
+
+                keyframes.push((timestamp, encoder.len()));
+
+                encoder.write_u8(KEYFRAME);
+                encoder.write_varint(timestamp);
+                encoder.write_varint(positions.iter().flatten().count() as u64);
+
+                for (idx, position) in positions.iter().enumerate() {
+                    if let Some(position) = position {
+                        encoder.write_varint(idx as u64);
+                        position
+                            .iter()
+                            .for_each(|value| encoder.write_signed(*value as i64));
+                    }
+                }
+
+                next_keyframe = (timestamp / keyframe_interval_ms + 1) * keyframe_interval_ms;
+            } else {
+                encoder.write_u8(DELTA_FRAME);
+                encoder.write_varint(timestamp - last_timestamp);
+                encoder.write_varint(changes.len() as u64);
+
+                for (idx, position) in &changes {
+                    let previous = positions[*idx].unwrap_or_default();
+
+                    encoder.write_varint(*idx as u64);
+                    for axis in 0..3 {
+                        encoder.write_signed(position[axis] as i64 - previous[axis] as i64);
+                    }
+
+                    positions[*idx] = Some(*position);
+                }
+            }
+
+            last_timestamp = timestamp;
+        }
+
+        (keyframes, encoder.into_bytes())
+    }
+}
@@ -1,8 +1,9 @@
-﻿use nalgebra::Vector3;
-use serde::Serialize;
+﻿use crate::r#match::player::state::PlayerState;
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResultPositionDataItem {
     pub timestamp: u64,
     pub position: Vector3<f32>,
@@ -23,10 +24,26 @@ impl PartialEq<ResultPositionDataItem> for ResultPositionDataItem {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResultPlayerStateItem {
+    pub timestamp: u64,
+    pub state: String,
+}
+
+impl ResultPlayerStateItem {
+    pub fn new(timestamp: u64, state: String) -> Self {
+        ResultPlayerStateItem { timestamp, state }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResultMatchPositionData {
     ball: Vec<ResultPositionDataItem>,
     players: HashMap<u32, Vec<ResultPositionDataItem>>,
+    // what each player was doing, written when it changes
+    states: HashMap<u32, Vec<ResultPlayerStateItem>>,
+    #[serde(skip)]
+    last_states: HashMap<u32, PlayerState>,
 }
 
 impl ResultMatchPositionData {
@@ -34,10 +51,22 @@ impl ResultMatchPositionData {
         ResultMatchPositionData {
             ball: Vec::new(),
             players: HashMap::with_capacity(22 * 2 * 9000),
+            states: HashMap::new(),
+            last_states: HashMap::new(),
         }
     }
 
-    pub fn compress(&mut self) {}
+    pub fn ball(&self) -> &[ResultPositionDataItem] {
+        &self.ball
+    }
+
+    pub fn players(&self) -> &HashMap<u32, Vec<ResultPositionDataItem>> {
+        &self.players
+    }
+
+    pub fn states(&self) -> &HashMap<u32, Vec<ResultPlayerStateItem>> {
+        &self.states
+    }
 
     pub fn add_player_positions(&mut self, player_id: u32, timestamp: u64, position: Vector3<f32>) {
         if let Some(player_data) = self.players.get_mut(&player_id) {
@@ -55,6 +84,21 @@ impl ResultMatchPositionData {
         }
     }
 
+    pub fn add_player_state(&mut self, player_id: u32, timestamp: u64, state: PlayerState) {
+        if self.last_states.insert(player_id, state) == Some(state) {
+            return;
+        }
+
+        self.add_player_state_label(player_id, timestamp, state.to_string());
+    }
+
+    pub fn add_player_state_label(&mut self, player_id: u32, timestamp: u64, state: String) {
+        self.states
+            .entry(player_id)
+            .or_default()
+            .push(ResultPlayerStateItem::new(timestamp, state));
+    }
+
     pub fn add_ball_positions(&mut self, timestamp: u64, position: Vector3<f32>) {
         let position = ResultPositionDataItem::new(timestamp, position);
 
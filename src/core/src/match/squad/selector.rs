@@ -1,7 +1,6 @@
 use crate::club::{PlayerPositionType, Staff};
 use crate::r#match::player::MatchPlayer;
 use crate::{Player, Tactics, Team};
-use std::borrow::Borrow;
 
 pub struct SquadSelector;
 
@@ -14,9 +13,7 @@ pub struct PlayerSelectionResult {
 }
 
 impl SquadSelector {
-    pub fn select(team: &Team, staff: &Staff) -> PlayerSelectionResult {
-        let current_tactics = team.tactics();
-
+    pub fn select(team: &Team, staff: &Staff, tactics: &Tactics) -> PlayerSelectionResult {
         let mut players: Vec<&Player> = team
             .players
             .players()
@@ -30,13 +27,13 @@ impl SquadSelector {
                 team.id,
                 &mut players,
                 staff,
-                current_tactics.borrow(),
+                tactics,
             ),
             substitutes: SquadSelector::select_substitutes(
                 team.id,
                 &mut players,
                 staff,
-                current_tactics.borrow(),
+                tactics,
             ),
         }
     }
@@ -58,7 +55,7 @@ impl SquadSelector {
                 .filter(|p| p.positions.has_position(*player_position))
             {
                 let player_rating =
-                    SquadSelector::calculate_player_rating(player, staff, player_position);
+                    SquadSelector::calculate_player_rating(player, staff, tactics, player_position);
 
                 if player_rating > best_player_rating {
                     best_player = Some(player);
@@ -66,6 +63,26 @@ impl SquadSelector {
                 }
             }
 
+            // nobody plays the position, so the best fit from the same group covers it
+            if best_player.is_none() {
+                for &player in players
+                    .iter()
+                    .filter(|p| p.positions.suitability(*player_position) > 0.0)
+                {
+                    let player_rating = SquadSelector::calculate_player_rating(
+                        player,
+                        staff,
+                        tactics,
+                        player_position,
+                    ) + player.positions.suitability(*player_position);
+
+                    if player_rating > best_player_rating {
+                        best_player = Some(player);
+                        best_player_rating = player_rating;
+                    }
+                }
+            }
+
             if let Some(player) = best_player {
                 squad.push(MatchPlayer::from_player(
                     team_id,
@@ -108,7 +125,7 @@ impl SquadSelector {
 
             for player in players.iter() {
                 let player_rating =
-                    SquadSelector::calculate_player_rating(player, staff, player_position);
+                    SquadSelector::calculate_player_rating(player, staff, tactics, player_position);
 
                 if player_rating > best_player_rating {
                     best_player = Some(player);
@@ -133,12 +150,16 @@ impl SquadSelector {
     fn calculate_player_rating(
         player: &Player,
         staff: &Staff,
+        tactics: &Tactics,
         position: &PlayerPositionType,
     ) -> f32 {
         let mut rating = 0.0;
 
         rating += player.positions.get_level(*position) as f32 / 20.0;
 
+        // how well the player's skills suit the role asked of the position
+        rating += tactics.role(*position).role.rating(&player.skills);
+
         rating += player.player_attributes.condition as f32 / 10000.0;
 
         rating += 0.3 * (player.player_attributes.world_reputation as f32 / 10000.0);
@@ -155,10 +176,11 @@ impl SquadSelector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::club::staff::staff_stub::StaffStub;
     use crate::{
         IntegerUtils, PlayerCollection, PlayerGenerator, StaffCollection
         , MatchTacticType, TeamReputation, TeamType, TrainingSchedule,
-        TACTICS_POSITIONS,
+        TACTICS_POSITIONS, Duty, PlayerRole, TacticalRole,
     };
     use chrono::{NaiveTime, Utc};
 
@@ -174,6 +196,41 @@ mod tests {
         //assert_eq!(11, squad.main_squad.len())
     }
 
+    #[test]
+    fn role_decides_between_equal_players() {
+        let date = Utc::now().date_naive();
+
+        let plain = PlayerGenerator::generate(0, date, PlayerPositionType::DefenderCenter, 15);
+
+        let mut passer =
+            PlayerGenerator::generate(0, date, PlayerPositionType::DefenderCenter, 15);
+        passer.id = plain.id + 1;
+        passer.player_attributes = plain.player_attributes;
+        passer.skills = plain.skills;
+        passer.skills.technical.passing = 20.0;
+        passer.skills.mental.composure = 20.0;
+
+        let tactics = Tactics::new(MatchTacticType::T442).with_role(
+            PlayerPositionType::DefenderCenterLeft,
+            TacticalRole::new(PlayerRole::BallPlayingDefender, Duty::Defend),
+        );
+
+        let mut left_back =
+            PlayerGenerator::generate(0, date, PlayerPositionType::DefenderLeft, 15);
+        left_back.id = plain.id + 2;
+
+        let mut players = vec![&left_back, &plain, &passer];
+
+        let squad =
+            SquadSelector::select_main_squad(0, &mut players, &StaffStub::default(), &tactics);
+
+        let ball_player = squad.iter().find(|p| {
+            p.tactical_position.current_position == PlayerPositionType::DefenderCenterLeft
+        });
+
+        assert_eq!(Some(passer.id), ball_player.map(|p| p.id));
+    }
+
     // helpers
 
     fn generate_team() -> Team {
@@ -0,0 +1,5 @@
+pub mod statistical_engine;
+pub mod strength;
+
+pub use statistical_engine::*;
+pub use strength::*;
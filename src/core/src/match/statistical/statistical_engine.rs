@@ -0,0 +1,709 @@
+use crate::r#match::statistics::{MatchPlayerResult, MatchStatisticType};
+use crate::r#match::{
+    FieldSquad, GoalDetail, MatchAnalytics, MatchConfig, MatchPlayer, MatchResultRaw, MatchState,
+    MatchTeamStatistics, MatchTimeline, PenaltyShootout, Score, Shot, TeamSquad, TeamStrength,
+    TimelineEventType, MATCH_TIME_MS,
+};
+use crate::utils::RandomGenerator;
+use crate::{InjuryCause, Mentality, MATCH_MINUTE_INJURY_CHANCE};
+use rand::Rng;
+use std::collections::HashMap;
+
+// Shots of a side over 90 minutes against an equal one on neutral ground, and
+// the expected goals of an average shot between them
+const BASE_SHOTS: f32 = 12.5;
+const BASE_SHOT_XG: f32 = 0.1;
+const MAX_SHOT_XG: f32 = 0.85;
+
+// How much the gap between one side's attack and the other's defense tells on
+// the chances created and on how good they are, even a mismatch only goes so far
+const SHOTS_STRENGTH_EXPONENT: f32 = 1.0;
+const QUALITY_STRENGTH_EXPONENT: f32 = 0.25;
+const MIN_EDGE: f32 = 0.75;
+const MAX_EDGE: f32 = 1.3;
+
+// the home side creates this much more, the visitors as much less
+const HOME_ADVANTAGE: f32 = 1.12;
+
+// misses that still force the keeper into a save
+const SAVED_SHOT_SHARE: f32 = 0.25;
+
+const ASSIST_CHANCE: f32 = 0.75;
+
+const YELLOW_CARD_CHANCE: f32 = 0.08;
+const SECOND_YELLOW_CHANCE: f32 = 0.05;
+const RED_CARD_CHANCE: f32 = 0.004;
+
+const PENALTY_CONVERSION: f32 = 0.76;
+
+const MATCH_MINUTES: f32 = 90.0;
+
+// What a side can expect from the match against this opponent
+#[derive(Debug, Clone, Copy)]
+struct TeamChances {
+    team_id: u32,
+    // over 90 minutes
+    shots: f32,
+    shot_quality: f32,
+    // percent
+    possession: f32,
+}
+
+impl TeamChances {
+    // An attacking side goes for more chances and leaves the opponent better ones
+    fn new(
+        team_id: u32,
+        team: TeamStrength,
+        opponent: TeamStrength,
+        mentality: Mentality,
+        opponent_mentality: Mentality,
+        advantage: f32,
+    ) -> Self {
+        let control = team.midfield / (team.midfield + opponent.midfield).max(1.0);
+        let edge = (team.attack / opponent.defense.max(1.0)).clamp(MIN_EDGE, MAX_EDGE);
+
+        TeamChances {
+            team_id,
+            shots: BASE_SHOTS
+                * 2.0
+                * control
+                * edge.powf(SHOTS_STRENGTH_EXPONENT)
+                * mentality.chances_factor()
+                * advantage,
+            shot_quality: BASE_SHOT_XG
+                * edge.powf(QUALITY_STRENGTH_EXPONENT)
+                * opponent_mentality.exposure_factor(),
+            possession: control * 100.0,
+        }
+    }
+}
+
+// Resolves a match at once instead of playing it out: each side gets shots at
+// a rate set by how its attack and midfield measure up to the opponent and by
+// the mentality of both sides, every shot carries a chance quality that decides
+// whether it goes in. The result has the shape the detailed engine produces,
+// scorers and timeline included
+pub struct StatisticalEngine {
+    home: TeamChances,
+    away: TeamChances,
+    players: Vec<MatchPlayer>,
+    // players sent off or injured take no part from then on
+    unavailable: HashMap<u32, u64>,
+    score: Score,
+    timeline: MatchTimeline,
+}
+
+impl StatisticalEngine {
+    pub fn play(
+        home_squad: TeamSquad,
+        away_squad: TeamSquad,
+        config: MatchConfig,
+    ) -> MatchResultRaw {
+        let home_strength = TeamStrength::from_squad(&home_squad);
+        let away_strength = TeamStrength::from_squad(&away_squad);
+
+        let home_mentality = home_squad.tactics.instructions.mentality;
+        let away_mentality = away_squad.tactics.instructions.mentality;
+
+        let mut engine = StatisticalEngine {
+            home: TeamChances::new(
+                home_squad.team_id,
+                home_strength,
+                away_strength,
+                home_mentality,
+                away_mentality,
+                HOME_ADVANTAGE,
+            ),
+            away: TeamChances::new(
+                away_squad.team_id,
+                away_strength,
+                home_strength,
+                away_mentality,
+                home_mentality,
+                1.0 / HOME_ADVANTAGE,
+            ),
+            players: Vec::new(),
+            unavailable: HashMap::new(),
+            score: Score::new(home_squad.team_id, away_squad.team_id),
+            timeline: MatchTimeline::new(),
+        };
+
+        let mut result = MatchResultRaw::with_match_time(0);
+
+        result.left_team_players = FieldSquad::from_team(&home_squad);
+        result.right_team_players = FieldSquad::from_team(&away_squad);
+
+        engine.players = home_squad
+            .main_squad
+            .into_iter()
+            .chain(away_squad.main_squad)
+            .collect();
+
+        engine.play_discipline_and_injuries();
+
+        let mut time = 0;
+
+        for period in [MatchState::FirstHalf, MatchState::SecondHalf] {
+            time = engine.play_period(period, time);
+        }
+
+        let (home, away) = engine.goals();
+
+        if !config.is_decided(home, away) {
+            for period in [
+                MatchState::ExtraTimeFirstHalf,
+                MatchState::ExtraTimeSecondHalf,
+            ] {
+                time = engine.play_period(period, time);
+            }
+
+            let (home_total, away_total) = engine.goals();
+
+            result.extra_time = Some((home_total - home, away_total - away));
+        }
+
+        let (home_goals, away_goals) = engine.goals();
+
+        if !config.is_decided(home_goals, away_goals) {
+            result.penalty_shootout = Some(engine.play_penalty_shootout(time));
+        }
+
+        // events were rolled side by side, the timeline tells them in order
+        engine.timeline.events.sort_by_key(|event| event.time);
+
+        let analytics = MatchAnalytics::new();
+
+        result.match_time_ms = time;
+
+        result.player_results = MatchPlayerResult::from_players(
+            engine.players.iter(),
+            &engine.score,
+            (time / 60_000) as u8,
+            &analytics,
+        );
+
+        result.team_statistics = [engine.home, engine.away]
+            .iter()
+            .map(|team| {
+                let mut statistics =
+                    MatchTeamStatistics::new(team.team_id, &result.player_results, &analytics);
+
+                statistics.possession = team.possession;
+
+                statistics
+            })
+            .collect();
+
+        result.score = Some(engine.score);
+        result.timeline = engine.timeline;
+
+        result
+    }
+
+    fn goals(&self) -> (u8, u8) {
+        (self.score.home_team.get(), self.score.away_team.get())
+    }
+
+    // Shots of both sides spread over the period, returns the time it ends at
+    fn play_period(&mut self, period: MatchState, start: u64) -> u64 {
+        let duration = period.duration();
+        let end = start + duration;
+
+        let kick_off_team = match period {
+            MatchState::FirstHalf | MatchState::ExtraTimeFirstHalf => self.home.team_id,
+            _ => self.away.team_id,
+        };
+
+        self.timeline.add(
+            start,
+            TimelineEventType::KickOff {
+                period,
+                team_id: kick_off_team,
+            },
+        );
+
+        let share = duration as f32 / MATCH_TIME_MS as f32;
+
+        let mut rng = RandomGenerator::current();
+
+        let mut shots: Vec<(u64, TeamChances, TeamChances)> = Vec::new();
+
+        for (team, opponent) in [(self.home, self.away), (self.away, self.home)] {
+            for _ in 0..Self::poisson(team.shots * share) {
+                shots.push((rng.gen_range(start..end), team, opponent));
+            }
+        }
+
+        shots.sort_by_key(|(time, _, _)| *time);
+
+        for (time, team, opponent) in shots {
+            self.play_shot(time, &team, &opponent);
+        }
+
+        self.timeline
+            .add(end, TimelineEventType::PeriodEnd { period });
+
+        end
+    }
+
+    fn play_shot(&mut self, time: u64, team: &TeamChances, opponent: &TeamChances) {
+        let mut rng = RandomGenerator::current();
+
+        let shooter_idx = match self.pick(team.team_id, time, |player| {
+            let group = player.tactical_position.current_position.position_group();
+
+            TeamStrength::attacking(player) * TeamStrength::attack_weight(group)
+        }) {
+            Some(idx) => idx,
+            None => return,
+        };
+
+        // chances come in all sizes, most of them small
+        let expected_goals = (team.shot_quality * -(1.0 - rng.gen::<f32>()).ln()).min(MAX_SHOT_XG);
+
+        let shooter = &mut self.players[shooter_idx];
+        let shooter_id = shooter.id;
+
+        let finishing = 0.8 + shooter.skills.technical.finishing / 50.0;
+        let is_goal = rng.gen::<f32>() < expected_goals * finishing;
+
+        let shot = Shot {
+            expected_goals,
+            is_on_target: is_goal || rng.gen::<f32>() < SAVED_SHOT_SHARE,
+        };
+
+        shooter.statistics.add_shot();
+        shooter.statistics.add_shot_quality(&shot);
+
+        self.timeline.add(
+            time,
+            TimelineEventType::Shot {
+                player_id: shooter_id,
+                is_on_target: shot.is_on_target,
+                expected_goals,
+            },
+        );
+
+        if is_goal {
+            self.play_goal(time, team.team_id, shooter_idx);
+        } else if shot.is_on_target {
+            let goalkeeper_id = self
+                .players
+                .iter()
+                .find(|p| {
+                    p.team_id == opponent.team_id
+                        && p.tactical_position.current_position.is_goalkeeper()
+                        && self.is_available(p.id, time)
+                })
+                .map(|p| p.id);
+
+            if let Some(goalkeeper_id) = goalkeeper_id {
+                self.timeline.add(
+                    time,
+                    TimelineEventType::Save {
+                        goalkeeper_id,
+                        shooter_id,
+                    },
+                );
+            }
+        }
+    }
+
+    fn play_goal(&mut self, time: u64, team_id: u32, scorer_idx: usize) {
+        if team_id == self.home.team_id {
+            self.score.increment_home_goals();
+        } else {
+            self.score.increment_away_goals();
+        }
+
+        let scorer_id = self.players[scorer_idx].id;
+
+        self.players[scorer_idx].statistics.add_goal(time);
+
+        self.score.add_goal_detail(GoalDetail {
+            player_id: scorer_id,
+            stat_type: MatchStatisticType::Goal,
+            time,
+        });
+
+        let assist_idx = if RandomGenerator::current().gen::<f32>() < ASSIST_CHANCE {
+            self.pick(team_id, time, |player| {
+                if player.id == scorer_id {
+                    return 0.0;
+                }
+
+                let group = player.tactical_position.current_position.position_group();
+
+                TeamStrength::creating(player) * TeamStrength::creation_weight(group)
+            })
+        } else {
+            None
+        };
+
+        let assist_player_id = assist_idx.map(|idx| {
+            let assistant = &mut self.players[idx];
+
+            assistant.statistics.add_assist(time);
+
+            self.score.add_goal_detail(GoalDetail {
+                player_id: assistant.id,
+                stat_type: MatchStatisticType::Assist,
+                time,
+            });
+
+            assistant.id
+        });
+
+        self.timeline.add(
+            time,
+            TimelineEventType::Goal {
+                player_id: scorer_id,
+                team_id,
+                assist_player_id,
+            },
+        );
+    }
+
+    // Bookings, sendings off and injuries are rolled for the whole match up front,
+    // the players missing from then on are left out of the play
+    fn play_discipline_and_injuries(&mut self) {
+        let mut rng = RandomGenerator::current();
+
+        for player in self.players.iter_mut() {
+            let aggression = 0.5 + player.skills.mental.aggression / 20.0;
+
+            let mut sent_off = None;
+
+            if rng.gen::<f32>() < YELLOW_CARD_CHANCE * aggression {
+                let time = rng.gen_range(1..MATCH_TIME_MS);
+
+                player.statistics.add_yellow_card(time);
+
+                self.timeline.add(
+                    time,
+                    TimelineEventType::YellowCard {
+                        player_id: player.id,
+                    },
+                );
+
+                if rng.gen::<f32>() < SECOND_YELLOW_CHANCE {
+                    let time = rng.gen_range(time..MATCH_TIME_MS);
+
+                    player.statistics.add_yellow_card(time);
+
+                    self.timeline.add(
+                        time,
+                        TimelineEventType::YellowCard {
+                            player_id: player.id,
+                        },
+                    );
+
+                    sent_off = Some((time, true));
+                }
+            }
+
+            if sent_off.is_none() && rng.gen::<f32>() < RED_CARD_CHANCE * aggression {
+                sent_off = Some((rng.gen_range(1..MATCH_TIME_MS), false));
+            }
+
+            if let Some((time, is_second_yellow)) = sent_off {
+                player.statistics.add_red_card(time);
+
+                self.timeline.add(
+                    time,
+                    TimelineEventType::RedCard {
+                        player_id: player.id,
+                        is_second_yellow,
+                    },
+                );
+
+                self.unavailable.insert(player.id, time);
+            }
+
+            if player.is_injured_by(MATCH_MINUTE_INJURY_CHANCE * MATCH_MINUTES) {
+                let time = rng.gen_range(1..MATCH_TIME_MS);
+
+                if sent_off.is_none_or(|(sent_off_time, _)| time < sent_off_time) {
+                    player.injure(InjuryCause::NonContact, time);
+
+                    self.timeline.add(
+                        time,
+                        TimelineEventType::Injury {
+                            player_id: player.id,
+                        },
+                    );
+
+                    self.unavailable.insert(player.id, time);
+                }
+            }
+        }
+    }
+
+    // Every player on the field kicks in turn, best takers first
+    fn play_penalty_shootout(&mut self, time: u64) -> (u8, u8) {
+        let on_field: Vec<MatchPlayer> = self
+            .players
+            .iter()
+            .filter(|p| self.is_available(p.id, time))
+            .cloned()
+            .collect();
+
+        let mut shootout =
+            PenaltyShootout::from_players(self.home.team_id, self.away.team_id, &on_field);
+
+        let mut rng = RandomGenerator::current();
+
+        while let Some(kick) = shootout.take_kick() {
+            let skill = on_field
+                .iter()
+                .find(|p| p.id == kick.taker_id)
+                .map_or(10.0, |p| p.skills.technical.penalty_taking);
+
+            if rng.gen::<f32>() < PENALTY_CONVERSION + (skill - 10.0) / 100.0 {
+                shootout.score_pending_kick();
+            }
+
+            if let Some(kick) = shootout.finish_kick() {
+                self.timeline.add(
+                    time,
+                    TimelineEventType::ShootoutKick {
+                        player_id: kick.taker_id,
+                        team_id: kick.team_id,
+                        is_scored: kick.is_scored,
+                    },
+                );
+            }
+        }
+
+        self.timeline.add(
+            time,
+            TimelineEventType::PeriodEnd {
+                period: MatchState::PenaltyShootout,
+            },
+        );
+
+        shootout.score()
+    }
+
+    fn is_available(&self, player_id: u32, time: u64) -> bool {
+        self.unavailable
+            .get(&player_id)
+            .is_none_or(|unavailable_from| time < *unavailable_from)
+    }
+
+    // Player of the team still on the field at the time, chosen by weight
+    fn pick(&self, team_id: u32, time: u64, weight: impl Fn(&MatchPlayer) -> f32) -> Option<usize> {
+        let candidates: Vec<(usize, f32)> = self
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.team_id == team_id && self.is_available(p.id, time))
+            .map(|(idx, p)| (idx, weight(p).max(0.0)))
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect();
+
+        let total: f32 = candidates.iter().map(|(_, weight)| weight).sum();
+
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut roll = RandomGenerator::current().gen::<f32>() * total;
+
+        for (idx, weight) in &candidates {
+            if roll < *weight {
+                return Some(*idx);
+            }
+
+            roll -= weight;
+        }
+
+        candidates.last().map(|(idx, _)| *idx)
+    }
+
+    // Number of events of a Poisson process with the given mean
+    fn poisson(mean: f32) -> u32 {
+        let mut rng = RandomGenerator::current();
+
+        let limit = (-mean as f64).exp();
+        let mut product: f64 = rng.gen();
+        let mut count = 0;
+
+        while product > limit {
+            count += 1;
+            product *= rng.gen::<f64>();
+        }
+
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::testing;
+
+    const SEASONS: u64 = 10;
+    const SEASON_MATCHES: u64 = 38;
+
+    // Same players for every team, only their condition differs
+    fn squad(team_id: u32, condition: i16) -> TeamSquad {
+        let mut squad = RandomGenerator::scope(1, || testing::squad(team_id));
+
+        for player in squad.main_squad.iter_mut() {
+            player.player_attributes.condition = condition;
+        }
+
+        squad
+    }
+
+    fn play(seed: u64, home_condition: i16, config: MatchConfig) -> MatchResultRaw {
+        RandomGenerator::scope(seed, || {
+            StatisticalEngine::play(squad(1, home_condition), squad(2, 10000), config)
+        })
+    }
+
+    #[test]
+    fn scorers_and_timeline_add_up_to_the_score() {
+        for seed in 0..50 {
+            let result = play(seed, 10000, MatchConfig::default());
+            let score = result.score.as_ref().unwrap();
+
+            for (team_id, goals) in [(1, score.home_team.get()), (2, score.away_team.get())] {
+                let scored: u8 = result
+                    .player_results
+                    .iter()
+                    .filter(|r| r.team_id == team_id)
+                    .map(|r| r.goals)
+                    .sum();
+
+                assert_eq!(goals, scored);
+            }
+
+            assert_eq!(
+                (score.home_team.get() + score.away_team.get()) as usize,
+                result.timeline.goals().count()
+            );
+            assert!(result
+                .timeline
+                .events
+                .windows(2)
+                .all(|pair| pair[0].time <= pair[1].time));
+            assert_eq!(2, result.team_statistics.len());
+        }
+    }
+
+    #[test]
+    fn fitter_side_wins_more_often() {
+        let (mut home_wins, mut away_wins) = (0, 0);
+
+        for seed in 0..200 {
+            let result = play(seed, 5000, MatchConfig::default());
+            let score = result.score.as_ref().unwrap();
+
+            if score.home_team.get() > score.away_team.get() {
+                home_wins += 1;
+            } else if score.away_team.get() > score.home_team.get() {
+                away_wins += 1;
+            }
+        }
+
+        assert!(away_wins > home_wins * 2);
+    }
+
+    #[test]
+    fn attacking_side_takes_more_shots() {
+        let home_shots = |mentality: Mentality| -> u32 {
+            (0..100)
+                .map(|seed| {
+                    let mut home = squad(1, 10000);
+                    home.tactics.instructions.mentality = mentality;
+
+                    let result = RandomGenerator::scope(seed, || {
+                        StatisticalEngine::play(home, squad(2, 10000), MatchConfig::default())
+                    });
+
+                    result.team_statistics[0].shots as u32
+                })
+                .sum()
+        };
+
+        assert!(home_shots(Mentality::VeryAttacking) > home_shots(Mentality::VeryDefensive));
+    }
+
+    #[test]
+    fn goals_and_scorers_stay_realistic() {
+        let rated_squad = |team_id: u32, skill: f32| {
+            let mut squad = squad(team_id, 10000);
+            squad
+                .main_squad
+                .iter_mut()
+                .for_each(|player| testing::set_skills(player, skill));
+            squad
+        };
+
+        // seasons of matches between equal sides and between a strong and a weak one
+        for (home_skill, away_skill) in [(12.0, 12.0), (15.0, 9.0)] {
+            let mut goals = 0;
+
+            for season in 0..SEASONS {
+                let mut scorers: HashMap<u32, u32> = HashMap::new();
+
+                for seed in season * SEASON_MATCHES..(season + 1) * SEASON_MATCHES {
+                    let result = RandomGenerator::scope(seed, || {
+                        StatisticalEngine::play(
+                            rated_squad(1, home_skill),
+                            rated_squad(2, away_skill),
+                            MatchConfig::default(),
+                        )
+                    });
+
+                    let score = result.score.as_ref().unwrap();
+                    goals += score.home_team.get() as u32 + score.away_team.get() as u32;
+
+                    for player_result in result.player_results.iter().filter(|r| r.goals > 0) {
+                        *scorers.entry(player_result.player_id).or_default() +=
+                            player_result.goals as u32;
+                    }
+                }
+
+                let top_scorer = scorers.values().copied().max().unwrap();
+
+                // goals spread over the side, nobody near the record seasons
+                assert!(top_scorer <= 32, "top scorer has {} goals", top_scorer);
+                assert!(scorers.len() >= 15, "only {} scorers", scorers.len());
+            }
+
+            let goals_per_match = goals as f32 / (SEASONS * SEASON_MATCHES) as f32;
+
+            assert!(
+                (2.0..3.5).contains(&goals_per_match),
+                "{} goals per match",
+                goals_per_match
+            );
+        }
+    }
+
+    #[test]
+    fn knockout_match_always_has_a_winner() {
+        let mut shootouts = 0;
+
+        for seed in 0..100 {
+            let result = play(seed, 10000, MatchConfig::knockout());
+            let score = result.score.as_ref().unwrap();
+
+            match result.penalty_shootout {
+                Some((home, away)) => {
+                    shootouts += 1;
+
+                    assert_ne!(home, away);
+                    assert_eq!(score.home_team.get(), score.away_team.get());
+                    assert!(result.extra_time.is_some());
+                }
+                None => assert_ne!(score.home_team.get(), score.away_team.get()),
+            }
+        }
+
+        assert!(shootouts > 0);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::r#match::{MatchPlayer, TeamSquad};
+use crate::PlayerFieldPositionGroup;
+
+const SQUAD_SIZE: f32 = 11.0;
+
+// form of a player with an average rating neither lifts nor drags them
+const NEUTRAL_FORM: f32 = 6.5;
+const FORM_WEIGHT: f32 = 0.03;
+const MAX_FORM_EFFECT: f32 = 0.1;
+
+// tired players give at least this share of what they can
+const MIN_CONDITION_FACTOR: f32 = 0.5;
+
+// share of the defense that is down to the goalkeeper
+const GOALKEEPER_SHARE: f32 = 0.25;
+
+// How good a team is at each phase of play on the 1-20 skill scale, from the
+// eleven that start the match and the positions the tactics put them in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TeamStrength {
+    pub attack: f32,
+    pub midfield: f32,
+    pub defense: f32,
+}
+
+impl TeamStrength {
+    pub fn from_squad(squad: &TeamSquad) -> Self {
+        Self::from_players(&squad.main_squad)
+    }
+
+    pub fn from_players(players: &[MatchPlayer]) -> Self {
+        let outfield_defense = Self::weighted(players, Self::defending, |group| match group {
+            PlayerFieldPositionGroup::Goalkeeper => 0.0,
+            PlayerFieldPositionGroup::Defender => 3.0,
+            PlayerFieldPositionGroup::Midfielder => 1.5,
+            PlayerFieldPositionGroup::Forward => 0.5,
+        });
+
+        let goalkeeping = Self::weighted(players, Self::goalkeeping, |group| {
+            if group == PlayerFieldPositionGroup::Goalkeeper {
+                1.0
+            } else {
+                0.0
+            }
+        });
+
+        // a side short of players covers less of the pitch
+        let numbers = (players.len() as f32 / SQUAD_SIZE).min(1.0);
+
+        TeamStrength {
+            attack: numbers * Self::weighted(players, Self::attacking, Self::attack_weight),
+            midfield: numbers * Self::weighted(players, Self::creating, Self::creation_weight),
+            defense: numbers
+                * ((1.0 - GOALKEEPER_SHARE) * outfield_defense + GOALKEEPER_SHARE * goalkeeping),
+        }
+    }
+
+    // How much of the attacking a player of the position group does
+    pub fn attack_weight(group: PlayerFieldPositionGroup) -> f32 {
+        match group {
+            PlayerFieldPositionGroup::Goalkeeper => 0.0,
+            PlayerFieldPositionGroup::Defender => 0.5,
+            PlayerFieldPositionGroup::Midfielder => 1.5,
+            PlayerFieldPositionGroup::Forward => 3.0,
+        }
+    }
+
+    // How much of the build-up play a player of the position group does
+    pub fn creation_weight(group: PlayerFieldPositionGroup) -> f32 {
+        match group {
+            PlayerFieldPositionGroup::Goalkeeper => 0.0,
+            PlayerFieldPositionGroup::Defender => 1.0,
+            PlayerFieldPositionGroup::Midfielder => 3.0,
+            PlayerFieldPositionGroup::Forward => 1.0,
+        }
+    }
+
+    pub fn attacking(player: &MatchPlayer) -> f32 {
+        let skills = &player.skills;
+
+        let rating = (skills.technical.finishing
+            + skills.technical.dribbling
+            + skills.technical.first_touch
+            + skills.mental.off_the_ball
+            + skills.mental.composure
+            + skills.physical.pace)
+            / 6.0;
+
+        rating * Self::readiness(player)
+    }
+
+    pub fn creating(player: &MatchPlayer) -> f32 {
+        let skills = &player.skills;
+
+        let rating = (skills.technical.passing
+            + skills.technical.technique
+            + skills.mental.vision
+            + skills.mental.decisions
+            + skills.mental.teamwork)
+            / 5.0;
+
+        rating * Self::readiness(player)
+    }
+
+    pub fn defending(player: &MatchPlayer) -> f32 {
+        let skills = &player.skills;
+
+        let rating = (skills.technical.tackling
+            + skills.technical.marking
+            + skills.technical.heading
+            + skills.mental.positioning
+            + skills.mental.anticipation
+            + skills.physical.strength)
+            / 6.0;
+
+        rating * Self::readiness(player)
+    }
+
+    pub fn goalkeeping(player: &MatchPlayer) -> f32 {
+        let skills = &player.skills;
+
+        let rating = (skills.mental.positioning
+            + skills.mental.concentration
+            + skills.mental.anticipation
+            + skills.mental.decisions
+            + skills.physical.agility
+            + skills.physical.jumping)
+            / 6.0;
+
+        rating * Self::readiness(player)
+    }
+
+    // Condition and form on the day
+    fn readiness(player: &MatchPlayer) -> f32 {
+        let condition = (player.player_attributes.condition_percentage() as f32 / 100.0)
+            .clamp(MIN_CONDITION_FACTOR, 1.0);
+
+        let form = if player.form > 0.0 {
+            ((player.form - NEUTRAL_FORM) * FORM_WEIGHT).clamp(-MAX_FORM_EFFECT, MAX_FORM_EFFECT)
+        } else {
+            0.0
+        };
+
+        condition * (1.0 + form)
+    }
+
+    // Weighted average over the players, the lowest skill without anybody to count
+    fn weighted(
+        players: &[MatchPlayer],
+        rating: fn(&MatchPlayer) -> f32,
+        weight: fn(PlayerFieldPositionGroup) -> f32,
+    ) -> f32 {
+        let (total, weights) = players.iter().fold((0.0, 0.0), |(total, weights), player| {
+            let weight = weight(player.tactical_position.current_position.position_group());
+
+            (total + rating(player) * weight, weights + weight)
+        });
+
+        if weights > 0.0 {
+            total / weights
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#match::testing;
+
+    fn players(skill: f32) -> Vec<MatchPlayer> {
+        testing::squad(1)
+            .main_squad
+            .into_iter()
+            .map(|mut player| {
+                player.player_attributes.condition = 10000;
+
+                testing::set_skills(&mut player, skill);
+
+                player
+            })
+            .collect()
+    }
+
+    #[test]
+    fn strength_follows_skills_numbers_and_form() {
+        let good = TeamStrength::from_players(&players(15.0));
+        let poor = TeamStrength::from_players(&players(8.0));
+
+        assert!((good.attack - 15.0).abs() < 0.001);
+        assert!((good.midfield - 15.0).abs() < 0.001);
+        assert!((good.defense - 15.0).abs() < 0.001);
+        assert!(poor.attack < good.attack && poor.defense < good.defense);
+
+        let mut short_handed = players(15.0);
+        short_handed.pop();
+
+        assert!(TeamStrength::from_players(&short_handed).attack < good.attack);
+
+        let mut in_form = players(15.0);
+        in_form.iter_mut().for_each(|p| p.form = 8.0);
+
+        assert!(TeamStrength::from_players(&in_form).midfield > good.midfield);
+    }
+}
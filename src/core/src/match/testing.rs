@@ -0,0 +1,104 @@
+use crate::r#match::{
+    MatchContext, MatchField, MatchPlayer, MatchPlayerCollection, Score, TeamSquad, MATCH_MINUTE_MS,
+};
+use crate::{MatchTacticType, PlayerGenerator, PlayerPositionType, Tactics};
+use chrono::NaiveDate;
+
+// Shared squads of the match tests, player ids are team_id * 100 + index
+
+pub const POSITIONS: [PlayerPositionType; 11] = [
+    PlayerPositionType::Goalkeeper,
+    PlayerPositionType::DefenderLeft,
+    PlayerPositionType::DefenderCenterLeft,
+    PlayerPositionType::DefenderCenterRight,
+    PlayerPositionType::DefenderRight,
+    PlayerPositionType::MidfielderLeft,
+    PlayerPositionType::MidfielderCenterLeft,
+    PlayerPositionType::MidfielderCenterRight,
+    PlayerPositionType::MidfielderRight,
+    PlayerPositionType::ForwardLeft,
+    PlayerPositionType::ForwardRight,
+];
+
+pub fn player(team_id: u32, id: u32, position: PlayerPositionType) -> MatchPlayer {
+    let date = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+
+    let mut player = PlayerGenerator::generate(1, date, position, 15);
+    player.id = id;
+
+    MatchPlayer::from_player(team_id, &player, position, false)
+}
+
+// Every skill the team strength is rated on set to the same value
+pub fn set_skills(player: &mut MatchPlayer, skill: f32) {
+    let skills = &mut player.skills;
+
+    skills.technical.finishing = skill;
+    skills.technical.dribbling = skill;
+    skills.technical.first_touch = skill;
+    skills.technical.passing = skill;
+    skills.technical.technique = skill;
+    skills.technical.tackling = skill;
+    skills.technical.marking = skill;
+    skills.technical.heading = skill;
+    skills.mental.off_the_ball = skill;
+    skills.mental.composure = skill;
+    skills.mental.vision = skill;
+    skills.mental.decisions = skill;
+    skills.mental.teamwork = skill;
+    skills.mental.positioning = skill;
+    skills.mental.anticipation = skill;
+    skills.mental.concentration = skill;
+    skills.physical.pace = skill;
+    skills.physical.strength = skill;
+    skills.physical.agility = skill;
+    skills.physical.jumping = skill;
+}
+
+pub fn squad(team_id: u32) -> TeamSquad {
+    squad_with(team_id, &POSITIONS)
+}
+
+pub fn squad_with(team_id: u32, positions: &[PlayerPositionType]) -> TeamSquad {
+    TeamSquad {
+        team_id,
+        team_name: format!("team {}", team_id),
+        tactics: Tactics::new(MatchTacticType::T442),
+        main_squad: positions
+            .iter()
+            .zip(team_id * 100..)
+            .map(|(position, id)| player(team_id, id, *position))
+            .collect(),
+        substitutes: Vec::new(),
+    }
+}
+
+// Bench players follow the starters from team_id * 100 + 20
+pub fn squad_with_substitutes(
+    team_id: u32,
+    positions: &[PlayerPositionType],
+    substitutes: &[PlayerPositionType],
+) -> TeamSquad {
+    let mut squad = squad_with(team_id, positions);
+
+    squad.substitutes = substitutes
+        .iter()
+        .zip(team_id * 100 + 20..)
+        .map(|(position, id)| player(team_id, id, *position))
+        .collect();
+
+    squad
+}
+
+// A match between the squads on a 840 x 545 field, home on the left, at the given minute
+pub fn match_at(home: TeamSquad, away: TeamSquad, minute: u64) -> (MatchField, MatchContext) {
+    let score = Score::new(home.team_id, away.team_id);
+
+    let players = MatchPlayerCollection::from_squads(&home, &away);
+    let field = MatchField::new(840, 545, home, away);
+    let mut context = MatchContext::new(&field, players, score);
+
+    context.time.time = minute * MATCH_MINUTE_MS;
+
+    (field, context)
+}
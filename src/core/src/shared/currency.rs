@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CurrencyValue {
     pub amount: f64,
     pub currency: Currency,
@@ -10,7 +12,7 @@ impl CurrencyValue {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum Currency {
     Usd,
 }
@@ -1,12 +1,21 @@
 use crate::context::{GlobalContext, SimulationContext};
 use crate::continent::{Continent, ContinentResult};
-use crate::league::League;
-use crate::r#match::MatchResult;
+use crate::league::{League, SeasonSummary};
+use crate::r#match::statistics::MatchPlayerResult;
+use crate::r#match::{MatchEngineMode, MatchResult, TeamScore};
 use crate::shared::{SimulatorDataIndexes, TeamData};
-use crate::transfers::TransferPool;
-use crate::utils::Logging;
-use crate::{Club, Country, Player, Team};
+use crate::snapshot::{SimulatorSnapshot, SnapshotError};
+use crate::transfers::{PlayerLoan, PlayerTransfer, TransferMarket};
+use crate::utils::{Logging, RandomGenerator, RandomStream};
+use crate::{
+    Club, Country, Injury, InjuryCause, MatchHistoryItem, Player, PlayerClubContract,
+    PlayerStatusType, Team,
+};
 use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
 
 pub struct FootballSimulator;
 
@@ -18,17 +27,35 @@ impl FootballSimulator {
 
         Logging::estimate(
             || {
-                let ctx = GlobalContext::new(SimulationContext::new(data.date));
+                let day_seed = RandomGenerator::derive_seed(
+                    data.seed,
+                    RandomStream::Simulation,
+                    data.date.and_utc().timestamp() as u64,
+                );
+
+                let ctx = GlobalContext::new(
+                    SimulationContext::new(data.date)
+                        .with_seed(day_seed)
+                        .with_match_engine(data.match_engine),
+                );
+
+                RandomGenerator::scope(day_seed, || {
+                    let results: Vec<ContinentResult> = data
+                        .continents
+                        .iter_mut()
+                        .map(|continent| continent.simulate(ctx.with_continent(continent.id)))
+                        .collect();
 
-                let results: Vec<ContinentResult> = data
-                    .continents
-                    .iter_mut()
-                    .map(|continent| continent.simulate(ctx.with_continent(continent.id)))
-                    .collect();
+                    for continent_result in results {
+                        continent_result.process(data, &mut result);
+                    }
 
-                for continent_result in results {
-                    continent_result.process(data, &mut result);
-                }
+                    let transfer_result = data
+                        .transfer_market
+                        .simulate(&data.continents, &ctx.simulation);
+
+                    transfer_result.process(data, &mut result);
+                });
 
                 data.next_date();
             },
@@ -39,32 +66,61 @@ impl FootballSimulator {
     }
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct SimulatorData {
     pub continents: Vec<Continent>,
 
     pub date: NaiveDateTime,
 
-    pub transfer_pool: TransferPool<Player>,
+    pub seed: u64,
+
+    // how matches are played unless a competition says otherwise
+    pub match_engine: MatchEngineMode,
 
+    pub transfer_market: TransferMarket,
+
+    #[serde(skip)]
     pub indexes: Option<SimulatorDataIndexes>,
 }
 
 impl SimulatorData {
     pub fn new(date: NaiveDateTime, continents: Vec<Continent>) -> Self {
+        Self::with_seed(date, rand::random(), continents)
+    }
+
+    pub fn with_seed(date: NaiveDateTime, seed: u64, continents: Vec<Continent>) -> Self {
         let mut data = SimulatorData {
             continents,
             date,
-            transfer_pool: TransferPool::new(),
+            seed,
+            match_engine: MatchEngineMode::Detailed,
+            transfer_market: TransferMarket::new(),
             indexes: None,
         };
 
-        let mut indexes = SimulatorDataIndexes::new();
+        data.rebuild_indexes();
+
+        data
+    }
 
-        indexes.refresh(&data);
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), SnapshotError> {
+        let file = File::create(path)?;
 
-        data.indexes = Some(indexes);
+        SimulatorSnapshot::write(self, BufWriter::new(file))
+    }
 
-        data
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, SnapshotError> {
+        let file = File::open(path)?;
+
+        SimulatorSnapshot::read(BufReader::new(file))
+    }
+
+    pub fn rebuild_indexes(&mut self) {
+        let mut indexes = SimulatorDataIndexes::new();
+
+        indexes.refresh(self);
+
+        self.indexes = Some(indexes);
     }
 
     pub fn next_date(&mut self) {
@@ -260,16 +316,250 @@ impl SimulatorData {
             })
             .and_then(|team| team.players.players.iter_mut().find(|c| c.id == id))
     }
+
+    // Stored match of a league, continental tournament or national cup
+    pub fn match_result(&self, competition_slug: &str, match_id: &str) -> Option<&MatchResult> {
+        let league_id = self
+            .indexes
+            .as_ref()
+            .and_then(|indexes| indexes.slug_indexes.get_league_by_slug(competition_slug));
+
+        if let Some(league_id) = league_id {
+            return self.league(league_id)?.matches.get(match_id);
+        }
+
+        for continent in &self.continents {
+            if let Some(tournament) = continent
+                .tournaments()
+                .into_iter()
+                .find(|tournament| tournament.competition().slug == competition_slug)
+            {
+                return tournament.competition().matches.get(match_id);
+            }
+
+            if let Some(cup) = continent
+                .countries
+                .iter()
+                .flat_map(|country| &country.cups.cups)
+                .find(|cup| cup.slug == competition_slug)
+            {
+                return cup.matches.get(match_id);
+            }
+        }
+
+        None
+    }
+
+    pub fn add_player_statistics(&mut self, result: &MatchResult) {
+        let player_results = match &result.details {
+            Some(details) => &details.player_results,
+            None => return,
+        };
+
+        let date = self.date.date();
+
+        // banned players sat this one out
+        for team_id in [result.home_team_id, result.away_team_id] {
+            if let Some(team) = self.team_mut(team_id) {
+                team.players
+                    .players
+                    .iter_mut()
+                    .filter(|player| player.player_attributes.is_banned)
+                    .for_each(|player| player.serve_suspension());
+            }
+        }
+
+        for player_result in player_results {
+            let injury = player_result
+                .injury
+                .and_then(|cause| self.match_injury(player_result, cause));
+
+            if let Some(player) = self.player_mut(player_result.player_id) {
+                player.statistics.add_match(player_result);
+                player.add_match_cards(player_result, date);
+
+                if let Some(injury) = injury {
+                    player.injure(injury);
+                }
+            }
+        }
+
+        for loan in self.transfer_market.loans.iter_mut() {
+            if loan.to_team_id == result.home_team_id || loan.to_team_id == result.away_team_id {
+                let has_played = player_results.iter().any(|p| p.player_id == loan.player_id);
+
+                loan.add_team_match(has_played);
+            }
+        }
+    }
+
+    // How long a player hurt in the match is out is up to the team's physio
+    fn match_injury(
+        &self,
+        player_result: &MatchPlayerResult,
+        cause: InjuryCause,
+    ) -> Option<Injury> {
+        let team = self.team(player_result.team_id)?;
+        let player = self.player(player_result.player_id)?;
+
+        Some(Injury::random(
+            cause,
+            self.date.date(),
+            player.skills.physical.natural_fitness,
+            &team.staffs.physio().staff_attributes.medical,
+        ))
+    }
+
+    // Pays the fee and moves the player to the buying team under the agreed contract
+    pub fn transfer_player(&mut self, transfer: &PlayerTransfer) -> bool {
+        let mut contract = PlayerClubContract::new(transfer.salary, transfer.contract_expiration);
+        contract.started = Some(transfer.date);
+
+        let moved = self.move_player(
+            transfer.player_id,
+            transfer.from_team_id,
+            transfer.to_team_id,
+            |player| {
+                player.contract = Some(contract);
+                player.statuses.remove(PlayerStatusType::Lst);
+            },
+        );
+
+        if !moved {
+            return false;
+        }
+
+        let fee = transfer.fee as i32;
+
+        if let Some(club) = self.club_mut(transfer.from_club_id) {
+            club.finance.receive_transfer_fee(&club.name, fee);
+        }
+
+        if let Some(club) = self.club_mut(transfer.to_club_id) {
+            club.finance.pay_transfer_fee(&club.name, fee);
+        }
+
+        self.transfer_market.history.push(transfer.clone());
+
+        true
+    }
+
+    // Registers the player with the borrowing team, the contract stays with the parent club
+    pub fn loan_player(&mut self, loan: &PlayerLoan) -> bool {
+        let moved = self.move_player(
+            loan.player_id,
+            loan.from_team_id,
+            loan.to_team_id,
+            |player| {
+                if let Some(contract) = &mut player.contract {
+                    contract.is_transfer_listed = false;
+                    contract.transfer_status = None;
+                }
+
+                player.statuses.remove(PlayerStatusType::Lst);
+                player.statuses.add(loan.start, PlayerStatusType::Loa);
+            },
+        );
+
+        if moved {
+            self.transfer_market.loans.push(loan.clone());
+        }
+
+        moved
+    }
+
+    pub fn return_loaned_player(&mut self, loan: &PlayerLoan) -> bool {
+        let moved = self.move_player(
+            loan.player_id,
+            loan.to_team_id,
+            loan.from_team_id,
+            |player| {
+                player.statuses.remove(PlayerStatusType::Loa);
+            },
+        );
+
+        // the loan ends even when the player is no longer with the borrowing team
+        self.transfer_market.loan_history.push(loan.clone());
+
+        moved
+    }
+
+    fn move_player<F: FnOnce(&mut Player)>(
+        &mut self,
+        player_id: u32,
+        from_team_id: u32,
+        to_team_id: u32,
+        update: F,
+    ) -> bool {
+        let (continent_id, country_id, club_id) = match self
+            .indexes
+            .as_ref()
+            .and_then(|indexes| indexes.get_team_location(to_team_id))
+        {
+            Some(location) => location,
+            None => return false,
+        };
+
+        let mut player = match self.team_mut(from_team_id).and_then(|team| {
+            team.transfer_list.remove(player_id);
+            team.players.take_player(&player_id)
+        }) {
+            Some(player) => player,
+            None => return false,
+        };
+
+        update(&mut player);
+
+        self.team_mut(to_team_id).unwrap().players.add(vec![player]);
+
+        if let Some(indexes) = self.indexes.as_mut() {
+            indexes.add_player_location(player_id, continent_id, country_id, club_id, to_team_id);
+        }
+
+        true
+    }
+
+    pub fn add_match_history(&mut self, result: &MatchResult) {
+        let now = self.date;
+
+        if let Some(home_team) = self.team_mut(result.score.home_team.team_id) {
+            home_team.match_history.add(MatchHistoryItem::new(
+                now,
+                result.score.home_team.team_id,
+                (
+                    TeamScore::from(&result.score.home_team),
+                    TeamScore::from(&result.score.away_team),
+                ),
+            ));
+        }
+
+        if let Some(away_team) = self.team_mut(result.score.away_team.team_id) {
+            away_team.match_history.add(MatchHistoryItem::new(
+                now,
+                result.score.away_team.team_id,
+                (
+                    TeamScore::from(&result.score.away_team),
+                    TeamScore::from(&result.score.home_team),
+                ),
+            ));
+        }
+    }
 }
 
 pub struct SimulationResult {
-    pub match_results: Vec<MatchResult>
+    pub match_results: Vec<MatchResult>,
+    pub season_summaries: Vec<SeasonSummary>,
+    pub transfers: Vec<PlayerTransfer>,
+    pub loans: Vec<PlayerLoan>,
 }
 
 impl SimulationResult {
     pub fn new() -> Self {
         SimulationResult {
             match_results: Vec::new(),
+            season_summaries: Vec::new(),
+            transfers: Vec::new(),
+            loans: Vec::new(),
         }
     }
 
@@ -277,3 +567,311 @@ impl SimulationResult {
         !self.match_results.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::club::testing::{club, team};
+    use crate::league::{DayMonthPeriod, LeagueCollection, LeagueSettings, RankingRule};
+    use crate::r#match::{MatchResultRaw, Score};
+    use crate::transfers::LoanTerms;
+    use crate::{
+        CountryGeneratorData, MatchTacticType, PersonBehaviourState, PlayerClubContract,
+        PlayerGenerator, TACTICS_POSITIONS,
+    };
+    use chrono::NaiveDate;
+
+    #[test]
+    fn simulation_with_same_seed_is_reproducible_regardless_of_threads() {
+        let single_thread = simulate_first_matchday(1);
+        let multi_thread = simulate_first_matchday(4);
+
+        assert!(!single_thread.is_empty());
+        assert_eq!(single_thread, multi_thread);
+    }
+
+    #[test]
+    fn transfer_moves_player_and_pays_fee() {
+        let mut data = generate_data(42);
+        let date = data.date.date();
+
+        data.team_mut(10).unwrap().list_player(101, date);
+
+        let transfer = PlayerTransfer {
+            player_id: 101,
+            from_club_id: 1,
+            from_team_id: 10,
+            to_club_id: 2,
+            to_team_id: 20,
+            fee: 300_000.0,
+            salary: 15000,
+            contract_expiration: NaiveDate::from_ymd_opt(2027, 7, 1).unwrap(),
+            date,
+        };
+
+        assert!(data.transfer_player(&transfer));
+
+        assert!(!data.team(10).unwrap().transfer_list.contains(101));
+        assert!(data
+            .team(10)
+            .unwrap()
+            .players
+            .players
+            .iter()
+            .all(|p| p.id != 101));
+
+        let player = data.player(101).unwrap();
+
+        assert_eq!(15000, player.contract.as_ref().unwrap().salary);
+        assert!(!player.statuses.get().contains(&PlayerStatusType::Lst));
+
+        assert_eq!(1_300_000, data.club(1).unwrap().finance.balance.balance);
+        assert_eq!(700_000, data.club(2).unwrap().finance.balance.balance);
+        assert_eq!(1, data.transfer_market.history.len());
+
+        // the player has already left
+        assert!(!data.transfer_player(&transfer));
+    }
+
+    #[test]
+    fn loaned_player_returns_to_parent_team() {
+        let mut data = generate_data(42);
+        let date = data.date.date();
+
+        data.team_mut(10).unwrap().list_player_for_loan(101);
+
+        let loan = PlayerLoan::new(data.player(101).unwrap(), loan_terms(0.5), date);
+
+        assert!(data.loan_player(&loan));
+
+        assert!(data.team(20).unwrap().players.players.iter().any(|p| p.id == 101));
+
+        let player = data.player(101).unwrap();
+
+        assert!(player.is_on_loan());
+        assert_eq!(None, player.contract.as_ref().unwrap().transfer_status);
+        assert_eq!(1, data.transfer_market.loans.len());
+
+        assert!(data.return_loaned_player(&loan));
+
+        assert!(data.team(10).unwrap().players.players.iter().any(|p| p.id == 101));
+        assert!(!data.player(101).unwrap().is_on_loan());
+        assert_eq!(1, data.transfer_market.loan_history.len());
+    }
+
+    #[test]
+    fn loan_of_missing_loanee_is_closed() {
+        let mut data = generate_data(42);
+        let date = data.date.date();
+
+        data.team_mut(10).unwrap().list_player_for_loan(101);
+
+        let loan = PlayerLoan::new(data.player(101).unwrap(), loan_terms(1.0), date);
+        assert!(data.loan_player(&loan));
+
+        // released by the borrowing team
+        data.team_mut(20).unwrap().players.take_player(&101);
+
+        let transfer_result = data
+            .transfer_market
+            .simulate(&data.continents, &SimulationContext::new(data.date));
+
+        transfer_result.process(&mut data, &mut SimulationResult::new());
+
+        assert!(!data.transfer_market.loans.iter().any(|l| l.player_id == 101));
+        assert_eq!(1, data.transfer_market.loan_history.len());
+        assert_eq!(101, data.transfer_market.loan_history[0].player_id);
+    }
+
+    #[test]
+    fn loan_playing_time_survives_season_end() {
+        let mut data = generate_data(42);
+        let date = data.date.date();
+
+        data.player_mut(101).unwrap().birth_date = NaiveDate::from_ymd_opt(2005, 3, 1).unwrap();
+        data.team_mut(10).unwrap().list_player_for_loan(101);
+
+        let loan = PlayerLoan::new(data.player(101).unwrap(), loan_terms(1.0), date);
+        assert!(loan.playing_time.is_some());
+        assert!(data.loan_player(&loan));
+
+        let team_match = |loanee_plays: bool| {
+            let mut details = MatchResultRaw::with_match_time(0);
+
+            if loanee_plays {
+                details.player_results.push(MatchPlayerResult {
+                    player_id: 101,
+                    team_id: 20,
+                    minutes: 90,
+                    ..Default::default()
+                });
+            }
+
+            MatchResult {
+                id: "match".to_string(),
+                league_id: 1,
+                league_slug: "league".to_string(),
+                home_team_id: 20,
+                away_team_id: 30,
+                details: Some(details),
+                score: Score::new(20, 30),
+            }
+        };
+
+        data.add_player_statistics(&team_match(true));
+        data.add_player_statistics(&team_match(true));
+
+        // the league season ends on 31 May, a month before the loan
+        let season_end = NaiveDate::from_ymd_opt(2025, 5, 31)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let ctx = GlobalContext::new(SimulationContext::new(season_end));
+
+        let country = &mut data.continents[0].countries[0];
+        country.leagues.end_season(&mut country.clubs, &ctx);
+
+        assert_eq!(0, data.player(101).unwrap().statistics.played);
+
+        data.add_player_statistics(&team_match(false));
+
+        let loan = &data.transfer_market.loans[0];
+
+        assert_eq!((2, 3), (loan.appearances, loan.team_matches));
+        assert!(loan.is_playing_time_kept());
+    }
+
+    // helpers
+
+    fn simulate_first_matchday(threads: usize) -> Vec<(String, u8, u8, Vec<(u32, u64)>)> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+
+        pool.install(|| {
+            let mut data = generate_data(42);
+
+            let mut results = Vec::new();
+
+            // schedule starts on the first saturday: 2024-07-06
+            for _ in 0..6 {
+                let result = FootballSimulator::simulate(&mut data);
+
+                results.extend(result.match_results.iter().map(|match_result| {
+                    (
+                        match_result.id.clone(),
+                        match_result.score.home_team.get(),
+                        match_result.score.away_team.get(),
+                        match_result
+                            .score
+                            .details
+                            .iter()
+                            .map(|detail| (detail.player_id, detail.time))
+                            .collect(),
+                    )
+                }));
+            }
+
+            results
+        })
+    }
+
+    // from team 10 of club 1 to team 20 of club 2
+    fn loan_terms(wage_share: f32) -> LoanTerms {
+        LoanTerms {
+            from_club_id: 1,
+            from_team_id: 10,
+            to_club_id: 2,
+            to_team_id: 20,
+            wage_share,
+            purchase: None,
+        }
+    }
+
+    fn generate_data(seed: u64) -> SimulatorData {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+
+        let clubs = RandomGenerator::scope(seed, || {
+            (1..=4).map(|club_id| generate_club(club_id, date.date())).collect()
+        });
+
+        let league = League::new(
+            1,
+            "League".to_string(),
+            "league".to_string(),
+            1,
+            0,
+            1,
+            LeagueSettings {
+                season_starting_half: DayMonthPeriod {
+                    from_day: 1,
+                    from_month: 7,
+                    to_day: 31,
+                    to_month: 12,
+                },
+                season_ending_half: DayMonthPeriod {
+                    from_day: 1,
+                    from_month: 1,
+                    to_day: 31,
+                    to_month: 5,
+                },
+                promotion: None,
+                ranking: RankingRule::defaults(),
+                match_engine: None,
+            },
+        );
+
+        let country = Country::new(
+            1,
+            "cn".to_string(),
+            "country".to_string(),
+            "Country".to_string(),
+            1,
+            LeagueCollection::new(vec![league]),
+            clubs,
+            0,
+            CountryGeneratorData::empty(),
+        );
+
+        SimulatorData::with_seed(
+            date,
+            seed,
+            vec![Continent {
+                id: 1,
+                name: "Continent".to_string(),
+                countries: vec![country],
+                champion_league: None,
+                league_europe: None,
+            }],
+        )
+    }
+
+    fn generate_club(club_id: u32, now: NaiveDate) -> Club {
+        let (_, positions) = TACTICS_POSITIONS
+            .iter()
+            .find(|(tactic_type, _)| *tactic_type == MatchTacticType::T442)
+            .unwrap();
+
+        let players = positions
+            .iter()
+            .chain(positions.iter())
+            .enumerate()
+            .map(|(idx, position)| {
+                let mut player = PlayerGenerator::generate(1, now, *position, 15);
+                player.id = club_id * 100 + idx as u32;
+                player.behaviour.state = PersonBehaviourState::Normal;
+                player.contract = Some(PlayerClubContract::new(
+                    10000,
+                    NaiveDate::from_ymd_opt(2027, 6, 1).unwrap(),
+                ));
+                player
+            })
+            .collect();
+
+        club(club_id, vec![team(club_id * 10, 1, club_id, players)])
+    }
+}
@@ -0,0 +1,37 @@
+use std::fmt::{Display, Formatter};
+use std::{error, io};
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    Serialization(bincode::Error),
+    InvalidHeader,
+    UnsupportedVersion(u16),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Io(err) => write!(f, "snapshot io error: {}", err),
+            SnapshotError::Serialization(err) => write!(f, "snapshot serialization error: {}", err),
+            SnapshotError::InvalidHeader => write!(f, "not a simulator snapshot"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version: {}", version)
+            }
+        }
+    }
+}
+
+impl error::Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+    fn from(err: io::Error) -> Self {
+        SnapshotError::Io(err)
+    }
+}
+
+impl From<bincode::Error> for SnapshotError {
+    fn from(err: bincode::Error) -> Self {
+        SnapshotError::Serialization(err)
+    }
+}
@@ -0,0 +1,5 @@
+mod error;
+mod snapshot;
+
+pub use error::*;
+pub use snapshot::*;
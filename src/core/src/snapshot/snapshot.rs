@@ -0,0 +1,146 @@
+use crate::snapshot::SnapshotError;
+use crate::SimulatorData;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"OFSS";
+
+// bump whenever the serialized world layout changes
+pub const SNAPSHOT_VERSION: u16 = 26;
+
+// header: magic + version (u16 LE), followed by gzip-compressed bincode of SimulatorData
+pub struct SimulatorSnapshot;
+
+impl SimulatorSnapshot {
+    pub fn write<W: Write>(data: &SimulatorData, mut writer: W) -> Result<(), SnapshotError> {
+        writer.write_all(SNAPSHOT_MAGIC)?;
+        writer.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+
+        let mut encoder = GzEncoder::new(writer, Compression::default());
+
+        bincode::serialize_into(&mut encoder, data)?;
+
+        encoder.finish()?.flush()?;
+
+        Ok(())
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> Result<SimulatorData, SnapshotError> {
+        let mut magic = [0u8; 4];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| SnapshotError::InvalidHeader)?;
+
+        if &magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidHeader);
+        }
+
+        let mut version = [0u8; 2];
+        reader
+            .read_exact(&mut version)
+            .map_err(|_| SnapshotError::InvalidHeader)?;
+
+        let version = u16::from_le_bytes(version);
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let mut data: SimulatorData = bincode::deserialize_from(GzDecoder::new(reader))?;
+
+        data.rebuild_indexes();
+
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::club::testing::{club, team};
+    use crate::continent::Continent;
+    use crate::league::LeagueCollection;
+    use crate::{Country, CountryGeneratorData, PlayerGenerator, PlayerPositionType};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn snapshot_roundtrip_restores_world_and_indexes() {
+        let data = generate_data();
+
+        let mut buffer = Vec::new();
+        SimulatorSnapshot::write(&data, &mut buffer).unwrap();
+
+        let restored = SimulatorSnapshot::read(buffer.as_slice()).unwrap();
+
+        assert_eq!(data.date, restored.date);
+
+        let player = data.player(100).unwrap();
+        let restored_player = restored.player(100).unwrap();
+
+        assert_eq!(player.full_name.to_string(), restored_player.full_name.to_string());
+        assert_eq!(player.skills.technical.passing, restored_player.skills.technical.passing);
+        assert_eq!(player.birth_date, restored_player.birth_date);
+
+        assert_eq!(Some(10), restored.indexes.as_ref().unwrap().slug_indexes.get_team_by_slug("team-10"));
+        assert_eq!(1_000_000, restored.club(1).unwrap().finance.balance.balance);
+    }
+
+    #[test]
+    fn snapshot_with_unknown_version_is_rejected() {
+        let mut buffer = Vec::new();
+        SimulatorSnapshot::write(&generate_data(), &mut buffer).unwrap();
+
+        buffer[4..6].copy_from_slice(&(SNAPSHOT_VERSION + 1).to_le_bytes());
+
+        assert!(matches!(
+            SimulatorSnapshot::read(buffer.as_slice()),
+            Err(SnapshotError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn snapshot_with_invalid_header_is_rejected() {
+        assert!(matches!(
+            SimulatorSnapshot::read(&b"JUNK"[..]),
+            Err(SnapshotError::InvalidHeader)
+        ));
+    }
+
+    // helpers
+
+    fn generate_data() -> SimulatorData {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+
+        let mut player = PlayerGenerator::generate(1, date.date(), PlayerPositionType::Striker, 15);
+        player.id = 100;
+
+        let club = club(1, vec![team(10, 0, 1, vec![player])]);
+
+        let country = Country::new(
+            1,
+            "cn".to_string(),
+            "country".to_string(),
+            "Country".to_string(),
+            1,
+            LeagueCollection::new(Vec::new()),
+            vec![club],
+            0,
+            CountryGeneratorData::empty(),
+        );
+
+        SimulatorData::new(
+            date,
+            vec![Continent {
+                id: 1,
+                name: "Continent".to_string(),
+                countries: vec![country],
+                champion_league: None,
+                league_europe: None,
+            }],
+        )
+    }
+}
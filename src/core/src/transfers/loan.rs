@@ -0,0 +1,250 @@
+use crate::{Person, Player};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+// Oldest players clubs send out to develop
+pub const LOAN_MAX_AGE: u8 = 21;
+
+// Share of the borrowing team's matches promised to a young loanee
+const PLAYING_TIME_SHARE: f32 = 0.5;
+
+// Borrowing clubs far below the parent club cover only part of the wage
+const REDUCED_WAGE_SHARE: f32 = 0.5;
+
+// Share of the parent team's reputation a player accepts to step down to without a promise to play
+const REPUTATION_TOLERANCE: f32 = 0.5;
+
+// Loans run until the end of June
+const LOAN_END_MONTH: u32 = 6;
+const LOAN_END_DAY: u32 = 30;
+
+// Loans signed from the season end on run through the next season
+const NEXT_SEASON_MONTH: u32 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoanPurchase {
+    pub fee: f64,
+    pub obligatory: bool,
+}
+
+// What the parent and the borrowing club agreed on
+#[derive(Debug, Clone)]
+pub struct LoanTerms {
+    pub from_club_id: u32,
+    pub from_team_id: u32,
+
+    pub to_club_id: u32,
+    pub to_team_id: u32,
+
+    pub wage_share: f32,
+    pub purchase: Option<LoanPurchase>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerLoan {
+    pub player_id: u32,
+
+    pub from_club_id: u32,
+    pub from_team_id: u32,
+
+    pub to_club_id: u32,
+    pub to_team_id: u32,
+
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+
+    // part of the wage paid by the borrowing club, the parent club pays the rest
+    pub wage_share: f32,
+    // share of the borrowing team's matches promised to the player
+    pub playing_time: Option<f32>,
+    // the parent club may take the player back from this date if the promise isn't kept
+    pub recall_from: Option<NaiveDate>,
+    pub purchase: Option<LoanPurchase>,
+
+    // counted on the loan itself, season statistics are archived before the loan ends
+    pub appearances: u16,
+    pub team_matches: u16,
+}
+
+impl PlayerLoan {
+    // Youngsters are promised playing time with a mid-season recall clause
+    pub fn new(player: &Player, terms: LoanTerms, date: NaiveDate) -> Self {
+        let end = loan_end(date);
+
+        let is_development_loan = player.age(date) <= LOAN_MAX_AGE;
+
+        let recall_from = NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+            .filter(|recall_date| is_development_loan && *recall_date < end);
+
+        PlayerLoan {
+            player_id: player.id,
+            from_club_id: terms.from_club_id,
+            from_team_id: terms.from_team_id,
+            to_club_id: terms.to_club_id,
+            to_team_id: terms.to_team_id,
+            start: date,
+            end,
+            wage_share: terms.wage_share,
+            playing_time: is_development_loan.then_some(PLAYING_TIME_SHARE),
+            recall_from,
+            purchase: terms.purchase,
+            appearances: 0,
+            team_matches: 0,
+        }
+    }
+
+    pub fn wage_share(from_reputation: u16, to_reputation: u16) -> f32 {
+        if (to_reputation as f32) < from_reputation as f32 * REPUTATION_TOLERANCE {
+            REDUCED_WAGE_SHARE
+        } else {
+            1.0
+        }
+    }
+
+    // A promise to play is enough, otherwise the borrowing club must not be much smaller
+    pub fn is_accepted(&self, from_reputation: u16, to_reputation: u16) -> bool {
+        self.playing_time.is_some()
+            || to_reputation as f32 >= from_reputation as f32 * REPUTATION_TOLERANCE
+    }
+
+    pub fn is_finished(&self, date: NaiveDate) -> bool {
+        date >= self.end
+    }
+
+    pub fn can_recall(&self, date: NaiveDate) -> bool {
+        self.recall_from
+            .is_some_and(|recall_date| date >= recall_date)
+    }
+
+    // A match of the borrowing team, with or without the loanee
+    pub fn add_team_match(&mut self, has_played: bool) {
+        self.team_matches += 1;
+
+        if has_played {
+            self.appearances += 1;
+        }
+    }
+
+    pub fn is_playing_time_kept(&self) -> bool {
+        let share = match self.playing_time {
+            Some(share) => share,
+            None => return true,
+        };
+
+        self.team_matches == 0 || self.appearances as f32 >= self.team_matches as f32 * share
+    }
+
+    pub fn week_wage_compensation(&self, salary: u32) -> u32 {
+        (salary as f32 * (1.0 - self.wage_share)) as u32
+    }
+}
+
+fn loan_end(date: NaiveDate) -> NaiveDate {
+    let end_year = if date.month() < NEXT_SEASON_MONTH {
+        date.year()
+    } else {
+        date.year() + 1
+    };
+
+    NaiveDate::from_ymd_opt(end_year, LOAN_END_MONTH, LOAN_END_DAY).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PlayerClubContract, PlayerGenerator, PlayerPositionType};
+
+    fn player(birth_year: i32) -> Player {
+        let mut player = PlayerGenerator::generate(
+            1,
+            NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+            PlayerPositionType::Striker,
+            15,
+        );
+
+        player.birth_date = NaiveDate::from_ymd_opt(birth_year, 3, 1).unwrap();
+        player.contract = Some(PlayerClubContract::new(
+            10000,
+            NaiveDate::from_ymd_opt(2027, 6, 1).unwrap(),
+        ));
+
+        player
+    }
+
+    fn terms(wage_share: f32) -> LoanTerms {
+        LoanTerms {
+            from_club_id: 1,
+            from_team_id: 10,
+            to_club_id: 2,
+            to_team_id: 20,
+            wage_share,
+            purchase: None,
+        }
+    }
+
+    #[test]
+    fn development_loan_runs_to_season_end_with_recall() {
+        let date = NaiveDate::from_ymd_opt(2024, 8, 15).unwrap();
+
+        let loan = PlayerLoan::new(
+            &player(2005),
+            terms(PlayerLoan::wage_share(1000, 300)),
+            date,
+        );
+
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 6, 30).unwrap(), loan.end);
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 1, 1), loan.recall_from);
+        assert_eq!(Some(PLAYING_TIME_SHARE), loan.playing_time);
+        assert_eq!(5000, loan.week_wage_compensation(10000));
+        assert!(loan.is_accepted(1000, 300));
+    }
+
+    #[test]
+    fn senior_loan_has_no_promise() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+
+        let loan = PlayerLoan::new(
+            &player(1995),
+            terms(PlayerLoan::wage_share(1000, 900)),
+            date,
+        );
+
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 6, 30).unwrap(), loan.end);
+        assert_eq!(None, loan.recall_from);
+        assert_eq!(0, loan.week_wage_compensation(10000));
+        assert!(!loan.is_accepted(1000, 300));
+    }
+
+    #[test]
+    fn summer_window_loan_runs_through_next_season() {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
+
+        let loan = PlayerLoan::new(&player(2005), terms(1.0), date);
+
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 6, 30).unwrap(), loan.end);
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 1, 1), loan.recall_from);
+    }
+
+    #[test]
+    fn playing_time_is_checked_since_loan_start() {
+        let date = NaiveDate::from_ymd_opt(2024, 8, 15).unwrap();
+
+        let mut young = player(2005);
+        young.statistics.played = 4;
+
+        let mut loan = PlayerLoan::new(&young, terms(1.0), date);
+
+        // appearances before the loan don't count
+        assert!(loan.is_playing_time_kept());
+
+        for match_idx in 0..10 {
+            loan.add_team_match(match_idx % 2 == 0);
+        }
+
+        assert!(loan.is_playing_time_kept());
+
+        loan.add_team_match(false);
+
+        assert!(!loan.is_playing_time_kept());
+    }
+}
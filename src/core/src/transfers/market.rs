@@ -0,0 +1,517 @@
+use crate::context::SimulationContext;
+use crate::continent::Continent;
+use crate::transfers::{
+    LoanEnding, LoanPurchase, LoanTerms, LoanWage, NegotiationOutcome, PersonalTerms,
+    PlayerLoan, PlayerTransfer, SquadNeeds, TransferMarketResult, TransferNegotiation,
+};
+use crate::utils::RandomGenerator;
+use crate::{
+    Club, Country, Player, PlayerFieldPositionGroup, PlayerTransferStatus, Team, TeamType,
+};
+use chrono::NaiveDate;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// Chance that a club without running talks looks for a player on a given day
+const BID_CHANCE: f32 = 0.1;
+
+// Opening offer relative to the asking price
+const OPENING_OFFER_SHARE: f64 = 0.75;
+
+// Clubs want more than the market value for players they haven't listed
+const UNLISTED_PREMIUM: f64 = 1.5;
+
+// New signings aren't sold on before this many days
+const RESALE_DAYS: i64 = 180;
+
+// Chance that a club looks for a loanee on a given day
+const LOAN_CHANCE: f32 = 0.05;
+
+// Most players a club takes on loan at once
+const MAX_LOANS_IN: usize = 4;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TransferMarket {
+    pub negotiations: Vec<TransferNegotiation>,
+    pub history: Vec<PlayerTransfer>,
+    pub loans: Vec<PlayerLoan>,
+    pub loan_history: Vec<PlayerLoan>,
+}
+
+impl TransferMarket {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Clubs of countries with an open window bid for players and move running talks on,
+    // deals agreed with both the selling club and the player are returned to be carried out
+    pub fn simulate(
+        &mut self,
+        continents: &[Continent],
+        ctx: &SimulationContext,
+    ) -> TransferMarketResult {
+        let date = ctx.date.date();
+
+        let all_countries: Vec<&Country> = continents
+            .iter()
+            .flat_map(|continent| &continent.countries)
+            .collect();
+
+        let countries: Vec<&Country> = all_countries
+            .iter()
+            .copied()
+            .filter(|country| country.transfer_windows.is_open(date))
+            .collect();
+
+        let mut result = TransferMarketResult::new();
+
+        // loans end and are paid for regardless of the windows
+        self.end_loans(&all_countries, &countries, date, &mut result);
+
+        if ctx.is_week_beginning() {
+            self.pay_loan_wages(&all_countries, &mut result);
+        }
+
+        // talks stop once the window of either club closes
+        for mut negotiation in std::mem::take(&mut self.negotiations) {
+            let (seller, buyer) = match (
+                find_club(&countries, negotiation.selling_club_id),
+                find_club(&countries, negotiation.buying_club_id),
+            ) {
+                (Some(seller), Some(buyer)) => (seller, buyer),
+                _ => continue,
+            };
+
+            let (selling_team, buying_team) = match (
+                find_team(seller, negotiation.selling_team_id),
+                find_team(buyer, negotiation.buying_team_id),
+            ) {
+                (Some(selling_team), Some(buying_team)) => (selling_team, buying_team),
+                _ => continue,
+            };
+
+            let player = match selling_team
+                .players
+                .players
+                .iter()
+                .find(|player| player.id == negotiation.player_id)
+            {
+                Some(player) => player,
+                None => continue,
+            };
+
+            match negotiation.negotiate(buyer.finance.transfer_budget()) {
+                NegotiationOutcome::InProgress => self.negotiations.push(negotiation),
+                NegotiationOutcome::FeeAgreed => {
+                    let terms = PersonalTerms::offer(player, date);
+
+                    if terms.is_accepted(
+                        player,
+                        selling_team.reputation.world,
+                        buying_team.reputation.world,
+                    ) {
+                        result.transfers.push(PlayerTransfer {
+                            player_id: player.id,
+                            from_club_id: seller.id,
+                            from_team_id: selling_team.id,
+                            to_club_id: buyer.id,
+                            to_team_id: buying_team.id,
+                            fee: negotiation.offer,
+                            salary: terms.salary,
+                            contract_expiration: terms.expiration,
+                            date,
+                        });
+                    }
+                }
+                NegotiationOutcome::Collapsed => {}
+            }
+        }
+
+        let listings = Self::listings(&countries, date);
+
+        let mut rng = RandomGenerator::current();
+
+        for club in countries.iter().flat_map(|country| &country.clubs) {
+            let is_busy = self
+                .negotiations
+                .iter()
+                .any(|negotiation| negotiation.buying_club_id == club.id)
+                || result
+                    .transfers
+                    .iter()
+                    .any(|transfer| transfer.to_club_id == club.id);
+
+            if is_busy || rng.gen::<f32>() >= BID_CHANCE {
+                continue;
+            }
+
+            if let Some(negotiation) = self.bid(club, &listings, date) {
+                self.negotiations.push(negotiation);
+            }
+        }
+
+        let loan_listings = Self::loan_listings(&countries, date);
+
+        for club in countries.iter().flat_map(|country| &country.clubs) {
+            let loans_in = self
+                .loans
+                .iter()
+                .filter(|loan| loan.to_club_id == club.id)
+                .count()
+                + result
+                    .loans
+                    .iter()
+                    .filter(|loan| loan.to_club_id == club.id)
+                    .count();
+
+            if loans_in >= MAX_LOANS_IN || rng.gen::<f32>() >= LOAN_CHANCE {
+                continue;
+            }
+
+            if let Some(loan) = Self::loan_bid(&countries, club, &loan_listings, &result, date) {
+                result.loans.push(loan);
+            }
+        }
+
+        result
+    }
+
+    // Loanees go back at the end of the loan, or earlier when the parent club recalls
+    // a youngster who doesn't get the promised playing time
+    fn end_loans(
+        &mut self,
+        all_countries: &[&Country],
+        countries: &[&Country],
+        date: NaiveDate,
+        result: &mut TransferMarketResult,
+    ) {
+        for loan in std::mem::take(&mut self.loans) {
+            let (borrower, _, player) = match find_loanee(
+                all_countries,
+                loan.to_club_id,
+                loan.to_team_id,
+                loan.player_id,
+            ) {
+                Some(loanee) => loanee,
+                None => {
+                    // released, retired or moved on, the loan is closed all the same
+                    result.loan_endings.push(LoanEnding {
+                        loan,
+                        transfer: None,
+                    });
+
+                    continue;
+                }
+            };
+
+            let is_playing_time_kept = loan.is_playing_time_kept();
+
+            if loan.is_finished(date) {
+                let transfer = loan
+                    .purchase
+                    .as_ref()
+                    .filter(|purchase| {
+                        purchase.obligatory
+                            || (is_playing_time_kept
+                                && borrower.finance.transfer_budget() >= purchase.fee)
+                    })
+                    .map(|purchase| {
+                        let terms = PersonalTerms::offer(player, date);
+
+                        PlayerTransfer {
+                            player_id: player.id,
+                            from_club_id: loan.from_club_id,
+                            from_team_id: loan.from_team_id,
+                            to_club_id: loan.to_club_id,
+                            to_team_id: loan.to_team_id,
+                            fee: purchase.fee,
+                            salary: terms.salary,
+                            contract_expiration: terms.expiration,
+                            date,
+                        }
+                    });
+
+                result.loan_endings.push(LoanEnding { loan, transfer });
+            } else if !is_playing_time_kept
+                && loan.can_recall(date)
+                && find_club(countries, loan.from_club_id).is_some()
+            {
+                result.loan_endings.push(LoanEnding {
+                    loan,
+                    transfer: None,
+                });
+            } else {
+                self.loans.push(loan);
+            }
+        }
+    }
+
+    // Parent clubs pay the part of the wage the borrowing club doesn't cover
+    fn pay_loan_wages(&self, countries: &[&Country], result: &mut TransferMarketResult) {
+        for loan in &self.loans {
+            let salary = find_loanee(countries, loan.to_club_id, loan.to_team_id, loan.player_id)
+                .and_then(|(_, _, player)| player.contract.as_ref())
+                .map_or(0, |contract| contract.salary);
+
+            let amount = loan.week_wage_compensation(salary);
+
+            if amount > 0 {
+                result.loan_wages.push(LoanWage {
+                    from_club_id: loan.from_club_id,
+                    to_club_id: loan.to_club_id,
+                    amount,
+                });
+            }
+        }
+    }
+
+    // Players of the clubs that may sell today with their asking prices
+    fn listings(countries: &[&Country], date: NaiveDate) -> Vec<TransferListing> {
+        let mut listings = Vec::new();
+
+        for club in countries.iter().flat_map(|country| &country.clubs) {
+            for team in &club.teams.teams {
+                for player in &team.players.players {
+                    let is_new_signing = player
+                        .contract
+                        .as_ref()
+                        .and_then(|contract| contract.started)
+                        .is_some_and(|started| (date - started).num_days() < RESALE_DAYS);
+
+                    // loanees can only be sold by their parent club once they're back
+                    if is_new_signing || player.is_on_loan() {
+                        continue;
+                    }
+
+                    let position_group = match SquadNeeds::position_group(player) {
+                        Some(position_group) => position_group,
+                        None => continue,
+                    };
+
+                    let asking_price = match team
+                        .transfer_list
+                        .items()
+                        .iter()
+                        .find(|item| item.player_id == player.id)
+                    {
+                        Some(item) => item.amount.amount,
+                        None => player.value(date) * UNLISTED_PREMIUM,
+                    };
+
+                    // players without a contract aren't sold
+                    if asking_price <= 0.0 {
+                        continue;
+                    }
+
+                    listings.push(TransferListing {
+                        player_id: player.id,
+                        club_id: club.id,
+                        team_id: team.id,
+                        position_group,
+                        asking_price,
+                    });
+                }
+            }
+        }
+
+        listings
+    }
+
+    // Players the clubs that may lend today have made available for loan
+    fn loan_listings(countries: &[&Country], date: NaiveDate) -> Vec<TransferListing> {
+        let mut listings = Vec::new();
+
+        for club in countries.iter().flat_map(|country| &country.clubs) {
+            for team in &club.teams.teams {
+                for player in &team.players.players {
+                    let transfer_status = player
+                        .contract
+                        .as_ref()
+                        .and_then(|contract| contract.transfer_status);
+
+                    let is_loan_listed = matches!(
+                        transfer_status,
+                        Some(PlayerTransferStatus::LoadListed)
+                            | Some(PlayerTransferStatus::TransferAndLoadListed)
+                    );
+
+                    if !is_loan_listed || player.is_on_loan() {
+                        continue;
+                    }
+
+                    let position_group = match SquadNeeds::position_group(player) {
+                        Some(position_group) => position_group,
+                        None => continue,
+                    };
+
+                    listings.push(TransferListing {
+                        player_id: player.id,
+                        club_id: club.id,
+                        team_id: team.id,
+                        position_group,
+                        asking_price: player.value(date),
+                    });
+                }
+            }
+        }
+
+        listings
+    }
+
+    // The most valuable loanee good enough for a line the club needs to strengthen,
+    // players also up for sale come with an option to buy that turns into an obligation
+    // when the borrowing club can already afford the fee
+    fn loan_bid(
+        countries: &[&Country],
+        club: &Club,
+        listings: &[TransferListing],
+        result: &TransferMarketResult,
+        date: NaiveDate,
+    ) -> Option<PlayerLoan> {
+        let team = club
+            .teams
+            .teams
+            .iter()
+            .find(|team| team.team_type == TeamType::Main)?;
+
+        let needs = SquadNeeds::new(team, date);
+
+        let listing = listings
+            .iter()
+            .filter(|listing| listing.club_id != club.id)
+            .filter(|listing| {
+                needs
+                    .min_value(listing.position_group)
+                    .is_some_and(|min_value| listing.asking_price >= min_value)
+            })
+            .filter(|listing| {
+                !result
+                    .loans
+                    .iter()
+                    .any(|loan| loan.player_id == listing.player_id)
+            })
+            .max_by(|a, b| a.asking_price.total_cmp(&b.asking_price))?;
+
+        let parent_team = find_club(countries, listing.club_id)
+            .and_then(|parent| find_team(parent, listing.team_id))?;
+
+        let player = parent_team
+            .players
+            .players
+            .iter()
+            .find(|player| player.id == listing.player_id)?;
+
+        let purchase = player
+            .contract
+            .as_ref()
+            .filter(|contract| {
+                contract.transfer_status == Some(PlayerTransferStatus::TransferAndLoadListed)
+            })
+            .map(|_| LoanPurchase {
+                fee: listing.asking_price,
+                obligatory: club.finance.transfer_budget() >= listing.asking_price,
+            });
+
+        let terms = LoanTerms {
+            from_club_id: listing.club_id,
+            from_team_id: listing.team_id,
+            to_club_id: club.id,
+            to_team_id: team.id,
+            wage_share: PlayerLoan::wage_share(parent_team.reputation.world, team.reputation.world),
+            purchase,
+        };
+
+        let loan = PlayerLoan::new(player, terms, date);
+
+        if !loan.is_accepted(parent_team.reputation.world, team.reputation.world) {
+            return None;
+        }
+
+        Some(loan)
+    }
+
+    // The most valuable player the club can afford for a line it needs to strengthen
+    fn bid(
+        &self,
+        club: &Club,
+        listings: &[TransferListing],
+        date: NaiveDate,
+    ) -> Option<TransferNegotiation> {
+        let team = club
+            .teams
+            .teams
+            .iter()
+            .find(|team| team.team_type == TeamType::Main)?;
+
+        let budget = club.finance.transfer_budget();
+
+        if budget <= 0.0 {
+            return None;
+        }
+
+        let needs = SquadNeeds::new(team, date);
+
+        listings
+            .iter()
+            .filter(|listing| listing.club_id != club.id && listing.asking_price <= budget)
+            .filter(|listing| {
+                needs
+                    .min_value(listing.position_group)
+                    .is_some_and(|min_value| listing.asking_price >= min_value)
+            })
+            .filter(|listing| {
+                !self
+                    .negotiations
+                    .iter()
+                    .any(|negotiation| negotiation.player_id == listing.player_id)
+            })
+            .max_by(|a, b| a.asking_price.total_cmp(&b.asking_price))
+            .map(|listing| {
+                TransferNegotiation::new(
+                    listing.player_id,
+                    listing.club_id,
+                    listing.team_id,
+                    club.id,
+                    team.id,
+                    listing.asking_price,
+                    listing.asking_price * OPENING_OFFER_SHARE,
+                )
+            })
+    }
+}
+
+struct TransferListing {
+    player_id: u32,
+    club_id: u32,
+    team_id: u32,
+    position_group: PlayerFieldPositionGroup,
+    asking_price: f64,
+}
+
+fn find_club<'c>(countries: &[&'c Country], club_id: u32) -> Option<&'c Club> {
+    countries
+        .iter()
+        .flat_map(|country| &country.clubs)
+        .find(|club| club.id == club_id)
+}
+
+fn find_team(club: &Club, team_id: u32) -> Option<&Team> {
+    club.teams.teams.iter().find(|team| team.id == team_id)
+}
+
+fn find_loanee<'c>(
+    countries: &[&'c Country],
+    club_id: u32,
+    team_id: u32,
+    player_id: u32,
+) -> Option<(&'c Club, &'c Team, &'c Player)> {
+    let club = find_club(countries, club_id)?;
+    let team = find_team(club, team_id)?;
+    let player = team
+        .players
+        .players
+        .iter()
+        .find(|player| player.id == player_id)?;
+
+    Some((club, team, player))
+}
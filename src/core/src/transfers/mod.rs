@@ -1,5 +1,17 @@
-pub mod pool;
+pub mod loan;
+pub mod market;
+pub mod needs;
+pub mod negotiation;
+pub mod result;
+pub mod terms;
 pub mod transfer;
+pub mod window;
 
-pub use pool::*;
+pub use loan::*;
+pub use market::*;
+pub use needs::*;
+pub use negotiation::*;
+pub use result::*;
+pub use terms::*;
 pub use transfer::*;
+pub use window::*;
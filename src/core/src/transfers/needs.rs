@@ -0,0 +1,76 @@
+use crate::{Player, PlayerFieldPositionGroup, Team};
+use chrono::NaiveDate;
+
+// Squad size over which a team lists its least valuable players
+pub const MAX_SQUAD_SIZE: usize = 30;
+
+// Players a first team keeps in every line
+const REQUIRED_PLAYERS: [(PlayerFieldPositionGroup, usize); 4] = [
+    (PlayerFieldPositionGroup::Goalkeeper, 2),
+    (PlayerFieldPositionGroup::Defender, 6),
+    (PlayerFieldPositionGroup::Midfielder, 6),
+    (PlayerFieldPositionGroup::Forward, 4),
+];
+
+// How much more than the average starter of the line a signing has to be worth
+const UPGRADE_FACTOR: f64 = 1.2;
+
+#[derive(Debug)]
+pub struct SquadNeed {
+    pub position_group: PlayerFieldPositionGroup,
+    pub min_value: f64,
+}
+
+#[derive(Debug)]
+pub struct SquadNeeds {
+    pub needs: Vec<SquadNeed>,
+}
+
+impl SquadNeeds {
+    // A short line accepts anyone, otherwise a signing has to be worth more than its regular starters.
+    // Listed players are already on their way out and don't count
+    pub fn new(team: &Team, date: NaiveDate) -> Self {
+        let needs = REQUIRED_PLAYERS
+            .iter()
+            .map(|(position_group, required)| {
+                let mut values: Vec<f64> = team
+                    .players
+                    .players
+                    .iter()
+                    .filter(|player| !team.transfer_list.contains(player.id))
+                    .filter(|player| Self::position_group(player) == Some(*position_group))
+                    .map(|player| player.value(date))
+                    .collect();
+
+                let min_value = if values.len() < *required {
+                    0.0
+                } else {
+                    values.sort_by(|a, b| b.total_cmp(a));
+
+                    values[..*required].iter().sum::<f64>() / *required as f64 * UPGRADE_FACTOR
+                };
+
+                SquadNeed {
+                    position_group: *position_group,
+                    min_value,
+                }
+            })
+            .collect();
+
+        SquadNeeds { needs }
+    }
+
+    pub fn min_value(&self, position_group: PlayerFieldPositionGroup) -> Option<f64> {
+        self.needs
+            .iter()
+            .find(|need| need.position_group == position_group)
+            .map(|need| need.min_value)
+    }
+
+    pub fn position_group(player: &Player) -> Option<PlayerFieldPositionGroup> {
+        player
+            .positions()
+            .first()
+            .map(|position| position.position_group())
+    }
+}
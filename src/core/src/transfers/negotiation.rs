@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+const MAX_ROUNDS: u8 = 5;
+
+// The selling club settles for slightly less than it asked
+const ACCEPTABLE_OFFER_SHARE: f64 = 0.95;
+
+// Part of the gap between the asking price and the offer each side gives up per round
+const SELLER_CONCESSION: f64 = 0.2;
+const BUYER_CONCESSION: f64 = 0.5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferNegotiation {
+    pub player_id: u32,
+
+    pub selling_club_id: u32,
+    pub selling_team_id: u32,
+
+    pub buying_club_id: u32,
+    pub buying_team_id: u32,
+
+    pub asking_price: f64,
+    pub offer: f64,
+    pub rounds: u8,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum NegotiationOutcome {
+    InProgress,
+    FeeAgreed,
+    Collapsed,
+}
+
+impl TransferNegotiation {
+    pub fn new(
+        player_id: u32,
+        selling_club_id: u32,
+        selling_team_id: u32,
+        buying_club_id: u32,
+        buying_team_id: u32,
+        asking_price: f64,
+        offer: f64,
+    ) -> Self {
+        TransferNegotiation {
+            player_id,
+            selling_club_id,
+            selling_team_id,
+            buying_club_id,
+            buying_team_id,
+            asking_price,
+            offer,
+            rounds: 0,
+        }
+    }
+
+    // One round of talks: the selling club answers the current offer,
+    // on a refusal both clubs move towards each other within the buyer's budget
+    pub fn negotiate(&mut self, budget: f64) -> NegotiationOutcome {
+        if self.offer >= self.asking_price * ACCEPTABLE_OFFER_SHARE {
+            return NegotiationOutcome::FeeAgreed;
+        }
+
+        self.rounds += 1;
+
+        if self.rounds >= MAX_ROUNDS {
+            return NegotiationOutcome::Collapsed;
+        }
+
+        self.asking_price -= (self.asking_price - self.offer) * SELLER_CONCESSION;
+
+        let improved_offer =
+            (self.offer + (self.asking_price - self.offer) * BUYER_CONCESSION).min(budget);
+
+        if improved_offer <= self.offer {
+            return NegotiationOutcome::Collapsed;
+        }
+
+        self.offer = improved_offer;
+
+        NegotiationOutcome::InProgress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn negotiate(negotiation: &mut TransferNegotiation, budget: f64) -> NegotiationOutcome {
+        loop {
+            match negotiation.negotiate(budget) {
+                NegotiationOutcome::InProgress => continue,
+                outcome => return outcome,
+            }
+        }
+    }
+
+    #[test]
+    fn fee_is_agreed_between_offer_and_asking_price() {
+        let mut negotiation = TransferNegotiation::new(1, 1, 10, 2, 20, 1_000_000.0, 600_000.0);
+
+        assert_eq!(
+            NegotiationOutcome::FeeAgreed,
+            negotiate(&mut negotiation, 2_000_000.0)
+        );
+
+        assert!(negotiation.offer > 600_000.0);
+        assert!(negotiation.offer <= 1_000_000.0);
+        assert!(negotiation.rounds < MAX_ROUNDS);
+    }
+
+    #[test]
+    fn negotiation_collapses_when_buyer_cannot_pay() {
+        let mut negotiation = TransferNegotiation::new(1, 1, 10, 2, 20, 1_000_000.0, 600_000.0);
+
+        assert_eq!(
+            NegotiationOutcome::Collapsed,
+            negotiate(&mut negotiation, 600_000.0)
+        );
+    }
+}
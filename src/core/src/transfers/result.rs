@@ -0,0 +1,71 @@
+use crate::simulator::SimulatorData;
+use crate::transfers::{PlayerLoan, PlayerTransfer};
+use crate::SimulationResult;
+
+pub struct LoanEnding {
+    pub loan: PlayerLoan,
+    // the borrowing club takes up the option or obligation to buy
+    pub transfer: Option<PlayerTransfer>,
+}
+
+pub struct LoanWage {
+    pub from_club_id: u32,
+    pub to_club_id: u32,
+    pub amount: u32,
+}
+
+#[derive(Default)]
+pub struct TransferMarketResult {
+    pub transfers: Vec<PlayerTransfer>,
+    pub loans: Vec<PlayerLoan>,
+    pub loan_endings: Vec<LoanEnding>,
+    pub loan_wages: Vec<LoanWage>,
+}
+
+impl TransferMarketResult {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The buyer's balance may have dropped since the fee was agreed
+    pub fn process(self, data: &mut SimulatorData, result: &mut SimulationResult) {
+        for ending in self.loan_endings {
+            if !data.return_loaned_player(&ending.loan) {
+                continue;
+            }
+
+            if let Some(transfer) = ending.transfer {
+                if data.transfer_player(&transfer) {
+                    result.transfers.push(transfer);
+                }
+            }
+        }
+
+        for wage in self.loan_wages {
+            if let Some(club) = data.club_mut(wage.from_club_id) {
+                club.finance.pay_loan_wage(&club.name, wage.amount as i32);
+            }
+
+            if let Some(club) = data.club_mut(wage.to_club_id) {
+                club.finance
+                    .receive_loan_wage(&club.name, wage.amount as i32);
+            }
+        }
+
+        for transfer in self.transfers {
+            let affordable = data
+                .club(transfer.to_club_id)
+                .is_some_and(|club| club.finance.transfer_budget() >= transfer.fee);
+
+            if affordable && data.transfer_player(&transfer) {
+                result.transfers.push(transfer);
+            }
+        }
+
+        for loan in self.loans {
+            if data.loan_player(&loan) {
+                result.loans.push(loan);
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::Player;
+use chrono::{Months, NaiveDate};
+
+const SALARY_RAISE: f32 = 1.2;
+const MIN_SALARY: u32 = 1000;
+const CONTRACT_YEARS: u32 = 3;
+
+// Share of the current team's reputation a player is ready to step down to
+const REPUTATION_TOLERANCE: f32 = 0.8;
+
+#[derive(Debug, PartialEq)]
+pub struct PersonalTerms {
+    pub salary: u32,
+    pub expiration: NaiveDate,
+}
+
+impl PersonalTerms {
+    // Buying club offers a raise on the current wage
+    pub fn offer(player: &Player, date: NaiveDate) -> Self {
+        let salary = player
+            .contract
+            .as_ref()
+            .map(|contract| (contract.salary as f32 * SALARY_RAISE) as u32)
+            .unwrap_or(0)
+            .max(MIN_SALARY);
+
+        PersonalTerms {
+            salary,
+            expiration: date
+                .checked_add_months(Months::new(12 * CONTRACT_YEARS))
+                .unwrap(),
+        }
+    }
+
+    // Players who want to leave agree to any raise, others refuse a much smaller club
+    pub fn is_accepted(&self, player: &Player, from_reputation: u16, to_reputation: u16) -> bool {
+        let current_salary = player.contract.as_ref().map(|c| c.salary).unwrap_or(0);
+
+        if self.salary < current_salary {
+            return false;
+        }
+
+        let wants_to_leave = player.behaviour.is_poor()
+            || player
+                .contract
+                .as_ref()
+                .is_some_and(|contract| contract.is_transfer_listed);
+
+        wants_to_leave || to_reputation as f32 >= from_reputation as f32 * REPUTATION_TOLERANCE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PersonBehaviourState, PlayerClubContract, PlayerGenerator, PlayerPositionType};
+
+    fn player(salary: u32) -> Player {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+
+        let mut player = PlayerGenerator::generate(1, date, PlayerPositionType::Striker, 15);
+
+        player.behaviour.state = PersonBehaviourState::Normal;
+        player.contract = Some(PlayerClubContract::new(
+            salary,
+            NaiveDate::from_ymd_opt(2026, 6, 1).unwrap(),
+        ));
+
+        player
+    }
+
+    #[test]
+    fn terms_raise_current_salary() {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+
+        let terms = PersonalTerms::offer(&player(10000), date);
+
+        assert_eq!(12000, terms.salary);
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2027, 7, 1).unwrap(),
+            terms.expiration
+        );
+    }
+
+    #[test]
+    fn player_refuses_much_smaller_club_unless_listed() {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+
+        let mut player = player(10000);
+        let terms = PersonalTerms::offer(&player, date);
+
+        assert!(terms.is_accepted(&player, 1000, 900));
+        assert!(!terms.is_accepted(&player, 1000, 500));
+
+        player.contract.as_mut().unwrap().is_transfer_listed = true;
+
+        assert!(terms.is_accepted(&player, 1000, 500));
+    }
+}
@@ -1,12 +1,20 @@
-use crate::Player;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 
+// Completed move of a player between two teams
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerTransfer {
-    pub player: Player,
-    pub club_id: u32,
-}
+    pub player_id: u32,
+
+    pub from_club_id: u32,
+    pub from_team_id: u32,
+
+    pub to_club_id: u32,
+    pub to_team_id: u32,
+
+    pub fee: f64,
+    pub salary: u32,
+    pub contract_expiration: NaiveDate,
 
-impl PlayerTransfer {
-    pub fn new(player: Player, club_id: u32) -> Self {
-        PlayerTransfer { player, club_id }
-    }
+    pub date: NaiveDate,
 }
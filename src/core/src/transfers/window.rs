@@ -0,0 +1,43 @@
+use crate::league::DayMonthPeriod;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+// Periods of the year when clubs of a country may sign players
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransferWindows {
+    pub periods: Vec<DayMonthPeriod>,
+}
+
+impl TransferWindows {
+    pub fn new(periods: Vec<DayMonthPeriod>) -> Self {
+        TransferWindows { periods }
+    }
+
+    pub fn is_open(&self, date: NaiveDate) -> bool {
+        self.periods.iter().any(|period| period.contains(date))
+    }
+}
+
+impl Default for TransferWindows {
+    // summer and winter windows
+    fn default() -> Self {
+        TransferWindows::new(vec![
+            DayMonthPeriod::new(1, 6, 31, 8),
+            DayMonthPeriod::new(1, 1, 31, 1),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_windows_are_open_in_summer_and_january() {
+        let windows = TransferWindows::default();
+
+        assert!(windows.is_open(NaiveDate::from_ymd_opt(2024, 7, 15).unwrap()));
+        assert!(windows.is_open(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()));
+        assert!(!windows.is_open(NaiveDate::from_ymd_opt(2024, 10, 1).unwrap()));
+    }
+}
@@ -16,9 +16,13 @@ impl DateUtils {
     }
 
     pub fn next_saturday(date: NaiveDate) -> NaiveDate {
+        Self::next_weekday(date, Weekday::Sat)
+    }
+
+    pub fn next_weekday(date: NaiveDate, weekday: Weekday) -> NaiveDate {
         let mut current_date = date;
 
-        while current_date.weekday() != Weekday::Sat {
+        while current_date.weekday() != weekday {
             current_date = current_date.succ_opt().unwrap();
         }
 
@@ -71,4 +75,22 @@ mod tests {
 
         assert_eq!(next_saturday, NaiveDate::from_ymd_opt(2024, 3, 23).unwrap());
     }
+
+    #[test]
+    fn test_next_weekday() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 12).unwrap(); // A Tuesday
+
+        assert_eq!(
+            DateUtils::next_weekday(date, Weekday::Tue),
+            NaiveDate::from_ymd_opt(2024, 3, 12).unwrap()
+        );
+        assert_eq!(
+            DateUtils::next_weekday(date, Weekday::Thu),
+            NaiveDate::from_ymd_opt(2024, 3, 14).unwrap()
+        );
+        assert_eq!(
+            DateUtils::next_weekday(date, Weekday::Mon),
+            NaiveDate::from_ymd_opt(2024, 3, 18).unwrap()
+        );
+    }
 }
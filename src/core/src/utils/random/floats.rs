@@ -1,12 +1,12 @@
-extern crate rand;
-use rand::*;
+use crate::utils::RandomGenerator;
+use rand::Rng;
 
 pub struct FloatUtils;
 
 impl FloatUtils {
     #[inline]
     pub fn random(min: f32, max: f32) -> f32 {
-        let random_val: f32 = random();
+        let random_val: f32 = RandomGenerator::current().gen();
 
         min + (random_val * (max - min))
     }
@@ -0,0 +1,144 @@
+use rand::rngs::StdRng;
+use rand::{thread_rng, Error, RngCore, SeedableRng};
+use std::cell::RefCell;
+
+thread_local! {
+    static SCOPED_GENERATOR: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RandomStream {
+    Simulation = 1,
+    Country = 2,
+    League = 3,
+    Match = 4,
+    Club = 5,
+    PlayerGenerator = 6,
+    Tournament = 7,
+    Cup = 8,
+}
+
+// Handle to the current thread's generator: seeded while inside `RandomGenerator::scope`,
+// falls back to `rand::thread_rng` outside of any scope
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomGenerator;
+
+impl RandomGenerator {
+    #[inline]
+    pub fn current() -> Self {
+        RandomGenerator
+    }
+
+    pub fn scope<F: FnOnce() -> R, R>(seed: u64, f: F) -> R {
+        let previous = SCOPED_GENERATOR.with(|generator| {
+            generator.replace(Some(StdRng::seed_from_u64(seed)))
+        });
+
+        let _guard = ScopeGuard { previous };
+
+        f()
+    }
+
+    pub fn derive_seed(seed: u64, stream: RandomStream, id: u64) -> u64 {
+        Self::mix(Self::mix(seed ^ Self::mix(stream as u64)) ^ id)
+    }
+
+    // splitmix64 finalizer
+    fn mix(value: u64) -> u64 {
+        let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    #[inline]
+    fn with_generator<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        SCOPED_GENERATOR.with(|generator| match generator.borrow_mut().as_mut() {
+            Some(scoped) => f(scoped),
+            None => f(&mut thread_rng()),
+        })
+    }
+}
+
+struct ScopeGuard {
+    previous: Option<StdRng>,
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+
+        SCOPED_GENERATOR.with(|generator| {
+            *generator.borrow_mut() = previous;
+        });
+    }
+}
+
+impl RngCore for RandomGenerator {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        Self::with_generator(|rng| rng.next_u32())
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        Self::with_generator(|rng| rng.next_u64())
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        Self::with_generator(|rng| rng.fill_bytes(dest))
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        Self::with_generator(|rng| rng.try_fill_bytes(dest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn scope_with_same_seed_is_reproducible() {
+        let first: Vec<u32> = RandomGenerator::scope(42, || {
+            (0..10).map(|_| RandomGenerator::current().gen()).collect()
+        });
+
+        let second: Vec<u32> = RandomGenerator::scope(42, || {
+            (0..10).map(|_| RandomGenerator::current().gen()).collect()
+        });
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn nested_scope_does_not_affect_outer_stream() {
+        let expected: Vec<u32> = RandomGenerator::scope(7, || {
+            (0..4).map(|_| RandomGenerator::current().gen()).collect()
+        });
+
+        let actual: Vec<u32> = RandomGenerator::scope(7, || {
+            let mut values: Vec<u32> = (0..2).map(|_| RandomGenerator::current().gen()).collect();
+
+            RandomGenerator::scope(8, || RandomGenerator::current().gen::<u64>());
+
+            values.extend((0..2).map(|_| RandomGenerator::current().gen::<u32>()));
+            values
+        });
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn derived_seeds_differ_by_stream_and_id() {
+        let club = RandomGenerator::derive_seed(1, RandomStream::Club, 10);
+
+        assert_ne!(club, RandomGenerator::derive_seed(1, RandomStream::Club, 11));
+        assert_ne!(club, RandomGenerator::derive_seed(1, RandomStream::Match, 10));
+        assert_ne!(club, RandomGenerator::derive_seed(2, RandomStream::Club, 10));
+        assert_eq!(club, RandomGenerator::derive_seed(1, RandomStream::Club, 10));
+    }
+}
@@ -1,12 +1,12 @@
-extern crate rand;
-use rand::*;
+use crate::utils::RandomGenerator;
+use rand::Rng;
 
 pub struct IntegerUtils;
 
 impl IntegerUtils {
     #[inline]
     pub fn random(min: i32, max: i32) -> i32 {
-        let random_val: f64 = random();
+        let random_val: f64 = RandomGenerator::current().gen();
 
         min + (random_val * ((max - min) as f64)) as i32
     }
@@ -1,5 +1,7 @@
 pub mod floats;
+mod generator;
 mod integers;
 
 pub use floats::*;
+pub use generator::*;
 pub use integers::*;
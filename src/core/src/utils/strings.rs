@@ -1,18 +1,19 @@
-extern crate rand;
-
-use rand::*;
+use crate::utils::RandomGenerator;
+use rand::Rng;
 
 pub struct StringUtils;
 
 impl StringUtils {
     #[inline]
     pub fn random_string(n: i32) -> String {
+        let mut rng = RandomGenerator::current();
+
         (0..n)
             .map(|i| {
                 if i == 0 {
-                    (65 + random::<u8>() % 26) as char
+                    (65 + rng.gen::<u8>() % 26) as char
                 } else {
-                    (97 + random::<u8>() % 26) as char
+                    (97 + rng.gen::<u8>() % 26) as char
                 }
             })
             .collect()
@@ -1,15 +1,20 @@
 use crate::generators::{PlayerGenerator, PositionType, StaffGenerator};
-use crate::loaders::ContinentEntity;
+use crate::loaders::{ContinentEntity, CountryEntity};
 use crate::DatabaseEntity;
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{NaiveDate, NaiveDateTime, Weekday};
 use core::club::academy::ClubAcademy;
 use core::context::NaiveTime;
-use core::continent::Continent;
+use core::continent::{ChampionLeague, Continent, LeagueEurope};
+use core::cup::{Cup, CupCollection, CupSettings};
 use core::league::LeagueCollection;
 use core::league::Schedule;
-use core::league::{DayMonthPeriod, League, LeagueSettings, LeagueTable};
+use core::league::{
+    DayMonthPeriod, League, LeagueHistory, LeagueSettings, LeagueTable, PromotionSettings,
+    RankingRule,
+};
 use core::shared::Location;
-use core::utils::IntegerUtils;
+use core::transfers::TransferWindows;
+use core::utils::{IntegerUtils, RandomGenerator, RandomStream};
 use core::ClubStatus;
 use core::TeamCollection;
 use core::{
@@ -20,10 +25,19 @@ use core::{
 use std::str::FromStr;
 use core::league::MatchStorage;
 
+const EUROPE_CONTINENT_NAME: &str = "Europe";
+
 pub struct DatabaseGenerator;
 
 impl DatabaseGenerator {
     pub fn generate(data: &DatabaseEntity) -> SimulatorData {
+        Self::generate_with_seed(data, rand::random())
+    }
+
+    pub fn generate_with_seed(data: &DatabaseEntity, seed: u64) -> SimulatorData {
+        PlayerGenerator::reset_id_sequence();
+        StaffGenerator::reset_id_sequence();
+
         let current_date = NaiveDateTime::new(
             NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
             NaiveTime::default(),
@@ -32,17 +46,27 @@ impl DatabaseGenerator {
         let continents = data
             .continents
             .iter()
-            .map(|continent| Continent {
-                id: continent.id,
-                name: continent.name.clone(),
-                countries: DatabaseGenerator::generate_countries(continent, data),
+            .map(|continent| {
+                let has_european_cups = continent.name == EUROPE_CONTINENT_NAME;
+
+                Continent {
+                    id: continent.id,
+                    name: continent.name.clone(),
+                    countries: DatabaseGenerator::generate_countries(continent, data, seed),
+                    champion_league: has_european_cups.then(ChampionLeague::new),
+                    league_europe: has_european_cups.then(LeagueEurope::new),
+                }
             })
             .collect();
 
-        SimulatorData::new(current_date, continents)
+        SimulatorData::with_seed(current_date, seed, continents)
     }
 
-    fn generate_countries(continent: &ContinentEntity, data: &DatabaseEntity) -> Vec<Country> {
+    fn generate_countries(
+        continent: &ContinentEntity,
+        data: &DatabaseEntity,
+        seed: u64,
+    ) -> Vec<Country> {
         data
             .countries
             .iter()
@@ -66,12 +90,17 @@ impl DatabaseGenerator {
                 let mut staff_generator =
                     StaffGenerator::with_people_names(&generator_data.people_names);
 
-                let clubs = DatabaseGenerator::generate_clubs(
-                    country.id,
-                    data,
-                    &mut player_generator,
-                    &mut staff_generator,
-                );
+                let generator_seed =
+                    RandomGenerator::derive_seed(seed, RandomStream::PlayerGenerator, country.id as u64);
+
+                let clubs = RandomGenerator::scope(generator_seed, || {
+                    DatabaseGenerator::generate_clubs(
+                        country.id,
+                        data,
+                        &mut player_generator,
+                        &mut staff_generator,
+                    )
+                });
 
                 let country = Country {
                     id: country.id,
@@ -82,8 +111,10 @@ impl DatabaseGenerator {
                     leagues: LeagueCollection::new(DatabaseGenerator::generate_leagues(
                         country.id, data,
                     )),
+                    cups: CupCollection::new(DatabaseGenerator::generate_cups(country.id, data)),
                     clubs,
                     reputation: country.reputation,
+                    transfer_windows: DatabaseGenerator::generate_transfer_windows(country),
                     generator_data,
                 };
 
@@ -92,6 +123,54 @@ impl DatabaseGenerator {
             .collect()
     }
 
+    fn generate_transfer_windows(country: &CountryEntity) -> TransferWindows {
+        if country.transfer_windows.is_empty() {
+            return TransferWindows::default();
+        }
+
+        TransferWindows::new(
+            country
+                .transfer_windows
+                .iter()
+                .map(|window| {
+                    DayMonthPeriod::new(
+                        window.from_day,
+                        window.from_month,
+                        window.to_day,
+                        window.to_month,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn generate_cups(country_id: u32, data: &DatabaseEntity) -> Vec<Cup> {
+        data.cups
+            .iter()
+            .filter(|c| c.country_id == country_id)
+            .map(|cup| {
+                Cup::new(
+                    cup.id,
+                    cup.name.clone(),
+                    cup.slug.clone(),
+                    cup.country_id,
+                    cup.reputation,
+                    CupSettings {
+                        season: DayMonthPeriod {
+                            from_day: cup.settings.season.from_day,
+                            from_month: cup.settings.season.from_month,
+                            to_day: cup.settings.season.to_day,
+                            to_month: cup.settings.season.to_month,
+                        },
+                        match_day: Weekday::from_str(&cup.settings.match_day).unwrap(),
+                        replays: cup.settings.replays,
+                        match_engine: None,
+                    },
+                )
+            })
+            .collect()
+    }
+
     fn generate_leagues(country_id: u32, data: &DatabaseEntity) -> Vec<League> {
         data
             .leagues
@@ -125,15 +204,33 @@ impl DatabaseGenerator {
                             to_day: league.settings.season_ending_half.to_day,
                             to_month: league.settings.season_ending_half.to_month,
                         },
+                        promotion: league.settings.promotion.as_ref().map(|promotion| {
+                            PromotionSettings::new(promotion.places, promotion.playoff_places)
+                        }),
+                        ranking: Self::generate_ranking(&league.settings.ranking),
+                        match_engine: None,
                     },
                     matches: MatchStorage::new(),
                     table: LeagueTable::new(&league_clubs),
-                    reputation: 0,
+                    reputation: league.reputation,
+                    tier: league.tier,
+                    history: LeagueHistory::new(),
                 }
             })
             .collect()
     }
 
+    fn generate_ranking(rules: &[String]) -> Vec<RankingRule> {
+        if rules.is_empty() {
+            return RankingRule::defaults();
+        }
+
+        rules
+            .iter()
+            .map(|rule| RankingRule::from_str(rule).unwrap())
+            .collect()
+    }
+
     fn generate_clubs(
         country_id: u32,
         data: &DatabaseEntity,
@@ -23,6 +23,10 @@ impl PlayerGenerator {
             },
         }
     }
+
+    pub fn reset_id_sequence() {
+        PLAYER_ID_SEQUENCE.store(1, Ordering::SeqCst);
+    }
 }
 
 pub enum PositionType {
@@ -1,6 +1,6 @@
 use core::shared::FullName;
 use core::utils::FloatUtils;
-use core::utils::{IntegerUtils, StringUtils};
+use core::utils::{IntegerUtils, RandomGenerator, StringUtils};
 use core::{
     CoachFocus, Datelike, MentalFocusType, NaiveDate, PeopleNameGeneratorData, PersonAttributes,
     PhysicalFocusType, Staff, StaffAttributes, StaffClubContract, StaffCoaching, StaffDataAnalysis,
@@ -26,6 +26,10 @@ impl StaffGenerator {
             },
         }
     }
+
+    pub fn reset_id_sequence() {
+        STAFF_ID_SEQUENCE.store(1, Ordering::SeqCst);
+    }
 }
 
 impl StaffGenerator {
@@ -202,7 +206,7 @@ const PHYSICAL_FOCUSES: &[PhysicalFocusType] = &[
 ];
 
 fn get_random_technical(count: usize) -> Vec<TechnicalFocusType> {
-    let mut rng = rand::thread_rng();
+    let mut rng = RandomGenerator::current();
 
     let mut random_values = Vec::with_capacity(count);
 
@@ -219,7 +223,7 @@ fn get_random_technical(count: usize) -> Vec<TechnicalFocusType> {
 }
 
 fn get_random_mental(count: usize) -> Vec<MentalFocusType> {
-    let mut rng = rand::thread_rng();
+    let mut rng = RandomGenerator::current();
 
     let mut random_values = Vec::with_capacity(count);
 
@@ -236,7 +240,7 @@ fn get_random_mental(count: usize) -> Vec<MentalFocusType> {
 }
 
 fn get_random_physical(count: usize) -> Vec<PhysicalFocusType> {
-    let mut rng = rand::thread_rng();
+    let mut rng = RandomGenerator::current();
 
     let mut random_values = Vec::with_capacity(count);
 
@@ -3,7 +3,7 @@ mod loaders;
 
 pub use loaders::{
     ClubEntity, ClubLoader, ContinentEntity, ContinentLoader, CountryEntity, CountryLoader,
-    LeagueEntity, LeagueLoader, NamesByCountryEntity, NamesByCountryLoader,
+    CupEntity, CupLoader, LeagueEntity, LeagueLoader, NamesByCountryEntity, NamesByCountryLoader,
 };
 
 pub use generators::DatabaseGenerator;
@@ -12,6 +12,7 @@ pub struct DatabaseEntity {
     pub continents: Vec<ContinentEntity>,
     pub countries: Vec<CountryEntity>,
     pub leagues: Vec<LeagueEntity>,
+    pub cups: Vec<CupEntity>,
     pub clubs: Vec<ClubEntity>,
 
     pub names_by_country: Vec<NamesByCountryEntity>,
@@ -25,6 +26,7 @@ impl DatabaseLoader {
             continents: ContinentLoader::load(),
             countries: CountryLoader::load(),
             leagues: LeagueLoader::load(),
+            cups: CupLoader::load(),
             clubs: ClubLoader::load(),
             names_by_country: NamesByCountryLoader::load(),
         }
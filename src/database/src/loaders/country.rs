@@ -1,3 +1,4 @@
+use crate::loaders::DayMonthPeriodEntity;
 use serde::Deserialize;
 
 const STATIC_COUNTRIES_JSON: &str = include_str!("../data/countries.json");
@@ -10,6 +11,8 @@ pub struct CountryEntity {
     pub name: String,
     pub continent_id: u32,
     pub reputation: u16,
+    #[serde(default)]
+    pub transfer_windows: Vec<DayMonthPeriodEntity>,
 }
 
 pub struct CountryLoader;
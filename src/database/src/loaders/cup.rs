@@ -0,0 +1,29 @@
+use crate::loaders::DayMonthPeriodEntity;
+use serde::Deserialize;
+
+const STATIC_CUPS_JSON: &str = include_str!("../data/cups.json");
+
+#[derive(Deserialize)]
+pub struct CupEntity {
+    pub id: u32,
+    pub slug: String,
+    pub name: String,
+    pub country_id: u32,
+    pub settings: CupSettingsEntity,
+    pub reputation: u16,
+}
+
+#[derive(Deserialize)]
+pub struct CupSettingsEntity {
+    pub season: DayMonthPeriodEntity,
+    pub match_day: String,
+    pub replays: bool,
+}
+
+pub struct CupLoader;
+
+impl CupLoader {
+    pub fn load() -> Vec<CupEntity> {
+        serde_json::from_str(STATIC_CUPS_JSON).unwrap()
+    }
+}
@@ -10,12 +10,23 @@ pub struct LeagueEntity {
     pub country_id: u32,
     pub settings: LeagueSettingsEntity,
     pub reputation: u16,
+    pub tier: u8,
 }
 
 #[derive(Deserialize)]
 pub struct LeagueSettingsEntity {
     pub season_starting_half: DayMonthPeriodEntity,
     pub season_ending_half: DayMonthPeriodEntity,
+    #[serde(default)]
+    pub promotion: Option<PromotionSettingsEntity>,
+    #[serde(default)]
+    pub ranking: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PromotionSettingsEntity {
+    pub places: u8,
+    pub playoff_places: u8,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,11 +1,13 @@
 pub mod country;
 mod league;
 mod club;
+mod cup;
 mod continent;
 mod names;
 
 pub use country::*;
 pub use league::*;
 pub use club::*;
+pub use cup::*;
 pub use continent::*;
 pub use names::*;
\ No newline at end of file
@@ -1,7 +1,9 @@
 mod create;
 mod process;
 pub mod routes;
+mod save;
 
 pub use create::*;
 pub use process::*;
 pub use routes::*;
+pub use save::*;
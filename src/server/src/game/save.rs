@@ -0,0 +1,62 @@
+use crate::GameAppData;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use core::SimulatorData;
+use log::{error, info};
+use std::sync::Arc;
+
+const SAVE_DIRECTORY: &str = "saves";
+const SAVE_FILE: &str = "saves/world.snapshot";
+
+pub async fn game_save_action(State(state): State<GameAppData>) -> impl IntoResponse {
+    let data = Arc::clone(&state.data);
+
+    let simulator_data_guard = data.read_owned().await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        let simulator_data = simulator_data_guard.as_ref()?;
+
+        if let Err(err) = std::fs::create_dir_all(SAVE_DIRECTORY) {
+            error!("failed to create save directory: {}", err);
+            return None;
+        }
+
+        Some(simulator_data.save(SAVE_FILE))
+    })
+    .await;
+
+    match result {
+        Ok(Some(Ok(_))) => {
+            info!("game saved to {}", SAVE_FILE);
+            (StatusCode::OK, Json(()))
+        }
+        Ok(Some(Err(err))) => {
+            error!("failed to save game: {}", err);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(()))
+        }
+        _ => (StatusCode::BAD_REQUEST, Json(())),
+    }
+}
+
+pub async fn game_load_action(State(state): State<GameAppData>) -> impl IntoResponse {
+    let mut state_data = state.data.write().await;
+
+    let result = tokio::task::spawn_blocking(|| SimulatorData::load(SAVE_FILE)).await;
+
+    match result {
+        Ok(Ok(data)) => {
+            info!("game loaded from {}, date: {}", SAVE_FILE, data.date);
+
+            *state_data = Some(data);
+
+            (StatusCode::OK, Json(()))
+        }
+        Ok(Err(err)) => {
+            error!("failed to load game: {}", err);
+            (StatusCode::NOT_FOUND, Json(()))
+        }
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, Json(())),
+    }
+}
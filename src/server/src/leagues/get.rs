@@ -66,7 +66,7 @@ pub struct LeagueTableRow<'l> {
     pub lost: u8,
     pub goal_scored: i32,
     pub goal_concerned: i32,
-    pub points: u8,
+    pub points: i16,
 }
 
 pub async fn league_get_action(
@@ -1,8 +1,11 @@
 ﻿use crate::GameAppData;
-use axum::extract::{Path, State};
+use async_compression::tokio::write::GzipEncoder;
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
-use serde::Deserialize;
+use core::r#match::ReplayReader;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
 use crate::r#match::stores::MatchStore;
 
 #[derive(Deserialize)]
@@ -11,20 +14,64 @@ pub struct MatchDataRequest {
     pub match_id: String,
 }
 
+// match time window in milliseconds, the whole match when omitted
+#[derive(Deserialize)]
+pub struct MatchDataQuery {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+impl MatchDataQuery {
+    pub fn is_range(&self) -> bool {
+        self.from.is_some() || self.to.is_some()
+    }
+
+    pub fn range(&self) -> (u64, u64) {
+        (self.from.unwrap_or(0), self.to.unwrap_or(u64::MAX))
+    }
+}
+
 pub async fn match_data_action(
     State(_): State<GameAppData>,
     Path(route_params): Path<MatchDataRequest>,
+    Query(query): Query<MatchDataQuery>,
 ) -> Response {
-    let match_data = MatchStore::get(&route_params.league_slug, &route_params.match_id).await;
+    let Some(replay) = MatchStore::get(&route_params.league_slug, &route_params.match_id).await
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let (from, to) = query.range();
+
+    let Ok(match_data) = ReplayReader::new(&replay).and_then(|reader| reader.read_range(from, to))
+    else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
 
-    let mut response = (StatusCode::OK, match_data).into_response();
+    // position data of a whole match is large, the json is sent compressed
+    let Some(compressed) = compress(&match_data).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    let mut response = (StatusCode::OK, compressed).into_response();
 
     response
         .headers_mut()
-        .append("Content-Type", "application/gzip".parse().unwrap());
+        .append("Content-Type", "application/json".parse().unwrap());
     response
         .headers_mut()
         .append("Content-Encoding", "gzip".parse().unwrap());
 
-    return response;
-}
\ No newline at end of file
+    response
+}
+
+async fn compress<T: Serialize>(match_data: &T) -> Option<Vec<u8>> {
+    let json = serde_json::to_vec(match_data).ok()?;
+
+    let mut encoder = GzipEncoder::new(Vec::new());
+
+    encoder.write_all(&json).await.ok()?;
+    encoder.shutdown().await.ok()?;
+
+    Some(encoder.into_inner())
+}
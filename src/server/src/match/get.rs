@@ -2,6 +2,8 @@
 use axum::extract::{Path, State};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
+use core::r#match::statistics::MatchPlayerResult;
+use core::r#match::MatchTeamStatistics;
 use core::SimulatorData;
 use serde::{Deserialize, Serialize};
 
@@ -13,18 +15,10 @@ pub async fn match_get_action(
 
     let simulator_data = guard.as_ref().expect("no simulator data");
 
-    let league_id = simulator_data
-        .indexes
-        .as_ref()
-        .unwrap()
-        .slug_indexes
-        .get_league_by_slug(&route_params.league_slug)
+    let match_result = simulator_data
+        .match_result(&route_params.league_slug, &route_params.match_id)
         .unwrap();
 
-    let league = simulator_data.league(league_id).unwrap();
-
-    let match_result = league.matches.get(route_params.match_id).unwrap();
-
     let home_team = simulator_data.team(match_result.home_team_id).unwrap();
     let away_team = simulator_data.team(match_result.away_team_id).unwrap();
 
@@ -68,6 +62,9 @@ pub async fn match_get_action(
                 .filter_map(|player_id| to_match_player(*player_id, simulator_data))
                 .collect(),
         },
+        home_statistics: result_details.team_statistics(match_result.home_team_id),
+        away_statistics: result_details.team_statistics(match_result.away_team_id),
+        player_statistics: &result_details.player_results,
     };
 
     Json(result).into_response()
@@ -111,6 +108,10 @@ pub struct MatchGetResponse<'p> {
     pub match_time_ms: u64,
 
     pub score: MatchScore,
+
+    pub home_statistics: Option<&'p MatchTeamStatistics>,
+    pub away_statistics: Option<&'p MatchTeamStatistics>,
+    pub player_statistics: &'p [MatchPlayerResult],
 }
 
 #[derive(Serialize)]
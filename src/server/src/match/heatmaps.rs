@@ -0,0 +1,25 @@
+use crate::r#match::data::MatchDataRequest;
+use crate::r#match::stores::MatchStore;
+use crate::GameAppData;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+
+pub async fn match_heatmaps_action(
+    State(_): State<GameAppData>,
+    Path(route_params): Path<MatchDataRequest>,
+) -> Response {
+    let Some(heatmaps) =
+        MatchStore::get_heatmaps(&route_params.league_slug, &route_params.match_id).await
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let mut response = (StatusCode::OK, heatmaps).into_response();
+
+    response
+        .headers_mut()
+        .append("Content-Type", "application/json".parse().unwrap());
+
+    response
+}
@@ -1,7 +1,10 @@
 ﻿pub mod data;
 mod get;
+mod heatmaps;
+mod replay;
 pub mod routes;
 pub mod stores;
+mod timeline;
 
 
 
@@ -0,0 +1,38 @@
+use crate::r#match::data::{MatchDataQuery, MatchDataRequest};
+use crate::r#match::stores::MatchStore;
+use crate::GameAppData;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use core::r#match::ReplayReader;
+
+pub async fn match_replay_action(
+    State(_): State<GameAppData>,
+    Path(route_params): Path<MatchDataRequest>,
+    Query(query): Query<MatchDataQuery>,
+) -> Response {
+    let Some(replay) = MatchStore::get(&route_params.league_slug, &route_params.match_id).await
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    // seeking cuts out a replay of its own, starting with a keyframe at `from`
+    let replay = if query.is_range() {
+        let (from, to) = query.range();
+
+        match ReplayReader::new(&replay).and_then(|reader| reader.slice(from, to)) {
+            Ok(slice) => slice,
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    } else {
+        replay
+    };
+
+    let mut response = (StatusCode::OK, replay).into_response();
+
+    response
+        .headers_mut()
+        .append("Content-Type", "application/octet-stream".parse().unwrap());
+
+    response
+}
@@ -3,9 +3,24 @@ use crate::GameAppData;
 use axum::routing::get;
 use axum::Router;
 use crate::r#match::get::match_get_action;
+use crate::r#match::heatmaps::match_heatmaps_action;
+use crate::r#match::replay::match_replay_action;
+use crate::r#match::timeline::match_timeline_action;
 
 pub fn match_routes() -> Router<GameAppData> {
     Router::new()
         .route("/api/match/{league_slug}/{match_id}", get(match_get_action))
         .route("/api/match/{league_slug}/{match_id}/data", get(match_data_action))
+        .route(
+            "/api/match/{league_slug}/{match_id}/heatmaps",
+            get(match_heatmaps_action),
+        )
+        .route(
+            "/api/match/{league_slug}/{match_id}/replay",
+            get(match_replay_action),
+        )
+        .route(
+            "/api/match/{league_slug}/{match_id}/timeline",
+            get(match_timeline_action),
+        )
 }
@@ -0,0 +1,62 @@
+use crate::GameAppData;
+use axum::extract::{Path, Query, State};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use core::r#match::{Commentary, CommentaryLine, CommentaryNames, TimelineEvent};
+use serde::{Deserialize, Serialize};
+
+pub async fn match_timeline_action(
+    State(state): State<GameAppData>,
+    Path(route_params): Path<MatchTimelineRequest>,
+    Query(query): Query<MatchTimelineQuery>,
+) -> Response {
+    let guard = state.data.read().await;
+
+    let simulator_data = guard.as_ref().expect("no simulator data");
+
+    let match_result = simulator_data
+        .match_result(&route_params.league_slug, &route_params.match_id)
+        .unwrap();
+
+    let result_details = match_result.details.as_ref().unwrap();
+
+    let mut names = CommentaryNames::new();
+
+    for team_id in [match_result.home_team_id, match_result.away_team_id] {
+        if let Some(team) = simulator_data.team(team_id) {
+            names.add_team(team_id, team.name.clone());
+        }
+    }
+
+    for player_result in &result_details.player_results {
+        if let Some(player) = simulator_data.player(player_result.player_id) {
+            names.add_player(player.id, player.full_name.last_name.clone());
+        }
+    }
+
+    let language = query.lang.as_deref().unwrap_or("en");
+
+    let result = MatchTimelineResponse {
+        events: &result_details.timeline.events,
+        commentary: Commentary::for_language(language).render(&result_details.timeline, &names),
+    };
+
+    Json(result).into_response()
+}
+
+#[derive(Deserialize)]
+pub struct MatchTimelineRequest {
+    pub league_slug: String,
+    pub match_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct MatchTimelineQuery {
+    pub lang: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct MatchTimelineResponse<'t> {
+    pub events: &'t [TimelineEvent],
+    pub commentary: Vec<CommentaryLine>,
+}
@@ -2,7 +2,7 @@
 use axum::extract::{Path, State};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
-use core::player::Player;
+use core::player::{Player, PlayerSkills};
 use core::player::PlayerStatusType;
 use core::utils::FormattingUtils;
 use core::SimulatorData;
@@ -48,12 +48,21 @@ pub struct PlayerGetViewModel<'p> {
     pub statistics: PlayerStatistics,
 
     pub status: PlayerStatusDto,
+
+    pub skill_history: Vec<PlayerSkillsRecordDto>,
+}
+
+#[derive(Serialize)]
+pub struct PlayerSkillsRecordDto {
+    pub date: String,
+    pub skills: PlayerSkillsDto,
 }
 
 #[derive(Serialize)]
 pub struct PlayerStatistics {
     pub played: u16,
     pub played_subs: u16,
+    pub minutes: u16,
 
     pub goals: u16,
     pub assists: u16,
@@ -64,7 +73,7 @@ pub struct PlayerStatistics {
 
     pub shots_on_target: f32,
     pub tackling: f32,
-    pub passes: u8,
+    pub passes: u16,
 
     pub average_rating: f32,
 }
@@ -207,7 +216,7 @@ pub async fn player_get_action(
         country_slug: &country.slug,
         country_code: &country.code,
         country_name: &country.name,
-        skills: get_skills(player),
+        skills: get_skills(&player.skills),
         conditions: get_conditions(player),
         current_ability: get_current_ability_stars(player),
         potential_ability: get_potential_ability_stars(player),
@@ -217,6 +226,7 @@ pub async fn player_get_action(
         neighbor_teams: get_neighbor_teams(team.club_id, simulator_data),
         statistics: get_statistics(player),
         status: PlayerStatusDto::new(player.statuses.get()),
+        skill_history: get_skill_history(player),
     };
 
     if let Some(contract) = &player.contract {
@@ -239,50 +249,62 @@ fn get_attributes(player: &Player) -> PlayerAttributesDto {
     }
 }
 
-fn get_skills(player: &Player) -> PlayerSkillsDto {
+fn get_skill_history(player: &Player) -> Vec<PlayerSkillsRecordDto> {
+    player
+        .training_history
+        .records()
+        .iter()
+        .map(|record| PlayerSkillsRecordDto {
+            date: record.date.format("%d.%m.%Y").to_string(),
+            skills: get_skills(&record.skills),
+        })
+        .collect()
+}
+
+fn get_skills(skills: &PlayerSkills) -> PlayerSkillsDto {
     PlayerSkillsDto {
         technical: TechnicalDto {
-            corners: player.skills.technical.corners.floor() as u8,
-            crossing: player.skills.technical.crossing.floor() as u8,
-            dribbling: player.skills.technical.dribbling.floor() as u8,
-            finishing: player.skills.technical.finishing.floor() as u8,
-            first_touch: player.skills.technical.first_touch.floor() as u8,
-            free_kick_taking: player.skills.technical.free_kicks.floor() as u8,
-            heading: player.skills.technical.heading.floor() as u8,
-            long_shots: player.skills.technical.long_shots.floor() as u8,
-            long_throws: player.skills.technical.long_throws.floor() as u8,
-            marking: player.skills.technical.marking.floor() as u8,
-            passing: player.skills.technical.passing.floor() as u8,
-            penalty_taking: player.skills.technical.penalty_taking.floor() as u8,
-            tackling: player.skills.technical.tackling.floor() as u8,
-            technique: player.skills.technical.technique.floor() as u8,
+            corners: skills.technical.corners.floor() as u8,
+            crossing: skills.technical.crossing.floor() as u8,
+            dribbling: skills.technical.dribbling.floor() as u8,
+            finishing: skills.technical.finishing.floor() as u8,
+            first_touch: skills.technical.first_touch.floor() as u8,
+            free_kick_taking: skills.technical.free_kicks.floor() as u8,
+            heading: skills.technical.heading.floor() as u8,
+            long_shots: skills.technical.long_shots.floor() as u8,
+            long_throws: skills.technical.long_throws.floor() as u8,
+            marking: skills.technical.marking.floor() as u8,
+            passing: skills.technical.passing.floor() as u8,
+            penalty_taking: skills.technical.penalty_taking.floor() as u8,
+            tackling: skills.technical.tackling.floor() as u8,
+            technique: skills.technical.technique.floor() as u8,
         },
         mental: MentalDto {
-            aggression: player.skills.mental.aggression.floor() as u8,
-            anticipation: player.skills.mental.anticipation.floor() as u8,
-            bravery: player.skills.mental.bravery.floor() as u8,
-            composure: player.skills.mental.composure.floor() as u8,
-            concentration: player.skills.mental.concentration.floor() as u8,
-            decisions: player.skills.mental.decisions.floor() as u8,
-            determination: player.skills.mental.determination.floor() as u8,
-            flair: player.skills.mental.flair.floor() as u8,
-            leadership: player.skills.mental.leadership.floor() as u8,
-            off_the_ball: player.skills.mental.off_the_ball.floor() as u8,
-            positioning: player.skills.mental.positioning.floor() as u8,
-            teamwork: player.skills.mental.teamwork.floor() as u8,
-            vision: player.skills.mental.vision.floor() as u8,
-            work_rate: player.skills.mental.work_rate.floor() as u8,
+            aggression: skills.mental.aggression.floor() as u8,
+            anticipation: skills.mental.anticipation.floor() as u8,
+            bravery: skills.mental.bravery.floor() as u8,
+            composure: skills.mental.composure.floor() as u8,
+            concentration: skills.mental.concentration.floor() as u8,
+            decisions: skills.mental.decisions.floor() as u8,
+            determination: skills.mental.determination.floor() as u8,
+            flair: skills.mental.flair.floor() as u8,
+            leadership: skills.mental.leadership.floor() as u8,
+            off_the_ball: skills.mental.off_the_ball.floor() as u8,
+            positioning: skills.mental.positioning.floor() as u8,
+            teamwork: skills.mental.teamwork.floor() as u8,
+            vision: skills.mental.vision.floor() as u8,
+            work_rate: skills.mental.work_rate.floor() as u8,
         },
         physical: PhysicalDto {
-            acceleration: player.skills.physical.acceleration.floor() as u8,
-            agility: player.skills.physical.agility.floor() as u8,
-            balance: player.skills.physical.balance.floor() as u8,
-            jumping_reach: player.skills.physical.jumping.floor() as u8,
-            natural_fitness: player.skills.physical.natural_fitness.floor() as u8,
-            pace: player.skills.physical.pace.floor() as u8,
-            stamina: player.skills.physical.stamina.floor() as u8,
-            strength: player.skills.physical.strength.floor() as u8,
-            match_readiness: player.skills.physical.match_readiness.floor() as u8,
+            acceleration: skills.physical.acceleration.floor() as u8,
+            agility: skills.physical.agility.floor() as u8,
+            balance: skills.physical.balance.floor() as u8,
+            jumping_reach: skills.physical.jumping.floor() as u8,
+            natural_fitness: skills.physical.natural_fitness.floor() as u8,
+            pace: skills.physical.pace.floor() as u8,
+            stamina: skills.physical.stamina.floor() as u8,
+            strength: skills.physical.strength.floor() as u8,
+            match_readiness: skills.physical.match_readiness.floor() as u8,
         },
     }
 }
@@ -310,6 +332,7 @@ fn get_statistics(player: &Player) -> PlayerStatistics {
     PlayerStatistics {
         played: player.statistics.played,
         played_subs: player.statistics.played_subs,
+        minutes: player.statistics.minutes,
         goals: player.statistics.goals,
         assists: player.statistics.assists,
         penalties: player.statistics.penalties,